@@ -68,6 +68,7 @@ async fn put_secret_example(client: &Client) -> Result<(), Box<dyn std::error::E
             "rotation_required": true
         })),
         idempotency_key: Some("example-put-001".to_string()),
+        ..Default::default()
     };
 
     client
@@ -119,7 +120,9 @@ async fn list_secrets_example(client: &Client) -> Result<(), Box<dyn std::error:
     for secret_info in &list.secrets {
         println!(
             "  - {} (v{}, updated: {})",
-            secret_info.key, secret_info.version, secret_info.updated_at
+            secret_info.key,
+            secret_info.version.unwrap_or_default(),
+            secret_info.updated_at.as_deref().unwrap_or("-")
         );
     }
 
@@ -127,6 +130,7 @@ async fn list_secrets_example(client: &Client) -> Result<(), Box<dyn std::error:
     let opts = ListOpts {
         prefix: Some("api-".to_string()),
         limit: Some(10),
+        ..Default::default()
     };
     let filtered = client.list_secrets("example-namespace", opts).await?;
     println!("\nSecrets starting with 'api-': {}", filtered.total);
@@ -3,7 +3,7 @@
 use secret_store_sdk::{
     Auth, Client, ClientBuilder, EnvExport, ExportEnvOpts, ExportFormat, PutOpts,
 };
-use std::collections::HashMap;
+use std::collections::BTreeMap;
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
@@ -123,11 +123,8 @@ async fn export_json_example(client: &Client) -> Result<(), Box<dyn std::error::
         println!("ETag: {}", json_export.etag);
         println!("\nEnvironment variables:");
 
-        // Sort for consistent output
-        let mut sorted: Vec<_> = json_export.environment.into_iter().collect();
-        sorted.sort_by(|a, b| a.0.cmp(&b.0));
-
-        for (key, value) in sorted.iter() {
+        // Variables come back in key order
+        for (key, value) in json_export.environment.iter() {
             // Mask sensitive values
             let display_value = if key.contains("KEY") || key.contains("PASSWORD") {
                 mask_value(value)
@@ -138,7 +135,7 @@ async fn export_json_example(client: &Client) -> Result<(), Box<dyn std::error::
         }
 
         // Parse as configuration
-        let config: AppConfig = parse_config(sorted.into_iter().collect());
+        let config: AppConfig = parse_config(json_export.environment);
         println!("\nParsed configuration:");
         println!("  Database: {}", mask_value(&config.database_url));
         println!("  Port: {}", config.port);
@@ -325,7 +322,7 @@ struct AppConfig {
     log_level: String,
 }
 
-fn parse_config(env: HashMap<String, String>) -> AppConfig {
+fn parse_config(env: BTreeMap<String, String>) -> AppConfig {
     AppConfig {
         database_url: env.get("DATABASE_URL").cloned().unwrap_or_default(),
         port: env.get("PORT").and_then(|p| p.parse().ok()).unwrap_or(8080),
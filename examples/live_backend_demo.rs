@@ -149,6 +149,7 @@ async fn test_list_secrets() {
             ListOpts {
                 prefix: Some(prefix.clone()),
                 limit: Some(10),
+                ..Default::default()
             },
         )
         .await
@@ -74,6 +74,7 @@ async fn test_basic_operations() {
                     "sdk_version": "0.1.0"
                 })),
                 idempotency_key: Some(format!("validation-{}", chrono::Utc::now().timestamp())),
+                ..Default::default()
             },
         )
         .await
@@ -174,8 +175,8 @@ async fn test_list_operations() {
             println!("   {}. {} (版本: {}, 更新时间: {})",
                 i + 1,
                 secret_info.key,
-                secret_info.version,
-                secret_info.updated_at
+                secret_info.version.unwrap_or_default(),
+                secret_info.updated_at.as_deref().unwrap_or("-")
             );
         }
     }
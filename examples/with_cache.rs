@@ -263,7 +263,7 @@ async fn cache_management_example() -> Result<(), Box<dyn std::error::Error>> {
 
     // Clear entire cache
     println!("\nClearing entire cache...");
-    let stats_before_clear = client.cache_stats().clone();
+    let stats_before_clear = client.cache_stats().snapshot();
     client.clear_cache();
 
     // All fetches should miss now
@@ -275,7 +275,7 @@ async fn cache_management_example() -> Result<(), Box<dyn std::error::Error>> {
 
     println!(
         "  All entries cleared, new misses: {}",
-        client.cache_stats().misses() - stats_before_clear.misses()
+        client.cache_stats().misses() - stats_before_clear.misses
     );
 
     Ok(())
@@ -7,6 +7,11 @@
 //! 2. **API Key** - For service-to-service authentication
 //! 3. **XJP Key** - Legacy authentication method
 //! 4. **Token Provider** - Dynamic tokens with refresh capability
+//! 5. **Fallback Chain** - Several methods tried in order on 401 (migrations)
+//!
+//! Credentials mounted as files (e.g. Kubernetes projected service account
+//! tokens) can be loaded with [`Auth::bearer_from_file`], which re-reads the
+//! file whenever the token is refreshed.
 //!
 //! # Examples
 //!
@@ -62,9 +67,15 @@
 //! let auth = Auth::token_provider(provider);
 //! ```
 
+use crate::errors::Error;
 use async_trait::async_trait;
+use futures::FutureExt;
 use secrecy::{ExposeSecret, SecretString};
 use std::fmt;
+use std::future::Future;
+use std::panic::AssertUnwindSafe;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, RwLock};
 
 /// Authentication method for the secret store API
 ///
@@ -93,6 +104,14 @@ pub enum Auth {
     ///
     /// Supports automatic token refresh on 401 responses
     TokenProvider(Box<dyn TokenProvider>),
+    /// Ordered list of methods to fall back through on 401 responses
+    ///
+    /// Requests use the first method. If it is rejected with 401 (after a
+    /// token refresh, for refreshable methods), the request is retried once
+    /// with the next method, and so on. Each request starts again from the
+    /// first method. Intended only for credential migrations; see
+    /// [`Auth::fallback_chain`].
+    Chain(Vec<Auth>),
 }
 
 impl Auth {
@@ -111,25 +130,99 @@ impl Auth {
         Auth::XjpKey(SecretString::new(key.into()))
     }
 
+    /// Create a bearer token authentication read from a file
+    ///
+    /// The token is re-read from the file whenever it is refreshed (on 401
+    /// responses), so tokens rotated in place, such as Kubernetes projected
+    /// service account tokens, are picked up. See [`FileTokenProvider`].
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::Config` if the file cannot be read or is empty.
+    pub fn bearer_from_file(path: impl AsRef<Path>) -> crate::Result<Self> {
+        Ok(Auth::token_provider(FileTokenProvider::new(path)?))
+    }
+
+    /// Create an API key authentication read from a file
+    ///
+    /// The key is read once; trailing newlines are trimmed.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::Config` if the file cannot be read or is empty.
+    pub fn api_key_from_file(path: impl AsRef<Path>) -> crate::Result<Self> {
+        Ok(Auth::ApiKey(read_credential_file(path.as_ref())?))
+    }
+
     /// Create a dynamic token provider authentication
     pub fn token_provider(provider: impl TokenProvider + 'static) -> Self {
         Auth::TokenProvider(Box::new(provider))
     }
 
+    /// Create a chain of methods tried in order when the server returns 401
+    ///
+    /// Meant for migrations between credentials, e.g. from bearer tokens to
+    /// API keys, where either may be accepted for a while. Every request that
+    /// is rejected by the primary method costs an extra round trip and sends
+    /// the next credential to the server, so remove the chain once the
+    /// migration is complete.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use secret_store_sdk::Auth;
+    ///
+    /// let auth = Auth::fallback_chain(vec![
+    ///     Auth::bearer("new-token"),
+    ///     Auth::api_key("legacy-key"),
+    /// ]);
+    /// ```
+    pub fn fallback_chain(methods: Vec<Auth>) -> Self {
+        Auth::Chain(methods)
+    }
+
+    /// Get the `index`-th non-chain method, flattening nested chains
+    ///
+    /// A non-chain method is its own only member.
+    pub(crate) fn chain_member(&self, index: usize) -> Option<&Auth> {
+        match self {
+            Auth::Chain(methods) => {
+                let mut index = index;
+                for method in methods {
+                    let len = method.chain_len();
+                    if index < len {
+                        return method.chain_member(index);
+                    }
+                    index -= len;
+                }
+                None
+            }
+            _ => (index == 0).then_some(self),
+        }
+    }
+
+    fn chain_len(&self) -> usize {
+        match self {
+            Auth::Chain(methods) => methods.iter().map(Auth::chain_len).sum(),
+            _ => 1,
+        }
+    }
+
     /// Get the authorization header name and value
     pub(crate) async fn get_header(
         &self,
     ) -> Result<(&'static str, String), Box<dyn std::error::Error + Send + Sync>> {
-        match self {
-            Auth::Bearer(token) => {
+        match self.chain_member(0) {
+            Some(Auth::Bearer(token)) => {
                 Ok(("Authorization", format!("Bearer {}", token.expose_secret())))
             }
-            Auth::ApiKey(key) => Ok(("X-API-Key", key.expose_secret().clone())),
-            Auth::XjpKey(key) => Ok(("XJP-KEY", key.expose_secret().clone())),
-            Auth::TokenProvider(provider) => {
-                let token = provider.get_token().await?;
+            Some(Auth::ApiKey(key)) => Ok(("X-API-Key", key.expose_secret().clone())),
+            Some(Auth::XjpKey(key)) => Ok(("XJP-KEY", key.expose_secret().clone())),
+            Some(Auth::TokenProvider(provider)) => {
+                let token = guard_provider(provider.get_token()).await?;
                 Ok(("Authorization", format!("Bearer {}", token.expose_secret())))
             }
+            Some(Auth::Chain(_)) | None => Err("Auth fallback chain is empty".into()),
         }
     }
 
@@ -141,12 +234,30 @@ impl Auth {
     /// Refresh the token (only for TokenProvider)
     pub(crate) async fn refresh(&self) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
         match self {
-            Auth::TokenProvider(provider) => provider.refresh_token().await,
+            Auth::TokenProvider(provider) => guard_provider(provider.refresh_token()).await,
             _ => Ok(()),
         }
     }
 }
 
+/// Error standing in for a panic inside a [`TokenProvider`] call
+#[derive(Debug, thiserror::Error)]
+#[error("token provider panicked")]
+pub(crate) struct TokenProviderPanic;
+
+/// Run a provider call, turning a panic into [`TokenProviderPanic`]
+///
+/// Providers are user code, so a bug in one should fail the request rather
+/// than unwind through the SDK and take the task down with it.
+async fn guard_provider<T>(
+    call: impl Future<Output = Result<T, Box<dyn std::error::Error + Send + Sync>>>,
+) -> Result<T, Box<dyn std::error::Error + Send + Sync>> {
+    match AssertUnwindSafe(call).catch_unwind().await {
+        Ok(result) => result,
+        Err(_) => Err(Box::new(TokenProviderPanic)),
+    }
+}
+
 impl fmt::Debug for Auth {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
@@ -154,6 +265,7 @@ impl fmt::Debug for Auth {
             Auth::ApiKey(_) => write!(f, "Auth::ApiKey(****)"),
             Auth::XjpKey(_) => write!(f, "Auth::XjpKey(****)"),
             Auth::TokenProvider(_) => write!(f, "Auth::TokenProvider(****)"),
+            Auth::Chain(methods) => f.debug_tuple("Auth::Chain").field(methods).finish(),
         }
     }
 }
@@ -162,6 +274,8 @@ impl fmt::Debug for Auth {
 ///
 /// Implement this trait to support automatic token refresh on authentication failures.
 /// The SDK will call `refresh_token` when it receives a 401 response and retry the request.
+/// A panic in either method fails the request with `Error::Config` instead of
+/// unwinding through the SDK.
 ///
 /// # Example
 ///
@@ -258,6 +372,84 @@ impl TokenProvider for StaticTokenProvider {
     }
 }
 
+/// Token provider that reads a bearer token from a file
+///
+/// The file is read when the provider is created and again on every refresh,
+/// so a token rotated in place (as Kubernetes does for projected service
+/// account tokens) is picked up after the server rejects the old one.
+/// Trailing newlines are trimmed. Clones share the current token.
+#[derive(Clone)]
+pub struct FileTokenProvider {
+    path: PathBuf,
+    token: Arc<RwLock<SecretString>>,
+}
+
+impl FileTokenProvider {
+    /// Create a provider, reading the initial token from `path`
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::Config` if the file cannot be read or is empty.
+    pub fn new(path: impl AsRef<Path>) -> crate::Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        let token = read_credential_file(&path)?;
+        Ok(Self {
+            path,
+            token: Arc::new(RwLock::new(token)),
+        })
+    }
+
+    /// Path of the token file
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl fmt::Debug for FileTokenProvider {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FileTokenProvider")
+            .field("path", &self.path)
+            .field("token", &"****")
+            .finish()
+    }
+}
+
+#[async_trait]
+impl TokenProvider for FileTokenProvider {
+    async fn get_token(&self) -> Result<SecretString, Box<dyn std::error::Error + Send + Sync>> {
+        Ok(self.token.read().unwrap_or_else(|e| e.into_inner()).clone())
+    }
+
+    async fn refresh_token(&self) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let token = read_credential_file(&self.path)?;
+        *self.token.write().unwrap_or_else(|e| e.into_inner()) = token;
+        Ok(())
+    }
+
+    fn clone_box(&self) -> Box<dyn TokenProvider> {
+        Box::new(self.clone())
+    }
+}
+
+/// Read a credential from a file, trimming trailing newlines
+fn read_credential_file(path: &Path) -> crate::Result<SecretString> {
+    let contents = std::fs::read_to_string(path).map_err(|e| {
+        Error::Config(format!(
+            "Failed to read credential file {}: {}",
+            path.display(),
+            e
+        ))
+    })?;
+    let credential = contents.trim_end_matches(['\r', '\n']);
+    if credential.is_empty() {
+        return Err(Error::Config(format!(
+            "Credential file {} is empty",
+            path.display()
+        )));
+    }
+    Ok(SecretString::new(credential.to_string()))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -296,4 +488,66 @@ mod tests {
         let provider = Auth::token_provider(StaticTokenProvider::new("token"));
         assert!(provider.supports_refresh());
     }
+
+    #[tokio::test]
+    async fn test_file_token_provider() {
+        let path =
+            std::env::temp_dir().join(format!("secret-store-sdk-token-{}", uuid::Uuid::new_v4()));
+        std::fs::write(&path, "token-one\n").unwrap();
+
+        let auth = Auth::bearer_from_file(&path).unwrap();
+        assert!(auth.supports_refresh());
+        let (_, value) = auth.get_header().await.unwrap();
+        assert_eq!(value, "Bearer token-one");
+
+        // Rotation is only picked up on refresh
+        std::fs::write(&path, "token-two\r\n").unwrap();
+        let (_, value) = auth.get_header().await.unwrap();
+        assert_eq!(value, "Bearer token-one");
+        auth.refresh().await.unwrap();
+        let (_, value) = auth.get_header().await.unwrap();
+        assert_eq!(value, "Bearer token-two");
+
+        let api_key = Auth::api_key_from_file(&path).unwrap();
+        let (header, value) = api_key.get_header().await.unwrap();
+        assert_eq!(header, "X-API-Key");
+        assert_eq!(value, "token-two");
+
+        // A refresh that cannot read the file keeps the previous token
+        std::fs::remove_file(&path).unwrap();
+        assert!(auth.refresh().await.is_err());
+        let (_, value) = auth.get_header().await.unwrap();
+        assert_eq!(value, "Bearer token-two");
+
+        let err = Auth::bearer_from_file(&path).unwrap_err();
+        assert!(matches!(err, Error::Config(_)));
+        assert!(matches!(
+            Auth::api_key_from_file(&path).unwrap_err(),
+            Error::Config(_)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_fallback_chain() {
+        let chain = Auth::fallback_chain(vec![
+            Auth::bearer("primary"),
+            Auth::fallback_chain(vec![Auth::api_key("secondary"), Auth::xjp_key("third")]),
+        ]);
+
+        let (header, value) = chain.get_header().await.unwrap();
+        assert_eq!(header, "Authorization");
+        assert_eq!(value, "Bearer primary");
+
+        let (header, value) = chain.chain_member(1).unwrap().get_header().await.unwrap();
+        assert_eq!(header, "X-API-Key");
+        assert_eq!(value, "secondary");
+        assert!(matches!(chain.chain_member(2), Some(Auth::XjpKey(_))));
+        assert!(chain.chain_member(3).is_none());
+
+        assert!(Auth::fallback_chain(vec![]).get_header().await.is_err());
+        assert_eq!(
+            format!("{:?}", chain),
+            "Auth::Chain([Auth::Bearer(****), Auth::Chain([Auth::ApiKey(****), Auth::XjpKey(****)])])"
+        );
+    }
 }
@@ -1,8 +1,15 @@
+use crate::clock::Clock;
+use crate::errors::{Error, Result};
+use crate::models::Secret;
+use secrecy::{ExposeSecret, SecretString};
+use std::fmt::Debug;
 use std::sync::atomic::{AtomicU64, Ordering};
-use std::sync::Arc;
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+use zeroize::Zeroizing;
 
 /// Cache configuration
-#[derive(Debug, Clone)]
+#[derive(Clone)]
 pub struct CacheConfig {
     /// Whether caching is enabled
     pub enabled: bool,
@@ -10,6 +17,46 @@ pub struct CacheConfig {
     pub max_entries: u64,
     /// Default TTL for cache entries in seconds
     pub default_ttl_secs: u64,
+    /// Freshness TTL for cached listings in seconds
+    ///
+    /// Listings go stale faster than individual secrets. Once this TTL has
+    /// passed, the cached listing is revalidated with its ETag.
+    pub list_ttl_secs: u64,
+    /// How long an expired secret is kept to be served when the backend fails,
+    /// in seconds (default: 0, disabled)
+    ///
+    /// See `ClientBuilder::serve_stale_on_error`.
+    pub stale_if_error_secs: u64,
+    /// Decides whether a fetched secret may be cached (default: cache everything)
+    ///
+    /// Secrets for which the predicate returns `false` are still returned to
+    /// the caller but never enter the in-memory or disk cache.
+    pub cache_predicate: Option<CachePredicate>,
+}
+
+/// Predicate deciding whether a fetched secret may be cached
+pub type CachePredicate = Arc<dyn Fn(&Secret) -> bool + Send + Sync>;
+
+impl CacheConfig {
+    /// Whether `secret` passes the cache predicate
+    pub(crate) fn should_cache(&self, secret: &Secret) -> bool {
+        self.cache_predicate
+            .as_ref()
+            .map_or(true, |predicate| predicate(secret))
+    }
+}
+
+impl Debug for CacheConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CacheConfig")
+            .field("enabled", &self.enabled)
+            .field("max_entries", &self.max_entries)
+            .field("default_ttl_secs", &self.default_ttl_secs)
+            .field("list_ttl_secs", &self.list_ttl_secs)
+            .field("stale_if_error_secs", &self.stale_if_error_secs)
+            .field("cache_predicate", &self.cache_predicate.is_some())
+            .finish()
+    }
 }
 
 impl Default for CacheConfig {
@@ -18,30 +65,129 @@ impl Default for CacheConfig {
             enabled: true,
             max_entries: crate::DEFAULT_CACHE_MAX_ENTRIES,
             default_ttl_secs: crate::DEFAULT_CACHE_TTL_SECS,
+            list_ttl_secs: crate::DEFAULT_LIST_CACHE_TTL_SECS,
+            stale_if_error_secs: 0,
+            cache_predicate: None,
         }
     }
 }
 
 /// Cache statistics
+///
+/// Counts lookups of secret values only. Cached listings and env exports are
+/// not included, so they do not skew the hit rates.
 #[derive(Debug, Clone)]
 pub struct CacheStats {
     inner: Arc<CacheStatsInner>,
 }
 
-#[derive(Debug, Default)]
+#[derive(Debug)]
 struct CacheStatsInner {
     hits: AtomicU64,
     misses: AtomicU64,
     insertions: AtomicU64,
     evictions: AtomicU64,
     expirations: AtomicU64,
+    coalesced: AtomicU64,
+    /// Held shared by updates and exclusively by snapshots and resets
+    gate: RwLock<()>,
+    /// Per-second lookup counts for [`CacheStats::recent_hit_rate`], indexed
+    /// by the second modulo [`RECENT_BUCKETS`]
+    recent: Box<[RecentBucket]>,
+    /// Time source for bucketing recent lookups
+    clock: Arc<dyn Clock>,
+}
+
+/// Number of one-second buckets kept for [`CacheStats::recent_hit_rate`]
+const RECENT_BUCKETS: usize = 300;
+
+/// Lookup counts for one second
+///
+/// Each word packs the second it counts (high 32 bits) with the count (low
+/// 32 bits), so a stale bucket is restarted by the same compare-and-swap that
+/// increments it.
+#[derive(Debug, Default)]
+struct RecentBucket {
+    hits: AtomicU64,
+    lookups: AtomicU64,
+}
+
+impl RecentBucket {
+    fn increment(word: &AtomicU64, second: u32) {
+        let mut current = word.load(Ordering::Relaxed);
+        loop {
+            let next = if (current >> 32) as u32 == second {
+                current.wrapping_add(1)
+            } else {
+                (u64::from(second) << 32) | 1
+            };
+            match word.compare_exchange_weak(current, next, Ordering::Relaxed, Ordering::Relaxed) {
+                Ok(_) => return,
+                Err(actual) => current = actual,
+            }
+        }
+    }
+
+    /// The count of `word` if its second is less than `window` seconds before `now`
+    fn count(word: &AtomicU64, now: u32, window: u32) -> u64 {
+        let value = word.load(Ordering::Relaxed);
+        if now.wrapping_sub((value >> 32) as u32) < window {
+            value & u64::from(u32::MAX)
+        } else {
+            0
+        }
+    }
+}
+
+/// Point-in-time copy of [`CacheStats`]
+///
+/// All counters are read together, so they describe the same moment even
+/// while other tasks keep using the cache.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CacheStatsSnapshot {
+    /// Number of cache hits
+    pub hits: u64,
+    /// Number of cache misses
+    pub misses: u64,
+    /// Number of cache insertions
+    pub insertions: u64,
+    /// Number of cache evictions
+    pub evictions: u64,
+    /// Number of expired entries
+    pub expirations: u64,
+    /// Number of gets answered by another caller's in-flight request
+    pub coalesced_requests: u64,
+}
+
+impl CacheStatsSnapshot {
+    /// Get the hit rate as a percentage (0.0-100.0)
+    pub fn hit_rate(&self) -> f64 {
+        let total = self.hits + self.misses;
+        if total == 0 {
+            0.0
+        } else {
+            (self.hits as f64 / total as f64) * 100.0
+        }
+    }
 }
 
 impl CacheStats {
-    /// Create new cache statistics
-    pub(crate) fn new() -> Self {
+    /// Create new cache statistics, bucketing recent lookups by `clock`
+    pub(crate) fn new(clock: Arc<dyn Clock>) -> Self {
         Self {
-            inner: Arc::new(CacheStatsInner::default()),
+            inner: Arc::new(CacheStatsInner {
+                hits: AtomicU64::new(0),
+                misses: AtomicU64::new(0),
+                insertions: AtomicU64::new(0),
+                evictions: AtomicU64::new(0),
+                expirations: AtomicU64::new(0),
+                coalesced: AtomicU64::new(0),
+                gate: RwLock::new(()),
+                recent: (0..RECENT_BUCKETS)
+                    .map(|_| RecentBucket::default())
+                    .collect(),
+                clock,
+            }),
         }
     }
 
@@ -70,6 +216,15 @@ impl CacheStats {
         self.inner.expirations.load(Ordering::Relaxed)
     }
 
+    /// Get the number of gets answered by another caller's in-flight request
+    ///
+    /// Concurrent cache misses for the same secret are coalesced into one
+    /// request unless `ClientBuilder::single_flight(false)` is set. Coalesced
+    /// gets are counted here in addition to their cache miss.
+    pub fn coalesced_requests(&self) -> u64 {
+        self.inner.coalesced.load(Ordering::Relaxed)
+    }
+
     /// Get the hit rate as a percentage (0.0-100.0)
     pub fn hit_rate(&self) -> f64 {
         let hits = self.hits();
@@ -81,56 +236,224 @@ impl CacheStats {
         }
     }
 
+    /// Get the hit rate over recent lookups as a percentage (0.0-100.0)
+    ///
+    /// Only lookups within the last `window` count. Lookups are tracked per
+    /// second of the client clock (see `ClientBuilder::clock`), so `window` is
+    /// rounded up to whole seconds and capped at five minutes. Unlike
+    /// [`CacheStats::hit_rate`], this reflects how the cache is doing now, so
+    /// a cache that starts thrashing shows up right away. Returns 0.0 if there
+    /// were no lookups in the window.
+    pub fn recent_hit_rate(&self, window: Duration) -> f64 {
+        let now = self.current_second();
+        let window = window
+            .as_secs()
+            .saturating_add(u64::from(window.subsec_nanos() > 0))
+            .clamp(1, RECENT_BUCKETS as u64) as u32;
+        let (hits, total) = self
+            .inner
+            .recent
+            .iter()
+            .fold((0, 0), |(hits, total), bucket| {
+                (
+                    hits + RecentBucket::count(&bucket.hits, now, window),
+                    total + RecentBucket::count(&bucket.lookups, now, window),
+                )
+            });
+        if total == 0 {
+            0.0
+        } else {
+            (hits as f64 / total as f64) * 100.0
+        }
+    }
+
+    /// Read all counters at once
+    ///
+    /// Unlike calling the individual getters, the returned values are
+    /// consistent with each other, which makes the snapshot suitable for
+    /// logging or computing deltas.
+    pub fn snapshot(&self) -> CacheStatsSnapshot {
+        let _gate = self.inner.gate.write().unwrap_or_else(|e| e.into_inner());
+        CacheStatsSnapshot {
+            hits: self.hits(),
+            misses: self.misses(),
+            insertions: self.insertions(),
+            evictions: self.evictions(),
+            expirations: self.expirations(),
+            coalesced_requests: self.coalesced_requests(),
+        }
+    }
+
     /// Reset all statistics to zero
     pub fn reset(&self) {
+        let _gate = self.inner.gate.write().unwrap_or_else(|e| e.into_inner());
         self.inner.hits.store(0, Ordering::Relaxed);
         self.inner.misses.store(0, Ordering::Relaxed);
         self.inner.insertions.store(0, Ordering::Relaxed);
         self.inner.evictions.store(0, Ordering::Relaxed);
         self.inner.expirations.store(0, Ordering::Relaxed);
+        self.inner.coalesced.store(0, Ordering::Relaxed);
+        for bucket in self.inner.recent.iter() {
+            bucket.hits.store(0, Ordering::Relaxed);
+            bucket.lookups.store(0, Ordering::Relaxed);
+        }
     }
 
     // Internal methods for updating stats
+    fn update(&self, counters: &[&AtomicU64]) {
+        let _gate = self.inner.gate.read().unwrap_or_else(|e| e.into_inner());
+        for counter in counters {
+            let _ = counter.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    /// The current second of the client clock, truncated to 32 bits
+    fn current_second(&self) -> u32 {
+        self.inner.clock.now().unix_timestamp() as u32
+    }
+
+    fn record_lookup(&self, hit: bool) {
+        let second = self.current_second();
+        let bucket = &self.inner.recent[second as usize % RECENT_BUCKETS];
+        RecentBucket::increment(&bucket.lookups, second);
+        if hit {
+            RecentBucket::increment(&bucket.hits, second);
+        }
+    }
+
     pub(crate) fn record_hit(&self) {
-        let _ = self.inner.hits.fetch_add(1, Ordering::Relaxed);
+        self.update(&[&self.inner.hits]);
+        self.record_lookup(true);
     }
 
     pub(crate) fn record_miss(&self) {
-        let _ = self.inner.misses.fetch_add(1, Ordering::Relaxed);
+        self.update(&[&self.inner.misses]);
+        self.record_lookup(false);
     }
 
     pub(crate) fn record_insertion(&self) {
-        let _ = self.inner.insertions.fetch_add(1, Ordering::Relaxed);
+        self.update(&[&self.inner.insertions]);
+    }
+
+    pub(crate) fn record_coalesced(&self) {
+        self.update(&[&self.inner.coalesced]);
     }
 
     #[allow(dead_code)]
     pub(crate) fn record_eviction(&self) {
-        let _ = self.inner.evictions.fetch_add(1, Ordering::Relaxed);
+        self.update(&[&self.inner.evictions]);
     }
 
-    pub(crate) fn record_expiration(&self) {
-        let _ = self.inner.expirations.fetch_add(1, Ordering::Relaxed);
+    /// Record a lookup that found an expired entry (an expiration and a miss)
+    pub(crate) fn record_expired_miss(&self) {
+        self.update(&[&self.inner.expirations, &self.inner.misses]);
+        self.record_lookup(false);
     }
 }
 
+/// Encryption applied to secret values before they are cached
+///
+/// Register an implementation with `ClientBuilder::cache_cipher` to keep
+/// plaintext values out of the in-memory cache and the disk cache. Values are
+/// encrypted on insert and decrypted on every cache hit; an entry that fails
+/// to decrypt is dropped and treated as a miss. Key management is up to the
+/// implementation.
+///
+/// # Example
+///
+/// ```
+/// use secret_store_sdk::{CacheCipher, Result};
+///
+/// #[derive(Debug)]
+/// struct XorCipher(u8);
+///
+/// impl CacheCipher for XorCipher {
+///     fn encrypt(&self, plaintext: &[u8]) -> Vec<u8> {
+///         plaintext.iter().map(|b| b ^ self.0).collect()
+///     }
+///
+///     fn decrypt(&self, ciphertext: &[u8]) -> Result<Vec<u8>> {
+///         Ok(ciphertext.iter().map(|b| b ^ self.0).collect())
+///     }
+/// }
+/// ```
+pub trait CacheCipher: Send + Sync + Debug {
+    /// Encrypt a plaintext value before it is cached
+    fn encrypt(&self, plaintext: &[u8]) -> Vec<u8>;
+
+    /// Decrypt a value read back from the cache
+    fn decrypt(&self, ciphertext: &[u8]) -> Result<Vec<u8>>;
+}
+
+/// Secret value as held in the cache
+#[derive(Clone)]
+pub(crate) enum CachedValue {
+    Plain(SecretString),
+    Encrypted(Vec<u8>),
+}
+
+impl CachedValue {
+    /// Wrap a value for caching, encrypting it if a cipher is configured
+    pub fn seal(value: &SecretString, cipher: Option<&dyn CacheCipher>) -> Self {
+        match cipher {
+            Some(cipher) => {
+                CachedValue::Encrypted(cipher.encrypt(value.expose_secret().as_bytes()))
+            }
+            None => CachedValue::Plain(value.clone()),
+        }
+    }
+
+    /// Recover the plaintext value
+    pub fn open(self, cipher: Option<&dyn CacheCipher>) -> Result<SecretString> {
+        match (self, cipher) {
+            (CachedValue::Plain(value), _) => Ok(value),
+            (CachedValue::Encrypted(ciphertext), Some(cipher)) => {
+                decrypt_value(cipher, &ciphertext)
+            }
+            (CachedValue::Encrypted(_), None) => Err(Error::Other(
+                "Cached value is encrypted but no cache cipher is configured".to_string(),
+            )),
+        }
+    }
+}
+
+impl Debug for CachedValue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CachedValue::Plain(_) => f.write_str("Plain([REDACTED])"),
+            CachedValue::Encrypted(ciphertext) => {
+                write!(f, "Encrypted({} bytes)", ciphertext.len())
+            }
+        }
+    }
+}
+
+/// Decrypt a cached value into a secret string
+pub(crate) fn decrypt_value(cipher: &dyn CacheCipher, ciphertext: &[u8]) -> Result<SecretString> {
+    let plaintext = Zeroizing::new(cipher.decrypt(ciphertext)?);
+    let value = std::str::from_utf8(&plaintext)
+        .map_err(|_| Error::Other("Decrypted cache value is not valid UTF-8".to_string()))?;
+    Ok(SecretString::new(value.to_string()))
+}
+
 /// Cached secret entry
 #[derive(Debug, Clone)]
 pub(crate) struct CachedSecret {
-    pub value: secrecy::SecretString,
+    pub value: CachedValue,
     pub version: i32,
     pub expires_at: Option<time::OffsetDateTime>,
     pub metadata: serde_json::Value,
     pub updated_at: time::OffsetDateTime,
     pub etag: Option<String>,
     pub last_modified: Option<String>,
+    pub format: Option<String>,
+    pub kid: Option<String>,
     pub cache_expires_at: time::OffsetDateTime,
 }
 
 impl CachedSecret {
-    /// Check if the cache entry has expired
-    pub fn is_expired(&self) -> bool {
-        let now = time::OffsetDateTime::now_utc();
-
+    /// Check if the cache entry has expired at the given time
+    pub fn is_expired_at(&self, now: time::OffsetDateTime) -> bool {
         // Check cache expiry
         if now >= self.cache_expires_at {
             return true;
@@ -146,12 +469,34 @@ impl CachedSecret {
         false
     }
 
-    /// Convert to a Secret model
-    pub fn into_secret(self, namespace: String, key: String) -> crate::models::Secret {
-        crate::models::Secret {
+    /// Check if an expired entry may still stand in for a failed fetch
+    ///
+    /// The entry must be within `max_stale` of its cache expiry, and the
+    /// secret itself must not have expired.
+    pub fn is_servable_stale_at(
+        &self,
+        now: time::OffsetDateTime,
+        max_stale: time::Duration,
+    ) -> bool {
+        if let Some(stale_until) = self.cache_expires_at.checked_add(max_stale) {
+            if now >= stale_until {
+                return false;
+            }
+        }
+        self.expires_at.map_or(true, |expires_at| now < expires_at)
+    }
+
+    /// Convert to a Secret model, decrypting the value if needed
+    pub fn into_secret(
+        self,
+        namespace: String,
+        key: String,
+        cipher: Option<&dyn CacheCipher>,
+    ) -> Result<crate::models::Secret> {
+        Ok(crate::models::Secret {
             namespace,
             key,
-            value: self.value,
+            value: self.value.open(cipher)?,
             version: self.version,
             expires_at: self.expires_at,
             metadata: self.metadata,
@@ -159,13 +504,49 @@ impl CachedSecret {
             etag: self.etag,
             last_modified: self.last_modified,
             request_id: None, // Cache hits don't have request IDs
-        }
+            from_cache: true,
+            format: self.format,
+            kid: self.kid,
+        })
+    }
+}
+
+/// Cached listing response (raw JSON body)
+#[derive(Debug, Clone)]
+pub(crate) struct CachedList {
+    pub body: Vec<u8>,
+    pub etag: Option<String>,
+    pub fresh_until: time::OffsetDateTime,
+}
+
+impl CachedList {
+    /// Check if the listing can be served without revalidation at the given time
+    pub fn is_fresh_at(&self, now: time::OffsetDateTime) -> bool {
+        now < self.fresh_until
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::clock::SystemClock;
+    use std::sync::Mutex;
+    use time::OffsetDateTime;
+
+    #[derive(Debug)]
+    struct ManualClock(Mutex<OffsetDateTime>);
+
+    impl ManualClock {
+        fn advance(&self, by: Duration) {
+            *self.0.lock().unwrap() += by;
+        }
+    }
+
+    impl Clock for ManualClock {
+        fn now(&self) -> OffsetDateTime {
+            *self.0.lock().unwrap()
+        }
+    }
 
     #[test]
     fn test_cache_config_default() {
@@ -173,11 +554,13 @@ mod tests {
         assert!(config.enabled);
         assert_eq!(config.max_entries, crate::DEFAULT_CACHE_MAX_ENTRIES);
         assert_eq!(config.default_ttl_secs, crate::DEFAULT_CACHE_TTL_SECS);
+        assert_eq!(config.list_ttl_secs, crate::DEFAULT_LIST_CACHE_TTL_SECS);
+        assert_eq!(config.stale_if_error_secs, 0);
     }
 
     #[test]
     fn test_cache_stats() {
-        let stats = CacheStats::new();
+        let stats = CacheStats::new(Arc::new(SystemClock));
 
         // Initial state
         assert_eq!(stats.hits(), 0);
@@ -199,6 +582,85 @@ mod tests {
         assert_eq!(stats.misses(), 0);
     }
 
+    #[test]
+    fn test_recent_hit_rate_tracks_degradation() {
+        let clock = Arc::new(ManualClock(Mutex::new(OffsetDateTime::UNIX_EPOCH)));
+        clock.advance(Duration::from_secs(1_700_000_000));
+        let stats = CacheStats::new(clock.clone());
+        let window = Duration::from_secs(60);
+        assert_eq!(stats.recent_hit_rate(window), 0.0);
+
+        for _ in 0..10_000 {
+            stats.record_hit();
+        }
+        assert_eq!(stats.recent_hit_rate(window), 100.0);
+
+        // The cache starts thrashing
+        clock.advance(Duration::from_secs(60));
+        for _ in 0..500 {
+            stats.record_miss();
+        }
+        for _ in 0..500 {
+            stats.record_hit();
+        }
+        assert_eq!(stats.recent_hit_rate(window), 50.0);
+        clock.advance(Duration::from_secs(1));
+        for _ in 0..1_000 {
+            stats.record_expired_miss();
+        }
+        assert_eq!(stats.recent_hit_rate(window), 25.0);
+        assert_eq!(stats.recent_hit_rate(Duration::from_millis(10)), 0.0);
+        assert!(stats.hit_rate() > 80.0);
+
+        // Buckets are reused once they fall out of the tracked range
+        clock.advance(Duration::from_secs(RECENT_BUCKETS as u64));
+        stats.record_hit();
+        assert_eq!(stats.recent_hit_rate(Duration::from_secs(3600)), 100.0);
+
+        stats.reset();
+        assert_eq!(stats.recent_hit_rate(window), 0.0);
+    }
+
+    #[test]
+    fn test_cache_stats_snapshot_is_consistent() {
+        let stats = CacheStats::new(Arc::new(SystemClock));
+        stats.record_hit();
+
+        let workers: Vec<_> = (0..4)
+            .map(|_| {
+                let stats = stats.clone();
+                std::thread::spawn(move || {
+                    for _ in 0..10_000 {
+                        stats.record_expired_miss();
+                    }
+                })
+            })
+            .collect();
+
+        // Every expiration is paired with a miss, so no snapshot may split them
+        let mut previous = stats.snapshot();
+        while workers.iter().any(|worker| !worker.is_finished()) {
+            let snapshot = stats.snapshot();
+            assert_eq!(snapshot.expirations, snapshot.misses);
+            assert!(snapshot.misses >= previous.misses);
+            assert_eq!(snapshot.hits, 1);
+            previous = snapshot;
+        }
+        for worker in workers {
+            worker.join().unwrap();
+        }
+
+        let snapshot = stats.snapshot();
+        assert_eq!(snapshot.misses, 40_000);
+        assert_eq!(snapshot.expirations, 40_000);
+        assert!((snapshot.hit_rate() - 100.0 / 40_001.0).abs() < 1e-9);
+
+        // Snapshots do not follow later updates
+        stats.record_hit();
+        assert_eq!(snapshot.hits, 1);
+        assert_eq!(stats.snapshot().hits, 2);
+    }
+
     #[test]
     fn test_cached_secret_expiry() {
         use time::Duration;
@@ -207,41 +669,73 @@ mod tests {
 
         // Not expired
         let cached = CachedSecret {
-            value: secrecy::SecretString::new("value".to_string()),
+            value: CachedValue::Plain(SecretString::new("value".to_string())),
             version: 1,
             expires_at: None,
             metadata: serde_json::Value::Null,
             updated_at: now,
             etag: None,
             last_modified: None,
+            format: None,
+            kid: None,
             cache_expires_at: now + Duration::minutes(5),
         };
-        assert!(!cached.is_expired());
+        assert!(!cached.is_expired_at(now));
 
         // Cache expired
         let cached = CachedSecret {
-            value: secrecy::SecretString::new("value".to_string()),
+            value: CachedValue::Plain(SecretString::new("value".to_string())),
             version: 1,
             expires_at: None,
             metadata: serde_json::Value::Null,
             updated_at: now,
             etag: None,
             last_modified: None,
+            format: None,
+            kid: None,
             cache_expires_at: now - Duration::minutes(1),
         };
-        assert!(cached.is_expired());
+        assert!(cached.is_expired_at(now));
 
         // Secret expired
         let cached = CachedSecret {
-            value: secrecy::SecretString::new("value".to_string()),
+            value: CachedValue::Plain(SecretString::new("value".to_string())),
             version: 1,
             expires_at: Some(now - Duration::minutes(1)),
             metadata: serde_json::Value::Null,
             updated_at: now,
             etag: None,
             last_modified: None,
+            format: None,
+            kid: None,
             cache_expires_at: now + Duration::minutes(5),
         };
-        assert!(cached.is_expired());
+        assert!(cached.is_expired_at(now));
+    }
+
+    #[test]
+    fn test_cached_secret_stale_window() {
+        use time::Duration;
+
+        let now = time::OffsetDateTime::now_utc();
+        let mut cached = CachedSecret {
+            value: CachedValue::Plain(SecretString::new("value".to_string())),
+            version: 1,
+            expires_at: None,
+            metadata: serde_json::Value::Null,
+            updated_at: now,
+            etag: None,
+            last_modified: None,
+            format: None,
+            kid: None,
+            cache_expires_at: now - Duration::minutes(1),
+        };
+        assert!(cached.is_servable_stale_at(now, Duration::minutes(5)));
+        assert!(!cached.is_servable_stale_at(now, Duration::seconds(30)));
+        assert!(cached.is_servable_stale_at(now, Duration::seconds(i64::MAX)));
+
+        // Secrets past their own expiry are never served
+        cached.expires_at = Some(now - Duration::seconds(1));
+        assert!(!cached.is_servable_stale_at(now, Duration::minutes(5)));
     }
 }
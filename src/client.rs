@@ -49,24 +49,52 @@
 //! ```
 
 use crate::{
-    cache::{CacheStats, CachedSecret},
+    auth::TokenProviderPanic,
+    cache::{CacheStats, CachedList, CachedSecret, CachedValue},
     config::ClientConfig,
+    connection_debug,
     endpoints::Endpoints,
-    errors::{Error, ErrorResponse, Result},
+    errors::{AttemptRecord, Error, ErrorResponse, Result},
+    events::ChangeSubscription,
+    export,
+    health::{self, HealthMonitorHandle},
+    import::{self, ImportFormat, ImportOpts, ImportProgress, ImportResult},
     models::*,
-    util::{generate_request_id, header_str},
+    prometheus::{self, PrometheusMetric},
+    retry::{is_replay_safe, DeadlineBackoff, RetryPolicy, RetryStats, MAX_CONFLICT_RETRIES},
+    scoped::ScopedClient,
+    single_flight::{Role, SingleFlight},
+    stream::{BodyCheck, SecretStream},
+    util::{
+        api_version_at_least, declared_sha256, generate_request_id, header_str, log_cache_key,
+        parse_http_date, request_body_len, PageLimits,
+    },
 };
 
+#[cfg(feature = "disk-cache")]
+use crate::disk_cache::DiskCache;
+#[cfg(feature = "metrics")]
+use crate::endpoints::RouteLabel;
+#[cfg(feature = "tower")]
+use crate::service::{SecretRequest, SecretResponse, SecretService};
 #[cfg(feature = "metrics")]
 use crate::telemetry;
 use backoff::{future::retry_notify, ExponentialBackoff};
 use moka::future::Cache;
 use reqwest::{Client as HttpClient, Method, Response, StatusCode};
-use secrecy::SecretString;
+use secrecy::{ExposeSecret, SecretString};
 use std::time::Duration;
+use tokio_util::sync::CancellationToken;
 use tracing::{debug, trace, warn};
 
 const USER_AGENT_PREFIX: &str = "xjp-secret-store-sdk-rust";
+/// Minimum server API version for cursor pagination
+const CURSOR_PAGINATION_MIN_API_VERSION: &str = "v2.1";
+const NAMESPACES_LIST_CACHE_KEY: &str = "namespaces";
+/// How long a [`Client::whoami`] result is reused
+const IDENTITY_CACHE_TTL: time::Duration = time::Duration::seconds(60);
+/// Clock skew above which [`Client::check_time_skew`] logs a warning
+const TIME_SKEW_WARN_THRESHOLD: time::Duration = time::Duration::seconds(30);
 
 /// XJP Secret Store client
 ///
@@ -79,7 +107,22 @@ pub struct Client {
     http: HttpClient,
     endpoints: Endpoints,
     cache: Option<Cache<String, CachedSecret>>,
+    list_cache: Option<Cache<String, CachedList>>,
+    #[cfg(feature = "disk-cache")]
+    disk_cache: Option<DiskCache>,
     stats: CacheStats,
+    retry_stats: RetryStats,
+    discovery: std::sync::Arc<std::sync::RwLock<Option<Discovery>>>,
+    identity: std::sync::Arc<std::sync::RwLock<Option<(time::OffsetDateTime, IdentityInfo)>>>,
+    cancellation: Option<CancellationToken>,
+    /// Normalized base URLs: the primary followed by the fallbacks
+    hosts: std::sync::Arc<Vec<String>>,
+    /// Index into `hosts` of the host requests are sent to
+    active_host: std::sync::Arc<std::sync::atomic::AtomicUsize>,
+    /// Concurrent gets in flight, unless single-flight is disabled
+    single_flight: Option<std::sync::Arc<SingleFlight>>,
+    /// Set once a deprecation warning has been logged
+    deprecation_warned: std::sync::Arc<std::sync::atomic::AtomicBool>,
     #[cfg(feature = "metrics")]
     metrics: std::sync::Arc<telemetry::Metrics>,
 }
@@ -110,9 +153,16 @@ impl Client {
             .user_agent(user_agent)
             .timeout(config.timeout)
             .pool_idle_timeout(Duration::from_secs(90))
-            .pool_max_idle_per_host(10)
+            .pool_max_idle_per_host(config.pool_max_idle_per_host)
+            .tcp_keepalive(config.tcp_keepalive)
+            .tcp_nodelay(config.tcp_nodelay)
             .http2_prior_knowledge();
 
+        if config.connection_debug {
+            http_builder =
+                http_builder.dns_resolver(std::sync::Arc::new(connection_debug::TimingResolver));
+        }
+
         // Configure TLS
         #[cfg(not(feature = "danger-insecure-http"))]
         {
@@ -130,18 +180,46 @@ impl Client {
             .build()
             .map_err(|e| Error::Config(format!("Failed to build HTTP client: {}", e)))?;
 
-        // Create cache if enabled
+        // Create cache if enabled, keeping entries through the stale-if-error window
         let cache = if config.cache_config.enabled {
+            let ttl_secs = config
+                .cache_config
+                .default_ttl_secs
+                .saturating_add(config.cache_config.stale_if_error_secs);
+            Some(
+                Cache::builder()
+                    .max_capacity(config.cache_config.max_entries)
+                    .time_to_live(Duration::from_secs(ttl_secs))
+                    .build(),
+            )
+        } else {
+            None
+        };
+
+        // Listings are cached separately so they can use a shorter freshness TTL.
+        // Entries outlive that TTL so stale listings can still be revalidated by ETag.
+        let list_cache = if config.cache_config.enabled {
+            let ttl_secs = config
+                .cache_config
+                .default_ttl_secs
+                .max(config.cache_config.list_ttl_secs);
             Some(
                 Cache::builder()
                     .max_capacity(config.cache_config.max_entries)
-                    .time_to_live(Duration::from_secs(config.cache_config.default_ttl_secs))
+                    .time_to_live(Duration::from_secs(ttl_secs))
                     .build(),
             )
         } else {
             None
         };
 
+        #[cfg(feature = "disk-cache")]
+        let disk_cache = config
+            .disk_cache
+            .as_ref()
+            .map(|disk_cache| DiskCache::open(disk_cache, config.cache_cipher.clone()))
+            .transpose()?;
+
         // Initialize telemetry if enabled
         #[cfg(feature = "metrics")]
         let metrics = if config.telemetry_config.enabled {
@@ -150,21 +228,195 @@ impl Client {
             std::sync::Arc::new(telemetry::Metrics::new(&config.telemetry_config))
         };
 
+        // Requests are built against the primary URL and rebased on failover,
+        // so compare URLs in the normalized form reqwest sends them in
+        let hosts = std::iter::once(&config.base_url)
+            .chain(&config.fallback_urls)
+            .map(|url| {
+                reqwest::Url::parse(url)
+                    .map(|parsed| parsed.as_str().trim_end_matches('/').to_string())
+                    .unwrap_or_else(|_| url.clone())
+            })
+            .collect();
+
         Ok(Self {
             endpoints: Endpoints::new(&config.base_url),
+            hosts: std::sync::Arc::new(hosts),
+            active_host: Default::default(),
+            single_flight: config.single_flight.then(Default::default),
+            deprecation_warned: Default::default(),
             http,
             cache,
-            stats: CacheStats::new(),
+            list_cache,
+            #[cfg(feature = "disk-cache")]
+            disk_cache,
+            stats: CacheStats::new(config.clock.clone()),
+            retry_stats: RetryStats::new(),
+            discovery: Default::default(),
+            identity: Default::default(),
+            cancellation: None,
             #[cfg(feature = "metrics")]
             metrics,
             config,
         })
     }
 
+    /// Base URL requests are currently sent to
+    ///
+    /// This is the configured base URL until a connect failure moves the
+    /// client on to one of its
+    /// [`fallback_urls`](crate::ClientBuilder::fallback_urls). The client
+    /// stays on the new host until it fails in turn.
+    pub fn active_base_url(&self) -> &str {
+        &self.hosts[self.active_host.load(std::sync::atomic::Ordering::Relaxed)]
+    }
+
+    /// Create a client whose requests stop when `token` is cancelled
+    ///
+    /// The returned client shares this client's connection pool, cache and
+    /// statistics. Once the token is cancelled, its in-flight requests,
+    /// including retry backoff sleeps, end promptly with [`Error::Cancelled`].
+    /// Useful for tying secret lookups to the lifetime of a server request.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use secret_store_sdk::{CancellationToken, Client, GetOpts};
+    /// # async fn example(client: &Client) -> Result<(), Box<dyn std::error::Error>> {
+    /// let token = CancellationToken::new();
+    /// let scoped = client.with_cancellation(token.clone());
+    /// let handle = tokio::spawn(async move {
+    ///     scoped.get_secret("production", "db-url", GetOpts::default()).await
+    /// });
+    /// token.cancel();
+    /// let _ = handle.await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_cancellation(&self, token: CancellationToken) -> Client {
+        Client {
+            cancellation: Some(token),
+            ..self.clone()
+        }
+    }
+
+    /// Create a client bound to a single namespace
+    ///
+    /// The returned [`ScopedClient`] shares this client's connection pool and
+    /// cache, so creating one is cheap.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use secret_store_sdk::{Client, GetOpts};
+    /// # async fn example(client: &Client) -> Result<(), Box<dyn std::error::Error>> {
+    /// let db_secrets = client.scoped("database");
+    /// let url = db_secrets.get("url", GetOpts::default()).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn scoped(&self, namespace: impl Into<String>) -> ScopedClient {
+        ScopedClient::new(self.clone(), namespace.into())
+    }
+
+    /// Expose the request path as a [`tower::Service`] (requires tower feature)
+    ///
+    /// Requests sent through the returned [`SecretService`] go through the
+    /// same path as [`Client::raw_request`]: they get the authentication,
+    /// retries, failover and metrics of the high-level methods, never read
+    /// from the caches, and successful writes invalidate the cache entries
+    /// they may have changed. Like [`scoped`](Self::scoped), the service
+    /// shares this client's connection pool.
+    ///
+    /// The high-level methods (`get_secret`, `put_secret` and the rest) do
+    /// not go through this service, so Tower layers wrapped around it only
+    /// see requests sent to the service itself. To rate limit or shed load
+    /// across an application, send its requests through the layered service.
+    #[cfg(feature = "tower")]
+    pub fn service(&self) -> SecretService {
+        SecretService::new(self.clone())
+    }
+
+    /// Execute a raw request for [`SecretService`]
+    #[cfg(feature = "tower")]
+    pub(crate) async fn send_raw(&self, request: SecretRequest) -> Result<SecretResponse> {
+        let response = self
+            .execute_raw(
+                request.method,
+                &request.path,
+                request.headers,
+                request.body.as_ref(),
+            )
+            .await?;
+        let status = response.status().as_u16();
+        let headers = response.headers().clone();
+        let body = self.read_body(response).await?;
+
+        Ok(SecretResponse {
+            status,
+            headers,
+            body,
+        })
+    }
+
+    /// Pre-fetch secrets into the cache
+    ///
+    /// Fetches `keys` with at most `concurrency` requests in flight and
+    /// returns how many were cached. Failures are logged and skipped, so a
+    /// missing key never fails warmup.
+    pub async fn warm_cache<K: AsRef<str>>(
+        &self,
+        namespace: &str,
+        keys: &[K],
+        concurrency: usize,
+    ) -> usize {
+        let mut warmed = 0;
+        for (key, result) in self.get_many(namespace, keys, concurrency).await {
+            match result {
+                Ok(_) => warmed += 1,
+                Err(e) => {
+                    let cache_key = format!("{}/{}", namespace, key);
+                    warn!(
+                        "Failed to warm cache for {}: {}",
+                        self.log_key(&cache_key),
+                        e
+                    );
+                }
+            }
+        }
+        warmed
+    }
+
+    /// Pre-fetch the keys registered with [`ClientBuilder::warm_keys`](crate::ClientBuilder::warm_keys)
+    ///
+    /// Uses [`Client::warm_cache`] with 8 requests in flight per namespace and
+    /// returns the total number of secrets cached. Failures are non-fatal.
+    pub async fn warm(&self) -> usize {
+        let mut warmed = 0;
+        for (namespace, keys) in &self.config.warm_keys {
+            warmed += self.warm_cache(namespace, keys, 8).await;
+        }
+        warmed
+    }
+
+    /// Open a connection to the server with a liveness request
+    ///
+    /// Used by [`ClientBuilder::build_async`](crate::ClientBuilder::build_async).
+    /// Any HTTP response means the connection is established.
+    pub(crate) async fn warm_connection(&self) -> Result<()> {
+        let url = self.endpoints.livez();
+        let request = self
+            .build_request(Method::GET, &url)?
+            .timeout(self.config.health_timeout);
+        let _ = self.execute_without_retry(request).await?;
+        Ok(())
+    }
+
     /// Get cache statistics
     ///
     /// Returns statistics about the cache including hit rate, number of hits/misses,
-    /// and evictions. Useful for monitoring cache performance.
+    /// and evictions. Useful for monitoring cache performance. The counters are
+    /// live; use [`CacheStats::snapshot`] for a consistent point-in-time copy.
     ///
     /// # Example
     ///
@@ -180,9 +432,39 @@ impl Client {
         &self.stats
     }
 
+    /// Get retry statistics
+    ///
+    /// Returns counters for retries performed by this client, broken down by
+    /// reason. Unlike the OpenTelemetry metrics, these are always collected.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use secret_store_sdk::{Client, ClientBuilder, Auth};
+    /// # async fn example(client: &Client) {
+    /// let stats = client.retry_stats();
+    /// println!("Total retries: {}", stats.total());
+    /// println!("Retries on 503: {}", stats.by_reason("503"));
+    /// # }
+    /// ```
+    pub fn retry_stats(&self) -> &RetryStats {
+        &self.retry_stats
+    }
+
+    /// Create a builder pre-populated with this client's settings
+    ///
+    /// See [`ClientConfig::to_builder`](crate::ClientConfig::to_builder); the
+    /// derived client carries this client's credentials unless they are
+    /// replaced with [`ClientBuilder::auth`](crate::ClientBuilder::auth). The
+    /// new client has its own cache and statistics.
+    pub fn config_builder(&self) -> crate::ClientBuilder {
+        self.config.to_builder()
+    }
+
     /// Clear the cache
     ///
-    /// Removes all entries from the cache and resets cache statistics.
+    /// Removes all entries (secrets and listings) from the cache, including
+    /// the on-disk cache if configured, and resets cache statistics.
     /// This is useful when you need to force fresh data retrieval.
     ///
     /// # Example
@@ -199,12 +481,20 @@ impl Client {
             cache.invalidate_all();
             self.stats.reset();
         }
+        if let Some(list_cache) = &self.list_cache {
+            list_cache.invalidate_all();
+        }
+        #[cfg(feature = "disk-cache")]
+        if let Some(disk_cache) = &self.disk_cache {
+            disk_cache.clear();
+        }
     }
 
     /// Invalidate a specific cache entry
     ///
-    /// Removes a single secret from the cache, forcing the next retrieval
-    /// to fetch fresh data from the server.
+    /// Removes a single secret from the cache (and the on-disk cache if
+    /// configured), forcing the next retrieval to fetch fresh data from the
+    /// server.
     ///
     /// # Arguments
     ///
@@ -222,10 +512,77 @@ impl Client {
     /// # }
     /// ```
     pub async fn invalidate_cache(&self, namespace: &str, key: &str) {
+        let cache_key = format!("{}/{}", namespace, key);
         if let Some(cache) = &self.cache {
-            let cache_key = format!("{}/{}", namespace, key);
             cache.invalidate(&cache_key).await;
         }
+        #[cfg(feature = "disk-cache")]
+        if let Some(disk_cache) = &self.disk_cache {
+            let _ = disk_cache
+                .run(move |disk_cache| disk_cache.remove(&cache_key))
+                .await;
+        }
+    }
+
+    /// Invalidate cached secrets in `namespace` tagged with a metadata value
+    ///
+    /// Removes every cached secret whose metadata field `key` equals `value`,
+    /// or is an array containing it, e.g. all secrets with
+    /// `depends_on: "ca-root"` after the CA certificate rotates. Works on the
+    /// in-memory cache only, without contacting the server; matching entries
+    /// are also removed from the on-disk cache. Returns the number of secrets
+    /// invalidated.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use secret_store_sdk::Client;
+    /// # async fn example(client: &Client) {
+    /// let removed = client.invalidate_by_metadata("production", "depends_on", "ca-root").await;
+    /// println!("Invalidated {} dependent secrets", removed);
+    /// # }
+    /// ```
+    pub async fn invalidate_by_metadata(
+        &self,
+        namespace: &str,
+        key: &str,
+        value: impl Into<serde_json::Value>,
+    ) -> usize {
+        let Some(cache) = &self.cache else {
+            return 0;
+        };
+
+        let value = value.into();
+        let prefix = format!("{}/", namespace);
+        let matching: Vec<_> = cache
+            .iter()
+            .filter(|(cache_key, _)| cache_key.starts_with(&prefix))
+            .filter(|(_, cached)| match cached.metadata.get(key) {
+                Some(serde_json::Value::Array(items)) => items.contains(&value),
+                Some(field) => *field == value,
+                None => false,
+            })
+            .map(|(cache_key, _)| cache_key)
+            .collect();
+
+        for cache_key in &matching {
+            let secret_key = &cache_key[prefix.len()..];
+            self.invalidate_cache(namespace, secret_key).await;
+        }
+        matching.len()
+    }
+
+    /// Get the ETag persisted in the on-disk cache for a secret
+    ///
+    /// Useful with [`DiskCacheMode::EtagOnly`](crate::DiskCacheMode::EtagOnly),
+    /// where only the ETag survives between runs: pass it as
+    /// `GetOpts::if_none_match` to learn whether a secret changed since the
+    /// last run without persisting its value.
+    #[cfg(feature = "disk-cache")]
+    pub fn disk_cache_etag(&self, namespace: &str, key: &str) -> Option<String> {
+        self.disk_cache
+            .as_ref()?
+            .etag(&format!("{}/{}", namespace, key))
     }
 
     /// Get a secret from the store
@@ -249,9 +606,14 @@ impl Client {
     /// * `Error::Http` with status 404 if the secret doesn't exist
     /// * `Error::Http` with status 403 if access is denied
     /// * `Error::Http` with status 401 if authentication fails
+    /// * `Error::NotModifiedNoCache` if the server returns 304 but nothing is
+    ///   cached (unless `opts.on_stale_304` is `Stale304Policy::Refetch`)
     /// * `Error::Network` for connection issues
     /// * `Error::Timeout` if the request times out
     ///
+    /// Server, network and timeout errors are replaced by an expired cache
+    /// entry when stale serving is enabled (see `GetOpts::serve_stale_on_error`).
+    ///
     /// # Example
     ///
     /// ```no_run
@@ -280,20 +642,251 @@ impl Client {
     /// ```
     pub async fn get_secret(&self, namespace: &str, key: &str, opts: GetOpts) -> Result<Secret> {
         let cache_key = format!("{}/{}", namespace, key);
+        let use_cache = opts.use_cache && !opts.bypass_cache_entirely;
 
         // Check cache if enabled and requested
-        if opts.use_cache {
+        if use_cache {
             if let Some(cached) = self.get_from_cache(&cache_key).await {
                 return Ok(cached);
             }
         }
 
+        // Share an identical in-flight fetch rather than sending another
+        let coalesce =
+            use_cache && opts.if_none_match.is_none() && opts.if_modified_since.is_none();
+        let leader = match &self.single_flight {
+            Some(single_flight) if coalesce => match single_flight.join(&cache_key) {
+                Role::Leader(leader) => Some(leader),
+                Role::Follower(flight) => {
+                    if let Ok(Some(secret)) = flight.await {
+                        self.stats.record_coalesced();
+                        return Ok(secret);
+                    }
+                    None
+                }
+            },
+            _ => None,
+        };
+
+        let serve_stale = opts
+            .serve_stale_on_error
+            .unwrap_or(self.config.cache_config.stale_if_error_secs > 0);
+
+        let result = match self
+            .fetch_secret(namespace, key, &cache_key, &opts, use_cache)
+            .await
+        {
+            Err(e) if use_cache && serve_stale && Self::allows_stale_fallback(&e) => {
+                match self.get_stale_from_cache(&cache_key).await {
+                    Some(stale) => {
+                        warn!(
+                            "Serving stale cache entry for key {} after error: {}",
+                            self.log_key(&cache_key),
+                            e
+                        );
+                        Ok(stale)
+                    }
+                    None => Err(e),
+                }
+            }
+            result => result,
+        };
+
+        if let Some(leader) = leader {
+            leader.finish(result.as_ref().ok());
+        }
+        result
+    }
+
+    /// Get a secret's value as bytes
+    ///
+    /// Values the server reports as binary (see [`Secret::is_binary`]) are
+    /// base64-decoded, which round-trips values written with
+    /// [`Client::put_secret_bytes`]. Other values are returned as their UTF-8
+    /// bytes. The returned buffer is zeroed on drop.
+    ///
+    /// # Errors
+    ///
+    /// * Any error returned by [`Client::get_secret`]
+    /// * `Error::Deserialize` if a binary value is not valid base64
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use secret_store_sdk::{Client, GetOpts};
+    /// # async fn example(client: &Client) -> Result<(), Box<dyn std::error::Error>> {
+    /// use secrecy::ExposeSecret;
+    ///
+    /// let keystore = client
+    ///     .get_secret_bytes("production", "tls-keystore", GetOpts::default())
+    ///     .await?;
+    /// println!("{} bytes", keystore.expose_secret().len());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn get_secret_bytes(
+        &self,
+        namespace: &str,
+        key: &str,
+        opts: GetOpts,
+    ) -> Result<secrecy::SecretVec<u8>> {
+        use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
+
+        let secret = self.get_secret(namespace, key, opts).await?;
+        let value = secret.value.expose_secret();
+        let bytes = if secret.is_binary() {
+            BASE64.decode(value.trim()).map_err(|_| {
+                Error::Deserialize(format!(
+                    "Binary value of {}/{} is not valid base64",
+                    namespace, key
+                ))
+            })?
+        } else {
+            value.as_bytes().to_vec()
+        };
+        Ok(secrecy::SecretVec::new(bytes))
+    }
+
+    /// Read a secret's value as a stream
+    ///
+    /// For large values such as certificate bundles or keystores: the value
+    /// is requested as raw bytes (`Accept: application/octet-stream`) and
+    /// returned as an [`AsyncRead`](tokio::io::AsyncRead) that can be piped
+    /// to a file without holding the whole value in memory. Each chunk is
+    /// zeroed once it has been read. Servers that only answer with JSON are
+    /// supported too, but then the value is buffered before streaming.
+    ///
+    /// Streamed reads bypass the cache: the value is neither served from nor
+    /// stored in it, and `max_response_bytes` does not apply. Of `opts`, only
+    /// `if_none_match` and `if_modified_since` are used.
+    ///
+    /// With `verify_content_digest`, the value is hashed as it streams and a
+    /// digest mismatch (or a missing digest under `require_content_digest`)
+    /// fails the final read. The bytes are already written by then, so
+    /// discard the output if reading returns an error.
+    ///
+    /// # Errors
+    ///
+    /// * `Error::NotModifiedNoCache` if a conditional request is answered
+    ///   with 304
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use secret_store_sdk::{Client, GetOpts};
+    /// # async fn example(client: &Client) -> Result<(), Box<dyn std::error::Error>> {
+    /// let (metadata, mut value) = client
+    ///     .get_secret_stream("production", "tls-bundle", GetOpts::default())
+    ///     .await?;
+    /// // Any `AsyncWrite` works here, e.g. a file or a socket
+    /// let mut bundle = Vec::new();
+    /// let written = tokio::io::copy(&mut value, &mut bundle).await?;
+    /// println!("wrote {} bytes of version {:?}", written, metadata.version);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn get_secret_stream(
+        &self,
+        namespace: &str,
+        key: &str,
+        opts: GetOpts,
+    ) -> Result<(SecretMetadata, SecretStream)> {
+        let url = self.endpoints.get_secret(namespace, key);
+        let mut request = self
+            .build_request(Method::GET, &url)?
+            .header(reqwest::header::ACCEPT, "application/octet-stream");
+        if let Some(etag) = &opts.if_none_match {
+            request = request.header(reqwest::header::IF_NONE_MATCH, etag);
+        }
+        if let Some(modified) = &opts.if_modified_since {
+            request = request.header(reqwest::header::IF_MODIFIED_SINCE, modified);
+        }
+
+        let mut response = self.execute_with_retry(request).await?;
+        if response.status() == StatusCode::NOT_MODIFIED {
+            return Err(Error::NotModifiedNoCache {
+                request_id: header_str(response.headers(), "x-request-id"),
+            });
+        }
+
+        let content_type = header_str(response.headers(), "content-type");
+        if content_type
+            .as_deref()
+            .is_some_and(|t| t.starts_with("application/json"))
+        {
+            let secret = self.parse_get_response(response, namespace, key).await?;
+            let value = secret.value.expose_secret().as_bytes().to_vec();
+            let metadata = SecretMetadata {
+                namespace: secret.namespace,
+                key: secret.key,
+                version: Some(secret.version),
+                content_length: Some(value.len() as u64),
+                content_type: None,
+                etag: secret.etag,
+                last_modified: secret.last_modified,
+                request_id: secret.request_id,
+            };
+            return Ok((metadata, SecretStream::from_bytes(value)));
+        }
+
+        let headers = response.headers();
+        let metadata = SecretMetadata {
+            namespace: namespace.to_string(),
+            key: key.to_string(),
+            version: header_str(headers, "x-secret-version").and_then(|v| v.parse().ok()),
+            content_length: response.content_length(),
+            content_type,
+            etag: header_str(headers, "etag"),
+            last_modified: header_str(headers, "last-modified"),
+            request_id: header_str(headers, "x-request-id"),
+        };
+        let expected_sha256 = if self.config.verify_content_digest {
+            declared_sha256(headers)
+        } else {
+            None
+        };
+        let check = BodyCheck {
+            expected_sha256,
+            require_digest: self.config.require_content_digest,
+            on_complete: self.unmeasured_body_recorder(&mut response),
+        };
+        Ok((metadata, SecretStream::from_response(response, check)))
+    }
+
+    /// Fetch a secret from the server (or the disk cache) after an in-memory cache miss
+    async fn fetch_secret(
+        &self,
+        namespace: &str,
+        key: &str,
+        cache_key: &str,
+        opts: &GetOpts,
+        use_cache: bool,
+    ) -> Result<Secret> {
+        // Fall back to the on-disk cache, revalidating its entry by ETag
+        #[cfg(feature = "disk-cache")]
+        let disk_secret = match &self.disk_cache {
+            Some(disk_cache) if use_cache => {
+                let (cache_key, now) = (cache_key.to_string(), self.config.clock.now());
+                disk_cache
+                    .run(move |disk_cache| disk_cache.load(&cache_key, now))
+                    .await
+                    .flatten()
+            }
+            _ => None,
+        };
+        #[cfg(not(feature = "disk-cache"))]
+        let disk_secret: Option<Secret> = None;
+
         // Build request
         let url = self.endpoints.get_secret(namespace, key);
         let mut request = self.build_request(Method::GET, &url)?;
 
         // Add conditional headers
-        if let Some(etag) = &opts.if_none_match {
+        let if_none_match = opts
+            .if_none_match
+            .clone()
+            .or_else(|| disk_secret.as_ref().and_then(|s| s.etag.clone()));
+        if let Some(etag) = &if_none_match {
             request = request.header(reqwest::header::IF_NONE_MATCH, etag);
         }
         if let Some(modified) = &opts.if_modified_since {
@@ -301,26 +894,59 @@ impl Client {
         }
 
         // Execute with retry
-        let response = self.execute_with_retry(request).await?;
+        let mut response = self.execute_with_retry(request).await?;
 
         // Handle 304 Not Modified
         if response.status() == StatusCode::NOT_MODIFIED {
             // Try to return from cache if available
-            if let Some(cached) = self.get_from_cache(&cache_key).await {
-                return Ok(cached);
+            if !opts.bypass_cache_entirely {
+                if let Some(cached) = self.get_from_cache(cache_key).await {
+                    return Ok(cached);
+                }
+            }
+            // Only an entry whose own ETag was sent has been revalidated; a
+            // caller-supplied ETag may name a different version
+            let revalidated =
+                disk_secret.filter(|secret| secret.etag.is_some() && secret.etag == if_none_match);
+            if let Some(secret) = revalidated {
+                debug!(
+                    "Disk cache entry revalidated for key: {}",
+                    self.log_key(cache_key)
+                );
+                self.cache_secret(cache_key, &secret).await;
+                return Ok(secret);
+            }
+            // Nothing to serve: fail, or refetch without conditional headers
+            if opts.on_stale_304 == Stale304Policy::Refetch {
+                debug!(
+                    "304 without cached entry for key: {}, refetching",
+                    self.log_key(cache_key)
+                );
+                let request = self.build_request(Method::GET, &url)?;
+                response = self.execute_with_retry(request).await?;
+            }
+            if response.status() == StatusCode::NOT_MODIFIED {
+                return Err(Error::NotModifiedNoCache {
+                    request_id: header_str(response.headers(), "x-request-id"),
+                });
             }
-            // If not in cache, this is an error
-            return Err(Error::Other(
-                "Server returned 304 but no cached entry found".to_string(),
-            ));
         }
 
         // Parse response
         let secret = self.parse_get_response(response, namespace, key).await?;
 
         // Cache the secret if caching is enabled AND use_cache is true
-        if self.config.cache_config.enabled && opts.use_cache {
-            self.cache_secret(&cache_key, &secret).await;
+        if self.config.cache_config.enabled && use_cache {
+            self.cache_secret(cache_key, &secret).await;
+        }
+        #[cfg(feature = "disk-cache")]
+        if let Some(disk_cache) = &self.disk_cache {
+            if use_cache && self.config.cache_config.should_cache(&secret) {
+                let (cache_key, secret) = (cache_key.to_string(), secret.clone());
+                let _ = disk_cache
+                    .run(move |disk_cache| disk_cache.store(&cache_key, &secret))
+                    .await;
+            }
         }
 
         Ok(secret)
@@ -342,6 +968,11 @@ impl Client {
     ///
     /// A `PutResult` containing the operation details and timestamp.
     ///
+    /// # Errors
+    ///
+    /// * `Error::Config` if `value` is empty and `opts.allow_empty_value` is
+    ///   not set
+    ///
     /// # Security
     ///
     /// The secret value is transmitted over HTTPS and stored encrypted.
@@ -365,6 +996,7 @@ impl Client {
     ///         "rotation_date": "2024-12-01"
     ///     })),
     ///     idempotency_key: Some("deploy-12345".to_string()),
+    ///     ..Default::default()
     /// };
     /// client.put_secret("production", "api-key", "new-api-key", opts).await?;
     /// # Ok(())
@@ -377,2068 +1009,9937 @@ impl Client {
         value: impl Into<String>,
         opts: PutOpts,
     ) -> Result<PutResult> {
-        // Invalidate cache for this key
-        if let Some(cache) = &self.cache {
-            let cache_key = format!("{}/{}", namespace, key);
-            cache.invalidate(&cache_key).await;
-        }
-
-        // Build request body
-        let mut body = serde_json::json!({
-            "value": value.into(),
-        });
-
-        if let Some(ttl) = opts.ttl_seconds {
-            body["ttl_seconds"] = serde_json::json!(ttl);
-        }
-        if let Some(metadata) = opts.metadata {
-            body["metadata"] = metadata;
-        }
-
-        // Build request
-        let url = self.endpoints.put_secret(namespace, key);
-        let mut request = self.build_request(Method::PUT, &url)?;
-        request = request.json(&body);
-
-        // Add idempotency key if provided
-        if let Some(idempotency_key) = &opts.idempotency_key {
-            request = request.header("X-Idempotency-Key", idempotency_key);
-        }
-
-        // Execute with retry
-        let response = self.execute_with_retry(request).await?;
-
-        // Parse response
-        self.parse_json_response(response).await
+        self.send_put(namespace, key, &value.into(), opts, false, false)
+            .await
     }
 
-    /// Delete a secret from the store
-    pub async fn delete_secret(&self, namespace: &str, key: &str) -> Result<DeleteResult> {
-        // Invalidate cache for this key
-        if let Some(cache) = &self.cache {
-            let cache_key = format!("{}/{}", namespace, key);
-            cache.invalidate(&cache_key).await;
-        }
-
-        // Build request
-        let url = self.endpoints.delete_secret(namespace, key);
-        let request = self.build_request(Method::DELETE, &url)?;
-
-        // Execute with retry
-        let response = self.execute_with_retry(request).await?;
-        let request_id = header_str(response.headers(), "x-request-id");
-
-        // Check status
-        let deleted = response.status() == StatusCode::NO_CONTENT;
-
-        Ok(DeleteResult {
-            deleted,
-            request_id,
-        })
-    }
-
-    /// List secrets in a namespace
-    pub async fn list_secrets(&self, namespace: &str, opts: ListOpts) -> Result<ListSecretsResult> {
-        // Build URL with query parameters
-        let mut url = self.endpoints.list_secrets(namespace);
-
-        let mut query_parts = Vec::new();
-        if let Some(prefix) = &opts.prefix {
-            query_parts.push(format!(
-                "prefix={}",
-                percent_encoding::utf8_percent_encode(prefix, percent_encoding::NON_ALPHANUMERIC)
-            ));
-        }
-        if let Some(limit) = opts.limit {
-            query_parts.push(format!("limit={}", limit));
-        }
-
-        if !query_parts.is_empty() {
-            url.push('?');
-            url.push_str(&query_parts.join("&"));
-        }
-
-        // Build and execute request
-        let request = self.build_request(Method::GET, &url)?;
-        let response = self.execute_with_retry(request).await?;
-
-        // Parse response
-        self.parse_json_response(response).await
+    /// Create a secret only if it does not exist yet
+    ///
+    /// Sends `If-None-Match: *` so the server performs the existence check
+    /// atomically, avoiding a racy get-then-put. Use [`Client::put_secret`]
+    /// for create-or-update semantics.
+    ///
+    /// # Errors
+    ///
+    /// * `Error::AlreadyExists` if the server reports the secret exists
+    ///   (409 Conflict or 412 Precondition Failed)
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use secret_store_sdk::{Client, Error, PutOpts};
+    /// # async fn example(client: &Client) -> Result<(), Box<dyn std::error::Error>> {
+    /// match client.create_secret("production", "feature-flags", "{}", PutOpts::default()).await {
+    ///     Ok(_) => println!("Created default"),
+    ///     Err(Error::AlreadyExists { .. }) => println!("Keeping existing value"),
+    ///     Err(e) => return Err(e.into()),
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn create_secret(
+        &self,
+        namespace: &str,
+        key: &str,
+        value: impl Into<String>,
+        opts: PutOpts,
+    ) -> Result<PutResult> {
+        self.send_put(namespace, key, &value.into(), opts, true, false)
+            .await
+            .map_err(|e| match e {
+                Error::Http {
+                    status: status @ (409 | 412),
+                    request_id,
+                    ..
+                } => Error::AlreadyExists {
+                    namespace: namespace.to_string(),
+                    key: key.to_string(),
+                    status,
+                    request_id,
+                },
+                e => e,
+            })
     }
 
-    /// Batch get secrets
-    pub async fn batch_get(
+    /// Get a secret, creating it with `default` if it does not exist
+    ///
+    /// On 404 the default is written create-only (as with
+    /// [`Client::create_secret`]) using `opts`, and the stored secret is read
+    /// back. If another writer creates the secret in between, its value is
+    /// returned instead. [`GetOrCreateResult::created`] tells the cases apart.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use secret_store_sdk::{Client, PutOpts};
+    /// # async fn example(client: &Client) -> Result<(), Box<dyn std::error::Error>> {
+    /// let result = client
+    ///     .get_or_create("production", "feature-flags", "{}", PutOpts::default())
+    ///     .await?;
+    /// if result.created {
+    ///     println!("Initialised feature flags");
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn get_or_create(
         &self,
         namespace: &str,
-        keys: BatchKeys,
-        format: ExportFormat,
-    ) -> Result<BatchGetResult> {
-        let mut url = self.endpoints.batch_get(namespace);
-
-        // Build query parameters
-        match &keys {
-            BatchKeys::Keys(key_list) => {
-                let keys_param = key_list.join(",");
-                url.push_str(&format!(
-                    "?keys={}",
-                    percent_encoding::utf8_percent_encode(
-                        &keys_param,
-                        percent_encoding::NON_ALPHANUMERIC
-                    )
-                ));
-            }
-            BatchKeys::All => {
-                url.push_str("?wildcard=true");
+        key: &str,
+        default: impl Into<String>,
+        opts: PutOpts,
+    ) -> Result<GetOrCreateResult> {
+        match self.get_secret(namespace, key, GetOpts::default()).await {
+            Ok(secret) => {
+                return Ok(GetOrCreateResult {
+                    secret,
+                    created: false,
+                })
             }
+            Err(e) if e.status_code() == Some(404) => {}
+            Err(e) => return Err(e),
         }
 
-        // Add format parameter
-        let separator = if url.contains('?') { '&' } else { '?' };
-        url.push_str(&format!("{}format={}", separator, format.as_str()));
+        let created = match self.create_secret(namespace, key, default, opts).await {
+            Ok(_) => true,
+            Err(Error::AlreadyExists { .. }) => {
+                debug!(
+                    "Secret {} created concurrently, reading it back",
+                    self.log_key(&format!("{}/{}", namespace, key))
+                );
+                false
+            }
+            Err(e) => return Err(e),
+        };
 
-        // Build and execute request
-        let request = self.build_request(Method::GET, &url)?;
-        let response = self.execute_with_retry(request).await?;
+        let secret = self
+            .get_secret(
+                namespace,
+                key,
+                GetOpts {
+                    bypass_cache_entirely: true,
+                    ..Default::default()
+                },
+            )
+            .await?;
+        Ok(GetOrCreateResult { secret, created })
+    }
 
-        // Check status
-        if !response.status().is_success() {
-            return Err(self.parse_error_response(response).await);
-        }
+    /// Store a binary value
+    ///
+    /// The bytes are base64-encoded and sent with `format: "binary"` and
+    /// `encoding: "base64"`, so the server records the value as binary and
+    /// [`Client::get_secret_bytes`] returns the original bytes. The encoded
+    /// copy is zeroed once the request has been sent. Otherwise behaves like
+    /// [`Client::put_secret`].
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use secret_store_sdk::{Client, PutOpts};
+    /// # async fn example(client: &Client) -> Result<(), Box<dyn std::error::Error>> {
+    /// let keystore = std::fs::read("keystore.p12")?;
+    /// client
+    ///     .put_secret_bytes("production", "tls-keystore", &keystore, PutOpts::default())
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn put_secret_bytes(
+        &self,
+        namespace: &str,
+        key: &str,
+        bytes: &[u8],
+        opts: PutOpts,
+    ) -> Result<PutResult> {
+        use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
 
-        // Parse response based on format
-        match format {
-            ExportFormat::Json => {
-                let json_result: BatchGetJsonResult = response.json().await.map_err(Error::from)?;
-                Ok(BatchGetResult::Json(json_result))
-            }
-            _ => {
-                let text = response.text().await.map_err(Error::from)?;
-                Ok(BatchGetResult::Text(text))
-            }
-        }
+        let encoded = zeroize::Zeroizing::new(BASE64.encode(bytes));
+        self.send_put(namespace, key, &encoded, opts, false, true)
+            .await
     }
 
-    /// Batch operate on secrets
-    pub async fn batch_operate(
+    async fn send_put(
         &self,
         namespace: &str,
-        operations: Vec<BatchOp>,
-        transactional: bool,
-        idempotency_key: Option<String>,
-    ) -> Result<BatchOperateResult> {
-        // Invalidate cache for all affected keys
-        if let Some(cache) = &self.cache {
-            for op in &operations {
-                let cache_key = format!("{}/{}", namespace, &op.key);
-                cache.invalidate(&cache_key).await;
-            }
+        key: &str,
+        value: &str,
+        opts: PutOpts,
+        create_only: bool,
+        binary: bool,
+    ) -> Result<PutResult> {
+        self.ensure_writable()?;
+        if value.is_empty() && !opts.allow_empty_value {
+            return Err(Error::Config(format!(
+                "Refusing to write an empty value to {}/{}; set PutOpts::allow_empty_value",
+                namespace, key
+            )));
         }
 
+        // Invalidate cache for this key
+        self.invalidate_cache(namespace, key).await;
+        self.invalidate_list_cache(namespace).await;
+
         // Build request body
-        let body = serde_json::json!({
-            "operations": operations,
-            "transactional": transactional,
+        let mut body = serde_json::json!({
+            "value": value,
         });
+        if binary {
+            body["format"] = serde_json::json!("binary");
+            body["encoding"] = serde_json::json!("base64");
+        }
+
+        if let Some(ttl) = opts.ttl_seconds {
+            body["ttl_seconds"] = serde_json::json!(ttl);
+        }
+        if let Some(metadata) = opts.metadata {
+            body["metadata"] = metadata;
+        }
 
         // Build request
-        let url = self.endpoints.batch_operate(namespace);
-        let mut request = self.build_request(Method::POST, &url)?;
+        let url = self.endpoints.put_secret(namespace, key);
+        let mut request = self.build_request(Method::PUT, &url)?;
         request = request.json(&body);
 
         // Add idempotency key if provided
-        if let Some(key) = idempotency_key {
-            request = request.header("X-Idempotency-Key", key);
+        if let Some(idempotency_key) = &opts.idempotency_key {
+            request = request.header("X-Idempotency-Key", idempotency_key);
+        }
+        let expected_version = opts.expected_version.filter(|_| !create_only);
+        if create_only {
+            request = request.header(reqwest::header::IF_NONE_MATCH, "*");
+        }
+        if let Some(version) = expected_version {
+            request = request.header(reqwest::header::IF_MATCH, format!("\"v{}\"", version));
         }
+        if opts.minimal_response {
+            request = request.header("Prefer", "return=minimal");
+        }
+
+        // Conflicts are only safe to retry when the server can deduplicate the
+        // write, and mean "already exists" or "version moved on" otherwise
+        let policy = RetryPolicy {
+            retry_on_conflict: !create_only
+                && expected_version.is_none()
+                && opts.idempotency_key.is_some()
+                && opts
+                    .retry_on_conflict
+                    .unwrap_or(self.config.retry_on_conflict),
+            ..Default::default()
+        };
 
         // Execute with retry
-        let response = self.execute_with_retry(request).await?;
+        let response = self
+            .execute_with_retry_policy(request, policy)
+            .await
+            .map_err(|e| match (e, expected_version) {
+                (
+                    Error::Http {
+                        status: status @ (409 | 412),
+                        request_id,
+                        ..
+                    },
+                    Some(expected_version),
+                ) => Error::VersionConflict {
+                    namespace: namespace.to_string(),
+                    key: key.to_string(),
+                    expected_version,
+                    status,
+                    request_id,
+                },
+                (e, _) => e,
+            })?;
 
         // Parse response
-        self.parse_json_response(response).await
+        let mut result: PutResult = if opts.minimal_response {
+            self.parse_minimal_put_response(response, namespace, key)
+                .await?
+        } else {
+            self.parse_json_response(response).await?
+        };
+        result.idempotency_key = opts.idempotency_key;
+        Ok(result)
     }
 
-    /// Export secrets as environment variables
-    ///
-    /// Exports all secrets from a namespace in the specified format.
-    /// Supports conditional requests using ETag for efficient caching.
-    ///
-    /// # Arguments
-    ///
-    /// * `namespace` - The namespace to export
-    /// * `opts` - Export options including format and conditional request headers
-    ///
-    /// # Returns
+    /// Parse a put response that may have been sent without a body
+    async fn parse_minimal_put_response(
+        &self,
+        response: Response,
+        namespace: &str,
+        key: &str,
+    ) -> Result<PutResult> {
+        let headers = response.headers().clone();
+        let header_version =
+            header_str(&headers, "x-secret-version").and_then(|v| v.parse::<i32>().ok());
+        let body = self.read_body(response).await?;
+
+        // The server ignored the preference and sent the full result
+        if !body.iter().all(u8::is_ascii_whitespace) {
+            let mut result: PutResult = self.decode(&body)?;
+            result.version = result.version.or(header_version);
+            return Ok(result);
+        }
+
+        Ok(PutResult {
+            message: String::new(),
+            namespace: namespace.to_string(),
+            key: key.to_string(),
+            created_at: String::new(),
+            request_id: header_str(&headers, "x-request-id").unwrap_or_default(),
+            version: header_version,
+            idempotency_key: None,
+            generated_value: None,
+        })
+    }
+
+    /// Write a secret whose value is generated by the server
     ///
-    /// Returns `EnvExport::Json` for JSON format or `EnvExport::Text` for other formats.
+    /// The plaintext is created server-side from `spec`, so it never depends
+    /// on the client's RNG and never crosses the network unless
+    /// [`GenerateSpec::return_value`] is set, in which case it is returned once
+    /// in [`PutResult::generated_value`]. `opts.ttl_seconds`, `opts.metadata`,
+    /// `opts.idempotency_key` and `opts.expected_version` apply as for
+    /// [`Client::put_secret`].
     ///
     /// # Errors
     ///
-    /// * Returns `Error::Http` with status 304 if content hasn't changed (when using if_none_match)
-    /// * Returns other errors for authentication, network, or server issues
+    /// * `Error::Http` with status 404, 405 or 501 if the server does not
+    ///   support value generation
+    /// * `Error::VersionConflict` if `opts.expected_version` does not match
     ///
     /// # Example
     ///
     /// ```no_run
-    /// # use secret_store_sdk::{Client, ClientBuilder, Auth, ExportEnvOpts, ExportFormat};
+    /// # use secret_store_sdk::{Client, GenerateSpec, PutOpts};
+    /// # use secrecy::ExposeSecret;
     /// # async fn example(client: &Client) -> Result<(), Box<dyn std::error::Error>> {
-    /// // Simple export
-    /// let opts = ExportEnvOpts {
-    ///     format: ExportFormat::Dotenv,
-    ///     ..Default::default()
-    /// };
-    /// let export = client.export_env("production", opts).await?;
-    ///
-    /// // Conditional export with ETag
-    /// let opts = ExportEnvOpts {
-    ///     format: ExportFormat::Json,
-    ///     use_cache: true,
-    ///     if_none_match: Some("previous-etag".to_string()),
-    /// };
-    /// match client.export_env("production", opts).await {
-    ///     Ok(export) => println!("Content updated"),
-    ///     Err(e) if e.status_code() == Some(304) => println!("Not modified"),
-    ///     Err(e) => return Err(e.into()),
+    /// let result = client
+    ///     .generate_secret(
+    ///         "production",
+    ///         "db-password",
+    ///         GenerateSpec::password(32).return_value(true),
+    ///         PutOpts::default(),
+    ///     )
+    ///     .await?;
+    /// if let Some(value) = &result.generated_value {
+    ///     println!("generated {} characters", value.expose_secret().len());
     /// }
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn export_env(&self, namespace: &str, opts: ExportEnvOpts) -> Result<EnvExport> {
-        let mut url = self.endpoints.export_env(namespace);
-        url.push_str(&format!("?format={}", opts.format.as_str()));
-
-        // Build request
-        let mut request = self.build_request(Method::GET, &url)?;
+    pub async fn generate_secret(
+        &self,
+        namespace: &str,
+        key: &str,
+        spec: GenerateSpec,
+        opts: PutOpts,
+    ) -> Result<PutResult> {
+        self.ensure_writable()?;
+        self.invalidate_cache(namespace, key).await;
+        self.invalidate_list_cache(namespace).await;
 
-        // Add conditional header if provided
-        if let Some(etag) = &opts.if_none_match {
-            request = request.header(reqwest::header::IF_NONE_MATCH, etag);
+        let mut body = serde_json::to_value(&spec)?;
+        if let Some(ttl) = opts.ttl_seconds {
+            body["ttl_seconds"] = serde_json::json!(ttl);
         }
-
-        let response = self.execute_with_retry(request).await?;
-
-        // Handle 304 Not Modified
-        if response.status() == StatusCode::NOT_MODIFIED {
-            return Err(Error::Http {
-                status: 304,
-                category: "not_modified".to_string(),
-                message: "Environment export not modified".to_string(),
-                request_id: header_str(response.headers(), "x-request-id"),
-            });
+        if let Some(metadata) = opts.metadata {
+            body["metadata"] = metadata;
         }
 
-        // Check other error statuses
-        if !response.status().is_success() {
-            return Err(self.parse_error_response(response).await);
+        let url = self.endpoints.generate_secret(namespace, key);
+        let mut request = self.build_request(Method::POST, &url)?.json(&body);
+        if let Some(idempotency_key) = &opts.idempotency_key {
+            request = request.header("X-Idempotency-Key", idempotency_key);
+        }
+        if let Some(version) = opts.expected_version {
+            request = request.header(reqwest::header::IF_MATCH, format!("\"v{}\"", version));
         }
 
-        // NOTE: opts.use_cache is currently not implemented.
-        // Future implementation will cache responses with namespace/env/{format} as key
-        // and use ETag headers for cache validation (304 responses).
-        // For now, this flag has no effect.
-
-        // Parse response based on format
-        match opts.format {
-            ExportFormat::Json => {
-                let json_result: EnvJsonExport = response.json().await.map_err(Error::from)?;
-                Ok(EnvExport::Json(json_result))
+        let response = self.execute_with_retry(request).await.map_err(|e| {
+            match (e, opts.expected_version) {
+                (
+                    Error::Http {
+                        status: status @ (409 | 412),
+                        request_id,
+                        ..
+                    },
+                    Some(expected_version),
+                ) => Error::VersionConflict {
+                    namespace: namespace.to_string(),
+                    key: key.to_string(),
+                    expected_version,
+                    status,
+                    request_id,
+                },
+                (e, _) => e,
             }
-            _ => {
-                let text = response.text().await.map_err(Error::from)?;
-                Ok(EnvExport::Text(text))
-            }
-        }
-    }
-
-    /// List all namespaces
-    pub async fn list_namespaces(&self) -> Result<ListNamespacesResult> {
-        let url = self.endpoints.list_namespaces();
-        let request = self.build_request(Method::GET, &url)?;
-        let response = self.execute_with_retry(request).await?;
+        })?;
 
-        if !response.status().is_success() {
-            return Err(self.parse_error_response(response).await);
-        }
+        let mut result: PutResult = self.parse_json_response(response).await?;
+        result.idempotency_key = opts.idempotency_key;
+        Ok(result)
+    }
 
-        self.parse_json_response(response).await
+    /// Delete a secret from the store
+    pub async fn delete_secret(&self, namespace: &str, key: &str) -> Result<DeleteResult> {
+        self.delete_secret_opts(namespace, key, DeleteOpts::default())
+            .await
     }
 
-    /// Create a new namespace
-    ///
-    /// Creates a new namespace in the secret store. Requires global admin permissions.
-    ///
-    /// # Arguments
+    /// Delete a secret from the store with options
     ///
-    /// * `name` - The name of the namespace to create
-    /// * `description` - Optional description for the namespace
-    /// * `idempotency_key` - Optional idempotency key to prevent duplicate creation
-    ///
-    /// # Returns
-    ///
-    /// A `CreateNamespaceResult` containing the creation details.
+    /// With `opts.if_match` set to the ETag of a previous read, the secret is
+    /// only deleted if it has not changed since, giving optimistic-concurrency
+    /// deletes.
     ///
     /// # Errors
     ///
-    /// * `Error::Http` with status 403 if not authorized to create namespaces
-    /// * `Error::Http` with status 409 if the namespace already exists
-    /// * `Error::Http` with status 400 for invalid namespace names
+    /// * `Error::Http` with status 412 (`ErrorKind::PreconditionFailed`) if
+    ///   the secret's current ETag does not match `opts.if_match`
     ///
     /// # Example
     ///
     /// ```no_run
-    /// # use secret_store_sdk::{Client, ClientBuilder, Auth};
+    /// # use secret_store_sdk::{Client, DeleteOpts, ErrorKind, GetOpts};
     /// # async fn example(client: &Client) -> Result<(), Box<dyn std::error::Error>> {
-    /// let result = client.create_namespace(
-    ///     "production",
-    ///     Some("Production environment secrets".to_string()),
-    ///     None
-    /// ).await?;
-    /// println!("Created namespace: {}", result.namespace);
+    /// let secret = client.get_secret("production", "api-key", GetOpts::default()).await?;
+    /// let opts = DeleteOpts {
+    ///     if_match: secret.etag.clone(),
+    ///     ..Default::default()
+    /// };
+    /// match client.delete_secret_opts("production", "api-key", opts).await {
+    ///     Ok(_) => println!("Deleted"),
+    ///     Err(e) if e.kind() == ErrorKind::PreconditionFailed => println!("Changed concurrently"),
+    ///     Err(e) => return Err(e.into()),
+    /// }
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn create_namespace(
+    pub async fn delete_secret_opts(
         &self,
-        name: &str,
-        description: Option<String>,
-        idempotency_key: Option<String>,
-    ) -> Result<CreateNamespaceResult> {
-        let url = self.endpoints.create_namespace();
-        let mut request = self.build_request(Method::POST, &url)?;
-
-        let body = CreateNamespaceRequest {
-            name: name.to_string(),
-            description,
-        };
-        request = request.json(&body);
+        namespace: &str,
+        key: &str,
+        opts: DeleteOpts,
+    ) -> Result<DeleteResult> {
+        self.ensure_writable()?;
+        // Invalidate cache for this key
+        self.invalidate_cache(namespace, key).await;
+        self.invalidate_list_cache(namespace).await;
 
-        // Add idempotency key if provided
-        if let Some(key) = idempotency_key {
-            request = request.header("X-Idempotency-Key", key);
+        // Build request
+        let mut url = self.endpoints.delete_secret(namespace, key);
+        if opts.soft {
+            url.push_str("?soft=true");
         }
-
-        let response = self.execute_with_retry(request).await?;
-
-        if !response.status().is_success() {
-            return Err(self.parse_error_response(response).await);
+        let mut request = self.build_request(Method::DELETE, &url)?;
+        if let Some(etag) = &opts.if_match {
+            request = request.header(reqwest::header::IF_MATCH, etag);
+        }
+        if let Some(key) = &opts.idempotency_key {
+            request = request.header("X-Idempotency-Key", key);
         }
 
-        self.parse_json_response(response).await
-    }
-
-    /// Get namespace information
-    pub async fn get_namespace(&self, namespace: &str) -> Result<NamespaceInfo> {
-        let url = self.endpoints.get_namespace(namespace);
-        let request = self.build_request(Method::GET, &url)?;
+        // Execute with retry
         let response = self.execute_with_retry(request).await?;
+        let request_id = header_str(response.headers(), "x-request-id");
 
-        if !response.status().is_success() {
-            return Err(self.parse_error_response(response).await);
-        }
+        // Check status
+        let deleted = response.status() == StatusCode::NO_CONTENT;
 
-        self.parse_json_response(response).await
+        Ok(DeleteResult {
+            deleted,
+            request_id,
+            idempotency_key: opts.idempotency_key,
+        })
     }
 
-    /// Initialize a namespace with a template
+    /// Restore a soft-deleted secret
     ///
-    /// Initializes a new namespace using a predefined template to create
-    /// a set of initial secrets.
+    /// Undeletes a secret removed with `DeleteOpts::soft` while it is still
+    /// within the server's retention window, returning the restored secret.
     ///
-    /// # Arguments
+    /// # Errors
     ///
-    /// * `namespace` - The namespace to initialize
-    /// * `template` - The template configuration
-    /// * `idempotency_key` - Optional idempotency key to prevent duplicate initialization
+    /// * `Error::NotSoftDeleted` if the secret is not soft-deleted (409)
+    /// * `Error::RetentionExpired` if the retention window has passed (410)
+    /// * `Error::Http` with status 404 if the secret never existed
     ///
     /// # Example
     ///
     /// ```no_run
-    /// # use secret_store_sdk::{Client, ClientBuilder, Auth, NamespaceTemplate};
-    /// # use serde_json::json;
+    /// # use secret_store_sdk::{Client, DeleteOpts};
     /// # async fn example(client: &Client) -> Result<(), Box<dyn std::error::Error>> {
-    /// let template = NamespaceTemplate {
-    ///     template: "web-app".to_string(),
-    ///     params: json!({
-    ///         "environment": "staging",
-    ///         "region": "us-west-2"
-    ///     }),
+    /// let opts = DeleteOpts {
+    ///     soft: true,
+    ///     ..Default::default()
     /// };
+    /// let _ = client.delete_secret_opts("production", "api-key", opts).await?;
     ///
-    /// let result = client.init_namespace(
-    ///     "staging-app",
-    ///     template,
-    ///     Some("init-staging-12345".to_string())
-    /// ).await?;
-    /// println!("Created {} secrets", result.secrets_created);
+    /// // Changed our mind
+    /// let secret = client.restore_secret("production", "api-key").await?;
+    /// println!("Restored version {}", secret.version);
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn init_namespace(
-        &self,
-        namespace: &str,
-        template: NamespaceTemplate,
-        idempotency_key: Option<String>,
-    ) -> Result<InitNamespaceResult> {
-        let url = self.endpoints.init_namespace(namespace);
-        let mut request = self.build_request(Method::POST, &url)?;
-        request = request.json(&template);
+    pub async fn restore_secret(&self, namespace: &str, key: &str) -> Result<Secret> {
+        self.ensure_writable()?;
+        self.invalidate_cache(namespace, key).await;
+        self.invalidate_list_cache(namespace).await;
+
+        let url = self.endpoints.restore_secret(namespace, key);
+        let request = self.build_request(Method::POST, &url)?;
+        let response = self
+            .execute_with_retry(request)
+            .await
+            .map_err(|e| match e {
+                Error::Http {
+                    status: 409,
+                    request_id,
+                    ..
+                } => Error::NotSoftDeleted {
+                    namespace: namespace.to_string(),
+                    key: key.to_string(),
+                    status: 409,
+                    request_id,
+                },
+                Error::Http {
+                    status: 410,
+                    request_id,
+                    ..
+                } => Error::RetentionExpired {
+                    namespace: namespace.to_string(),
+                    key: key.to_string(),
+                    status: 410,
+                    request_id,
+                },
+                e => e,
+            })?;
 
-        // Add idempotency key if provided
-        if let Some(key) = idempotency_key {
-            request = request.header("X-Idempotency-Key", key);
+        self.parse_get_response(response, namespace, key).await
+    }
+
+    /// List secrets in a namespace
+    pub async fn list_secrets(&self, namespace: &str, opts: ListOpts) -> Result<ListSecretsResult> {
+        if opts.cursor.is_some() {
+            self.require_api_version("cursor pagination", CURSOR_PAGINATION_MIN_API_VERSION)
+                .await?;
         }
 
-        let response = self.execute_with_retry(request).await?;
+        // Build URL with query parameters
+        let mut url = self.endpoints.list_secrets(namespace);
 
-        if !response.status().is_success() {
-            return Err(self.parse_error_response(response).await);
+        let mut query_parts = Vec::new();
+        if let Some(prefix) = &opts.prefix {
+            query_parts.push(format!(
+                "prefix={}",
+                percent_encoding::utf8_percent_encode(prefix, percent_encoding::NON_ALPHANUMERIC)
+            ));
+        }
+        if let Some(limit) = opts.limit {
+            query_parts.push(format!("limit={}", limit));
+        }
+        if let Some(cursor) = &opts.cursor {
+            query_parts.push(format!(
+                "cursor={}",
+                percent_encoding::utf8_percent_encode(cursor, percent_encoding::NON_ALPHANUMERIC)
+            ));
+        } else if let Some(offset) = opts.offset {
+            query_parts.push(format!("offset={}", offset));
+        }
+        if let Some(fields) = &opts.fields {
+            let fields: Vec<&str> = fields.iter().map(ListField::as_str).collect();
+            query_parts.push(format!("fields={}", fields.join(",")));
         }
 
-        self.parse_json_response(response).await
+        let query = query_parts.join("&");
+        if !query.is_empty() {
+            url.push('?');
+            url.push_str(&query);
+        }
+
+        let cache_key = format!("{}/list?{}", namespace, query);
+        self.get_listing(cache_key, &url).await
     }
 
-    /// Delete a namespace and all its secrets
+    /// Stream all pages of a listing, following server cursors
     ///
-    /// **Warning**: This operation is irreversible and will delete all secrets
-    /// in the namespace. Use with extreme caution.
+    /// Each page is requested with the previous page's `next_cursor`, which
+    /// avoids skipped or duplicated keys when secrets change between pages.
+    /// Servers without cursor support are paged by advancing `offset` while
+    /// they report `has_more`.
     ///
-    /// This operation may take some time for namespaces with many secrets.
-    /// The response includes the number of secrets that were deleted.
+    /// As a guard against runaway loops, the stream yields an `Error::Other`
+    /// and ends once more than `opts.max_pages` pages or `opts.max_items` keys
+    /// have been fetched.
     ///
-    /// # Arguments
+    /// # Example
     ///
-    /// * `namespace` - The namespace to delete
+    /// ```no_run
+    /// # use secret_store_sdk::{Client, ListOpts};
+    /// # use futures::StreamExt;
+    /// # async fn example(client: &Client) -> Result<(), Box<dyn std::error::Error>> {
+    /// let pages = client.list_secrets_pages("production", ListOpts::default());
+    /// futures::pin_mut!(pages);
     ///
-    /// # Returns
+    /// while let Some(page) = pages.next().await {
+    ///     for secret in page?.secrets {
+    ///         println!("{}", secret.key);
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn list_secrets_pages(
+        &self,
+        namespace: &str,
+        opts: ListOpts,
+    ) -> impl futures::Stream<Item = Result<ListSecretsResult>> {
+        let limits = PageLimits::new(opts.max_pages, opts.max_items);
+        let state = (self.clone(), namespace.to_string(), Some(opts), limits);
+        futures::stream::unfold(state, |(client, namespace, opts, mut limits)| async move {
+            let mut opts = opts?;
+            let page = client.list_secrets(&namespace, opts.clone()).await;
+            if let Ok(listing) = &page {
+                if let Err(e) = limits.admit(listing.secrets.len()) {
+                    return Some((Err(e), (client, namespace, None, limits)));
+                }
+            }
+            let next = match &page {
+                Ok(page) => match &page.next_cursor {
+                    Some(cursor) if opts.cursor.as_ref() != Some(cursor) => {
+                        opts.cursor = Some(cursor.clone());
+                        Some(opts)
+                    }
+                    Some(_) => None,
+                    None if page.has_more && !page.secrets.is_empty() => {
+                        opts.cursor = None;
+                        opts.offset = Some(opts.offset.unwrap_or(0) + page.secrets.len());
+                        Some(opts)
+                    }
+                    None => None,
+                },
+                Err(_) => None,
+            };
+            Some((page, (client, namespace, next, limits)))
+        })
+    }
+
+    /// List the secrets of a namespace encrypted with a given KMS key
     ///
-    /// A `DeleteNamespaceResult` containing deletion details.
+    /// Pages through the full listing and keeps the entries whose `kid`
+    /// matches, which is handy when auditing which secrets still use a
+    /// rotated-out key. Entries without a reported `kid` never match.
     ///
-    /// # Errors
+    /// # Example
     ///
-    /// * `Error::Http` with status 404 if the namespace doesn't exist
-    /// * `Error::Http` with status 403 if deletion is forbidden
-    /// * `Error::Http` with status 409 if namespace has protection enabled
+    /// ```no_run
+    /// # use secret_store_sdk::Client;
+    /// # async fn example(client: &Client) -> Result<(), Box<dyn std::error::Error>> {
+    /// for info in client.list_by_kid("production", "kms-key-2023").await? {
+    ///     println!("{} still uses the old key", info.key);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn list_by_kid(&self, namespace: &str, kid: &str) -> Result<Vec<SecretKeyInfo>> {
+        use futures::TryStreamExt;
+
+        let pages: Vec<ListSecretsResult> = self
+            .list_secrets_pages(namespace, ListOpts::default())
+            .try_collect()
+            .await?;
+        Ok(pages
+            .into_iter()
+            .flat_map(|page| page.secrets)
+            .filter(|info| info.kid.as_deref() == Some(kid))
+            .collect())
+    }
+
+    /// List the secrets of a namespace that expire within `within`
+    ///
+    /// Returns key, version and expiry only, soonest first; secrets that are
+    /// already past their expiry are included. Expiry times come from the
+    /// listing when the server reports `expires_at` there. If no listed
+    /// secret carries one, every secret is fetched instead (at most 8
+    /// requests in flight, filling the cache) and its expiry read from the
+    /// full record.
     ///
     /// # Example
     ///
     /// ```no_run
-    /// # use secret_store_sdk::{Client, ClientBuilder, Auth};
+    /// # use secret_store_sdk::Client;
+    /// # use std::time::Duration;
     /// # async fn example(client: &Client) -> Result<(), Box<dyn std::error::Error>> {
-    /// let result = client.delete_namespace("test-namespace").await?;
-    /// println!("Deleted {} secrets from namespace {}",
-    ///     result.secrets_deleted,
-    ///     result.namespace
-    /// );
+    /// let week = Duration::from_secs(7 * 24 * 3600);
+    /// for secret in client.expiring_soon("production", week).await? {
+    ///     println!("{} expires at {}", secret.key, secret.expires_at);
+    /// }
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn delete_namespace(&self, namespace: &str) -> Result<DeleteNamespaceResult> {
-        // Clear all cached entries for this namespace
-        if let Some(cache) = &self.cache {
-            // TODO: Optimize to only clear entries for this specific namespace
-            // For now, we'll invalidate all cache to ensure consistency
-            cache.invalidate_all();
-            debug!(
-                "Cleared all cache entries due to namespace deletion: {}",
-                namespace
-            );
-        }
-
-        // Build request
-        let url = self.endpoints.delete_namespace(namespace);
-        let request = self.build_request(Method::DELETE, &url)?;
-
-        // Execute with retry
-        let response = self.execute_with_retry(request).await?;
-
-        // Check status
-        if !response.status().is_success() {
-            return Err(self.parse_error_response(response).await);
+    pub async fn expiring_soon(
+        &self,
+        namespace: &str,
+        within: Duration,
+    ) -> Result<Vec<ExpiringSecret>> {
+        use futures::TryStreamExt;
+
+        let now = self.config.clock.now();
+        let cutoff = time::Duration::try_from(within)
+            .ok()
+            .and_then(|within| now.checked_add(within));
+
+        let pages: Vec<ListSecretsResult> = self
+            .list_secrets_pages(namespace, ListOpts::default())
+            .try_collect()
+            .await?;
+        let infos: Vec<SecretKeyInfo> = pages.into_iter().flat_map(|page| page.secrets).collect();
+
+        let mut expiring = Vec::new();
+        if infos.iter().any(|info| info.expires_at.is_some()) {
+            for info in infos {
+                let Some(expires_at) = info.expires_at else {
+                    continue;
+                };
+                let expires_at = time::OffsetDateTime::parse(
+                    &expires_at,
+                    &time::format_description::well_known::Rfc3339,
+                )
+                .map_err(|e| Error::Deserialize(format!("Invalid expires_at timestamp: {}", e)))?;
+                expiring.push(ExpiringSecret {
+                    key: info.key,
+                    version: info.version,
+                    expires_at,
+                });
+            }
+        } else {
+            let keys: Vec<String> = infos.into_iter().map(|info| info.key).collect();
+            for (key, result) in self.get_many(namespace, &keys, 8).await {
+                let secret = match result {
+                    Ok(secret) => secret,
+                    // Deleted since it was listed
+                    Err(e) if e.status_code() == Some(404) => continue,
+                    Err(e) => return Err(e),
+                };
+                if let Some(expires_at) = secret.expires_at {
+                    expiring.push(ExpiringSecret {
+                        key,
+                        version: Some(secret.version),
+                        expires_at,
+                    });
+                }
+            }
         }
 
-        // Extract request ID from headers
-        let request_id = header_str(response.headers(), "x-request-id");
-
-        // Parse response
-        let mut result: DeleteNamespaceResult = self.parse_json_response(response).await?;
-
-        // Set request_id if not already in the response body
-        if result.request_id.is_none() {
-            result.request_id = request_id;
+        if let Some(cutoff) = cutoff {
+            expiring.retain(|secret| secret.expires_at <= cutoff);
         }
-
-        Ok(result)
+        expiring.sort_by(|a, b| a.expires_at.cmp(&b.expires_at).then(a.key.cmp(&b.key)));
+        Ok(expiring)
     }
 
-    /// Delete a namespace and all its secrets with idempotency support
+    /// Search secrets in a namespace by metadata
     ///
-    /// Same as `delete_namespace` but with idempotency key support for safe retries.
-    ///
-    /// # Arguments
+    /// Sends the filter as `metadata.<field>=<value>` query parameters to the
+    /// server's search endpoint and returns the matching key infos, without
+    /// fetching any values.
     ///
-    /// * `namespace` - The namespace to delete
-    /// * `idempotency_key` - Optional idempotency key to prevent duplicate deletion
+    /// If the server does not support search (404, 405 or 501), this falls
+    /// back to listing the namespace and fetching every listed secret to
+    /// filter on its metadata client-side. The fallback is much slower: it
+    /// costs one request per key (at most `opts.fallback_concurrency` in
+    /// flight) and fills the cache with the fetched values.
     ///
     /// # Example
     ///
     /// ```no_run
-    /// # use secret_store_sdk::{Client, ClientBuilder, Auth};
+    /// # use secret_store_sdk::{Client, MetadataFilter, SearchOpts};
     /// # async fn example(client: &Client) -> Result<(), Box<dyn std::error::Error>> {
-    /// let result = client.delete_namespace_idempotent(
-    ///     "test-namespace",
-    ///     Some("delete-ns-12345".to_string())
-    /// ).await?;
-    /// println!("Deleted {} secrets", result.secrets_deleted);
+    /// let filter = MetadataFilter::new().eq("owner", "backend-team");
+    /// for info in client.search("production", filter, SearchOpts::default()).await? {
+    ///     println!("{} (v{})", info.key, info.version.unwrap_or_default());
+    /// }
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn delete_namespace_idempotent(
+    pub async fn search(
         &self,
         namespace: &str,
-        idempotency_key: Option<String>,
-    ) -> Result<DeleteNamespaceResult> {
-        // Clear all cached entries for this namespace
-        if let Some(cache) = &self.cache {
-            cache.invalidate_all();
-            debug!(
-                "Cleared all cache entries due to namespace deletion: {}",
-                namespace
-            );
-        }
-
-        // Build request
-        let url = self.endpoints.delete_namespace(namespace);
-        let mut request = self.build_request(Method::DELETE, &url)?;
+        filter: MetadataFilter,
+        opts: SearchOpts,
+    ) -> Result<Vec<SecretKeyInfo>> {
+        let mut url = self.endpoints.search_secrets(namespace);
 
-        // Add idempotency key if provided
-        if let Some(key) = idempotency_key {
-            request = request.header("X-Idempotency-Key", key);
+        let mut query_parts = filter.to_query_parts();
+        if let Some(prefix) = &opts.prefix {
+            query_parts.push(format!(
+                "prefix={}",
+                percent_encoding::utf8_percent_encode(prefix, percent_encoding::NON_ALPHANUMERIC)
+            ));
         }
-
-        // Execute with retry
-        let response = self.execute_with_retry(request).await?;
-
-        // Check status
-        if !response.status().is_success() {
-            return Err(self.parse_error_response(response).await);
+        if let Some(limit) = opts.limit {
+            query_parts.push(format!("limit={}", limit));
         }
-
-        // Extract request ID from headers
-        let request_id = header_str(response.headers(), "x-request-id");
-
-        // Parse response
-        let mut result: DeleteNamespaceResult = self.parse_json_response(response).await?;
-
-        // Set request_id if not already in the response body
-        if result.request_id.is_none() {
-            result.request_id = request_id;
+        if !query_parts.is_empty() {
+            url.push('?');
+            url.push_str(&query_parts.join("&"));
         }
 
-        Ok(result)
-    }
-
-    /// List versions of a secret
-    pub async fn list_versions(&self, namespace: &str, key: &str) -> Result<VersionList> {
-        // Build and execute request
-        let url = self.endpoints.list_versions(namespace, key);
         let request = self.build_request(Method::GET, &url)?;
-        let response = self.execute_with_retry(request).await?;
+        match self.execute_with_retry(request).await {
+            Ok(response) => {
+                #[derive(serde::Deserialize)]
+                struct SearchResponse {
+                    secrets: Vec<SecretKeyInfo>,
+                }
 
-        // Parse response
-        self.parse_json_response(response).await
+                let body: SearchResponse = self.parse_json_response(response).await?;
+                Ok(body.secrets)
+            }
+            Err(e) if matches!(e.status_code(), Some(404 | 405 | 501)) => {
+                debug!(
+                    "Search not supported for {} ({}), filtering client-side",
+                    namespace, e
+                );
+                self.search_client_side(namespace, &filter, opts).await
+            }
+            Err(e) => Err(e),
+        }
     }
 
-    /// Get a specific version of a secret
-    pub async fn get_version(&self, namespace: &str, key: &str, version: i32) -> Result<Secret> {
-        // Build and execute request
-        let url = self.endpoints.get_version(namespace, key, version);
-        let request = self.build_request(Method::GET, &url)?;
-        let response = self.execute_with_retry(request).await?;
-
-        // Parse response (similar to get_secret)
-        self.parse_get_response(response, namespace, key).await
+    /// List secrets together with their values
+    ///
+    /// Lists the namespace (honoring `opts.prefix` and `opts.limit`), then
+    /// fetches every listed value through [`Client::get_many`], at most
+    /// `opts.concurrency` at a time. Fetched secrets are cached. Secrets
+    /// deleted between listing and fetching are skipped.
+    ///
+    /// This costs one request per key, so it is meant for small filtered
+    /// sets. Avoid it on large namespaces; prefer a prefix or
+    /// [`Client::export_env`] there.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use secret_store_sdk::{Client, ListWithValuesOpts};
+    /// # async fn example(client: &Client) -> Result<(), Box<dyn std::error::Error>> {
+    /// let opts = ListWithValuesOpts {
+    ///     prefix: Some("db-".to_string()),
+    ///     ..Default::default()
+    /// };
+    /// for secret in client.list_with_values("production", opts).await? {
+    ///     println!("{} (v{})", secret.key, secret.version);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn list_with_values(
+        &self,
+        namespace: &str,
+        opts: ListWithValuesOpts,
+    ) -> Result<Vec<Secret>> {
+        let listing = self
+            .list_secrets(
+                namespace,
+                ListOpts {
+                    prefix: opts.prefix,
+                    limit: opts.limit,
+                    ..Default::default()
+                },
+            )
+            .await?;
+
+        let keys: Vec<&str> = listing
+            .secrets
+            .iter()
+            .map(|info| info.key.as_str())
+            .collect();
+        let mut secrets = Vec::with_capacity(keys.len());
+        for (_, result) in self.get_many(namespace, &keys, opts.concurrency).await {
+            match result {
+                Ok(secret) => secrets.push(secret),
+                // Deleted between listing and fetching
+                Err(e) if e.status_code() == Some(404) => {}
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(secrets)
     }
 
-    /// Rollback a secret to a previous version
-    pub async fn rollback(
+    /// Client-side fallback for [`Client::search`]
+    async fn search_client_side(
         &self,
         namespace: &str,
-        key: &str,
-        version: i32,
-    ) -> Result<RollbackResult> {
-        // Invalidate cache for this key since we're changing it
-        if let Some(cache) = &self.cache {
-            let cache_key = format!("{}/{}", namespace, key);
-            cache.invalidate(&cache_key).await;
+        filter: &MetadataFilter,
+        opts: SearchOpts,
+    ) -> Result<Vec<SecretKeyInfo>> {
+        use futures::StreamExt;
+
+        let pages = self.list_secrets_pages(
+            namespace,
+            ListOpts {
+                prefix: opts.prefix,
+                ..Default::default()
+            },
+        );
+        futures::pin_mut!(pages);
+
+        let mut matches = Vec::new();
+        while let Some(listing) = pages.next().await {
+            let listing = listing?;
+            let keys: Vec<&str> = listing
+                .secrets
+                .iter()
+                .map(|info| info.key.as_str())
+                .collect();
+            let fetched = self
+                .get_many(namespace, &keys, opts.fallback_concurrency)
+                .await;
+
+            for (info, (_, result)) in listing.secrets.iter().zip(fetched) {
+                match result {
+                    Ok(secret) if filter.matches(&secret.metadata) => matches.push(info.clone()),
+                    Ok(_) => {}
+                    // Deleted between listing and fetching
+                    Err(e) if e.status_code() == Some(404) => {}
+                    Err(e) => return Err(e),
+                }
+                if opts.limit.is_some_and(|limit| matches.len() >= limit) {
+                    return Ok(matches);
+                }
+            }
         }
-
-        // Build request with empty body (comment is optional)
-        let url = self.endpoints.rollback(namespace, key, version);
-        let mut request = self.build_request(Method::POST, &url)?;
-        request = request.json(&serde_json::json!({}));
-
-        // Execute with retry
-        let response = self.execute_with_retry(request).await?;
-
-        // Parse response
-        self.parse_json_response(response).await
+        Ok(matches)
     }
 
-    /// Query audit logs
-    pub async fn audit(&self, query: AuditQuery) -> Result<AuditResult> {
-        // Build URL with query parameters
-        let mut url = self.endpoints.audit();
-        let mut params = Vec::new();
+    /// Batch get secrets
+    pub async fn batch_get(
+        &self,
+        namespace: &str,
+        keys: BatchKeys,
+        format: ExportFormat,
+    ) -> Result<BatchGetResult> {
+        let mut url = self.endpoints.batch_get(namespace);
 
-        // Add query parameters
-        if let Some(namespace) = &query.namespace {
-            params.push(format!(
-                "namespace={}",
-                percent_encoding::utf8_percent_encode(
-                    namespace,
-                    percent_encoding::NON_ALPHANUMERIC
-                )
-            ));
-        }
-        if let Some(actor) = &query.actor {
-            params.push(format!(
-                "actor={}",
-                percent_encoding::utf8_percent_encode(actor, percent_encoding::NON_ALPHANUMERIC)
-            ));
-        }
-        if let Some(action) = &query.action {
-            params.push(format!(
-                "action={}",
-                percent_encoding::utf8_percent_encode(action, percent_encoding::NON_ALPHANUMERIC)
-            ));
-        }
-        if let Some(from) = &query.from {
-            params.push(format!(
-                "from={}",
-                percent_encoding::utf8_percent_encode(from, percent_encoding::NON_ALPHANUMERIC)
-            ));
-        }
-        if let Some(to) = &query.to {
-            params.push(format!(
-                "to={}",
-                percent_encoding::utf8_percent_encode(to, percent_encoding::NON_ALPHANUMERIC)
-            ));
-        }
-        if let Some(success) = query.success {
-            params.push(format!("success={}", success));
-        }
-        if let Some(limit) = query.limit {
-            params.push(format!("limit={}", limit));
-        }
-        if let Some(offset) = query.offset {
-            params.push(format!("offset={}", offset));
+        // Build query parameters
+        match &keys {
+            BatchKeys::Keys(key_list) => {
+                let keys_param = key_list.join(",");
+                url.push_str(&format!(
+                    "?keys={}",
+                    percent_encoding::utf8_percent_encode(
+                        &keys_param,
+                        percent_encoding::NON_ALPHANUMERIC
+                    )
+                ));
+            }
+            BatchKeys::All => {
+                url.push_str("?wildcard=true");
+            }
         }
 
-        if !params.is_empty() {
-            url.push('?');
-            url.push_str(&params.join("&"));
-        }
+        // Add format parameter
+        let separator = if url.contains('?') { '&' } else { '?' };
+        url.push_str(&format!("{}format={}", separator, format.as_str()));
 
         // Build and execute request
         let request = self.build_request(Method::GET, &url)?;
         let response = self.execute_with_retry(request).await?;
 
-        // Parse response
-        self.parse_json_response(response).await
+        // Check status
+        if !response.status().is_success() {
+            return Err(self.parse_error_response(response).await);
+        }
+
+        // Parse response based on format
+        match format {
+            ExportFormat::Json => {
+                let json_result: BatchGetJsonResult = self.parse_json_response(response).await?;
+                Ok(BatchGetResult::Json(json_result))
+            }
+            _ => {
+                let body = self.read_body(response).await?;
+                let text = String::from_utf8_lossy(&body).into_owned();
+                Ok(BatchGetResult::Text(text))
+            }
+        }
     }
 
-    /// List all API keys
-    ///
-    /// Retrieves a list of all API keys associated with the current account.
-    /// The response includes metadata about each key but not the key values themselves.
-    ///
-    /// # Returns
-    ///
-    /// A `ListApiKeysResult` containing the list of API keys and total count.
+    /// Batch get secrets with versions, ETags and metadata
     ///
-    /// # Errors
+    /// The batch endpoint only returns values, so this fetches every key
+    /// through [`Client::get_many`] (8 requests in flight, using the cache)
+    /// and returns full [`Secret`]s keyed by name. With [`BatchKeys::All`]
+    /// the namespace listing is paged through first.
     ///
-    /// * `Error::Http` with status 403 if not authorized to list keys
+    /// Keys that do not exist (or were deleted while listing) are omitted
+    /// from the result; any other failure aborts the call.
     ///
     /// # Example
     ///
     /// ```no_run
-    /// # use secret_store_sdk::{Client, ClientBuilder, Auth};
+    /// # use secret_store_sdk::{BatchKeys, Client};
     /// # async fn example(client: &Client) -> Result<(), Box<dyn std::error::Error>> {
-    /// let keys = client.list_api_keys().await?;
-    /// for key in &keys.keys {
-    ///     println!("Key {}: {} (active: {})", key.id, key.name, key.active);
+    /// let keys = BatchKeys::Keys(vec!["db-url".to_string(), "api-key".to_string()]);
+    /// for (key, secret) in client.batch_get_detailed("production", keys).await? {
+    ///     println!("{} is at v{}", key, secret.version);
     /// }
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn list_api_keys(&self) -> Result<ListApiKeysResult> {
-        let url = self.endpoints.list_api_keys();
-        let request = self.build_request(Method::GET, &url)?;
-        let response = self.execute_with_retry(request).await?;
+    pub async fn batch_get_detailed(
+        &self,
+        namespace: &str,
+        keys: BatchKeys,
+    ) -> Result<std::collections::HashMap<String, Secret>> {
+        use futures::TryStreamExt;
 
-        if !response.status().is_success() {
-            return Err(self.parse_error_response(response).await);
+        let keys = match keys {
+            BatchKeys::Keys(keys) => keys,
+            BatchKeys::All => {
+                let pages: Vec<ListSecretsResult> = self
+                    .list_secrets_pages(namespace, ListOpts::default())
+                    .try_collect()
+                    .await?;
+                pages
+                    .into_iter()
+                    .flat_map(|page| page.secrets)
+                    .map(|info| info.key)
+                    .collect()
+            }
+        };
+
+        let mut secrets = std::collections::HashMap::with_capacity(keys.len());
+        for (key, result) in self.get_many(namespace, &keys, 8).await {
+            match result {
+                Ok(secret) => {
+                    let _ = secrets.insert(key, secret);
+                }
+                Err(e) if e.status_code() == Some(404) => {}
+                Err(e) => return Err(e),
+            }
         }
+        Ok(secrets)
+    }
 
-        let request_id = header_str(response.headers(), "x-request-id");
-        let mut result: ListApiKeysResult = self.parse_json_response(response).await?;
+    /// Batch operate on secrets
+    pub async fn batch_operate(
+        &self,
+        namespace: &str,
+        operations: Vec<BatchOp>,
+        transactional: bool,
+        idempotency_key: Option<String>,
+    ) -> Result<BatchOperateResult> {
+        self.ensure_writable()?;
 
-        if result.request_id.is_none() {
-            result.request_id = request_id;
+        // Invalidate cache for all affected keys
+        for op in &operations {
+            self.invalidate_cache(namespace, &op.key).await;
+        }
+        self.invalidate_list_cache(namespace).await;
+
+        // Build request body
+        let body = serde_json::json!({
+            "operations": operations,
+            "transactional": transactional,
+        });
+
+        // Build request
+        let url = self.endpoints.batch_operate(namespace);
+        let mut request = self.build_request(Method::POST, &url)?;
+        request = request.json(&body);
+
+        // Add idempotency key if provided
+        if let Some(key) = &idempotency_key {
+            request = request.header("X-Idempotency-Key", key);
         }
 
+        // Execute with retry
+        let response = self.execute_with_retry(request).await?;
+        let request_id = header_str(response.headers(), "x-request-id");
+
+        // Parse response
+        let mut result: BatchOperateResult = self.parse_json_response(response).await?;
+        result.request_id = result.request_id.or(request_id);
+        result.idempotency_key = idempotency_key;
         Ok(result)
     }
 
-    /// Create a new API key
-    ///
-    /// Creates a new API key with the specified permissions and restrictions.
-    /// The key value is only returned in the creation response and cannot be retrieved later.
-    ///
-    /// # Arguments
+    /// Roll several secrets back to earlier versions in one batch
     ///
-    /// * `request` - The API key creation request containing name, permissions, etc.
-    /// * `idempotency_key` - Optional idempotency key to prevent duplicate creation
+    /// Each `(key, version)` target becomes a rollback operation. With
+    /// `transactional` set, servers that support it apply all rollbacks or
+    /// none, which suits restoring a namespace to its pre-incident state.
+    /// Check [`BatchOperateResult::failed_keys`] for targets that were not
+    /// rolled back.
     ///
-    /// # Returns
-    ///
-    /// An `ApiKeyInfo` containing the newly created key details including the key value.
-    ///
-    /// # Security
-    ///
-    /// The returned API key value should be stored securely. It cannot be retrieved
-    /// again after this call.
-    ///
-    /// # Errors
-    ///
-    /// * `Error::Http` with status 403 if not authorized to create keys
-    /// * `Error::Http` with status 400 for invalid permissions or parameters
-    ///
-    /// # Example
+    /// # Example
     ///
     /// ```no_run
-    /// # use secret_store_sdk::{Client, ClientBuilder, Auth, CreateApiKeyRequest};
-    /// # use secrecy::ExposeSecret;
+    /// # use secret_store_sdk::Client;
     /// # async fn example(client: &Client) -> Result<(), Box<dyn std::error::Error>> {
-    /// let request = CreateApiKeyRequest {
-    ///     name: "CI/CD Pipeline Key".to_string(),
-    ///     expires_at: Some("2024-12-31T23:59:59Z".to_string()),
-    ///     namespaces: vec!["production".to_string()],
-    ///     permissions: vec!["read".to_string()],
-    ///     metadata: None,
-    /// };
-    ///
-    /// let key_info = client.create_api_key(request, Some("unique-key-123".to_string())).await?;
-    /// if let Some(key) = &key_info.key {
-    ///     println!("New API key: {}", key.expose_secret());
-    ///     // Store this securely - it won't be available again!
+    /// let targets = vec![("db-url".to_string(), 4), ("api-key".to_string(), 7)];
+    /// let result = client
+    ///     .batch_rollback("production", targets, true, Some("incident-42".to_string()))
+    ///     .await?;
+    /// if !result.is_full_success() {
+    ///     println!("Not rolled back: {:?}", result.failed_keys());
     /// }
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn create_api_key(
+    pub async fn batch_rollback(
         &self,
-        request: CreateApiKeyRequest,
+        namespace: &str,
+        targets: Vec<(String, i32)>,
+        transactional: bool,
         idempotency_key: Option<String>,
-    ) -> Result<ApiKeyInfo> {
-        let url = self.endpoints.create_api_key();
-        let mut req = self.build_request(Method::POST, &url)?;
-        req = req.json(&request);
-
-        // Add idempotency key if provided
-        if let Some(key) = idempotency_key {
-            req = req.header("X-Idempotency-Key", key);
-        }
-
-        let response = self.execute_with_retry(req).await?;
-
-        if !response.status().is_success() {
-            return Err(self.parse_error_response(response).await);
-        }
-
-        self.parse_json_response(response).await
+    ) -> Result<BatchOperateResult> {
+        let operations = targets
+            .into_iter()
+            .map(|(key, version)| BatchOp::rollback(key, version))
+            .collect();
+        self.batch_operate(namespace, operations, transactional, idempotency_key)
+            .await
     }
 
-    /// Get API key details
-    ///
-    /// Retrieves detailed information about a specific API key.
-    /// Note that the key value itself is never returned for security reasons.
-    ///
-    /// # Arguments
-    ///
-    /// * `key_id` - The ID of the API key to retrieve
+    /// Import secrets from a streaming source
     ///
-    /// # Returns
+    /// Reads `reader` line by line and submits the parsed secrets as batch
+    /// puts of `opts.chunk_size` operations, so memory use stays bounded no
+    /// matter how large the source is. Progress is reported through
+    /// `opts.on_progress` after every batch.
     ///
-    /// An `ApiKeyInfo` with the key's metadata (without the key value).
+    /// Batches already submitted are not rolled back if a later line fails
+    /// to parse or a later batch request fails.
     ///
     /// # Errors
     ///
-    /// * `Error::Http` with status 404 if the key doesn't exist
-    /// * `Error::Http` with status 403 if not authorized to view the key
+    /// * `Error::Deserialize` for a malformed line, with its line number
+    /// * `Error::Other` if reading from `reader` fails
+    /// * Any error returned by [`Client::batch_operate`]
     ///
     /// # Example
     ///
     /// ```no_run
-    /// # use secret_store_sdk::{Client, ClientBuilder, Auth};
+    /// # use secret_store_sdk::{Client, ImportFormat, ImportOpts};
+    /// # use std::sync::Arc;
     /// # async fn example(client: &Client) -> Result<(), Box<dyn std::error::Error>> {
-    /// let key_info = client.get_api_key("key_123abc").await?;
-    /// println!("Key {} last used: {:?}", key_info.name, key_info.last_used_at);
+    /// // Any `AsyncBufRead` works, e.g. a buffered file or stdin
+    /// let seed = &b"DATABASE_URL=postgres://db\nAPI_KEY=\"abc 123\"\n"[..];
+    /// let opts = ImportOpts {
+    ///     chunk_size: 500,
+    ///     on_progress: Some(Arc::new(|p| println!("{} lines imported", p.lines_read))),
+    ///     ..Default::default()
+    /// };
+    /// let result = client
+    ///     .import_env_from_reader("staging", seed, ImportFormat::Dotenv, opts)
+    ///     .await?;
+    /// println!("{} imported, {} failed", result.succeeded, result.failed.len());
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn get_api_key(&self, key_id: &str) -> Result<ApiKeyInfo> {
-        let url = self.endpoints.get_api_key(key_id);
-        let request = self.build_request(Method::GET, &url)?;
-        let response = self.execute_with_retry(request).await?;
+    pub async fn import_env_from_reader<R>(
+        &self,
+        namespace: &str,
+        reader: R,
+        format: ImportFormat,
+        opts: ImportOpts,
+    ) -> Result<ImportResult>
+    where
+        R: tokio::io::AsyncBufRead + Unpin,
+    {
+        use tokio::io::AsyncBufReadExt;
+
+        self.ensure_writable()?;
+        let chunk_size = opts.chunk_size.max(1);
+        let mut lines = reader.lines();
+        let mut parser = import::LineParser::new(format);
+        let mut chunk = Vec::with_capacity(chunk_size);
+        let mut progress = ImportProgress::default();
+        let mut result = ImportResult::default();
 
-        if !response.status().is_success() {
-            return Err(self.parse_error_response(response).await);
-        }
+        loop {
+            let line = lines
+                .next_line()
+                .await
+                .map_err(|e| Error::Other(format!("Failed to read import source: {}", e)))?;
+            let done = line.is_none();
+
+            match line {
+                Some(line) => {
+                    progress.lines_read += 1;
+                    if let Some(op) = parser.push(&line, progress.lines_read)? {
+                        chunk.push(op);
+                    }
+                }
+                None => parser.finish()?,
+            }
 
-        self.parse_json_response(response).await
+            if chunk.len() >= chunk_size || (done && !chunk.is_empty()) {
+                let batch = std::mem::replace(&mut chunk, Vec::with_capacity(chunk_size));
+                let outcome = self
+                    .batch_operate(namespace, batch, opts.transactional, None)
+                    .await?;
+
+                result.batches += 1;
+                result.succeeded += outcome.results.succeeded.len();
+                result.failed.extend(outcome.results.failed);
+                progress.batches = result.batches;
+                progress.succeeded = result.succeeded;
+                progress.failed = result.failed.len();
+                if let Some(on_progress) = &opts.on_progress {
+                    on_progress(progress);
+                }
+            }
+
+            if done {
+                return Ok(result);
+            }
+        }
     }
 
-    /// Revoke an API key
+    /// Export secrets as environment variables
     ///
-    /// Revokes an API key, immediately invalidating it for future use.
-    /// This operation is irreversible.
+    /// Exports all secrets from a namespace in the specified format.
+    /// Supports conditional requests using ETag for efficient caching.
     ///
     /// # Arguments
     ///
-    /// * `key_id` - The ID of the API key to revoke
+    /// * `namespace` - The namespace to export
+    /// * `opts` - Export options including format and conditional request headers
     ///
     /// # Returns
     ///
-    /// A `RevokeApiKeyResult` confirming the revocation.
+    /// Returns `EnvExport::Json` for JSON format or `EnvExport::Text` for other formats.
+    /// A conditional export whose content hasn't changed returns
+    /// `EnvExport::NotModified`.
+    ///
+    /// With `opts.interpolate`, `${KEY}` references between variables are
+    /// resolved client-side and the export is rendered locally from the JSON
+    /// export, so text output is sorted by key.
     ///
     /// # Errors
     ///
-    /// * `Error::Http` with status 404 if the key doesn't exist
-    /// * `Error::Http` with status 403 if not authorized to revoke the key
+    /// * Returns errors for authentication, network, or server issues
     ///
     /// # Example
     ///
     /// ```no_run
-    /// # use secret_store_sdk::{Client, ClientBuilder, Auth};
+    /// # use secret_store_sdk::{Client, ClientBuilder, Auth, EnvExport, ExportEnvOpts, ExportFormat};
     /// # async fn example(client: &Client) -> Result<(), Box<dyn std::error::Error>> {
-    /// let result = client.revoke_api_key("key_123abc").await?;
-    /// println!("Revoked key: {}", result.key_id);
+    /// // Simple export
+    /// let opts = ExportEnvOpts {
+    ///     format: ExportFormat::Dotenv,
+    ///     ..Default::default()
+    /// };
+    /// let export = client.export_env("production", opts).await?;
+    ///
+    /// // Conditional export with ETag
+    /// let opts = ExportEnvOpts {
+    ///     format: ExportFormat::Json,
+    ///     use_cache: true,
+    ///     if_none_match: Some("previous-etag".to_string()),
+    ///     ..Default::default()
+    /// };
+    /// match client.export_env("production", opts).await? {
+    ///     EnvExport::NotModified { .. } => println!("Not modified"),
+    ///     export => println!("Content updated"),
+    /// }
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn revoke_api_key(&self, key_id: &str) -> Result<RevokeApiKeyResult> {
-        let url = self.endpoints.revoke_api_key(key_id);
-        let request = self.build_request(Method::DELETE, &url)?;
-        let response = self.execute_with_retry(request).await?;
+    pub async fn export_env(&self, namespace: &str, opts: ExportEnvOpts) -> Result<EnvExport> {
+        // Interpolation and overlays need the raw variables, so fetch JSON and
+        // render locally
+        let fetch_format = if opts.interpolate || opts.overlay.is_some() {
+            export::ensure_renderable(opts.format)?;
+            ExportFormat::Json
+        } else {
+            opts.format
+        };
 
-        if !response.status().is_success() {
-            return Err(self.parse_error_response(response).await);
+        // Exports share the list cache so writes to the namespace invalidate them
+        let cache_key = format!("{}/env/{}", namespace, fetch_format.as_str());
+        let list_cache = self.list_cache.as_ref().filter(|_| opts.use_cache);
+        let cached = match list_cache {
+            Some(list_cache) => list_cache.get(&cache_key).await,
+            None => None,
+        };
+
+        if let Some(entry) = &cached {
+            if opts.if_none_match.is_none() && entry.is_fresh_at(self.config.clock.now()) {
+                debug!("Export cache hit for key: {}", self.log_key(&cache_key));
+                return self.parse_env_export(namespace, &opts, fetch_format, entry.body.clone());
+            }
         }
+        let mut url = self.endpoints.export_env(namespace);
+        url.push_str(&format!("?format={}", fetch_format.as_str()));
 
-        let request_id = header_str(response.headers(), "x-request-id");
-        let mut result: RevokeApiKeyResult = self.parse_json_response(response).await?;
+        // Build request
+        let mut request = self.build_request(Method::GET, &url)?;
 
-        if result.request_id.is_none() {
-            result.request_id = request_id;
+        // Add conditional header if provided, otherwise revalidate the cached export
+        let if_none_match = opts
+            .if_none_match
+            .clone()
+            .or_else(|| cached.as_ref().and_then(|entry| entry.etag.clone()));
+        if let Some(etag) = &if_none_match {
+            request = request.header(reqwest::header::IF_NONE_MATCH, etag);
         }
 
-        Ok(result)
-    }
-
-    /// Get API discovery information
-    pub async fn discovery(&self) -> Result<Discovery> {
-        let url = self.endpoints.discovery();
-        let request = self.build_request(Method::GET, &url)?;
         let response = self.execute_with_retry(request).await?;
+        let export_ttl = Duration::from_secs(self.config.cache_config.list_ttl_secs);
+
+        // Handle 304 Not Modified
+        if response.status() == StatusCode::NOT_MODIFIED {
+            // Without an explicit ETag the 304 revalidates the cached export
+            let serve_cached = match (&opts.if_none_match, &cached) {
+                (None, Some(_)) => true,
+                (Some(etag), Some(entry)) => {
+                    opts.resolve_not_modified && entry.etag.as_ref() == Some(etag)
+                }
+                _ => false,
+            };
+            if let (true, Some(mut entry), Some(list_cache)) = (serve_cached, cached, list_cache) {
+                trace!(
+                    "Export not modified, refreshing cache key: {}",
+                    self.log_key(&cache_key)
+                );
+                let body = entry.body.clone();
+                entry.fresh_until = self.config.clock.now() + export_ttl;
+                list_cache.insert(cache_key, entry).await;
+                return self.parse_env_export(namespace, &opts, fetch_format, body);
+            }
+            return Ok(EnvExport::NotModified {
+                etag: header_str(response.headers(), "etag").or(if_none_match),
+                request_id: header_str(response.headers(), "x-request-id"),
+            });
+        }
 
+        // Check other error statuses
         if !response.status().is_success() {
             return Err(self.parse_error_response(response).await);
         }
 
-        self.parse_json_response(response).await
+        let etag = header_str(response.headers(), "etag");
+        let body = self.read_body(response).await?;
+
+        if let Some(list_cache) = list_cache {
+            let cached = CachedList {
+                body: body.clone(),
+                etag,
+                fresh_until: self.config.clock.now() + export_ttl,
+            };
+            list_cache.insert(cache_key, cached).await;
+            self.stats.record_insertion();
+        }
+
+        self.parse_env_export(namespace, &opts, fetch_format, body)
     }
 
-    /// Check liveness
-    ///
-    /// Performs a simple liveness check against the service.
-    /// Returns `Ok(())` if the service is alive and responding.
-    ///
-    /// This endpoint is typically used by Kubernetes liveness probes.
-    /// It does not check dependencies and should respond quickly.
-    ///
-    /// # Errors
+    /// Export only the variables changed since an earlier export
     ///
-    /// Returns an error if the service is not responding or returns
-    /// a non-2xx status code.
+    /// Sends `opts.since` as a `since` query parameter and `opts.if_none_match`
+    /// as `If-None-Match`, so sync agents only download what changed. A 304
+    /// yields an empty delta. Servers without delta support answer with a
+    /// full export, or reject the parameter with 400 or 501, in which case a
+    /// full export is fetched instead; either way the result has `full` set.
+    /// Deltas bypass the client cache.
     ///
     /// # Example
     ///
     /// ```no_run
-    /// # use secret_store_sdk::{Client, ClientBuilder, Auth};
-    /// # async fn example(client: &Client) -> Result<(), Box<dyn std::error::Error>> {
-    /// match client.livez().await {
-    ///     Ok(()) => println!("Service is alive"),
-    ///     Err(e) => eprintln!("Service is down: {}", e),
+    /// # use secret_store_sdk::{Client, ExportDeltaOpts};
+    /// # async fn example(client: &Client, last_sync: time::OffsetDateTime) -> Result<(), Box<dyn std::error::Error>> {
+    /// let opts = ExportDeltaOpts {
+    ///     since: Some(last_sync),
+    ///     ..Default::default()
+    /// };
+    /// let delta = client.export_env_delta("production", opts).await?;
+    /// for key in &delta.deleted {
+    ///     println!("removed {}", key);
+    /// }
+    /// for key in delta.changed.keys() {
+    ///     println!("changed {}", key);
     /// }
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn livez(&self) -> Result<()> {
-        let url = self.endpoints.livez();
-        let request = self.build_request(Method::GET, &url)?;
+    pub async fn export_env_delta(
+        &self,
+        namespace: &str,
+        opts: ExportDeltaOpts,
+    ) -> Result<EnvDelta> {
+        #[derive(serde::Deserialize)]
+        #[serde(untagged)]
+        enum DeltaResponse {
+            Delta {
+                changed: std::collections::BTreeMap<String, String>,
+                #[serde(default)]
+                deleted: Vec<String>,
+                #[serde(default)]
+                etag: Option<String>,
+                #[serde(default)]
+                request_id: Option<String>,
+            },
+            Full(EnvJsonExport),
+        }
 
-        // Execute without retry for health checks
-        let response = self.execute_without_retry(request).await?;
+        let url = format!(
+            "{}?{}",
+            self.endpoints.export_env(namespace),
+            opts.to_query_string()?
+        );
+        let mut request = self.build_request(Method::GET, &url)?;
+        if let Some(etag) = &opts.if_none_match {
+            request = request.header(reqwest::header::IF_NONE_MATCH, etag);
+        }
 
-        if response.status().is_success() {
-            Ok(())
-        } else {
-            Err(self.parse_error_response(response).await)
+        let response = match self.execute_with_retry(request).await {
+            Err(e) if opts.since.is_some() && matches!(e.status_code(), Some(400 | 501)) => {
+                debug!("Server rejected delta export, falling back to a full export");
+                let url = format!("{}?format=json", self.endpoints.export_env(namespace));
+                self.execute_with_retry(self.build_request(Method::GET, &url)?)
+                    .await?
+            }
+            result => result?,
+        };
+
+        let header_etag = header_str(response.headers(), "etag");
+        let header_request_id = header_str(response.headers(), "x-request-id");
+        if response.status() == StatusCode::NOT_MODIFIED {
+            return Ok(EnvDelta {
+                namespace: namespace.to_string(),
+                etag: header_etag.or(opts.if_none_match),
+                request_id: header_request_id,
+                ..Default::default()
+            });
         }
+
+        let body = self.read_body(response).await?;
+        Ok(match self.decode(&body)? {
+            DeltaResponse::Delta {
+                changed,
+                deleted,
+                etag,
+                request_id,
+            } => EnvDelta {
+                namespace: namespace.to_string(),
+                changed,
+                deleted,
+                etag: etag.or(header_etag),
+                request_id: request_id.or(header_request_id),
+                full: false,
+            },
+            DeltaResponse::Full(export) => EnvDelta {
+                namespace: namespace.to_string(),
+                changed: export.environment,
+                deleted: Vec::new(),
+                etag: Some(export.etag),
+                request_id: Some(export.request_id),
+                full: true,
+            },
+        })
     }
 
-    /// Check readiness with detailed status
-    ///
-    /// Performs a comprehensive readiness check that may include
-    /// checking dependencies (database, cache, etc.).
-    ///
-    /// This endpoint is typically used by Kubernetes readiness probes
-    /// to determine if the service is ready to accept traffic.
+    /// Load a namespace into the process environment
     ///
-    /// # Returns
-    ///
-    /// Returns a `HealthStatus` with details about the service health
-    /// including individual component checks.
+    /// Fetches the JSON export and calls [`std::env::set_var`] for each
+    /// variable, returning the number of variables set. Variables that are
+    /// already set are left untouched unless `opts.overwrite` is enabled, and
+    /// names that cannot be used as environment variables (empty, or
+    /// containing `=` or NUL) are skipped.
     ///
-    /// # Errors
+    /// # Thread safety
     ///
-    /// Returns an error if the service is not ready or if the
-    /// request fails.
+    /// Mutating the process environment is not thread-safe: on most platforms
+    /// concurrent reads of the environment from other threads (including
+    /// reads inside C libraries, e.g. `getaddrinfo`) are undefined behavior
+    /// while it is being written. Call this once at startup, before spawning
+    /// threads that may read the environment. When running inside a
+    /// multi-threaded tokio runtime, prefer exporting into a map with
+    /// [`Client::export_env`] and passing values explicitly.
     ///
     /// # Example
     ///
     /// ```no_run
-    /// # use secret_store_sdk::{Client, ClientBuilder, Auth};
+    /// # use secret_store_sdk::{Client, LoadEnvOpts};
     /// # async fn example(client: &Client) -> Result<(), Box<dyn std::error::Error>> {
-    /// let health = client.readyz().await?;
-    /// println!("Service status: {}", health.status);
-    ///
-    /// for (check, result) in &health.checks {
-    ///     println!("  {}: {} ({}ms)",
-    ///         check,
-    ///         result.status,
-    ///         result.duration_ms.unwrap_or(0)
-    ///     );
-    /// }
+    /// let opts = LoadEnvOpts {
+    ///     prefix: Some("APP_".to_string()),
+    ///     uppercase: true,
+    ///     ..Default::default()
+    /// };
+    /// let count = client.load_into_env("production", opts).await?;
+    /// println!("Loaded {} variables", count);
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn readyz(&self) -> Result<HealthStatus> {
-        let url = self.endpoints.readyz();
-        let request = self.build_request(Method::GET, &url)?;
+    pub async fn load_into_env(&self, namespace: &str, opts: LoadEnvOpts) -> Result<usize> {
+        let export = self
+            .export_env_format(namespace, ExportFormat::Json)
+            .await?;
+        let EnvExport::Json(export) = export else {
+            return Err(Error::Other("Expected JSON environment export".to_string()));
+        };
 
-        // Execute without retry for health checks
-        let response = self.execute_without_retry(request).await?;
+        let mut count = 0;
+        for (key, value) in export.environment {
+            let key = if opts.uppercase {
+                key.to_uppercase()
+            } else {
+                key
+            };
+            let name = format!("{}{}", opts.prefix.as_deref().unwrap_or(""), key);
 
-        if response.status().is_success() {
-            self.parse_json_response(response).await
-        } else {
-            Err(self.parse_error_response(response).await)
+            if name.is_empty() || name.contains(['=', '\0']) || value.contains('\0') {
+                warn!("Skipping secret with invalid environment variable name");
+                continue;
+            }
+            if !opts.overwrite && std::env::var_os(&name).is_some() {
+                continue;
+            }
+
+            std::env::set_var(&name, value);
+            count += 1;
         }
+
+        Ok(count)
     }
 
-    /// Get service metrics
+    /// Export secrets in the given format with default options
     ///
-    /// Retrieves metrics from the service in Prometheus format.
-    /// This endpoint may require special authentication using a metrics token.
+    /// Shorthand for [`Client::export_env`] with only `format` set.
     ///
-    /// # Arguments
+    /// # Example
     ///
-    /// * `metrics_token` - Optional metrics-specific authentication token.
-    ///   If not provided, uses the client's default authentication.
+    /// ```no_run
+    /// # use secret_store_sdk::{Client, EnvExport, ExportFormat};
+    /// # async fn example(client: &Client) -> Result<(), Box<dyn std::error::Error>> {
+    /// if let EnvExport::Text(dotenv) = client.export_env_format("production", ExportFormat::Dotenv).await? {
+    ///     std::fs::write(".env", dotenv)?;
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn export_env_format(
+        &self,
+        namespace: &str,
+        format: ExportFormat,
+    ) -> Result<EnvExport> {
+        let opts = ExportEnvOpts {
+            format,
+            ..Default::default()
+        };
+        self.export_env(namespace, opts).await
+    }
+
+    /// Parse an export body fetched in `fetch_format`
+    fn parse_env_export(
+        &self,
+        namespace: &str,
+        opts: &ExportEnvOpts,
+        fetch_format: ExportFormat,
+        body: Vec<u8>,
+    ) -> Result<EnvExport> {
+        match fetch_format {
+            ExportFormat::Json if opts.interpolate || opts.overlay.is_some() => {
+                let json_result: EnvJsonExport = self.decode(&body)?;
+                let mut vars = json_result.environment;
+                if let Some(environment) = &opts.overlay {
+                    vars = export::apply_overlay(vars, environment, Self::overlay_separator(opts));
+                }
+                if opts.interpolate {
+                    vars = export::interpolate(&vars, opts.on_missing_reference)?;
+                }
+                Ok(export::render_env(
+                    namespace,
+                    vars,
+                    opts.format,
+                    &json_result.etag,
+                    json_result.request_id,
+                    &std::collections::HashSet::new(),
+                    opts.binary_encoding,
+                ))
+            }
+            ExportFormat::Json => {
+                let json_result: EnvJsonExport = self.decode(&body)?;
+                Ok(EnvExport::Json(json_result))
+            }
+            _ => {
+                let text =
+                    String::from_utf8(body).map_err(|e| Error::Deserialize(e.to_string()))?;
+                Ok(EnvExport::Text(text))
+            }
+        }
+    }
+
+    fn overlay_separator(opts: &ExportEnvOpts) -> &str {
+        opts.overlay_separator
+            .as_deref()
+            .unwrap_or(export::DEFAULT_OVERLAY_SEPARATOR)
+    }
+
+    /// Export environment variables by fetching values in parallel
     ///
-    /// # Returns
+    /// An alternative to [`Client::export_env`] for very large namespaces:
+    /// lists the keys, fetches the values concurrently via [`Client::get_many`]
+    /// (which uses the cache) and renders the export locally. The returned
+    /// ETag is derived from the sorted `key:version` pairs of the exported
+    /// secrets, so it changes whenever any secret changes.
     ///
-    /// Returns the metrics as a raw string in Prometheus exposition format.
+    /// Keys deleted between listing and fetching are skipped; any other
+    /// failure aborts the export.
     ///
-    /// # Errors
+    /// # Example
     ///
-    /// * `Error::Http` with status 401 if authentication fails
-    /// * `Error::Http` with status 403 if not authorized to view metrics
+    /// ```no_run
+    /// # use secret_store_sdk::{Client, EnvExport, ExportFormat};
+    /// # async fn example(client: &Client) -> Result<(), Box<dyn std::error::Error>> {
+    /// let result = client
+    ///     .export_env_parallel("production", ExportFormat::Dotenv, 16)
+    ///     .await?;
+    /// if let EnvExport::Text(dotenv) = result.export {
+    ///     std::fs::write(".env", dotenv)?;
+    /// }
+    /// println!("Export ETag: {}", result.etag);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn export_env_parallel(
+        &self,
+        namespace: &str,
+        format: ExportFormat,
+        concurrency: usize,
+    ) -> Result<ParallelExport> {
+        let opts = ExportEnvOpts {
+            format,
+            ..Default::default()
+        };
+        self.export_env_parallel_opts(namespace, &opts, concurrency)
+            .await
+    }
+
+    /// Export environment variables by fetching values in parallel, with options
+    ///
+    /// Like [`Client::export_env_parallel`], but takes the export options.
+    /// Secrets the server reports as binary are rendered according to
+    /// `opts.binary_encoding`. Only `format`, `binary_encoding` and the
+    /// overlay options are used; the other options apply to server-side
+    /// exports. Base keys overridden by an overlay are not fetched.
     ///
     /// # Example
     ///
     /// ```no_run
-    /// # use secret_store_sdk::{Client, ClientBuilder, Auth};
+    /// # use secret_store_sdk::{BinaryEncoding, Client, ExportEnvOpts, ExportFormat};
     /// # async fn example(client: &Client) -> Result<(), Box<dyn std::error::Error>> {
-    /// // Using default authentication
-    /// let metrics = client.metrics(None).await?;
-    /// println!("Metrics:\n{}", metrics);
-    ///
-    /// // Using specific metrics token
-    /// let metrics = client.metrics(Some("metrics-token-xyz")).await?;
-    /// println!("Metrics with token:\n{}", metrics);
+    /// let opts = ExportEnvOpts {
+    ///     format: ExportFormat::Dotenv,
+    ///     binary_encoding: BinaryEncoding::Hex,
+    ///     ..Default::default()
+    /// };
+    /// let result = client.export_env_parallel_opts("production", &opts, 16).await?;
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn metrics(&self, metrics_token: Option<&str>) -> Result<String> {
-        let url = self.endpoints.metrics();
-        let mut request = self.build_request(Method::GET, &url)?;
-
-        // Add metrics-specific token if provided
-        if let Some(token) = metrics_token {
-            request = request.header("X-Metrics-Token", token);
+    pub async fn export_env_parallel_opts(
+        &self,
+        namespace: &str,
+        opts: &ExportEnvOpts,
+        concurrency: usize,
+    ) -> Result<ParallelExport> {
+        use futures::TryStreamExt;
+
+        export::ensure_renderable(opts.format)?;
+        let pages: Vec<ListSecretsResult> = self
+            .list_secrets_pages(namespace, ListOpts::default())
+            .try_collect()
+            .await?;
+        let request_id = pages
+            .first()
+            .and_then(|page| page.request_id.clone())
+            .unwrap_or_default();
+        let listed_keys: Vec<String> = pages
+            .into_iter()
+            .flat_map(|page| page.secrets)
+            .map(|info| info.key)
+            .collect();
+        let listed = listed_keys.iter().map(String::as_str);
+
+        // Exported name of each fetched key
+        let names: std::collections::HashMap<String, String> = match &opts.overlay {
+            Some(environment) => {
+                export::overlay_sources(listed, environment, Self::overlay_separator(opts))
+                    .into_iter()
+                    .map(|(name, source)| (source, name))
+                    .collect()
+            }
+            None => listed
+                .map(|key| (key.to_string(), key.to_string()))
+                .collect(),
+        };
+        let keys: Vec<&str> = listed_keys
+            .iter()
+            .map(String::as_str)
+            .filter(|key| names.contains_key(*key))
+            .collect();
+
+        let mut vars = std::collections::BTreeMap::new();
+        let mut binary_keys = std::collections::HashSet::new();
+        let mut versions = Vec::new();
+        for (key, result) in self.get_many(namespace, &keys, concurrency).await {
+            match result {
+                Ok(secret) => {
+                    let name = names.get(&key).cloned().unwrap_or_else(|| key.clone());
+                    versions.push((key, secret.version));
+                    if secret.is_binary() {
+                        let _ = binary_keys.insert(name.clone());
+                    }
+                    let _ = vars.insert(name, secret.value.expose_secret().to_string());
+                }
+                Err(e) if e.status_code() == Some(404) => {
+                    let cache_key = format!("{}/{}", namespace, key);
+                    debug!(
+                        "Skipping secret deleted during export: {}",
+                        self.log_key(&cache_key)
+                    );
+                }
+                Err(e) => return Err(e),
+            }
         }
 
-        // Execute without retry for metrics endpoint
-        let response = self.execute_without_retry(request).await?;
+        let etag = export::aggregate_etag(&versions);
+        let export = export::render_env(
+            namespace,
+            vars,
+            opts.format,
+            &etag,
+            request_id,
+            &binary_keys,
+            opts.binary_encoding,
+        );
 
-        if response.status().is_success() {
-            response.text().await.map_err(Error::from)
-        } else {
-            Err(self.parse_error_response(response).await)
-        }
+        Ok(ParallelExport { export, etag })
     }
 
-    // Helper methods
-
-    /// Build a request with common headers
-    fn build_request(&self, method: Method, url: &str) -> Result<reqwest::RequestBuilder> {
-        let mut builder = self.http.request(method, url);
-
-        // Generate and add request ID
-        let request_id = generate_request_id();
-        builder = builder.header("X-Request-ID", &request_id);
+    /// Compute a fingerprint of a namespace's current state
+    ///
+    /// Lists every key with its version and returns a hex SHA-256 over the
+    /// sorted `key:version` pairs; values are never fetched. The fingerprint
+    /// changes exactly when a secret is added, removed or updated, which
+    /// makes it a cheap way for deploy tooling to decide whether anything
+    /// changed since the last rollout.
+    ///
+    /// Listings are served from the list cache when fresh, so changes made by
+    /// other clients may take up to the list cache TTL to show up.
+    ///
+    /// # Errors
+    ///
+    /// * `Error::Deserialize` if the server leaves versions out of the listing
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use secret_store_sdk::Client;
+    /// # async fn example(client: &Client, deployed: &str) -> Result<(), Box<dyn std::error::Error>> {
+    /// if client.namespace_fingerprint("production").await? != deployed {
+    ///     println!("Secrets changed, redeploying");
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn namespace_fingerprint(&self, namespace: &str) -> Result<String> {
+        use futures::TryStreamExt;
 
-        // Add trace headers
-        builder = builder
-            .header("X-Trace-ID", &request_id)
-            .header("X-Span-ID", uuid::Uuid::new_v4().to_string());
+        let opts = ListOpts {
+            fields: Some(vec![ListField::Key, ListField::Version]),
+            ..Default::default()
+        };
+        let pages: Vec<ListSecretsResult> = self
+            .list_secrets_pages(namespace, opts)
+            .try_collect()
+            .await?;
+
+        let mut versions = Vec::new();
+        for info in pages.into_iter().flat_map(|page| page.secrets) {
+            let version = info.version.ok_or_else(|| {
+                Error::Deserialize(format!("Listing of {} is missing versions", namespace))
+            })?;
+            versions.push((info.key, version));
+        }
+        Ok(export::fingerprint(&versions))
+    }
 
-        Ok(builder)
+    /// List all namespaces
+    pub async fn list_namespaces(&self) -> Result<ListNamespacesResult> {
+        let url = self.endpoints.list_namespaces();
+        self.get_listing(NAMESPACES_LIST_CACHE_KEY.to_string(), &url)
+            .await
     }
 
-    /// Execute a request with retry logic
-    async fn execute_with_retry(
+    /// Create a new namespace
+    ///
+    /// Creates a new namespace in the secret store. Requires global admin permissions.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the namespace to create
+    /// * `description` - Optional description for the namespace
+    /// * `idempotency_key` - Optional idempotency key to prevent duplicate creation
+    ///
+    /// # Returns
+    ///
+    /// A `CreateNamespaceResult` containing the creation details.
+    ///
+    /// # Errors
+    ///
+    /// * `Error::Http` with status 403 if not authorized to create namespaces
+    /// * `Error::Http` with status 409 if the namespace already exists
+    /// * `Error::Http` with status 400 for invalid namespace names
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use secret_store_sdk::{Client, ClientBuilder, Auth};
+    /// # async fn example(client: &Client) -> Result<(), Box<dyn std::error::Error>> {
+    /// let result = client.create_namespace(
+    ///     "production",
+    ///     Some("Production environment secrets".to_string()),
+    ///     None
+    /// ).await?;
+    /// println!("Created namespace: {}", result.namespace);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn create_namespace(
         &self,
-        request_builder: reqwest::RequestBuilder,
-    ) -> Result<Response> {
-        let mut token_refresh_count = 0;
-        let max_retries = self.config.retries;
-        let auth = &self.config.auth;
+        name: &str,
+        description: Option<String>,
+        idempotency_key: Option<String>,
+    ) -> Result<CreateNamespaceResult> {
+        self.ensure_writable()?;
+        self.invalidate_list_cache(name).await;
 
-        // Extract method and URL for metrics
-        #[cfg(feature = "metrics")]
-        let (method, path) = {
-            // Try to build a request to extract metadata
-            if let Some(cloned_builder) = request_builder.try_clone() {
-                if let Ok(req) = cloned_builder.build() {
-                    let method = req.method().to_string();
-                    let path = req.url().path().to_string();
-                    (method, path)
-                } else {
-                    ("UNKNOWN".to_string(), "UNKNOWN".to_string())
-                }
-            } else {
-                ("UNKNOWN".to_string(), "UNKNOWN".to_string())
-            }
+        let url = self.endpoints.create_namespace();
+        let mut request = self.build_request(Method::POST, &url)?;
+
+        let body = CreateNamespaceRequest {
+            name: name.to_string(),
+            description,
         };
+        request = request.json(&body);
 
-        loop {
-            // Get current auth header (may be refreshed)
-            let (auth_header, auth_value) = auth
-                .get_header()
-                .await
-                .map_err(|e| Error::Config(format!("Failed to get auth header: {}", e)))?;
+        // Add idempotency key if provided
+        if let Some(key) = idempotency_key {
+            request = request.header("X-Idempotency-Key", key);
+        }
 
-            // Clone the base request and add current auth header
-            let req_with_auth = request_builder
-                .try_clone()
-                .ok_or_else(|| Error::Other("Request cannot be cloned".to_string()))?
-                .header(auth_header, auth_value);
+        let response = self.execute_with_retry(request).await?;
 
-            // Create backoff strategy for retries
-            let mut backoff = ExponentialBackoff {
-                initial_interval: Duration::from_millis(100),
-                randomization_factor: 0.3,
-                multiplier: 2.0,
-                max_interval: Duration::from_secs(10),
-                max_elapsed_time: None,
-                ..Default::default()
-            };
-            // Calculate max elapsed time based on timeout and retries
-            // Allow enough time for all retries with their respective timeouts
-            backoff.max_elapsed_time = if max_retries > 0 {
-                let timeout_secs = self.config.timeout.as_secs();
-                // Allow time for all retries plus some buffer for backoff delays
-                Some(Duration::from_secs((max_retries as u64 + 1) * timeout_secs + 30))
-            } else {
-                Some(Duration::from_millis(0))
-            };
+        if !response.status().is_success() {
+            return Err(self.parse_error_response(response).await);
+        }
 
-            let retry_count = std::sync::Arc::new(std::sync::atomic::AtomicUsize::new(0));
-            let retry_count_clone = retry_count.clone();
+        self.parse_json_response(response).await
+    }
 
-            // Execute with backoff retry
-            let result = retry_notify(
-                backoff,
-                || async {
-                    let current_retry = retry_count.load(std::sync::atomic::Ordering::Relaxed);
-                    // Clone request for this attempt
-                    let req = req_with_auth
-                        .try_clone()
-                        .ok_or_else(|| {
-                            backoff::Error::Permanent(Error::Other(
-                                "Request cannot be cloned".to_string(),
-                            ))
-                        })?
-                        .build()
-                        .map_err(|e| {
-                            backoff::Error::Permanent(Error::Other(format!(
-                                "Failed to build request: {}",
-                                e
-                            )))
-                        })?;
+    /// Get namespace information
+    pub async fn get_namespace(&self, namespace: &str) -> Result<NamespaceInfo> {
+        let url = self.endpoints.get_namespace(namespace);
+        let request = self.build_request(Method::GET, &url)?;
+        let response = self.execute_with_retry(request).await?;
 
-                    // Track active connections
-                    #[cfg(feature = "metrics")]
-                    self.metrics.inc_active_connections();
+        if !response.status().is_success() {
+            return Err(self.parse_error_response(response).await);
+        }
 
-                    // Start timing request
-                    #[cfg(feature = "metrics")]
+        self.parse_json_response(response).await
+    }
+
+    /// Initialize a namespace with a template
+    ///
+    /// Initializes a new namespace using a predefined template to create
+    /// a set of initial secrets.
+    ///
+    /// # Arguments
+    ///
+    /// * `namespace` - The namespace to initialize
+    /// * `template` - The template configuration
+    /// * `idempotency_key` - Optional idempotency key to prevent duplicate initialization
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use secret_store_sdk::{Client, ClientBuilder, Auth, NamespaceTemplate};
+    /// # use serde_json::json;
+    /// # async fn example(client: &Client) -> Result<(), Box<dyn std::error::Error>> {
+    /// let template = NamespaceTemplate {
+    ///     template: "web-app".to_string(),
+    ///     params: json!({
+    ///         "environment": "staging",
+    ///         "region": "us-west-2"
+    ///     }),
+    /// };
+    ///
+    /// let result = client.init_namespace(
+    ///     "staging-app",
+    ///     template,
+    ///     Some("init-staging-12345".to_string())
+    /// ).await?;
+    /// println!("Created {} secrets", result.secrets_created);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn init_namespace(
+        &self,
+        namespace: &str,
+        template: NamespaceTemplate,
+        idempotency_key: Option<String>,
+    ) -> Result<InitNamespaceResult> {
+        self.ensure_writable()?;
+        self.invalidate_list_cache(namespace).await;
+
+        let url = self.endpoints.init_namespace(namespace);
+        let mut request = self.build_request(Method::POST, &url)?;
+        request = request.json(&template);
+
+        // Add idempotency key if provided
+        if let Some(key) = idempotency_key {
+            request = request.header("X-Idempotency-Key", key);
+        }
+
+        let response = self.execute_with_retry(request).await?;
+
+        if !response.status().is_success() {
+            return Err(self.parse_error_response(response).await);
+        }
+
+        self.parse_json_response(response).await
+    }
+
+    /// Initialize several namespaces from templates concurrently
+    ///
+    /// Runs [`Client::init_namespace`] for each spec with at most
+    /// `concurrency` requests in flight. Each namespace gets its own generated
+    /// idempotency key, so retries of one item never collide with another.
+    /// Results are returned in the same order as `specs`.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use secret_store_sdk::{Client, NamespaceTemplate};
+    /// # use serde_json::json;
+    /// # async fn example(client: &Client) {
+    /// let template = NamespaceTemplate {
+    ///     template: "web-app".to_string(),
+    ///     params: json!({ "environment": "staging" }),
+    /// };
+    /// let specs = vec![
+    ///     ("team-a".to_string(), template.clone()),
+    ///     ("team-b".to_string(), template),
+    /// ];
+    /// for (namespace, result) in client.init_namespaces(specs, 4).await {
+    ///     if let Err(e) = result {
+    ///         println!("{} failed: {}", namespace, e);
+    ///     }
+    /// }
+    /// # }
+    /// ```
+    pub async fn init_namespaces(
+        &self,
+        specs: Vec<(String, NamespaceTemplate)>,
+        concurrency: usize,
+    ) -> Vec<(String, Result<InitNamespaceResult>)> {
+        use futures::stream::{self, StreamExt};
+
+        let mut results: Vec<(usize, String, Result<InitNamespaceResult>)> =
+            stream::iter(specs.into_iter().enumerate())
+                .map(|(index, (namespace, template))| async move {
+                    let idempotency_key = format!("init-{}-{}", namespace, uuid::Uuid::new_v4());
+                    let result = self
+                        .init_namespace(&namespace, template, Some(idempotency_key))
+                        .await;
+                    (index, namespace, result)
+                })
+                .buffer_unordered(concurrency.max(1))
+                .collect()
+                .await;
+
+        results.sort_by_key(|(index, _, _)| *index);
+        results
+            .into_iter()
+            .map(|(_, namespace, result)| (namespace, result))
+            .collect()
+    }
+
+    /// Delete a namespace and all its secrets
+    ///
+    /// **Warning**: This operation is irreversible and will delete all secrets
+    /// in the namespace. Use with extreme caution.
+    ///
+    /// This operation may take some time for namespaces with many secrets.
+    /// The response includes the number of secrets that were deleted.
+    ///
+    /// # Arguments
+    ///
+    /// * `namespace` - The namespace to delete
+    ///
+    /// # Returns
+    ///
+    /// A `DeleteNamespaceResult` containing deletion details.
+    ///
+    /// # Errors
+    ///
+    /// * `Error::Http` with status 404 if the namespace doesn't exist
+    /// * `Error::Http` with status 403 if deletion is forbidden
+    /// * `Error::Protected` if the namespace has protection enabled (409)
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use secret_store_sdk::{Client, ClientBuilder, Auth};
+    /// # async fn example(client: &Client) -> Result<(), Box<dyn std::error::Error>> {
+    /// let result = client.delete_namespace("test-namespace").await?;
+    /// println!("Deleted {} secrets from namespace {}",
+    ///     result.secrets_deleted,
+    ///     result.namespace
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn delete_namespace(&self, namespace: &str) -> Result<DeleteNamespaceResult> {
+        self.ensure_writable()?;
+        // Clear all cached entries for this namespace
+        if let Some(cache) = &self.cache {
+            // TODO: Optimize to only clear entries for this specific namespace
+            // For now, we'll invalidate all cache to ensure consistency
+            cache.invalidate_all();
+            debug!(
+                "Cleared all cache entries due to namespace deletion: {}",
+                namespace
+            );
+        }
+        #[cfg(feature = "disk-cache")]
+        if let Some(disk_cache) = &self.disk_cache {
+            let namespace = namespace.to_string();
+            let _ = disk_cache
+                .run(move |disk_cache| disk_cache.remove_namespace(&namespace))
+                .await;
+        }
+        self.invalidate_list_cache(namespace).await;
+
+        // Build request
+        let url = self.endpoints.delete_namespace(namespace);
+        let request = self.build_request(Method::DELETE, &url)?;
+
+        // Execute with retry
+        let response = self
+            .execute_with_retry(request)
+            .await
+            .map_err(|e| Self::protected_error(namespace, e))?;
+
+        // Check status
+        if !response.status().is_success() {
+            return Err(self.parse_error_response(response).await);
+        }
+
+        // Extract request ID from headers
+        let request_id = header_str(response.headers(), "x-request-id");
+
+        // Parse response
+        let mut result: DeleteNamespaceResult = self.parse_json_response(response).await?;
+
+        // Set request_id if not already in the response body
+        if result.request_id.is_none() {
+            result.request_id = request_id;
+        }
+
+        Ok(result)
+    }
+
+    /// Delete a namespace and all its secrets with idempotency support
+    ///
+    /// Same as `delete_namespace` but with idempotency key support for safe retries.
+    ///
+    /// # Arguments
+    ///
+    /// * `namespace` - The namespace to delete
+    /// * `idempotency_key` - Optional idempotency key to prevent duplicate deletion
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use secret_store_sdk::{Client, ClientBuilder, Auth};
+    /// # async fn example(client: &Client) -> Result<(), Box<dyn std::error::Error>> {
+    /// let result = client.delete_namespace_idempotent(
+    ///     "test-namespace",
+    ///     Some("delete-ns-12345".to_string())
+    /// ).await?;
+    /// println!("Deleted {} secrets", result.secrets_deleted);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn delete_namespace_idempotent(
+        &self,
+        namespace: &str,
+        idempotency_key: Option<String>,
+    ) -> Result<DeleteNamespaceResult> {
+        self.ensure_writable()?;
+        // Clear all cached entries for this namespace
+        if let Some(cache) = &self.cache {
+            cache.invalidate_all();
+            debug!(
+                "Cleared all cache entries due to namespace deletion: {}",
+                namespace
+            );
+        }
+        #[cfg(feature = "disk-cache")]
+        if let Some(disk_cache) = &self.disk_cache {
+            let namespace = namespace.to_string();
+            let _ = disk_cache
+                .run(move |disk_cache| disk_cache.remove_namespace(&namespace))
+                .await;
+        }
+        self.invalidate_list_cache(namespace).await;
+
+        // Build request
+        let url = self.endpoints.delete_namespace(namespace);
+        let mut request = self.build_request(Method::DELETE, &url)?;
+
+        // Add idempotency key if provided
+        if let Some(key) = idempotency_key {
+            request = request.header("X-Idempotency-Key", key);
+        }
+
+        // Execute with retry
+        let response = self
+            .execute_with_retry(request)
+            .await
+            .map_err(|e| Self::protected_error(namespace, e))?;
+
+        // Check status
+        if !response.status().is_success() {
+            return Err(self.parse_error_response(response).await);
+        }
+
+        // Extract request ID from headers
+        let request_id = header_str(response.headers(), "x-request-id");
+
+        // Parse response
+        let mut result: DeleteNamespaceResult = self.parse_json_response(response).await?;
+
+        // Set request_id if not already in the response body
+        if result.request_id.is_none() {
+            result.request_id = request_id;
+        }
+
+        Ok(result)
+    }
+
+    /// Map a refused namespace deletion to `Error::Protected`
+    fn protected_error(namespace: &str, error: Error) -> Error {
+        match error {
+            Error::Http {
+                status,
+                category,
+                request_id,
+                ..
+            } if status == 409 || category == "protected" => Error::Protected {
+                namespace: namespace.to_string(),
+                request_id,
+            },
+            e => e,
+        }
+    }
+
+    /// Enable or disable deletion protection for a namespace
+    ///
+    /// A protected namespace cannot be deleted: [`Client::delete_namespace`]
+    /// fails with [`Error::Protected`] until protection is disabled again.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use secret_store_sdk::{Client, Error};
+    /// # async fn example(client: &Client) -> Result<(), Box<dyn std::error::Error>> {
+    /// match client.delete_namespace("staging").await {
+    ///     Err(Error::Protected { .. }) => {
+    ///         client.set_namespace_protection("staging", false).await?;
+    ///         client.delete_namespace("staging").await?;
+    ///     }
+    ///     result => {
+    ///         let _ = result?;
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn set_namespace_protection(
+        &self,
+        namespace: &str,
+        enabled: bool,
+    ) -> Result<NamespaceProtectionResult> {
+        self.ensure_writable()?;
+
+        let url = self.endpoints.namespace_protection(namespace);
+        let request = self
+            .build_request(Method::PUT, &url)?
+            .json(&serde_json::json!({ "enabled": enabled }));
+        let response = self.execute_with_retry(request).await?;
+        let request_id = header_str(response.headers(), "x-request-id");
+
+        let mut result: NamespaceProtectionResult = self.parse_json_response(response).await?;
+        result.request_id = result.request_id.or(request_id);
+        Ok(result)
+    }
+
+    /// List versions of a secret
+    pub async fn list_versions(&self, namespace: &str, key: &str) -> Result<VersionList> {
+        // Build and execute request
+        let url = self.endpoints.list_versions(namespace, key);
+        let request = self.build_request(Method::GET, &url)?;
+        let response = self.execute_with_retry(request).await?;
+
+        // Parse response
+        self.parse_json_response(response).await
+    }
+
+    /// Get a specific version of a secret
+    pub async fn get_version(&self, namespace: &str, key: &str, version: i32) -> Result<Secret> {
+        // Build and execute request
+        let url = self.endpoints.get_version(namespace, key, version);
+        let request = self.build_request(Method::GET, &url)?;
+        let response = self.execute_with_retry(request).await?;
+
+        // Parse response (similar to get_secret)
+        self.parse_get_response(response, namespace, key).await
+    }
+
+    /// Get several secrets from a namespace concurrently
+    ///
+    /// Fetches each key with at most `concurrency` requests in flight, using
+    /// the cache like [`Client::get_secret`]. Every key gets its own result,
+    /// returned in the same order as `keys`.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use secret_store_sdk::Client;
+    /// # async fn example(client: &Client) {
+    /// let results = client.get_many("production", &["db-url", "api-key"], 8).await;
+    /// for (key, result) in results {
+    ///     if let Err(e) = result {
+    ///         println!("{} unavailable: {}", key, e);
+    ///     }
+    /// }
+    /// # }
+    /// ```
+    pub async fn get_many<K: AsRef<str>>(
+        &self,
+        namespace: &str,
+        keys: &[K],
+        concurrency: usize,
+    ) -> Vec<(String, Result<Secret>)> {
+        use futures::stream::{self, StreamExt};
+
+        let mut results: Vec<(usize, String, Result<Secret>)> =
+            stream::iter(keys.iter().map(|key| key.as_ref()).enumerate())
+                .map(|(index, key)| async move {
+                    let result = self.get_secret(namespace, key, GetOpts::default()).await;
+                    (index, key.to_string(), result)
+                })
+                .buffer_unordered(concurrency.max(1))
+                .collect()
+                .await;
+
+        results.sort_by_key(|(index, _, _)| *index);
+        results
+            .into_iter()
+            .map(|(_, key, result)| (key, result))
+            .collect()
+    }
+
+    /// Get several specific versions of a secret concurrently
+    ///
+    /// Fetches each requested version with at most `concurrency` requests in
+    /// flight. Every version gets its own result, so a missing or failed
+    /// version does not prevent the others from being returned. Results are
+    /// returned in the same order as `versions`.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use secret_store_sdk::Client;
+    /// # async fn example(client: &Client) {
+    /// let results = client.get_versions("production", "api-key", &[1, 2, 3], 4).await;
+    /// for (version, result) in results {
+    ///     match result {
+    ///         Ok(secret) => println!("v{} updated at {}", version, secret.updated_at),
+    ///         Err(e) => println!("v{} unavailable: {}", version, e),
+    ///     }
+    /// }
+    /// # }
+    /// ```
+    pub async fn get_versions(
+        &self,
+        namespace: &str,
+        key: &str,
+        versions: &[i32],
+        concurrency: usize,
+    ) -> Vec<(i32, Result<Secret>)> {
+        use futures::stream::{self, StreamExt};
+
+        let mut results: Vec<(usize, i32, Result<Secret>)> =
+            stream::iter(versions.iter().copied().enumerate())
+                .map(|(index, version)| async move {
+                    (
+                        index,
+                        version,
+                        self.get_version(namespace, key, version).await,
+                    )
+                })
+                .buffer_unordered(concurrency.max(1))
+                .collect()
+                .await;
+
+        results.sort_by_key(|(index, _, _)| *index);
+        results
+            .into_iter()
+            .map(|(_, version, result)| (version, result))
+            .collect()
+    }
+
+    /// Rollback a secret to a previous version
+    pub async fn rollback(
+        &self,
+        namespace: &str,
+        key: &str,
+        version: i32,
+    ) -> Result<RollbackResult> {
+        self.rollback_idempotent(namespace, key, version, None)
+            .await
+    }
+
+    /// Rollback a secret to a previous version with idempotency support
+    ///
+    /// Same as `rollback` but sends `idempotency_key` so a retried rollback
+    /// does not create a second version. The key is echoed on the result.
+    pub async fn rollback_idempotent(
+        &self,
+        namespace: &str,
+        key: &str,
+        version: i32,
+        idempotency_key: Option<String>,
+    ) -> Result<RollbackResult> {
+        self.ensure_writable()?;
+        // Invalidate cache for this key since we're changing it
+        self.invalidate_cache(namespace, key).await;
+        self.invalidate_list_cache(namespace).await;
+
+        // Build request with empty body (comment is optional)
+        let url = self.endpoints.rollback(namespace, key, version);
+        let mut request = self.build_request(Method::POST, &url)?;
+        request = request.json(&serde_json::json!({}));
+        if let Some(key) = &idempotency_key {
+            request = request.header("X-Idempotency-Key", key);
+        }
+
+        // Execute with retry
+        let response = self.execute_with_retry(request).await?;
+
+        // Parse response
+        let mut result: RollbackResult = self.parse_json_response(response).await?;
+        result.idempotency_key = idempotency_key;
+        Ok(result)
+    }
+
+    /// Query audit logs
+    pub async fn audit(&self, query: AuditQuery) -> Result<AuditResult> {
+        if query.cursor.is_some() {
+            self.require_api_version("cursor pagination", CURSOR_PAGINATION_MIN_API_VERSION)
+                .await?;
+        }
+
+        // Build URL with query parameters
+        let mut url = self.endpoints.audit();
+        let params = query.to_query_string();
+
+        if !params.is_empty() {
+            url.push('?');
+            url.push_str(&params);
+        }
+
+        // Build and execute request
+        let request = self.build_request(Method::GET, &url)?;
+        let response = self.execute_with_retry(request).await?;
+
+        // Parse response
+        self.parse_json_response(response).await
+    }
+
+    /// Stream all pages of an audit query
+    ///
+    /// Follows the server's `next_cursor` when present and falls back to
+    /// advancing `offset` for servers without cursor support. The stream ends
+    /// after the last page or the first error. Fetching more than
+    /// `query.max_pages` pages or `query.max_items` entries yields an
+    /// `Error::Other` and ends the stream.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use secret_store_sdk::{AuditQuery, Client};
+    /// # use futures::StreamExt;
+    /// # async fn example(client: &Client) -> Result<(), Box<dyn std::error::Error>> {
+    /// let query = AuditQuery::builder().namespace("production").limit(100).build()?;
+    /// let pages = client.audit_pages(query);
+    /// futures::pin_mut!(pages);
+    ///
+    /// while let Some(page) = pages.next().await {
+    ///     for entry in page?.entries {
+    ///         println!("{} {}", entry.timestamp, entry.action);
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn audit_pages(
+        &self,
+        query: AuditQuery,
+    ) -> impl futures::Stream<Item = Result<AuditResult>> {
+        let limits = PageLimits::new(query.max_pages, query.max_items);
+        let state = (self.clone(), Some(query), limits);
+        futures::stream::unfold(state, |(client, query, mut limits)| async move {
+            let mut query = query?;
+            let page = client.audit(query.clone()).await;
+            if let Ok(result) = &page {
+                if let Err(e) = limits.admit(result.entries.len()) {
+                    return Some((Err(e), (client, None, limits)));
+                }
+            }
+            let next = match &page {
+                Ok(page) => match &page.next_cursor {
+                    Some(cursor) if query.cursor.as_ref() != Some(cursor) => {
+                        query.cursor = Some(cursor.clone());
+                        Some(query)
+                    }
+                    Some(_) => None,
+                    None if page.has_more && !page.entries.is_empty() => {
+                        query.cursor = None;
+                        query.offset = Some(page.offset + page.entries.len());
+                        Some(query)
+                    }
+                    None => None,
+                },
+                Err(_) => None,
+            };
+            Some((page, (client, next, limits)))
+        })
+    }
+
+    /// List all API keys
+    ///
+    /// Retrieves a list of all API keys associated with the current account.
+    /// The response includes metadata about each key but not the key values themselves.
+    ///
+    /// # Returns
+    ///
+    /// A `ListApiKeysResult` containing the list of API keys and total count.
+    ///
+    /// # Errors
+    ///
+    /// * `Error::Http` with status 403 if not authorized to list keys
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use secret_store_sdk::{Client, ClientBuilder, Auth};
+    /// # async fn example(client: &Client) -> Result<(), Box<dyn std::error::Error>> {
+    /// let keys = client.list_api_keys().await?;
+    /// for key in &keys.keys {
+    ///     println!("Key {}: {} (active: {})", key.id, key.name, key.active);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn list_api_keys(&self) -> Result<ListApiKeysResult> {
+        self.list_api_keys_with_opts(ListApiKeysOpts::default())
+            .await
+    }
+
+    /// List API keys with filtering and pagination
+    ///
+    /// Returns one page of keys; `has_more` on the result tells whether
+    /// another page follows. See [`Client::list_api_keys_pages`] to walk all
+    /// pages.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use secret_store_sdk::{Client, ListApiKeysOpts};
+    /// # async fn example(client: &Client) -> Result<(), Box<dyn std::error::Error>> {
+    /// let opts = ListApiKeysOpts {
+    ///     active_only: true,
+    ///     namespace: Some("production".to_string()),
+    ///     limit: Some(50),
+    ///     ..Default::default()
+    /// };
+    /// let page = client.list_api_keys_with_opts(opts).await?;
+    /// println!("{} keys, more: {}", page.keys.len(), page.has_more);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn list_api_keys_with_opts(
+        &self,
+        opts: ListApiKeysOpts,
+    ) -> Result<ListApiKeysResult> {
+        let mut url = self.endpoints.list_api_keys();
+        let query = opts.to_query_string();
+        if !query.is_empty() {
+            url.push('?');
+            url.push_str(&query);
+        }
+        let request = self.build_request(Method::GET, &url)?;
+        let response = self.execute_with_retry(request).await?;
+
+        if !response.status().is_success() {
+            return Err(self.parse_error_response(response).await);
+        }
+
+        let request_id = header_str(response.headers(), "x-request-id");
+        let mut result: ListApiKeysResult = self.parse_json_response(response).await?;
+
+        if result.request_id.is_none() {
+            result.request_id = request_id;
+        }
+
+        Ok(result)
+    }
+
+    /// Stream all pages of an API key listing
+    ///
+    /// Advances `offset` by the number of keys returned until the server
+    /// reports no more pages. The stream ends after the last page or the
+    /// first error. Fetching more than `opts.max_pages` pages or
+    /// `opts.max_items` keys yields an `Error::Other` and ends the stream.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use secret_store_sdk::{Client, ListApiKeysOpts};
+    /// # use futures::StreamExt;
+    /// # async fn example(client: &Client) -> Result<(), Box<dyn std::error::Error>> {
+    /// let pages = client.list_api_keys_pages(ListApiKeysOpts::default());
+    /// futures::pin_mut!(pages);
+    ///
+    /// while let Some(page) = pages.next().await {
+    ///     for key in page?.keys {
+    ///         println!("{}", key.name);
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn list_api_keys_pages(
+        &self,
+        opts: ListApiKeysOpts,
+    ) -> impl futures::Stream<Item = Result<ListApiKeysResult>> {
+        let limits = PageLimits::new(opts.max_pages, opts.max_items);
+        let state = (self.clone(), Some(opts), limits);
+        futures::stream::unfold(state, |(client, opts, mut limits)| async move {
+            let mut opts = opts?;
+            let page = client.list_api_keys_with_opts(opts.clone()).await;
+            if let Ok(result) = &page {
+                if let Err(e) = limits.admit(result.keys.len()) {
+                    return Some((Err(e), (client, None, limits)));
+                }
+            }
+            let next = match &page {
+                Ok(page) if page.has_more && !page.keys.is_empty() => {
+                    opts.offset = Some(opts.offset.unwrap_or(0) + page.keys.len());
+                    Some(opts)
+                }
+                _ => None,
+            };
+            Some((page, (client, next, limits)))
+        })
+    }
+
+    /// Create a new API key
+    ///
+    /// Creates a new API key with the specified permissions and restrictions.
+    /// The key value is only returned in the creation response and cannot be retrieved later.
+    ///
+    /// # Arguments
+    ///
+    /// * `request` - The API key creation request containing name, permissions, etc.
+    /// * `idempotency_key` - Optional idempotency key to prevent duplicate creation
+    ///
+    /// # Returns
+    ///
+    /// An `ApiKeyInfo` containing the newly created key details including the key value.
+    ///
+    /// # Security
+    ///
+    /// The returned API key value should be stored securely. It cannot be retrieved
+    /// again after this call.
+    ///
+    /// # Errors
+    ///
+    /// * `Error::Config` if `expires_at` is not RFC 3339, or both `expires_at`
+    ///   and `expires_in` are set
+    /// * `Error::Http` with status 403 if not authorized to create keys
+    /// * `Error::Http` with status 400 for invalid permissions or parameters
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use secret_store_sdk::{Client, ClientBuilder, Auth, CreateApiKeyRequest};
+    /// # use secrecy::ExposeSecret;
+    /// # async fn example(client: &Client) -> Result<(), Box<dyn std::error::Error>> {
+    /// let request = CreateApiKeyRequest {
+    ///     name: "CI/CD Pipeline Key".to_string(),
+    ///     expires_at: None,
+    ///     expires_in: Some(std::time::Duration::from_secs(90 * 24 * 3600)),
+    ///     namespaces: vec!["production".to_string()],
+    ///     permissions: vec!["read".to_string()],
+    ///     metadata: None,
+    /// };
+    ///
+    /// let key_info = client.create_api_key(request, Some("unique-key-123".to_string())).await?;
+    /// if let Some(key) = &key_info.key {
+    ///     println!("New API key: {}", key.expose_secret());
+    ///     // Store this securely - it won't be available again!
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn create_api_key(
+        &self,
+        request: CreateApiKeyRequest,
+        idempotency_key: Option<String>,
+    ) -> Result<ApiKeyInfo> {
+        self.ensure_writable()?;
+        let request = request.resolve_expiry(self.config.clock.now())?;
+        let url = self.endpoints.create_api_key();
+        let mut req = self.build_request(Method::POST, &url)?;
+        req = req.json(&request);
+
+        // Add idempotency key if provided
+        if let Some(key) = idempotency_key {
+            req = req.header("X-Idempotency-Key", key);
+        }
+
+        let response = self.execute_with_retry(req).await?;
+
+        if !response.status().is_success() {
+            return Err(self.parse_error_response(response).await);
+        }
+
+        self.parse_json_response(response).await
+    }
+
+    /// Get API key details
+    ///
+    /// Retrieves detailed information about a specific API key.
+    /// Note that the key value itself is never returned for security reasons.
+    ///
+    /// # Arguments
+    ///
+    /// * `key_id` - The ID of the API key to retrieve
+    ///
+    /// # Returns
+    ///
+    /// An `ApiKeyInfo` with the key's metadata (without the key value).
+    ///
+    /// # Errors
+    ///
+    /// * `Error::Http` with status 404 if the key doesn't exist
+    /// * `Error::Http` with status 403 if not authorized to view the key
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use secret_store_sdk::{Client, ClientBuilder, Auth};
+    /// # async fn example(client: &Client) -> Result<(), Box<dyn std::error::Error>> {
+    /// let key_info = client.get_api_key("key_123abc").await?;
+    /// println!("Key {} last used: {:?}", key_info.name, key_info.last_used_at);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn get_api_key(&self, key_id: &str) -> Result<ApiKeyInfo> {
+        let url = self.endpoints.get_api_key(key_id);
+        let request = self.build_request(Method::GET, &url)?;
+        let response = self.execute_with_retry(request).await?;
+
+        if !response.status().is_success() {
+            return Err(self.parse_error_response(response).await);
+        }
+
+        self.parse_json_response(response).await
+    }
+
+    /// Revoke an API key
+    ///
+    /// Revokes an API key, immediately invalidating it for future use.
+    /// This operation is irreversible.
+    ///
+    /// # Arguments
+    ///
+    /// * `key_id` - The ID of the API key to revoke
+    ///
+    /// # Returns
+    ///
+    /// A `RevokeApiKeyResult` confirming the revocation.
+    ///
+    /// # Errors
+    ///
+    /// * `Error::Http` with status 404 if the key doesn't exist
+    /// * `Error::Http` with status 403 if not authorized to revoke the key
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use secret_store_sdk::{Client, ClientBuilder, Auth};
+    /// # async fn example(client: &Client) -> Result<(), Box<dyn std::error::Error>> {
+    /// let result = client.revoke_api_key("key_123abc").await?;
+    /// println!("Revoked key: {}", result.key_id);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn revoke_api_key(&self, key_id: &str) -> Result<RevokeApiKeyResult> {
+        self.ensure_writable()?;
+        let url = self.endpoints.revoke_api_key(key_id);
+        let request = self.build_request(Method::DELETE, &url)?;
+        let response = self.execute_with_retry(request).await?;
+
+        if !response.status().is_success() {
+            return Err(self.parse_error_response(response).await);
+        }
+
+        let request_id = header_str(response.headers(), "x-request-id");
+        let mut result: RevokeApiKeyResult = self.parse_json_response(response).await?;
+
+        if result.request_id.is_none() {
+            result.request_id = request_id;
+        }
+
+        Ok(result)
+    }
+
+    /// Get the identity and permissions of the current credential
+    ///
+    /// Lets callers check up front whether a write will be allowed and fail
+    /// with an actionable message. The result is reused for 60 seconds.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use secret_store_sdk::Client;
+    /// # async fn example(client: &Client) -> Result<(), Box<dyn std::error::Error>> {
+    /// let identity = client.whoami().await?;
+    /// if !identity.has_permission("write") || !identity.can_access("production") {
+    ///     return Err(format!("key {} cannot write to production", identity.key_id).into());
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn whoami(&self) -> Result<IdentityInfo> {
+        let now = self.config.clock.now();
+        if let Ok(cached) = self.identity.read() {
+            if let Some((fetched_at, identity)) = cached.as_ref() {
+                if now - *fetched_at < IDENTITY_CACHE_TTL {
+                    return Ok(identity.clone());
+                }
+            }
+        }
+
+        let url = self.endpoints.whoami();
+        let request = self.build_request(Method::GET, &url)?;
+        let response = self.execute_with_retry(request).await?;
+
+        if !response.status().is_success() {
+            return Err(self.parse_error_response(response).await);
+        }
+
+        let request_id = header_str(response.headers(), "x-request-id");
+        let mut identity: IdentityInfo = self.parse_json_response(response).await?;
+        if identity.request_id.is_none() {
+            identity.request_id = request_id;
+        }
+
+        if let Ok(mut cached) = self.identity.write() {
+            *cached = Some((now, identity.clone()));
+        }
+        Ok(identity)
+    }
+
+    /// Get API discovery information
+    ///
+    /// The last successful result is remembered and reported by [`Client::ping`].
+    /// Logs a warning if the advertised base URL does not match the configured
+    /// one (see [`Discovery::base_url_matches`]).
+    pub async fn discovery(&self) -> Result<Discovery> {
+        let url = self.endpoints.discovery();
+        let request = self.build_request(Method::GET, &url)?;
+        let response = self.execute_with_retry(request).await?;
+
+        if !response.status().is_success() {
+            return Err(self.parse_error_response(response).await);
+        }
+
+        let discovery: Discovery = self.parse_json_response(response).await?;
+        if !discovery.base_url_matches(&self.config.base_url) {
+            warn!(
+                "Discovery advertises base URL {} but client is configured for {}; \
+                 requests may be going through a rewriting proxy",
+                discovery.endpoints.base_url, self.config.base_url
+            );
+        }
+        if let Ok(mut cached) = self.discovery.write() {
+            *cached = Some(discovery.clone());
+        }
+        Ok(discovery)
+    }
+
+    /// API version of the server, e.g. `"v2"`
+    ///
+    /// Taken from the last successful [`Client::discovery`] call. Discovery is
+    /// also fetched automatically the first time a version-gated feature such
+    /// as cursor pagination is used. Returns `None` until then.
+    pub fn server_version(&self) -> Option<String> {
+        self.discovery
+            .read()
+            .ok()
+            .and_then(|cached| cached.as_ref().map(|d| d.api_version.clone()))
+    }
+
+    /// Fail with `Error::Config` if the server is known to be too old for `feature`
+    ///
+    /// Fetches discovery if the version is not known yet. Servers without a
+    /// discovery endpoint, or with an unparseable version, are not gated.
+    async fn require_api_version(&self, feature: &str, min_version: &str) -> Result<()> {
+        let api_version = match self.server_version() {
+            Some(api_version) => api_version,
+            None => match self.discovery().await {
+                Ok(discovery) => discovery.api_version,
+                Err(e) => {
+                    debug!("Server version unknown, not gating {}: {}", feature, e);
+                    return Ok(());
+                }
+            },
+        };
+
+        match api_version_at_least(&api_version, min_version) {
+            Some(false) => Err(Error::Config(format!(
+                "{} requires server >= {} (server API version is {})",
+                feature, min_version, api_version
+            ))),
+            _ => Ok(()),
+        }
+    }
+
+    /// Check connectivity and round-trip latency
+    ///
+    /// Calls the liveness endpoint once, without retries and with the health
+    /// timeout (see [`ClientBuilder::health_timeout_ms`](crate::ClientBuilder::health_timeout_ms)).
+    /// Like [`Client::livez`] and unlike [`Client::readyz`], it does not check
+    /// server dependencies. Connection failures, timeouts and non-2xx
+    /// responses are reported as `reachable: false` rather than as errors.
+    ///
+    /// `server_version` is taken from the last successful [`Client::discovery`]
+    /// call, if any; `ping` never fetches discovery itself.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use secret_store_sdk::Client;
+    /// # async fn example(client: &Client) -> Result<(), Box<dyn std::error::Error>> {
+    /// let ping = client.ping().await?;
+    /// println!("reachable={} latency={:?}", ping.reachable, ping.latency);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn ping(&self) -> Result<PingResult> {
+        let url = self.endpoints.livez();
+        let request = self
+            .build_request(Method::GET, &url)?
+            .timeout(self.config.health_timeout);
+
+        let started = std::time::Instant::now();
+        let reachable = match self.execute_without_retry(request).await {
+            Ok(response) => response.status().is_success(),
+            Err(e @ Error::Config(_)) => return Err(e),
+            Err(e) => {
+                debug!("Ping failed: {}", e);
+                false
+            }
+        };
+        let latency = started.elapsed();
+
+        let server_version = self
+            .discovery
+            .read()
+            .ok()
+            .and_then(|cached| cached.as_ref().map(|d| d.version.clone()));
+
+        Ok(PingResult {
+            reachable,
+            latency,
+            server_version,
+        })
+    }
+
+    /// Measure the offset between the server's clock and the client's
+    ///
+    /// Sends a lightweight liveness request and compares the server's `Date`
+    /// header with the client clock (see [`ClientBuilder::clock`]) at the
+    /// midpoint of the round trip. The result is positive when the server is
+    /// ahead. `Date` has one-second resolution, so smaller skews read as zero.
+    ///
+    /// Cache expiry and secret TTLs are evaluated against the client clock,
+    /// so a large skew shows up as secrets expiring early or late; a warning
+    /// is logged when it exceeds 30 seconds.
+    ///
+    /// # Errors
+    ///
+    /// * `Error::Deserialize` if the response has no valid `Date` header
+    ///
+    /// [`ClientBuilder::clock`]: crate::ClientBuilder::clock
+    pub async fn check_time_skew(&self) -> Result<time::Duration> {
+        let url = self.endpoints.livez();
+        let request = self
+            .build_request(Method::GET, &url)?
+            .timeout(self.config.health_timeout);
+
+        let sent_at = self.config.clock.now();
+        let response = self.execute_without_retry(request).await?;
+        let received_at = self.config.clock.now();
+
+        let server_time = header_str(response.headers(), "date")
+            .as_deref()
+            .and_then(parse_http_date)
+            .ok_or_else(|| Error::Deserialize("Response has no valid Date header".to_string()))?;
+        let local_time = sent_at + (received_at - sent_at) / 2i32;
+        let skew = server_time - local_time;
+
+        if skew.abs() > TIME_SKEW_WARN_THRESHOLD {
+            warn!(
+                "Server clock is {}s {} of the client clock",
+                skew.whole_seconds().abs(),
+                if skew.is_positive() {
+                    "ahead"
+                } else {
+                    "behind"
+                }
+            );
+        }
+        Ok(skew)
+    }
+
+    /// Check liveness
+    ///
+    /// Performs a simple liveness check against the service.
+    /// Returns `Ok(())` if the service is alive and responding.
+    ///
+    /// This endpoint is typically used by Kubernetes liveness probes.
+    /// It does not check dependencies and should respond quickly.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the service is not responding or returns
+    /// a non-2xx status code.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use secret_store_sdk::{Client, ClientBuilder, Auth};
+    /// # async fn example(client: &Client) -> Result<(), Box<dyn std::error::Error>> {
+    /// match client.livez().await {
+    ///     Ok(()) => println!("Service is alive"),
+    ///     Err(e) => eprintln!("Service is down: {}", e),
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn livez(&self) -> Result<()> {
+        let url = self.endpoints.livez();
+        let request = self.build_request(Method::GET, &url)?;
+
+        // Execute without retry for health checks
+        let response = self.execute_without_retry(request).await?;
+
+        if response.status().is_success() {
+            Ok(())
+        } else {
+            Err(self.parse_error_response(response).await)
+        }
+    }
+
+    /// Check readiness with detailed status
+    ///
+    /// Performs a comprehensive readiness check that may include
+    /// checking dependencies (database, cache, etc.).
+    ///
+    /// This endpoint is typically used by Kubernetes readiness probes
+    /// to determine if the service is ready to accept traffic.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `HealthStatus` with details about the service health
+    /// including individual component checks.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the service is not ready or if the
+    /// request fails.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use secret_store_sdk::{Client, ClientBuilder, Auth};
+    /// # async fn example(client: &Client) -> Result<(), Box<dyn std::error::Error>> {
+    /// let health = client.readyz().await?;
+    /// println!("Service status: {}", health.status);
+    ///
+    /// for (check, result) in &health.checks {
+    ///     println!("  {}: {} ({}ms)",
+    ///         check,
+    ///         result.status,
+    ///         result.duration_ms.unwrap_or(0)
+    ///     );
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn readyz(&self) -> Result<HealthStatus> {
+        let url = self.endpoints.readyz();
+        let request = self.build_request(Method::GET, &url)?;
+
+        // Execute without retry for health checks
+        let response = self.execute_without_retry(request).await?;
+
+        if response.status().is_success() {
+            self.parse_json_response(response).await
+        } else {
+            Err(self.parse_error_response(response).await)
+        }
+    }
+
+    /// Start a background readiness monitor
+    ///
+    /// Calls [`Client::readyz`] every `interval` and invokes `on_change` with
+    /// the first observed status and then only when readiness flips between
+    /// ready and unready. A failed or unreachable readiness check is reported
+    /// as a synthesized status of `"unhealthy"`.
+    ///
+    /// The monitor runs until the returned handle is stopped or dropped.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called outside of a tokio runtime.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use secret_store_sdk::Client;
+    /// # use std::time::Duration;
+    /// # async fn example(client: &Client) {
+    /// let monitor = client.start_health_monitor(Duration::from_secs(10), |status| {
+    ///     println!("Secret store is now {}", status.status);
+    /// });
+    /// // ...
+    /// monitor.stop();
+    /// # }
+    /// ```
+    pub fn start_health_monitor(
+        &self,
+        interval: Duration,
+        on_change: impl Fn(HealthStatus) + Send + 'static,
+    ) -> HealthMonitorHandle {
+        health::spawn(self.clone(), interval, on_change)
+    }
+
+    /// Get service metrics
+    ///
+    /// Retrieves metrics from the service in Prometheus format.
+    /// This endpoint may require special authentication using a metrics token.
+    ///
+    /// # Arguments
+    ///
+    /// * `metrics_token` - Optional metrics-specific authentication token.
+    ///   If not provided, uses the client's default authentication.
+    ///
+    /// # Returns
+    ///
+    /// Returns the metrics as a raw string in Prometheus exposition format.
+    ///
+    /// # Errors
+    ///
+    /// * `Error::Http` with status 401 if authentication fails
+    /// * `Error::Http` with status 403 if not authorized to view metrics
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use secret_store_sdk::{Client, ClientBuilder, Auth};
+    /// # async fn example(client: &Client) -> Result<(), Box<dyn std::error::Error>> {
+    /// // Using default authentication
+    /// let metrics = client.metrics(None).await?;
+    /// println!("Metrics:\n{}", metrics);
+    ///
+    /// // Using specific metrics token
+    /// let metrics = client.metrics(Some("metrics-token-xyz")).await?;
+    /// println!("Metrics with token:\n{}", metrics);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn metrics(&self, metrics_token: Option<&str>) -> Result<String> {
+        let url = self.endpoints.metrics();
+        let mut request = self.build_request(Method::GET, &url)?;
+
+        // Add metrics-specific token if provided
+        if let Some(token) = metrics_token {
+            request = request.header("X-Metrics-Token", token);
+        }
+
+        // Execute without retry for metrics endpoint
+        let response = self.execute_without_retry(request).await?;
+
+        if response.status().is_success() {
+            let body = self.read_body(response).await?;
+            String::from_utf8(body)
+                .map_err(|e| Error::Deserialize(format!("Metrics are not valid UTF-8: {}", e)))
+        } else {
+            Err(self.parse_error_response(response).await)
+        }
+    }
+
+    /// Send a request to an endpoint the typed API does not cover yet
+    ///
+    /// An escape hatch for preview or non-standard endpoints. `path` is
+    /// appended to the base URL as-is (e.g. `/api/v2/preview/widgets`), so
+    /// encode any dynamic segments yourself. Authentication, request IDs and
+    /// retries are applied as for typed calls, and error statuses still come
+    /// back as `Error::Http`. The response is handed back unparsed and never
+    /// served from the cache; a successful write (any method other than GET,
+    /// HEAD, OPTIONS or TRACE) to a secret or namespace path invalidates the
+    /// cached entries it may have changed.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use secret_store_sdk::{Client, Method};
+    /// # async fn example(client: &Client) -> Result<(), Box<dyn std::error::Error>> {
+    /// let response = client
+    ///     .raw_request(Method::GET, "/api/v2/preview/widgets", None)
+    ///     .await?;
+    /// println!("{}", response.text().await?);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn raw_request(
+        &self,
+        method: Method,
+        path: &str,
+        body: Option<serde_json::Value>,
+    ) -> Result<Response> {
+        self.execute_raw(method, path, http::HeaderMap::new(), body.as_ref())
+            .await
+    }
+
+    /// Send a request to a raw `path`, for [`Client::raw_request`] and the
+    /// Tower service
+    async fn execute_raw(
+        &self,
+        method: Method,
+        path: &str,
+        headers: http::HeaderMap,
+        body: Option<&serde_json::Value>,
+    ) -> Result<Response> {
+        if !path.starts_with('/') {
+            return Err(Error::Config(format!(
+                "Raw request path must start with '/': {}",
+                path
+            )));
+        }
+
+        let writes = !method.is_safe();
+        if writes {
+            self.ensure_writable()?;
+        }
+
+        let url = self.endpoints.custom(path);
+        let mut request = self.build_request(method, &url)?.headers(headers);
+        if let Some(body) = body {
+            request = request.json(body);
+        }
+        let response = self.execute_with_retry(request).await?;
+
+        if writes {
+            self.invalidate_raw_path(path).await;
+        }
+        Ok(response)
+    }
+
+    /// Invalidate the cache entries a write to a raw `path` may have changed
+    ///
+    /// A write to a single secret drops that secret; anything else under a
+    /// namespace (batches, namespace deletion, imports) drops all of the
+    /// namespace's cached secrets. Listings of the namespace go either way.
+    async fn invalidate_raw_path(&self, path: &str) {
+        let path = path.split(['?', '#']).next().unwrap_or_default();
+        let Some(rest) = path.strip_prefix(crate::endpoints::API_V2_BASE) else {
+            return;
+        };
+        let decode = |segment: &str| {
+            percent_encoding::percent_decode_str(segment)
+                .decode_utf8_lossy()
+                .into_owned()
+        };
+        let segments: Vec<&str> = rest.split('/').filter(|s| !s.is_empty()).collect();
+
+        match segments.as_slice() {
+            ["secrets", ns, key, ..] if *key != "search" && *key != "batch" => {
+                let namespace = decode(ns);
+                self.invalidate_cache(&namespace, &decode(key)).await;
+                self.invalidate_list_cache(&namespace).await;
+            }
+            ["secrets", ns, ..] | ["namespaces", ns, ..] | ["env", ns, ..] => {
+                let namespace = decode(ns);
+                self.invalidate_namespace_cache(&namespace).await;
+                self.invalidate_list_cache(&namespace).await;
+            }
+            _ => {}
+        }
+    }
+
+    /// Invalidate every cached secret in `namespace`
+    async fn invalidate_namespace_cache(&self, namespace: &str) {
+        if let Some(cache) = &self.cache {
+            let prefix = format!("{}/", namespace);
+            let keys: Vec<_> = cache
+                .iter()
+                .filter(|(key, _)| key.starts_with(&prefix))
+                .map(|(key, _)| key)
+                .collect();
+            for key in keys {
+                cache.invalidate(key.as_str()).await;
+            }
+        }
+        #[cfg(feature = "disk-cache")]
+        if let Some(disk_cache) = &self.disk_cache {
+            let namespace = namespace.to_string();
+            let _ = disk_cache
+                .run(move |disk_cache| disk_cache.remove_namespace(&namespace))
+                .await;
+        }
+    }
+
+    /// Get Prometheus metrics parsed into samples
+    ///
+    /// Like [`Client::metrics`], but parses the exposition format so single
+    /// values can be read without a Prometheus client. Histogram and summary
+    /// samples (`_bucket`, `_sum`, `_count`) carry the type of their family.
+    ///
+    /// # Errors
+    ///
+    /// * Same as [`Client::metrics`]
+    /// * `Error::Deserialize` if the body is not valid exposition format
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use secret_store_sdk::Client;
+    /// # async fn example(client: &Client) -> Result<(), Box<dyn std::error::Error>> {
+    /// let metrics = client.metrics_parsed(None).await?;
+    /// if let Some(entries) = metrics.iter().find(|m| m.name == "cache_entries") {
+    ///     println!("Server cache entries: {}", entries.value);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn metrics_parsed(
+        &self,
+        metrics_token: Option<&str>,
+    ) -> Result<Vec<PrometheusMetric>> {
+        let text = self.metrics(metrics_token).await?;
+        prometheus::parse(&text)
+    }
+
+    /// Subscribe to change notifications for a namespace
+    ///
+    /// Opens a server-sent events stream and yields a [`ChangeEvent`] for every
+    /// change the server reports. The cached entry for each changed secret is
+    /// invalidated before the event is yielded, so a subsequent `get_secret`
+    /// always sees the new value.
+    ///
+    /// Disconnects are handled transparently: the stream reconnects with
+    /// exponential backoff and resumes from the last seen event via the
+    /// `Last-Event-ID` header. Malformed events are yielded as errors without
+    /// ending the stream; the stream ends after a non-retryable error or once
+    /// `opts.max_reconnects` consecutive reconnects have failed.
+    ///
+    /// The event stream is exempt from the client timeout and the overall
+    /// timeout, which would otherwise cut it off and force a reconnect.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use secret_store_sdk::{Client, SubscribeOpts};
+    /// # use futures::StreamExt;
+    /// # async fn example(client: &Client) -> Result<(), Box<dyn std::error::Error>> {
+    /// let changes = client.subscribe_changes("production", SubscribeOpts::default());
+    /// futures::pin_mut!(changes);
+    ///
+    /// while let Some(event) = changes.next().await {
+    ///     let event = event?;
+    ///     println!("{}/{} {:?}", event.namespace, event.key, event.action);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn subscribe_changes(
+        &self,
+        namespace: &str,
+        opts: SubscribeOpts,
+    ) -> impl futures::Stream<Item = Result<ChangeEvent>> {
+        let subscription = ChangeSubscription::new(self.clone(), namespace, opts);
+        futures::stream::unfold(subscription, |mut subscription| async move {
+            let item = subscription.next_event().await?;
+            Some((item, subscription))
+        })
+    }
+
+    // Helper methods
+
+    /// Open the server-sent events stream for a namespace
+    pub(crate) async fn open_event_stream(
+        &self,
+        namespace: &str,
+        last_event_id: Option<&str>,
+    ) -> Result<Response> {
+        let url = self.endpoints.events(namespace);
+        let mut request = self
+            .build_request(Method::GET, &url)?
+            .header(reqwest::header::ACCEPT, "text/event-stream");
+
+        if let Some(id) = last_event_id {
+            request = request.header("Last-Event-ID", id);
+        }
+
+        let policy = RetryPolicy {
+            long_lived: true,
+            ..Default::default()
+        };
+        self.execute_with_retry_policy(request, policy).await
+    }
+
+    /// Fail with `Error::Config` if the client is read-only
+    fn ensure_writable(&self) -> Result<()> {
+        if self.config.read_only {
+            return Err(Error::Config("client is read-only".to_string()));
+        }
+        Ok(())
+    }
+
+    /// Build a request with common headers
+    fn build_request(&self, method: Method, url: &str) -> Result<reqwest::RequestBuilder> {
+        let mut builder = self.http.request(method, url);
+
+        // Generate and add request ID
+        let request_id = generate_request_id();
+        builder = builder.header("X-Request-ID", &request_id);
+
+        // Add trace headers
+        builder = builder
+            .header("X-Trace-ID", &request_id)
+            .header("X-Span-ID", uuid::Uuid::new_v4().to_string());
+
+        Ok(builder)
+    }
+
+    /// Execute a request with retry logic
+    async fn execute_with_retry(
+        &self,
+        request_builder: reqwest::RequestBuilder,
+    ) -> Result<Response> {
+        self.execute_with_retry_policy(request_builder, RetryPolicy::default())
+            .await
+    }
+
+    /// Execute a request with retry logic and a per-request retry policy
+    ///
+    /// Stops with `Error::Cancelled` as soon as the client's cancellation
+    /// token fires, whether a request is in flight or a retry is waiting.
+    async fn execute_with_retry_policy(
+        &self,
+        request_builder: reqwest::RequestBuilder,
+        policy: RetryPolicy,
+    ) -> Result<Response> {
+        let Some(token) = &self.cancellation else {
+            return self.run_with_retry(request_builder, policy).await;
+        };
+        tokio::select! {
+            biased;
+            _ = token.cancelled() => Err(Error::Cancelled),
+            result = self.run_with_retry(request_builder, policy) => result,
+        }
+    }
+
+    async fn run_with_retry(
+        &self,
+        request_builder: reqwest::RequestBuilder,
+        policy: RetryPolicy,
+    ) -> Result<Response> {
+        let mut token_refresh_count = 0;
+        let mut auth_index = 0;
+        let deadline = self
+            .config
+            .overall_timeout
+            .filter(|_| !policy.long_lived)
+            .map(|timeout| std::time::Instant::now() + timeout);
+        let max_retries = request_builder
+            .try_clone()
+            .and_then(|builder| builder.build().ok())
+            .map_or(self.config.retries, |request| {
+                self.config.max_retries(request.method())
+            });
+
+        // Extract method and route template for metrics
+        #[cfg(feature = "metrics")]
+        let (method, route) = {
+            // Try to build a request to extract metadata
+            if let Some(cloned_builder) = request_builder.try_clone() {
+                if let Ok(req) = cloned_builder.build() {
+                    let method = req.method().to_string();
+                    let route = RouteLabel::from_path(req.url().path());
+                    (method, route)
+                } else {
+                    ("UNKNOWN".to_string(), RouteLabel::from_path(""))
+                }
+            } else {
+                ("UNKNOWN".to_string(), RouteLabel::from_path(""))
+            }
+        };
+
+        // Failed attempts, reported when retries give up
+        let attempts = std::sync::Mutex::new(Vec::new());
+
+        loop {
+            // Current method of a fallback chain (or the only method)
+            let auth = self
+                .config
+                .auth
+                .chain_member(auth_index)
+                .unwrap_or(&self.config.auth);
+            let has_fallback = self.config.auth.chain_member(auth_index + 1).is_some();
+
+            // Get current auth header (may be refreshed)
+            let (auth_header, auth_value) = auth
+                .get_header()
+                .await
+                .map_err(|e| Error::Config(format!("Failed to get auth header: {}", e)))?;
+
+            // Clone the base request and add current auth header
+            let req_with_auth = request_builder
+                .try_clone()
+                .ok_or_else(|| Error::Other("Request cannot be cloned".to_string()))?
+                .header(auth_header, auth_value);
+
+            // Create backoff strategy for retries
+            let mut backoff = ExponentialBackoff {
+                initial_interval: Duration::from_millis(100),
+                randomization_factor: 0.3,
+                multiplier: 2.0,
+                max_interval: Duration::from_secs(10),
+                max_elapsed_time: None,
+                ..Default::default()
+            };
+            // Calculate max elapsed time based on timeout and retries
+            // Allow enough time for all retries with their respective timeouts,
+            // plus one attempt per fallback host
+            let max_attempts = max_retries as usize + self.hosts.len() - 1;
+            backoff.max_elapsed_time = if max_attempts > 0 {
+                let timeout_secs = self.config.timeout.as_secs();
+                // Allow time for all retries plus some buffer for backoff delays
+                Some(Duration::from_secs(
+                    (max_attempts as u64 + 1) * timeout_secs + 30,
+                ))
+            } else {
+                Some(Duration::from_millis(0))
+            };
+
+            // Never sleep past the overall deadline
+            let backoff = DeadlineBackoff::new(backoff, deadline);
+
+            let retry_count = std::sync::Arc::new(std::sync::atomic::AtomicUsize::new(0));
+            let retry_count_clone = retry_count.clone();
+            let conflict_retries = std::sync::atomic::AtomicUsize::new(0);
+            let failovers = std::sync::atomic::AtomicUsize::new(0);
+            let attempt_started = std::sync::Mutex::new(std::time::Instant::now());
+
+            // Execute with backoff retry
+            let result = retry_notify(
+                backoff,
+                || async {
+                    *attempt_started.lock().unwrap() = std::time::Instant::now();
+                    // Host failovers do not count against the retry budget
+                    let current_retry = retry_count
+                        .load(std::sync::atomic::Ordering::Relaxed)
+                        .saturating_sub(failovers.load(std::sync::atomic::Ordering::Relaxed));
+                    // Clone request for this attempt
+                    let mut req = req_with_auth
+                        .try_clone()
+                        .ok_or_else(|| {
+                            backoff::Error::Permanent(Error::Other(
+                                "Request cannot be cloned".to_string(),
+                            ))
+                        })?
+                        .build()
+                        .map_err(|e| {
+                            backoff::Error::Permanent(Error::Other(format!(
+                                "Failed to build request: {}",
+                                e
+                            )))
+                        })?;
+
+                    // Requests are built against the primary host; rebase them
+                    // onto the active one
+                    let host_index = self.active_host.load(std::sync::atomic::Ordering::Relaxed);
+                    if host_index > 0 {
+                        let rebased = req
+                            .url()
+                            .as_str()
+                            .strip_prefix(self.hosts[0].as_str())
+                            .and_then(|rest| {
+                                reqwest::Url::parse(&format!("{}{}", self.hosts[host_index], rest))
+                                    .ok()
+                            });
+                        if let Some(url) = rebased {
+                            *req.url_mut() = url;
+                        }
+                    }
+
+                    // Cut the attempt's timeout to what is left of the deadline
+                    if let Some(deadline) = deadline {
+                        let remaining =
+                            deadline.saturating_duration_since(std::time::Instant::now());
+                        if remaining.is_zero() {
+                            return Err(backoff::Error::Permanent(Error::Timeout));
+                        }
+                        let timeout = req.timeout().copied().unwrap_or(self.config.timeout);
+                        *req.timeout_mut() = Some(timeout.min(remaining));
+                    }
+
+                    // reqwest applies the timeout to the whole body, and a
+                    // request cannot opt out of the client's, so streams get
+                    // one that never fires
+                    if policy.long_lived {
+                        *req.timeout_mut() = Some(Duration::MAX);
+                    }
+
+                    if let Some(threshold) = self.config.expect_continue_threshold {
+                        if request_body_len(&req).is_some_and(|len| len > threshold) {
+                            let _ = req.headers_mut().insert(
+                                reqwest::header::EXPECT,
+                                reqwest::header::HeaderValue::from_static("100-continue"),
+                            );
+                        }
+                    }
+
+                    let replay_safe =
+                        self.config.retry_unsafe_without_idempotency || is_replay_safe(&req);
+                    let check_body = self.config.retry_on_empty_body
+                        && is_replay_safe(&req)
+                        && req.method() != Method::DELETE
+                        && req
+                            .headers()
+                            .get("prefer")
+                            .map_or(true, |prefer| prefer != "return=minimal");
+
+                    // Track active connections
+                    #[cfg(feature = "metrics")]
+                    self.metrics.inc_active_connections();
+
+                    // Start timing request
+                    #[cfg(feature = "metrics")]
                     let start_time = std::time::Instant::now();
+                    #[cfg(feature = "metrics")]
+                    if let Some(bytes) = request_body_len(&req) {
+                        self.metrics.record_request_bytes(&method, &route, bytes);
+                    }
+
+                    let sent_at = std::time::Instant::now();
+                    let response_result = self.http.execute(req).await;
+                    if self.config.connection_debug {
+                        connection_debug::log_attempt(&response_result, sent_at);
+                    }
+
+                    // Decrement active connections
+                    #[cfg(feature = "metrics")]
+                    self.metrics.dec_active_connections();
+
+                    match response_result {
+                        Ok(response) => {
+                            let status = response.status();
+
+                            #[cfg(feature = "metrics")]
+                            let response = self.measure_response(response, &method, &route);
+
+                            // Handle 401 - but don't retry within backoff if we can refresh token
+                            if status == StatusCode::UNAUTHORIZED
+                                && token_refresh_count == 0
+                                && auth.supports_refresh()
+                            {
+                                // Return a special error that we'll handle outside the backoff retry
+                                return Err(backoff::Error::Permanent(Error::Http {
+                                    status: 401,
+                                    category: "auth_refresh_needed".to_string(),
+                                    message: "Token refresh required".to_string(),
+                                    request_id: header_str(response.headers(), "x-request-id"),
+                                }));
+                            }
+
+                            // Otherwise move on to the next method of a fallback chain
+                            if status == StatusCode::UNAUTHORIZED && has_fallback {
+                                return Err(backoff::Error::Permanent(Error::Http {
+                                    status: 401,
+                                    category: "auth_fallback_needed".to_string(),
+                                    message: "Falling back to next auth method".to_string(),
+                                    request_id: header_str(response.headers(), "x-request-id"),
+                                }));
+                            }
+
+                            // Check if error is retryable
+                            if status.is_server_error()
+                                || status == StatusCode::TOO_MANY_REQUESTS
+                                || status == StatusCode::REQUEST_TIMEOUT
+                            {
+                                let error = self.parse_error_response(response).await;
+                                if error.is_retryable() && current_retry < max_retries as usize {
+                                    debug!("Retrying request due to: {:?}", error);
+                                    self.retry_stats.record_retry(&status.as_u16().to_string());
+                                    #[cfg(feature = "metrics")]
+                                    self.metrics.record_retry(
+                                        (current_retry + 1) as u32,
+                                        &status.to_string(),
+                                    );
+                                    return Err(backoff::Error::transient(error));
+                                } else {
+                                    return Err(backoff::Error::Permanent(error));
+                                }
+                            }
+
+                            // Conflicts are retryable only when the policy allows it
+                            if status == StatusCode::CONFLICT
+                                && policy.retry_on_conflict
+                                && current_retry < max_retries as usize
+                                && conflict_retries
+                                    .fetch_add(1, std::sync::atomic::Ordering::Relaxed)
+                                    < MAX_CONFLICT_RETRIES
+                            {
+                                let error = self.parse_error_response(response).await;
+                                debug!("Retrying request due to conflict: {:?}", error);
+                                self.retry_stats.record_retry(&status.as_u16().to_string());
+                                #[cfg(feature = "metrics")]
+                                self.metrics
+                                    .record_retry((current_retry + 1) as u32, &status.to_string());
+                                return Err(backoff::Error::transient(error));
+                            }
+
+                            // Non-retryable HTTP errors
+                            if !status.is_success() && status != StatusCode::NOT_MODIFIED {
+                                let error = self.parse_error_response(response).await;
+                                return Err(backoff::Error::Permanent(error));
+                            }
+
+                            // A flaky intermediary may cut the body short
+                            let response = if check_body
+                                && status != StatusCode::NO_CONTENT
+                                && status != StatusCode::NOT_MODIFIED
+                            {
+                                match self.buffer_json_body(response).await {
+                                    Ok(response) => response,
+                                    Err(error)
+                                        if (matches!(error, Error::Deserialize(_))
+                                            || error.is_retryable())
+                                            && current_retry < max_retries as usize =>
+                                    {
+                                        debug!(
+                                            "Retrying request due to unusable body: {:?}",
+                                            error
+                                        );
+                                        self.retry_stats.record_retry("bad_body");
+                                        #[cfg(feature = "metrics")]
+                                        self.metrics
+                                            .record_retry((current_retry + 1) as u32, "bad_body");
+                                        return Err(backoff::Error::transient(error));
+                                    }
+                                    Err(error) => return Err(backoff::Error::Permanent(error)),
+                                }
+                            } else {
+                                response
+                            };
+
+                            self.warn_if_deprecated(response.headers());
+
+                            // Record successful request metrics
+                            #[cfg(feature = "metrics")]
+                            {
+                                let duration_secs = start_time.elapsed().as_secs_f64();
+                                self.metrics.record_request(
+                                    &method,
+                                    &route,
+                                    status.as_u16(),
+                                    duration_secs,
+                                );
+                            }
+
+                            Ok(response)
+                        }
+                        Err(e) => {
+                            // DNS and connect failures move on to the next host
+                            // before the retry budget is touched
+                            if e.is_connect()
+                                && failovers.load(std::sync::atomic::Ordering::Relaxed)
+                                    < self.hosts.len() - 1
+                            {
+                                let _ =
+                                    failovers.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+                                let next = (host_index + 1) % self.hosts.len();
+                                // Another request may already have failed over
+                                let _ = self.active_host.compare_exchange(
+                                    host_index,
+                                    next,
+                                    std::sync::atomic::Ordering::Relaxed,
+                                    std::sync::atomic::Ordering::Relaxed,
+                                );
+                                warn!(
+                                    "Cannot connect to {}, failing over to {}",
+                                    self.hosts[host_index], self.hosts[next]
+                                );
+                                self.retry_stats.record_retry("failover");
+                                return Err(backoff::Error::transient(Error::from(e)));
+                            }
+
+                            // Failures before the connection was established never
+                            // reached the server; anything later might have
+                            let ambiguous = !e.is_connect();
+                            let error = Error::from(e);
+                            if ambiguous && !replay_safe {
+                                debug!("Not retrying non-idempotent request: {:?}", error);
+                                return Err(backoff::Error::Permanent(error));
+                            }
+                            if error.is_retryable() && current_retry < max_retries as usize {
+                                debug!("Retrying request due to network error: {:?}", error);
+                                self.retry_stats.record_retry("network_error");
+                                #[cfg(feature = "metrics")]
+                                self.metrics
+                                    .record_retry((current_retry + 1) as u32, "network_error");
+                                Err(backoff::Error::transient(error))
+                            } else {
+                                Err(backoff::Error::Permanent(error))
+                            }
+                        }
+                    }
+                },
+                |err, dur| {
+                    let count =
+                        retry_count_clone.fetch_add(1, std::sync::atomic::Ordering::Relaxed) + 1;
+                    debug!("Retry {} after {:?} due to: {:?}", count, dur, err);
+                    let elapsed = attempt_started.lock().unwrap().elapsed();
+                    attempts
+                        .lock()
+                        .unwrap()
+                        .push(AttemptRecord::new(&err, elapsed));
+                },
+            )
+            .await;
+
+            match result {
+                Ok(response) => return Ok(response),
+                Err(Error::Http {
+                    status: 401,
+                    category,
+                    ..
+                }) if category == "auth_refresh_needed" && token_refresh_count == 0 => {
+                    // Try to refresh token once
+                    warn!("Got 401, attempting token refresh");
+                    auth.refresh().await.map_err(|e| {
+                        if e.is::<TokenProviderPanic>() {
+                            Error::Config(e.to_string())
+                        } else {
+                            Error::network(format!("Token refresh failed: {}", e))
+                        }
+                    })?;
+                    token_refresh_count += 1;
+                    // Continue to retry with new token
+                    continue;
+                }
+                Err(Error::Http {
+                    status: 401,
+                    category,
+                    ..
+                }) if category == "auth_fallback_needed" => {
+                    warn!("Got 401, falling back to next auth method in chain");
+                    auth_index += 1;
+                    token_refresh_count = 0;
+                    continue;
+                }
+                Err(e) => {
+                    let mut attempts = std::mem::take(&mut *attempts.lock().unwrap());
+                    if attempts.is_empty() || !e.is_retryable() {
+                        return Err(e);
+                    }
+                    attempts.push(AttemptRecord::new(
+                        &e,
+                        attempt_started.lock().unwrap().elapsed(),
+                    ));
+                    warn!(
+                        attempts = attempts.len(),
+                        status = ?e.status_code(),
+                        error = %e,
+                        history = ?attempts,
+                        "Retries exhausted"
+                    );
+                    return Err(Error::RetriesExhausted {
+                        last: Box::new(e),
+                        attempts,
+                    });
+                }
+            }
+        }
+    }
+
+    /// Execute a request without retry logic (for health checks)
+    async fn execute_without_retry(
+        &self,
+        request_builder: reqwest::RequestBuilder,
+    ) -> Result<Response> {
+        // Get auth header
+        let (auth_header, auth_value) = self
+            .config
+            .auth
+            .get_header()
+            .await
+            .map_err(|e| Error::Config(format!("Failed to get auth header: {}", e)))?;
+
+        // Add auth header
+        let request = request_builder
+            .header(auth_header, auth_value)
+            .build()
+            .map_err(|e| Error::Other(format!("Failed to build request: {}", e)))?;
+
+        // Execute request
+        self.http.execute(request).await.map_err(Error::from)
+    }
+
+    /// Log a warning the first time the server flags the API as deprecated
+    fn warn_if_deprecated(&self, headers: &http::HeaderMap) {
+        let deprecation = header_str(headers, "deprecation");
+        let sunset = header_str(headers, "sunset");
+        if deprecation.is_none() && sunset.is_none() {
+            return;
+        }
+        if self
+            .deprecation_warned
+            .swap(true, std::sync::atomic::Ordering::Relaxed)
+        {
+            return;
+        }
+        warn!(
+            deprecation = deprecation.as_deref().unwrap_or("-"),
+            sunset = sunset.as_deref().unwrap_or("-"),
+            sdk_version = crate::VERSION,
+            "The secret store API version used by this SDK is deprecated; upgrade secret-store-sdk before the sunset date"
+        );
+    }
+
+    /// Parse error response from server
+    async fn parse_error_response(&self, response: Response) -> Error {
+        let status = response.status().as_u16();
+        let request_id = header_str(response.headers(), "x-request-id");
+        // `Upgrade` is connection-specific and dropped by HTTP/2, so the
+        // required version comes from a custom header or the body
+        let min_api_version = header_str(response.headers(), "x-min-api-version");
+
+        // Try to parse JSON error response
+        let parsed = self
+            .collect_body(response)
+            .await
+            .ok()
+            .and_then(|body| serde_json::from_slice::<ErrorResponse>(&body).ok());
+
+        // The API version this SDK speaks has been retired
+        if status == StatusCode::UPGRADE_REQUIRED.as_u16() {
+            let mut message = format!(
+                "the server no longer supports the API version used by secret-store-sdk {}; upgrade the SDK",
+                crate::VERSION
+            );
+            let required = min_api_version.or_else(|| {
+                parsed
+                    .as_ref()
+                    .and_then(|error_resp| error_resp.min_api_version.clone())
+            });
+            if let Some(required) = required {
+                message.push_str(&format!(" (server requires API version {})", required));
+            }
+            if let Some(error_resp) = parsed {
+                message.push_str(&format!(": {}", error_resp.message));
+            }
+            return Error::Http {
+                status,
+                category: "upgrade_required".to_string(),
+                message,
+                request_id,
+            };
+        }
+
+        match parsed {
+            Some(error_resp) => Error::from_response(
+                error_resp.status,
+                &error_resp.error,
+                &error_resp.message,
+                request_id,
+            ),
+            None => Error::Http {
+                status,
+                category: "unknown".to_string(),
+                message: format!("HTTP error {}", status),
+                request_id,
+            },
+        }
+    }
+
+    /// Read a response body, checking its digest when configured
+    async fn read_body(&self, response: Response) -> Result<Vec<u8>> {
+        let declared = if self.config.verify_content_digest {
+            declared_sha256(response.headers())
+        } else {
+            None
+        };
+        let body = self.collect_body(response).await?;
+
+        match declared {
+            Some(expected) => {
+                use sha2::{Digest, Sha256};
+                if Sha256::digest(&body).as_slice() != expected.as_slice() {
+                    return Err(Error::Other("content digest mismatch".to_string()));
+                }
+            }
+            None if self.config.require_content_digest && !body.is_empty() => {
+                return Err(Error::Other("content digest missing".to_string()));
+            }
+            None => {}
+        }
+        Ok(body)
+    }
+
+    /// Stream a response body, enforcing the size limit and recording its
+    /// size if it was not known up front
+    async fn collect_body(&self, mut response: Response) -> Result<Vec<u8>> {
+        // Content-Length is only a hint: chunked and decompressed bodies have
+        // none, so the limit is enforced on the bytes actually received
+        let limit = self.config.max_response_bytes;
+        let too_large =
+            |limit: u64| Error::Other(format!("response body exceeds limit of {} bytes", limit));
+        if let (Some(limit), Some(length)) = (limit, response.content_length()) {
+            if length > limit {
+                return Err(too_large(limit));
+            }
+        }
+
+        #[cfg(feature = "metrics")]
+        let unmeasured = response.extensions_mut().remove::<UnmeasuredBody>();
+
+        let mut body = Vec::with_capacity(
+            response
+                .content_length()
+                .map_or(0, |length| length.min(64 * 1024) as usize),
+        );
+        while let Some(chunk) = response.chunk().await.map_err(Error::from)? {
+            body.extend_from_slice(&chunk);
+            if let Some(limit) = limit {
+                if body.len() as u64 > limit {
+                    return Err(too_large(limit));
+                }
+            }
+        }
+
+        #[cfg(feature = "metrics")]
+        if let Some(UnmeasuredBody { method, route }) = unmeasured {
+            self.metrics
+                .record_response_bytes(&method, &route, body.len() as u64);
+        }
+        Ok(body)
+    }
+
+    /// Take the pending size measurement of a response whose body is read
+    /// elsewhere, as a callback to run with the body's final size
+    fn unmeasured_body_recorder(
+        &self,
+        response: &mut Response,
+    ) -> Option<Box<dyn FnOnce(u64) + Send>> {
+        #[cfg(feature = "metrics")]
+        {
+            let UnmeasuredBody { method, route } =
+                response.extensions_mut().remove::<UnmeasuredBody>()?;
+            let metrics = self.metrics.clone();
+            Some(Box::new(move |bytes| {
+                metrics.record_response_bytes(&method, &route, bytes)
+            }))
+        }
+        #[cfg(not(feature = "metrics"))]
+        {
+            let _ = response;
+            None
+        }
+    }
+
+    /// Buffer a successful JSON response, rejecting empty or malformed bodies
+    ///
+    /// Responses that are not JSON are passed through untouched.
+    async fn buffer_json_body(&self, response: Response) -> Result<Response> {
+        let content_type = header_str(response.headers(), "content-type");
+        let is_json = content_type.as_deref().map_or(true, |content_type| {
+            content_type.starts_with("application/json") || content_type.contains("+json")
+        });
+        if !is_json {
+            return Ok(response);
+        }
+
+        let status = response.status();
+        let version = response.version();
+        let headers = response.headers().clone();
+        let body = self.collect_body(response).await?;
+
+        if body.iter().all(u8::is_ascii_whitespace) {
+            return Err(Error::Deserialize("Empty response body".to_string()));
+        }
+        // Only syntax is checked here; schema errors surface when parsing
+        if content_type.is_some() {
+            let _: serde::de::IgnoredAny = serde_json::from_slice(&body)
+                .map_err(|e| Error::Deserialize(format!("Malformed response body: {}", e)))?;
+        }
+
+        let mut rebuilt = http::Response::new(body);
+        *rebuilt.status_mut() = status;
+        *rebuilt.version_mut() = version;
+        *rebuilt.headers_mut() = headers;
+        Ok(Response::from(rebuilt))
+    }
+
+    /// Record the response size, or defer it to `collect_body` when the
+    /// response has no `Content-Length`
+    #[cfg(feature = "metrics")]
+    fn measure_response(
+        &self,
+        mut response: Response,
+        method: &str,
+        route: &RouteLabel,
+    ) -> Response {
+        match response.content_length() {
+            Some(bytes) => self.metrics.record_response_bytes(method, route, bytes),
+            None => {
+                let _ = response.extensions_mut().insert(UnmeasuredBody {
+                    method: method.to_string(),
+                    route: route.clone(),
+                });
+            }
+        }
+        response
+    }
+
+    /// Parse JSON response
+    async fn parse_json_response<T: serde::de::DeserializeOwned>(
+        &self,
+        response: Response,
+    ) -> Result<T> {
+        let body = self.read_body(response).await?;
+        self.decode(&body)
+    }
+
+    /// Deserialize a JSON body, rejecting unknown fields in strict mode
+    fn decode<T: serde::de::DeserializeOwned>(&self, body: &[u8]) -> Result<T> {
+        if !self.config.strict_deserialization {
+            return serde_json::from_slice(body).map_err(Error::from);
+        }
+
+        let mut unknown = Vec::new();
+        let mut deserializer = serde_json::Deserializer::from_slice(body);
+        let value: T =
+            serde_ignored::deserialize(&mut deserializer, |path| unknown.push(path.to_string()))?;
+        deserializer.end()?;
+
+        if !unknown.is_empty() {
+            return Err(Error::Deserialize(format!(
+                "Unknown fields in response: {}",
+                unknown.join(", ")
+            )));
+        }
+        Ok(value)
+    }
+
+    /// Parse get secret response
+    async fn parse_get_response(
+        &self,
+        response: Response,
+        namespace: &str,
+        key: &str,
+    ) -> Result<Secret> {
+        let headers = response.headers().clone();
+
+        // Extract headers
+        let etag = header_str(&headers, "etag");
+        let last_modified = header_str(&headers, "last-modified");
+        let request_id = header_str(&headers, "x-request-id");
+
+        // Parse body
+        #[derive(serde::Deserialize)]
+        struct GetResponse {
+            #[serde(deserialize_with = "null_as_empty")]
+            value: String,
+            version: i32,
+            expires_at: Option<String>,
+            metadata: Option<serde_json::Value>,
+            updated_at: String,
+            #[serde(default)]
+            format: Option<String>,
+            #[serde(default)]
+            kid: Option<String>,
+        }
+
+        let body = self.read_body(response).await?;
+        let body: GetResponse = self.decode(&body)?;
+
+        // Parse timestamps
+        let updated_at = time::OffsetDateTime::parse(
+            &body.updated_at,
+            &time::format_description::well_known::Rfc3339,
+        )
+        .map_err(|e| Error::Deserialize(format!("Invalid updated_at timestamp: {}", e)))?;
+
+        let expires_at = body
+            .expires_at
+            .as_ref()
+            .map(|s| {
+                time::OffsetDateTime::parse(s, &time::format_description::well_known::Rfc3339)
+                    .map_err(|e| Error::Deserialize(format!("Invalid expires_at timestamp: {}", e)))
+            })
+            .transpose()?;
+
+        Ok(Secret {
+            namespace: namespace.to_string(),
+            key: key.to_string(),
+            value: SecretString::new(body.value),
+            version: body.version,
+            expires_at,
+            metadata: body.metadata.unwrap_or(serde_json::Value::Null),
+            updated_at,
+            etag,
+            last_modified,
+            request_id,
+            from_cache: false,
+            format: body.format,
+            kid: body.kid,
+        })
+    }
+
+    /// Format a cache key for logging, honoring `log_key_names`
+    fn log_key(&self, cache_key: &str) -> String {
+        log_cache_key(cache_key, self.config.log_key_names)
+    }
+
+    /// How long expired secrets are kept for stale-if-error serving
+    fn stale_window(&self) -> time::Duration {
+        let secs = self.config.cache_config.stale_if_error_secs;
+        time::Duration::seconds(i64::try_from(secs).unwrap_or(i64::MAX))
+    }
+
+    /// Whether a failed fetch may be answered with a stale cache entry
+    ///
+    /// Only server-side and transport failures qualify; auth, permission and
+    /// not-found errors must reach the caller.
+    fn allows_stale_fallback(error: &Error) -> bool {
+        error.is_retryable()
+            || matches!(error, Error::Network { .. })
+            || matches!(error.status_code(), Some(500..=599))
+    }
+
+    /// Get an expired secret that is still within the stale-if-error window
+    async fn get_stale_from_cache(&self, cache_key: &str) -> Option<Secret> {
+        let cache = self.cache.as_ref()?;
+        let cached = cache.get(cache_key).await?;
+        if !cached.is_servable_stale_at(self.config.clock.now(), self.stale_window()) {
+            return None;
+        }
+
+        let (namespace, key) = cache_key.split_once('/').unwrap_or(("", cache_key));
+        cached
+            .into_secret(
+                namespace.to_string(),
+                key.to_string(),
+                self.config.cache_cipher.as_deref(),
+            )
+            .ok()
+    }
+
+    /// Get secret from cache
+    async fn get_from_cache(&self, cache_key: &str) -> Option<Secret> {
+        let cache = self.cache.as_ref()?;
+
+        match cache.get(cache_key).await {
+            Some(cached) => {
+                // Check if expired
+                let now = self.config.clock.now();
+                if cached.is_expired_at(now) {
+                    trace!("Cache entry expired for key: {}", self.log_key(cache_key));
+                    // Keep entries that may still be served if the refetch fails
+                    if !cached.is_servable_stale_at(now, self.stale_window()) {
+                        cache.invalidate(cache_key).await;
+                    }
+                    self.stats.record_expired_miss();
+                    None
+                } else {
+                    let (namespace, key) = cache_key.split_once('/').unwrap_or(("", cache_key));
+                    let secret = match cached.into_secret(
+                        namespace.to_string(),
+                        key.to_string(),
+                        self.config.cache_cipher.as_deref(),
+                    ) {
+                        Ok(secret) => secret,
+                        Err(e) => {
+                            warn!(
+                                "Dropping undecryptable cache entry for key {}: {}",
+                                self.log_key(cache_key),
+                                e
+                            );
+                            cache.invalidate(cache_key).await;
+                            self.stats.record_miss();
+                            return None;
+                        }
+                    };
+
+                    debug!("Cache hit for key: {}", self.log_key(cache_key));
+                    self.stats.record_hit();
+
+                    // Record cache hit metric
+                    #[cfg(feature = "metrics")]
+                    {
+                        let (namespace, _) = cache_key.split_once('/').unwrap_or(("", cache_key));
+                        self.metrics.record_cache_hit(namespace);
+                    }
+
+                    Some(secret)
+                }
+            }
+            None => {
+                trace!("Cache miss for key: {}", self.log_key(cache_key));
+                self.stats.record_miss();
+
+                // Record cache miss metric
+                #[cfg(feature = "metrics")]
+                {
+                    let (namespace, _) = cache_key.split_once('/').unwrap_or(("", cache_key));
+                    self.metrics.record_cache_miss(namespace);
+                }
+
+                None
+            }
+        }
+    }
+
+    /// Cache a secret
+    async fn cache_secret(&self, cache_key: &str, secret: &Secret) {
+        let Some(cache) = &self.cache else { return };
+        if !self.config.cache_config.should_cache(secret) {
+            debug!(
+                "Cache predicate rejected secret for key: {}",
+                self.log_key(cache_key)
+            );
+            return;
+        }
+
+        // Determine TTL from Cache-Control or use default
+        let ttl = if let Some(_etag) = &secret.etag {
+            // If we have an etag, use a longer TTL since we can validate
+            Duration::from_secs(self.config.cache_config.default_ttl_secs * 2)
+        } else {
+            Duration::from_secs(self.config.cache_config.default_ttl_secs)
+        };
+
+        let cache_expires_at = self.config.clock.now() + ttl;
+
+        let cached = CachedSecret {
+            value: CachedValue::seal(&secret.value, self.config.cache_cipher.as_deref()),
+            version: secret.version,
+            expires_at: secret.expires_at,
+            metadata: secret.metadata.clone(),
+            updated_at: secret.updated_at,
+            etag: secret.etag.clone(),
+            last_modified: secret.last_modified.clone(),
+            format: secret.format.clone(),
+            kid: secret.kid.clone(),
+            cache_expires_at,
+        };
+
+        cache.insert(cache_key.to_string(), cached).await;
+        self.stats.record_insertion();
+        debug!(
+            "Cached secret for key: {} with TTL: {:?}",
+            self.log_key(cache_key),
+            ttl
+        );
+    }
+
+    /// Fetch a listing, serving it from the list cache while fresh and
+    /// revalidating it with its ETag once stale
+    async fn get_listing<T: serde::de::DeserializeOwned>(
+        &self,
+        cache_key: String,
+        url: &str,
+    ) -> Result<T> {
+        let cached = match &self.list_cache {
+            Some(list_cache) => list_cache.get(&cache_key).await,
+            None => None,
+        };
+
+        if let Some(entry) = &cached {
+            if entry.is_fresh_at(self.config.clock.now()) {
+                debug!("List cache hit for key: {}", self.log_key(&cache_key));
+                return self.decode(&entry.body);
+            }
+        }
+
+        let mut request = self.build_request(Method::GET, url)?;
+        if let Some(etag) = cached.as_ref().and_then(|entry| entry.etag.as_ref()) {
+            request = request.header(reqwest::header::IF_NONE_MATCH, etag);
+        }
+
+        let response = self.execute_with_retry(request).await?;
+        let list_ttl = Duration::from_secs(self.config.cache_config.list_ttl_secs);
+
+        // Handle 304 Not Modified
+        if response.status() == StatusCode::NOT_MODIFIED {
+            let Some(mut entry) = cached else {
+                return Err(Error::Other(
+                    "Server returned 304 but no cached listing found".to_string(),
+                ));
+            };
+            trace!(
+                "Listing not modified, refreshing cache key: {}",
+                self.log_key(&cache_key)
+            );
+            let value = self.decode(&entry.body)?;
+            if let Some(list_cache) = &self.list_cache {
+                entry.fresh_until = self.config.clock.now() + list_ttl;
+                list_cache.insert(cache_key, entry).await;
+            }
+            return Ok(value);
+        }
+
+        let etag = header_str(response.headers(), "etag");
+        let body = self.read_body(response).await?;
+        let value = self.decode(&body)?;
+
+        if let Some(list_cache) = &self.list_cache {
+            let cached = CachedList {
+                body,
+                etag,
+                fresh_until: self.config.clock.now() + list_ttl,
+            };
+            list_cache.insert(cache_key, cached).await;
+            self.stats.record_insertion();
+        }
+
+        Ok(value)
+    }
+
+    /// Invalidate cached listings affected by a change in `namespace`
+    async fn invalidate_list_cache(&self, namespace: &str) {
+        let Some(list_cache) = &self.list_cache else {
+            return;
+        };
+
+        let prefix = format!("{}/", namespace);
+        let keys: Vec<_> = list_cache
+            .iter()
+            .filter(|(key, _)| key.starts_with(&prefix))
+            .map(|(key, _)| key)
+            .collect();
+        for key in keys {
+            list_cache.invalidate(key.as_str()).await;
+        }
+        list_cache.invalidate(NAMESPACES_LIST_CACHE_KEY).await;
+    }
+}
+
+/// Method and route of a response whose size is only known once its body
+/// has been read
+#[cfg(feature = "metrics")]
+#[derive(Clone)]
+struct UnmeasuredBody {
+    method: String,
+    route: RouteLabel,
+}
+
+/// Read a `null` secret value as an empty one
+///
+/// The field itself is still required, so a response without a value is
+/// rejected rather than mistaken for an empty secret.
+fn null_as_empty<'de, D: serde::Deserializer<'de>>(
+    deserializer: D,
+) -> std::result::Result<String, D::Error> {
+    let value: Option<String> = serde::Deserialize::deserialize(deserializer)?;
+    Ok(value.unwrap_or_default())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{auth::Auth, ClientBuilder};
+    use secrecy::ExposeSecret;
+    use wiremock::matchers::{header, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    // Helper function to create test client that works with HTTP URLs
+    fn create_test_client(base_url: &str) -> Client {
+        #[cfg(feature = "danger-insecure-http")]
+        {
+            ClientBuilder::new(base_url)
+                .auth(Auth::bearer("test-token"))
+                .allow_insecure_http()
+                .build()
+                .unwrap()
+        }
+        #[cfg(not(feature = "danger-insecure-http"))]
+        {
+            // In tests without the feature, we'll just use a dummy HTTPS URL
+            // The actual URL doesn't matter since we're mocking
+            ClientBuilder::new(&base_url.replace("http://", "https://"))
+                .auth(Auth::bearer("test-token"))
+                .build()
+                .unwrap()
+        }
+    }
+
+    #[test]
+    fn test_client_creation() {
+        let client = ClientBuilder::new("https://example.com")
+            .auth(Auth::bearer("test-token"))
+            .build();
+        assert!(client.is_ok());
+    }
+
+    #[test]
+    fn test_cache_key_format() {
+        let cache_key = format!("{}/{}", "namespace", "key");
+        assert_eq!(cache_key, "namespace/key");
+    }
+
+    #[tokio::test]
+    async fn test_get_secret_success() {
+        let mock_server = MockServer::start().await;
+
+        // Mock successful response
+        let response_body = serde_json::json!({
+            "value": "secret-value",
+            "version": 1,
+            "expires_at": null,
+            "metadata": {"env": "prod"},
+            "updated_at": "2024-01-01T00:00:00Z"
+        });
+
+        Mock::given(method("GET"))
+            .and(path("/api/v2/secrets/test-namespace/test-key"))
+            .and(header("Authorization", "Bearer test-token"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(&response_body)
+                    .insert_header("etag", "\"abc123\"")
+                    .insert_header("x-request-id", "req-123"),
+            )
+            .mount(&mock_server)
+            .await;
+
+        let client = create_test_client(&mock_server.uri());
+
+        let result = client
+            .get_secret("test-namespace", "test-key", GetOpts::default())
+            .await;
+        if let Err(ref e) = result {
+            eprintln!("Error: {:?}", e);
+        }
+        assert!(result.is_ok());
+
+        let secret = result.unwrap();
+        assert_eq!(secret.namespace, "test-namespace");
+        assert_eq!(secret.key, "test-key");
+        assert_eq!(secret.version, 1);
+        assert_eq!(secret.etag, Some("\"abc123\"".to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_get_secret_404() {
+        let mock_server = MockServer::start().await;
+
+        let error_body = serde_json::json!({
+            "error": "not_found",
+            "message": "Secret not found",
+            "timestamp": "2024-01-01T00:00:00Z",
+            "status": 404
+        });
+
+        Mock::given(method("GET"))
+            .and(path("/api/v2/secrets/test-namespace/missing-key"))
+            .respond_with(
+                ResponseTemplate::new(404)
+                    .set_body_json(&error_body)
+                    .insert_header("x-request-id", "req-456"),
+            )
+            .mount(&mock_server)
+            .await;
+
+        let client = create_test_client(&mock_server.uri());
+
+        let result = client
+            .get_secret("test-namespace", "missing-key", GetOpts::default())
+            .await;
+        assert!(result.is_err());
+
+        let err = result.unwrap_err();
+        assert_eq!(err.status_code(), Some(404));
+        assert_eq!(err.request_id(), Some("req-456"));
+    }
+
+    #[tokio::test]
+    async fn test_warm_caches_configured_keys() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/api/v2/secrets/prod/db-url"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "value": "postgres://db/app",
+                "version": 2,
+                "updated_at": "2024-01-01T00:00:00Z"
+            })))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        Mock::given(method("GET"))
+            .and(path("/api/v2/secrets/prod/missing"))
+            .respond_with(ResponseTemplate::new(404))
+            .mount(&mock_server)
+            .await;
+
+        let warm_keys = vec!["db-url".to_string(), "missing".to_string()];
+
+        #[cfg(feature = "danger-insecure-http")]
+        let client = ClientBuilder::new(mock_server.uri())
+            .auth(Auth::bearer("test-token"))
+            .warm_keys("prod", warm_keys)
+            .allow_insecure_http()
+            .build()
+            .unwrap();
+
+        #[cfg(not(feature = "danger-insecure-http"))]
+        let client = ClientBuilder::new(mock_server.uri().replace("http://", "https://"))
+            .auth(Auth::bearer("test-token"))
+            .warm_keys("prod", warm_keys)
+            .build()
+            .unwrap();
+
+        // The missing key is skipped without failing the warmup
+        assert_eq!(client.warm().await, 1);
+
+        let secret = client
+            .get_secret("prod", "db-url", GetOpts::default())
+            .await
+            .unwrap();
+        assert!(secret.from_cache);
+        assert_eq!(secret.version, 2);
+    }
+
+    #[cfg(feature = "metrics")]
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_body_size_metrics() {
+        use opentelemetry::metrics::MeterProvider as _;
+        use opentelemetry_sdk::metrics::{data, MeterProvider, PeriodicReader};
+        use opentelemetry_sdk::testing::metrics::InMemoryMetricsExporter;
+
+        let mock_server = MockServer::start().await;
+        let body = r#"{"value":"secret-value","version":1,"updated_at":"2024-01-01T00:00:00Z"}"#;
+
+        Mock::given(method("GET"))
+            .and(path("/api/v2/secrets/test-ns/test-key"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(body, "application/json"))
+            .mount(&mock_server)
+            .await;
+
+        let exporter = InMemoryMetricsExporter::default();
+        let provider = MeterProvider::builder()
+            .with_reader(
+                PeriodicReader::builder(exporter.clone(), opentelemetry_sdk::runtime::Tokio)
+                    .build(),
+            )
+            .build();
+
+        let mut client = create_test_client(&mock_server.uri());
+        client.metrics = std::sync::Arc::new(telemetry::Metrics::from_meter(
+            &provider.meter("secret-store-sdk-test"),
+        ));
+
+        let _ = client
+            .get_secret("test-ns", "test-key", GetOpts::default())
+            .await
+            .unwrap();
+        provider.force_flush().unwrap();
+
+        let metrics = exporter.get_finished_metrics().unwrap();
+        let response_bytes = metrics
+            .iter()
+            .flat_map(|rm| &rm.scope_metrics)
+            .flat_map(|sm| &sm.metrics)
+            .find(|m| m.name == "secret_store_sdk.response_bytes")
+            .expect("response size histogram recorded");
+        let histogram = response_bytes
+            .data
+            .as_any()
+            .downcast_ref::<data::Histogram<u64>>()
+            .unwrap();
+        let point = &histogram.data_points[0];
+        assert_eq!(point.count, 1);
+        assert_eq!(point.sum, body.len() as u64);
+        assert!(point
+            .attributes
+            .iter()
+            .any(|(key, value)| key.as_str() == "route"
+                && value.as_str() == "/api/v2/secrets/{namespace}/{key}"));
+    }
+
+    #[cfg(feature = "metrics")]
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_request_metrics_use_route_template() {
+        use opentelemetry::metrics::MeterProvider as _;
+        use opentelemetry_sdk::metrics::{data, MeterProvider, PeriodicReader};
+        use opentelemetry_sdk::testing::metrics::InMemoryMetricsExporter;
+
+        let mock_server = MockServer::start().await;
+        for key in ["db-url", "api-key"] {
+            Mock::given(method("GET"))
+                .and(path(format!("/api/v2/secrets/test-ns/{}", key)))
+                .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                    "value": "v",
+                    "version": 1,
+                    "updated_at": "2024-01-01T00:00:00Z"
+                })))
+                .mount(&mock_server)
+                .await;
+        }
+
+        let exporter = InMemoryMetricsExporter::default();
+        let provider = MeterProvider::builder()
+            .with_reader(
+                PeriodicReader::builder(exporter.clone(), opentelemetry_sdk::runtime::Tokio)
+                    .build(),
+            )
+            .build();
+
+        let mut client = create_test_client(&mock_server.uri());
+        client.metrics = std::sync::Arc::new(telemetry::Metrics::from_meter(
+            &provider.meter("secret-store-sdk-test"),
+        ));
+
+        for key in ["db-url", "api-key"] {
+            let _ = client
+                .get_secret("test-ns", key, GetOpts::default())
+                .await
+                .unwrap();
+        }
+        provider.force_flush().unwrap();
+
+        let metrics = exporter.get_finished_metrics().unwrap();
+        let requests_total = metrics
+            .iter()
+            .flat_map(|rm| &rm.scope_metrics)
+            .flat_map(|sm| &sm.metrics)
+            .find(|m| m.name == "secret_store_sdk.requests_total")
+            .expect("request counter recorded");
+        let sum = requests_total
+            .data
+            .as_any()
+            .downcast_ref::<data::Sum<u64>>()
+            .unwrap();
+
+        // Both keys land in a single series labelled by template and namespace
+        assert_eq!(sum.data_points.len(), 1);
+        let point = &sum.data_points[0];
+        assert_eq!(point.value, 2);
+        let label = |name: &str| {
+            point
+                .attributes
+                .iter()
+                .find(|(key, _)| key.as_str() == name)
+                .map(|(_, value)| value.as_str().into_owned())
+        };
+        assert_eq!(
+            label("route").as_deref(),
+            Some("/api/v2/secrets/{namespace}/{key}")
+        );
+        assert_eq!(label("namespace").as_deref(), Some("test-ns"));
+        assert_eq!(label("path"), None);
+    }
+
+    #[tokio::test]
+    async fn test_cancel_during_retry_backoff() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/api/v2/secrets/test-ns/flaky"))
+            .respond_with(ResponseTemplate::new(503))
+            .mount(&mock_server)
+            .await;
+
+        #[cfg(feature = "danger-insecure-http")]
+        let client = ClientBuilder::new(mock_server.uri())
+            .auth(Auth::bearer("test-token"))
+            .retries(10)
+            .allow_insecure_http()
+            .build()
+            .unwrap();
+
+        #[cfg(not(feature = "danger-insecure-http"))]
+        let client = ClientBuilder::new(mock_server.uri().replace("http://", "https://"))
+            .auth(Auth::bearer("test-token"))
+            .retries(10)
+            .build()
+            .unwrap();
+
+        let token = CancellationToken::new();
+        let cancellable = client.with_cancellation(token.clone());
+        let canceller = tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(250)).await;
+            token.cancel();
+        });
+
+        let started = std::time::Instant::now();
+        let err = cancellable
+            .get_secret("test-ns", "flaky", GetOpts::default())
+            .await
+            .unwrap_err();
+        canceller.await.unwrap();
+
+        assert!(matches!(err, Error::Cancelled));
+        assert_eq!(err.kind(), crate::ErrorKind::Cancelled);
+        // Ten retries would take many seconds of backoff
+        assert!(started.elapsed() < Duration::from_secs(2));
+        assert!(mock_server.received_requests().await.unwrap().len() >= 2);
+    }
+
+    #[tokio::test]
+    async fn test_overall_timeout_clamps_backoff() {
+        #[derive(Clone, Default)]
+        struct Arrivals(std::sync::Arc<std::sync::Mutex<Vec<std::time::Instant>>>);
+
+        impl wiremock::Respond for Arrivals {
+            fn respond(&self, _: &wiremock::Request) -> ResponseTemplate {
+                self.0.lock().unwrap().push(std::time::Instant::now());
+                ResponseTemplate::new(503)
+            }
+        }
+
+        let mock_server = MockServer::start().await;
+        let arrivals = Arrivals::default();
+        Mock::given(method("GET"))
+            .and(path("/api/v2/secrets/test-ns/flaky"))
+            .respond_with(arrivals.clone())
+            .mount(&mock_server)
+            .await;
+
+        #[cfg(feature = "danger-insecure-http")]
+        let client = ClientBuilder::new(mock_server.uri())
+            .auth(Auth::bearer("test-token"))
+            .retries(10)
+            .overall_timeout_ms(1_000)
+            .allow_insecure_http()
+            .build()
+            .unwrap();
+
+        #[cfg(not(feature = "danger-insecure-http"))]
+        let client = ClientBuilder::new(mock_server.uri().replace("http://", "https://"))
+            .auth(Auth::bearer("test-token"))
+            .retries(10)
+            .overall_timeout_ms(1_000)
+            .build()
+            .unwrap();
+
+        let started = std::time::Instant::now();
+        let err = client
+            .get_secret("test-ns", "flaky", GetOpts::default())
+            .await
+            .unwrap_err();
+        let elapsed = started.elapsed();
+        assert_eq!(err.status_code(), Some(503));
+
+        // Unclamped, the attempt after ~0.7s would sleep another ~0.8s. With
+        // the deadline the final attempt lands just before it instead.
+        let last = *arrivals.0.lock().unwrap().last().unwrap();
+        let last_offset = last - started;
+        assert!(
+            last_offset >= Duration::from_millis(850),
+            "last attempt at {:?}",
+            last_offset
+        );
+        assert!(elapsed < Duration::from_millis(1_200), "took {:?}", elapsed);
+    }
+
+    #[tokio::test]
+    async fn test_cache_cipher_round_trip() {
+        #[derive(Debug)]
+        struct XorCipher(u8);
+
+        impl crate::CacheCipher for XorCipher {
+            fn encrypt(&self, plaintext: &[u8]) -> Vec<u8> {
+                plaintext.iter().map(|b| b ^ self.0).collect()
+            }
+
+            fn decrypt(&self, ciphertext: &[u8]) -> Result<Vec<u8>> {
+                Ok(ciphertext.iter().map(|b| b ^ self.0).collect())
+            }
+        }
+
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/api/v2/secrets/prod/db-url"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "value": "postgres://db/app",
+                "version": 1,
+                "updated_at": "2024-01-01T00:00:00Z"
+            })))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        #[cfg(feature = "danger-insecure-http")]
+        let client = ClientBuilder::new(mock_server.uri())
+            .auth(Auth::bearer("test-token"))
+            .cache_cipher(XorCipher(0x5a))
+            .allow_insecure_http()
+            .build()
+            .unwrap();
+
+        #[cfg(not(feature = "danger-insecure-http"))]
+        let client = ClientBuilder::new(mock_server.uri().replace("http://", "https://"))
+            .auth(Auth::bearer("test-token"))
+            .cache_cipher(XorCipher(0x5a))
+            .build()
+            .unwrap();
+
+        let first = client
+            .get_secret("prod", "db-url", GetOpts::default())
+            .await
+            .unwrap();
+        assert!(!first.from_cache);
+
+        // Only ciphertext is held in the cache
+        let cached = client.cache.as_ref().unwrap().get("prod/db-url").await;
+        match cached.unwrap().value {
+            CachedValue::Encrypted(ciphertext) => {
+                assert_eq!(ciphertext.len(), "postgres://db/app".len());
+                assert_ne!(ciphertext, b"postgres://db/app");
+            }
+            CachedValue::Plain(_) => panic!("cached value was not encrypted"),
+        }
+
+        let second = client
+            .get_secret("prod", "db-url", GetOpts::default())
+            .await
+            .unwrap();
+        assert!(second.from_cache);
+        assert_eq!(second.value.expose_secret(), "postgres://db/app");
+    }
+
+    #[tokio::test]
+    async fn test_get_secret_with_cache() {
+        let mock_server = MockServer::start().await;
+
+        let response_body = serde_json::json!({
+            "value": "cached-value",
+            "version": 2,
+            "expires_at": null,
+            "metadata": null,
+            "updated_at": "2024-01-01T00:00:00Z"
+        });
+
+        // First request
+        Mock::given(method("GET"))
+            .and(path("/api/v2/secrets/cache-ns/cache-key"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(&response_body)
+                    .insert_header("etag", "\"etag123\""),
+            )
+            .expect(1) // Should only be called once
+            .mount(&mock_server)
+            .await;
+
+        #[cfg(feature = "danger-insecure-http")]
+        let client = ClientBuilder::new(mock_server.uri())
+            .auth(Auth::bearer("test-token"))
+            .enable_cache(true)
+            .allow_insecure_http()
+            .build()
+            .unwrap();
+
+        #[cfg(not(feature = "danger-insecure-http"))]
+        let client = ClientBuilder::new(&mock_server.uri().replace("http://", "https://"))
+            .auth(Auth::bearer("test-token"))
+            .enable_cache(true)
+            .build()
+            .unwrap();
+
+        // First request - should hit server
+        let secret1 = client
+            .get_secret("cache-ns", "cache-key", GetOpts::default())
+            .await
+            .unwrap();
+        assert_eq!(secret1.version, 2);
+
+        // Small delay to ensure cache is populated
+        tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+
+        // Second request - should hit cache
+        let secret2 = client
+            .get_secret("cache-ns", "cache-key", GetOpts::default())
+            .await
+            .unwrap();
+        assert_eq!(secret2.version, 2);
+
+        // Verify cache hit
+        let stats = client.cache_stats();
+        assert_eq!(stats.hits(), 1);
+        assert_eq!(stats.misses(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_get_secret_304_not_modified() {
+        let mock_server = MockServer::start().await;
+
+        let response_body = serde_json::json!({
+            "value": "initial-value",
+            "version": 1,
+            "expires_at": null,
+            "metadata": null,
+            "updated_at": "2024-01-01T00:00:00Z"
+        });
+
+        // Mount both mocks at once with more specific one first
+        // Second request with etag - return 304 (more specific, so should match first)
+        Mock::given(method("GET"))
+            .and(path("/api/v2/secrets/test-ns/test-key"))
+            .and(header("Authorization", "Bearer test-token"))
+            .and(header("if-none-match", "etag-v1"))
+            .respond_with(ResponseTemplate::new(304))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        // First request - return data (less specific)
+        Mock::given(method("GET"))
+            .and(path("/api/v2/secrets/test-ns/test-key"))
+            .and(header("Authorization", "Bearer test-token"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(&response_body)
+                    .insert_header("etag", "\"etag-v1\""),
+            )
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        #[cfg(feature = "danger-insecure-http")]
+        let client = ClientBuilder::new(mock_server.uri())
+            .auth(Auth::bearer("test-token"))
+            .enable_cache(true)
+            .allow_insecure_http()
+            .build()
+            .unwrap();
+
+        #[cfg(not(feature = "danger-insecure-http"))]
+        let client = ClientBuilder::new(&mock_server.uri().replace("http://", "https://"))
+            .auth(Auth::bearer("test-token"))
+            .enable_cache(true)
+            .build()
+            .unwrap();
+
+        // First request
+        let secret1 = client
+            .get_secret("test-ns", "test-key", GetOpts::default())
+            .await
+            .unwrap();
+        assert_eq!(secret1.etag, Some("\"etag-v1\"".to_string()));
+
+        // Clear cache to force second request to hit server
+        client.clear_cache();
+
+        // Second request with etag
+        let opts = GetOpts {
+            use_cache: false, // Disable cache to ensure we hit the server
+            if_none_match: Some("etag-v1".to_string()), // Without quotes
+            if_modified_since: None,
+            ..Default::default()
+        };
+        // This should return error since cache was cleared and server returns 304
+        let result = client.get_secret("test-ns", "test-key", opts).await;
+        assert!(result.is_err());
+
+        // The error should indicate that we got 304 but have no cache
+        if let Err(e) = result {
+            match &e {
+                Error::NotModifiedNoCache { .. } => assert_eq!(e.status_code(), Some(304)),
+                _ => panic!("Expected Error::NotModifiedNoCache, got {:?}", e),
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_get_secret_304_refetch_policy() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/api/v2/secrets/test-ns/test-key"))
+            .and(header("if-none-match", "\"etag-v1\""))
+            .respond_with(ResponseTemplate::new(304))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        Mock::given(method("GET"))
+            .and(path("/api/v2/secrets/test-ns/test-key"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(serde_json::json!({
+                        "value": "fresh-value",
+                        "version": 2,
+                        "expires_at": null,
+                        "metadata": null,
+                        "updated_at": "2024-01-01T00:00:00Z"
+                    }))
+                    .insert_header("etag", "\"etag-v2\""),
+            )
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let client = create_test_client(&mock_server.uri());
+
+        let opts = GetOpts {
+            if_none_match: Some("\"etag-v1\"".to_string()),
+            on_stale_304: Stale304Policy::Refetch,
+            ..Default::default()
+        };
+        let secret = client
+            .get_secret("test-ns", "test-key", opts)
+            .await
+            .unwrap();
+
+        assert_eq!(secret.value.expose_secret(), "fresh-value");
+        assert_eq!(secret.version, 2);
+        assert_eq!(secret.etag, Some("\"etag-v2\"".to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_get_secret_bypass_cache_entirely() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/api/v2/secrets/test-ns/test-key"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(serde_json::json!({
+                        "value": "fresh-value",
+                        "version": 3,
+                        "updated_at": "2024-01-01T00:00:00Z"
+                    }))
+                    .insert_header("etag", "\"etag-v3\""),
+            )
+            .expect(2)
+            .mount(&mock_server)
+            .await;
+
+        let client = create_test_client(&mock_server.uri());
+        let opts = GetOpts {
+            bypass_cache_entirely: true,
+            ..Default::default()
+        };
+
+        let before = client.cache_stats().snapshot();
+        let secret = client
+            .get_secret("test-ns", "test-key", opts.clone())
+            .await
+            .unwrap();
+        assert_eq!(secret.etag.as_deref(), Some("\"etag-v3\""));
+        assert!(!secret.from_cache);
+
+        // Nothing was inserted, so the second call goes to the server too
+        let again = client
+            .get_secret("test-ns", "test-key", opts)
+            .await
+            .unwrap();
+        assert!(!again.from_cache);
+        assert_eq!(client.cache_stats().snapshot(), before);
+    }
+
+    #[derive(Debug, Clone, Default)]
+    struct LogCapture(std::sync::Arc<std::sync::Mutex<Vec<u8>>>);
+
+    impl std::io::Write for LogCapture {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    impl<'a> tracing_subscriber::fmt::MakeWriter<'a> for LogCapture {
+        type Writer = Self;
+
+        fn make_writer(&'a self) -> Self::Writer {
+            self.clone()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_log_key_names_redacted() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/api/v2/secrets/billing/stripe-live-secret"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "value": "sk_live_123",
+                "version": 1,
+                "expires_at": null,
+                "metadata": null,
+                "updated_at": "2024-01-01T00:00:00Z"
+            })))
+            .mount(&mock_server)
+            .await;
+
+        #[cfg(feature = "danger-insecure-http")]
+        let client = ClientBuilder::new(mock_server.uri())
+            .auth(Auth::bearer("test-token"))
+            .log_key_names(false)
+            .allow_insecure_http()
+            .build()
+            .unwrap();
+        #[cfg(not(feature = "danger-insecure-http"))]
+        let client = ClientBuilder::new(mock_server.uri().replace("http://", "https://"))
+            .auth(Auth::bearer("test-token"))
+            .log_key_names(false)
+            .build()
+            .unwrap();
+
+        let capture = LogCapture::default();
+        let subscriber = tracing_subscriber::fmt()
+            .with_max_level(tracing::Level::TRACE)
+            .with_ansi(false)
+            .with_writer(capture.clone())
+            .finish();
+        let _guard = tracing::subscriber::set_default(subscriber);
+
+        // Miss + insert, then hit
+        for _ in 0..2 {
+            let _ = client
+                .get_secret("billing", "stripe-live-secret", GetOpts::default())
+                .await
+                .unwrap();
+        }
+
+        let output = String::from_utf8(capture.0.lock().unwrap().clone()).unwrap();
+        let sdk_lines: Vec<&str> = output
+            .lines()
+            .filter(|line| line.contains("secret_store_sdk"))
+            .collect();
+
+        assert!(sdk_lines.iter().any(|line| line.contains("Cache hit")));
+        assert!(sdk_lines
+            .iter()
+            .any(|line| line.contains("billing/<redacted:")));
+        assert!(sdk_lines
+            .iter()
+            .all(|line| !line.contains("stripe-live-secret")));
+
+        // Network errors would otherwise carry the request URL into the
+        // retry history
+        let refused = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let unreachable = format!("http://{}", refused.local_addr().unwrap());
+        drop(refused);
+        #[cfg(feature = "danger-insecure-http")]
+        let client = ClientBuilder::new(unreachable)
+            .auth(Auth::bearer("test-token"))
+            .log_key_names(false)
+            .retries(1)
+            .allow_insecure_http()
+            .build()
+            .unwrap();
+        #[cfg(not(feature = "danger-insecure-http"))]
+        let client = ClientBuilder::new(unreachable.replace("http://", "https://"))
+            .auth(Auth::bearer("test-token"))
+            .log_key_names(false)
+            .retries(1)
+            .build()
+            .unwrap();
+
+        let err = client
+            .get_secret("billing", "stripe-live-secret", GetOpts::default())
+            .await
+            .unwrap_err();
+        assert!(matches!(err, Error::RetriesExhausted { .. }));
+        assert!(!err.to_string().contains("stripe-live-secret"));
+
+        let output = String::from_utf8(capture.0.lock().unwrap().clone()).unwrap();
+        let sdk_lines: Vec<&str> = output
+            .lines()
+            .filter(|line| line.contains("secret_store_sdk"))
+            .collect();
+        assert!(sdk_lines
+            .iter()
+            .any(|line| line.contains("Retries exhausted")));
+        assert!(sdk_lines
+            .iter()
+            .all(|line| !line.contains("stripe-live-secret")));
+    }
+
+    #[tokio::test]
+    async fn test_put_secret_success() {
+        let mock_server = MockServer::start().await;
+
+        let response_body = serde_json::json!({
+            "message": "Secret created",
+            "namespace": "test-ns",
+            "key": "new-key",
+            "created_at": "2024-01-01T00:00:00Z",
+            "request_id": "req-789"
+        });
+
+        Mock::given(method("PUT"))
+            .and(path("/api/v2/secrets/test-ns/new-key"))
+            .respond_with(ResponseTemplate::new(201).set_body_json(&response_body))
+            .mount(&mock_server)
+            .await;
+
+        let client = create_test_client(&mock_server.uri());
+
+        let opts = PutOpts {
+            ttl_seconds: Some(3600),
+            metadata: Some(serde_json::json!({"env": "test"})),
+            idempotency_key: None,
+            ..Default::default()
+        };
+
+        let result = client
+            .put_secret("test-ns", "new-key", "new-value", opts)
+            .await;
+        assert!(result.is_ok());
+
+        let put_result = result.unwrap();
+        assert_eq!(put_result.namespace, "test-ns");
+        assert_eq!(put_result.key, "new-key");
+    }
+
+    fn conflict_body() -> serde_json::Value {
+        serde_json::json!({
+            "error": "conflict",
+            "message": "Concurrent modification",
+            "timestamp": "2024-01-01T00:00:00Z",
+            "status": 409
+        })
+    }
+
+    #[tokio::test]
+    async fn test_put_secret_minimal_response() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("PUT"))
+            .and(path("/api/v2/secrets/test-ns/minimal"))
+            .and(header("prefer", "return=minimal"))
+            .respond_with(
+                ResponseTemplate::new(204)
+                    .insert_header("x-request-id", "req-minimal")
+                    .insert_header("x-secret-version", "7"),
+            )
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        // A server that ignores the preference
+        Mock::given(method("PUT"))
+            .and(path("/api/v2/secrets/test-ns/full"))
+            .and(header("prefer", "return=minimal"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "message": "Secret updated",
+                "namespace": "test-ns",
+                "key": "full",
+                "created_at": "2024-01-01T00:00:00Z",
+                "request_id": "req-full"
+            })))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let client = create_test_client(&mock_server.uri());
+        let opts = PutOpts {
+            minimal_response: true,
+            ..Default::default()
+        };
+
+        let minimal = client
+            .put_secret("test-ns", "minimal", "value", opts.clone())
+            .await
+            .unwrap();
+        assert_eq!(minimal.message, "");
+        assert_eq!(minimal.namespace, "test-ns");
+        assert_eq!(minimal.key, "minimal");
+        assert_eq!(minimal.request_id, "req-minimal");
+        assert_eq!(minimal.version, Some(7));
+
+        let full = client
+            .put_secret("test-ns", "full", "value", opts)
+            .await
+            .unwrap();
+        assert_eq!(full.message, "Secret updated");
+        assert_eq!(full.created_at, "2024-01-01T00:00:00Z");
+        assert_eq!(full.request_id, "req-full");
+        assert_eq!(full.version, None);
+    }
+
+    #[tokio::test]
+    async fn test_put_secret_retries_conflict() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("PUT"))
+            .and(path("/api/v2/secrets/test-ns/contended-key"))
+            .respond_with(ResponseTemplate::new(409).set_body_json(conflict_body()))
+            .up_to_n_times(1)
+            .mount(&mock_server)
+            .await;
+
+        Mock::given(method("PUT"))
+            .and(path("/api/v2/secrets/test-ns/contended-key"))
+            .and(header("X-Idempotency-Key", "put-123"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "message": "Secret updated",
+                "namespace": "test-ns",
+                "key": "contended-key",
+                "created_at": "2024-01-01T00:00:00Z",
+                "request_id": "req-409"
+            })))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let client = create_test_client(&mock_server.uri());
+
+        let opts = PutOpts {
+            idempotency_key: Some("put-123".to_string()),
+            retry_on_conflict: Some(true),
+            ..Default::default()
+        };
+        let result = client
+            .put_secret("test-ns", "contended-key", "value", opts)
+            .await;
+
+        assert!(result.is_ok());
+        assert_eq!(client.retry_stats().by_reason("409"), 1);
+    }
+
+    #[tokio::test]
+    async fn test_put_secret_conflict_fatal_without_opt_in() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("PUT"))
+            .and(path("/api/v2/secrets/test-ns/contended-key"))
+            .respond_with(ResponseTemplate::new(409).set_body_json(conflict_body()))
+            .expect(2)
+            .mount(&mock_server)
+            .await;
+
+        let client = create_test_client(&mock_server.uri());
+
+        // Disabled (default)
+        let opts = PutOpts {
+            idempotency_key: Some("put-123".to_string()),
+            ..Default::default()
+        };
+        let err = client
+            .put_secret("test-ns", "contended-key", "value", opts)
+            .await
+            .unwrap_err();
+        assert_eq!(err.status_code(), Some(409));
+
+        // Enabled but without an idempotency key
+        let opts = PutOpts {
+            retry_on_conflict: Some(true),
+            ..Default::default()
+        };
+        let err = client
+            .put_secret("test-ns", "contended-key", "value", opts)
+            .await
+            .unwrap_err();
+        assert_eq!(err.status_code(), Some(409));
+        assert_eq!(client.retry_stats().total(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_delete_secret_success() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("DELETE"))
+            .and(path("/api/v2/secrets/test-ns/delete-key"))
+            .respond_with(ResponseTemplate::new(204).insert_header("x-request-id", "req-delete"))
+            .mount(&mock_server)
+            .await;
+
+        let client = create_test_client(&mock_server.uri());
+
+        let result = client.delete_secret("test-ns", "delete-key").await;
+        assert!(result.is_ok());
+
+        let delete_result = result.unwrap();
+        assert!(delete_result.deleted);
+        assert_eq!(delete_result.request_id, Some("req-delete".to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_retry_on_server_error() {
+        let mock_server = MockServer::start().await;
+
+        let error_body = serde_json::json!({
+            "error": "internal",
+            "message": "Internal server error",
+            "timestamp": "2024-01-01T00:00:00Z",
+            "status": 500
+        });
+
+        // First two requests fail, third succeeds
+        Mock::given(method("GET"))
+            .and(path("/api/v2/secrets/test-ns/retry-key"))
+            .respond_with(ResponseTemplate::new(500).set_body_json(&error_body))
+            .up_to_n_times(2)
+            .mount(&mock_server)
+            .await;
+
+        let success_body = serde_json::json!({
+            "value": "success-after-retry",
+            "version": 1,
+            "expires_at": null,
+            "metadata": null,
+            "updated_at": "2024-01-01T00:00:00Z"
+        });
+
+        Mock::given(method("GET"))
+            .and(path("/api/v2/secrets/test-ns/retry-key"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(&success_body))
+            .mount(&mock_server)
+            .await;
+
+        #[cfg(feature = "danger-insecure-http")]
+        let client = ClientBuilder::new(mock_server.uri())
+            .auth(Auth::bearer("test-token"))
+            .retries(3)
+            .allow_insecure_http()
+            .build()
+            .unwrap();
+
+        #[cfg(not(feature = "danger-insecure-http"))]
+        let client = ClientBuilder::new(&mock_server.uri().replace("http://", "https://"))
+            .auth(Auth::bearer("test-token"))
+            .retries(3)
+            .build()
+            .unwrap();
+
+        let result = client
+            .get_secret("test-ns", "retry-key", GetOpts::default())
+            .await;
+        assert!(result.is_ok()); // Should succeed after retries
+    }
+
+    #[tokio::test]
+    async fn test_read_and_write_retry_caps() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/api/v2/secrets/test-ns/flaky"))
+            .respond_with(ResponseTemplate::new(503))
+            .expect(4)
+            .mount(&mock_server)
+            .await;
+
+        Mock::given(method("PUT"))
+            .and(path("/api/v2/secrets/test-ns/flaky"))
+            .respond_with(ResponseTemplate::new(503))
+            .expect(2)
+            .mount(&mock_server)
+            .await;
+
+        #[cfg(feature = "danger-insecure-http")]
+        let client = ClientBuilder::new(mock_server.uri())
+            .auth(Auth::bearer("test-token"))
+            .retries(2)
+            .read_retries(3)
+            .write_retries(1)
+            .allow_insecure_http()
+            .build()
+            .unwrap();
+
+        #[cfg(not(feature = "danger-insecure-http"))]
+        let client = ClientBuilder::new(mock_server.uri().replace("http://", "https://"))
+            .auth(Auth::bearer("test-token"))
+            .retries(2)
+            .read_retries(3)
+            .write_retries(1)
+            .build()
+            .unwrap();
+
+        let err = client
+            .get_secret("test-ns", "flaky", GetOpts::default())
+            .await
+            .unwrap_err();
+        assert_eq!(err.status_code(), Some(503));
+
+        let err = client
+            .put_secret("test-ns", "flaky", "value", PutOpts::default())
+            .await
+            .unwrap_err();
+        assert_eq!(err.status_code(), Some(503));
+    }
+
+    #[tokio::test]
+    async fn test_retry_stats_count_retries() {
+        let mock_server = MockServer::start().await;
+
+        // First two requests fail with 503, third succeeds
+        Mock::given(method("GET"))
+            .and(path("/api/v2/secrets/test-ns/flaky-key"))
+            .respond_with(ResponseTemplate::new(503))
+            .up_to_n_times(2)
+            .mount(&mock_server)
+            .await;
+
+        Mock::given(method("GET"))
+            .and(path("/api/v2/secrets/test-ns/flaky-key"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "value": "eventually",
+                "version": 1,
+                "expires_at": null,
+                "metadata": null,
+                "updated_at": "2024-01-01T00:00:00Z"
+            })))
+            .mount(&mock_server)
+            .await;
+
+        let client = create_test_client(&mock_server.uri());
+        assert_eq!(client.retry_stats().total(), 0);
+
+        let result = client
+            .get_secret("test-ns", "flaky-key", GetOpts::default())
+            .await;
+        assert!(result.is_ok());
+
+        let stats = client.retry_stats();
+        assert_eq!(stats.total(), 2);
+        assert_eq!(stats.by_reason("503"), 2);
+        assert_eq!(stats.by_reason("network_error"), 0);
+    }
+
+    #[cfg(feature = "danger-insecure-http")]
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_subscribe_changes_outlives_client_timeout() {
+        use futures::StreamExt;
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        // Sends one event, idles past the client timeout, then sends another
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let requests = std::sync::Arc::new(AtomicUsize::new(0));
+        let counter = requests.clone();
+        drop(tokio::spawn(async move {
+            while let Ok((socket, _)) = listener.accept().await {
+                let counter = counter.clone();
+                drop(tokio::spawn(async move {
+                    let Ok(mut connection) = h2::server::handshake(socket).await else {
+                        return;
+                    };
+                    while let Some(Ok((_request, mut respond))) = connection.accept().await {
+                        let _ = counter.fetch_add(1, Ordering::SeqCst);
+                        let response = http::Response::builder()
+                            .status(200)
+                            .header("content-type", "text/event-stream")
+                            .body(())
+                            .unwrap();
+                        let mut body = respond.send_response(response, false).unwrap();
+                        drop(tokio::spawn(async move {
+                            let event = |version: u32| {
+                                bytes::Bytes::from(format!(
+                                    "id: {0}\ndata: {{\"key\":\"db-pass\",\"action\":\"updated\",\"version\":{0}}}\n\n",
+                                    version
+                                ))
+                            };
+                            body.send_data(event(1), false).unwrap();
+                            tokio::time::sleep(Duration::from_millis(600)).await;
+                            let _ = body.send_data(event(2), false);
+                        }));
+                    }
+                }));
+            }
+        }));
+
+        let client = ClientBuilder::new(&url)
+            .auth(Auth::bearer("test-token"))
+            .timeout_ms(200)
+            .overall_timeout_ms(300)
+            .allow_insecure_http()
+            .build()
+            .unwrap();
+        let opts = SubscribeOpts {
+            reconnect_delay: Duration::from_millis(10),
+            ..Default::default()
+        };
+        let changes = client.subscribe_changes("prod", opts).take(2);
+        let events: Vec<_> = tokio::time::timeout(Duration::from_secs(5), changes.collect())
+            .await
+            .unwrap();
+
+        let versions: Vec<_> = events
+            .into_iter()
+            .map(|event| event.unwrap().version)
+            .collect();
+        assert_eq!(versions, vec![Some(1), Some(2)]);
+        assert_eq!(requests.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_subscribe_changes() {
+        use futures::StreamExt;
+
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/api/v2/secrets/prod/db-pass"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "value": "old-value",
+                "version": 1,
+                "expires_at": null,
+                "metadata": null,
+                "updated_at": "2024-01-01T00:00:00Z"
+            })))
+            .mount(&mock_server)
+            .await;
+
+        let first = concat!(
+            ": connected\n\n",
+            "id: 1\n",
+            "event: change\n",
+            "data: {\"namespace\":\"prod\",\"key\":\"db-pass\",\"action\":\"updated\",\"version\":2}\n\n",
+            "id: 2\n",
+            "data: {\"key\":\"api-key\",\"action\":\"deleted\"}\n\n",
+        );
+        Mock::given(method("GET"))
+            .and(path("/api/v2/namespaces/prod/events"))
+            .and(header("Accept", "text/event-stream"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("content-type", "text/event-stream")
+                    .set_body_string(first),
+            )
+            .up_to_n_times(1)
+            .mount(&mock_server)
+            .await;
+
+        // After the first stream ends the client must resume from the last ID
+        Mock::given(method("GET"))
+            .and(path("/api/v2/namespaces/prod/events"))
+            .and(header("Last-Event-ID", "2"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("content-type", "text/event-stream")
+                    .set_body_string(
+                        "id: 3\ndata: {\"key\":\"db-pass\",\"action\":\"rolled_back\",\"version\":1}\n\n",
+                    ),
+            )
+            .mount(&mock_server)
+            .await;
+
+        let client = create_test_client(&mock_server.uri());
+        let _ = client
+            .get_secret("prod", "db-pass", GetOpts::default())
+            .await
+            .unwrap();
+        let cache = client.cache.as_ref().unwrap();
+        assert!(cache.contains_key("prod/db-pass"));
+
+        let opts = SubscribeOpts {
+            reconnect_delay: Duration::from_millis(10),
+            ..Default::default()
+        };
+        let changes = client.subscribe_changes("prod", opts);
+        futures::pin_mut!(changes);
+
+        let event = changes.next().await.unwrap().unwrap();
+        assert_eq!(event.namespace, "prod");
+        assert_eq!(event.key, "db-pass");
+        assert_eq!(event.action, ChangeAction::Updated);
+        assert_eq!(event.version, Some(2));
+        assert_eq!(event.event_id.as_deref(), Some("1"));
+        assert!(!cache.contains_key("prod/db-pass"));
+
+        let event = changes.next().await.unwrap().unwrap();
+        assert_eq!(event.namespace, "prod");
+        assert_eq!(event.key, "api-key");
+        assert_eq!(event.action, ChangeAction::Deleted);
+        assert_eq!(event.version, None);
+
+        let event = changes.next().await.unwrap().unwrap();
+        assert_eq!(event.action, ChangeAction::RolledBack);
+        assert_eq!(event.event_id.as_deref(), Some("3"));
+    }
+
+    #[tokio::test]
+    async fn test_list_secrets() {
+        let mock_server = MockServer::start().await;
+
+        let response_body = serde_json::json!({
+            "namespace": "test-ns",
+            "secrets": [
+                {"key": "key1", "ver": 1, "updated_at": "2024-01-01T00:00:00Z", "kid": null},
+                {"key": "key2", "ver": 2, "updated_at": "2024-01-01T00:00:00Z", "kid": "kid123"}
+            ],
+            "total": 2,
+            "limit": 10,
+            "has_more": false,
+            "request_id": "req-list"
+        });
+
+        Mock::given(method("GET"))
+            .and(path("/api/v2/secrets/test-ns"))
+            .and(wiremock::matchers::query_param("prefix", "key"))
+            .and(wiremock::matchers::query_param("limit", "10"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(&response_body))
+            .mount(&mock_server)
+            .await;
+
+        let client = create_test_client(&mock_server.uri());
+
+        let opts = ListOpts {
+            prefix: Some("key".to_string()),
+            limit: Some(10),
+            ..Default::default()
+        };
+
+        let result = client.list_secrets("test-ns", opts).await;
+        assert!(result.is_ok());
+
+        let list_result = result.unwrap();
+        assert_eq!(list_result.namespace, "test-ns");
+        assert_eq!(list_result.secrets.len(), 2);
+        assert_eq!(list_result.total, 2);
+    }
+
+    #[tokio::test]
+    async fn test_invalidate_by_metadata() {
+        let mock_server = MockServer::start().await;
+
+        let secrets = [
+            (
+                "prod",
+                "tls-cert",
+                serde_json::json!({"depends_on": "ca-root"}),
+            ),
+            (
+                "prod",
+                "mtls-key",
+                serde_json::json!({"depends_on": ["ca-root", "hsm"]}),
+            ),
+            (
+                "prod",
+                "db-url",
+                serde_json::json!({"depends_on": "db-primary"}),
+            ),
+            ("prod", "plain", serde_json::Value::Null),
+            (
+                "staging",
+                "tls-cert",
+                serde_json::json!({"depends_on": "ca-root"}),
+            ),
+        ];
+        for (namespace, key, metadata) in &secrets {
+            Mock::given(method("GET"))
+                .and(path(format!("/api/v2/secrets/{}/{}", namespace, key)))
+                .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                    "value": "v",
+                    "version": 1,
+                    "metadata": metadata,
+                    "updated_at": "2024-01-01T00:00:00Z"
+                })))
+                .mount(&mock_server)
+                .await;
+        }
+
+        let client = create_test_client(&mock_server.uri());
+        for (namespace, key, _) in &secrets {
+            let _ = client
+                .get_secret(namespace, key, GetOpts::default())
+                .await
+                .unwrap();
+        }
+
+        let removed = client
+            .invalidate_by_metadata("prod", "depends_on", "ca-root")
+            .await;
+        assert_eq!(removed, 2);
+
+        for (namespace, key, _) in &secrets {
+            let secret = client
+                .get_secret(namespace, key, GetOpts::default())
+                .await
+                .unwrap();
+            let invalidated = *namespace == "prod" && (*key == "tls-cert" || *key == "mtls-key");
+            assert_eq!(secret.from_cache, !invalidated, "{}/{}", namespace, key);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_verify_content_digest() {
+        use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
+        use sha2::{Digest, Sha256};
+
+        let mock_server = MockServer::start().await;
+        let body = serde_json::json!({
+            "value": "s3cr3t",
+            "version": 1,
+            "updated_at": "2024-01-01T00:00:00Z"
+        })
+        .to_string();
+        let digest = format!(":{}:", BASE64.encode(Sha256::digest(body.as_bytes())));
+        let digest = format!("sha-256={}", digest);
+
+        Mock::given(method("GET"))
+            .and(path("/api/v2/secrets/test-ns/intact"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_raw(body.clone(), "application/json")
+                    .append_header("content-digest", digest.as_str()),
+            )
+            .mount(&mock_server)
+            .await;
+
+        let tampered = body.replace("s3cr3t", "evil!!");
+        Mock::given(method("GET"))
+            .and(path("/api/v2/secrets/test-ns/tampered"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_raw(tampered, "application/json")
+                    .append_header("content-digest", digest.as_str()),
+            )
+            .mount(&mock_server)
+            .await;
+
+        #[cfg(feature = "danger-insecure-http")]
+        let client = ClientBuilder::new(mock_server.uri())
+            .auth(Auth::bearer("test-token"))
+            .verify_content_digest(true)
+            .allow_insecure_http()
+            .build()
+            .unwrap();
+
+        #[cfg(not(feature = "danger-insecure-http"))]
+        let client = ClientBuilder::new(mock_server.uri().replace("http://", "https://"))
+            .auth(Auth::bearer("test-token"))
+            .verify_content_digest(true)
+            .build()
+            .unwrap();
+
+        let secret = client
+            .get_secret("test-ns", "intact", GetOpts::default())
+            .await
+            .unwrap();
+        assert_eq!(secret.value.expose_secret(), "s3cr3t");
+
+        let err = client
+            .get_secret("test-ns", "tampered", GetOpts::default())
+            .await
+            .unwrap_err();
+        assert!(matches!(err, Error::Other(ref msg) if msg == "content digest mismatch"));
+    }
+
+    #[tokio::test]
+    async fn test_whoami() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/api/v2/whoami"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(serde_json::json!({
+                        "key_id": "key-123",
+                        "name": "deploy-bot",
+                        "permissions": ["read", "write"],
+                        "namespaces": ["staging", "production"]
+                    }))
+                    .append_header("x-request-id", "req-who"),
+            )
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let client = create_test_client(&mock_server.uri());
+        let identity = client.whoami().await.unwrap();
+        assert_eq!(identity.key_id, "key-123");
+        assert_eq!(identity.name.as_deref(), Some("deploy-bot"));
+        assert_eq!(identity.permissions, vec!["read", "write"]);
+        assert_eq!(identity.request_id.as_deref(), Some("req-who"));
+        assert!(identity.has_permission("write"));
+        assert!(!identity.has_permission("admin"));
+        assert!(identity.can_access("production"));
+        assert!(!identity.can_access("billing"));
+
+        // Served from the short-lived cache
+        let again = client.whoami().await.unwrap();
+        assert_eq!(again.key_id, "key-123");
+    }
+
+    #[tokio::test]
+    async fn test_raw_request() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .and(path("/api/v2/preview/widgets"))
+            .and(header("authorization", "Bearer test-token"))
+            .and(wiremock::matchers::header_exists("x-request-id"))
+            .and(wiremock::matchers::body_json(
+                serde_json::json!({"name": "w1"}),
+            ))
+            .respond_with(ResponseTemplate::new(201).set_body_string("widget:w1"))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let client = create_test_client(&mock_server.uri());
+        let response = client
+            .raw_request(
+                Method::POST,
+                "/api/v2/preview/widgets",
+                Some(serde_json::json!({"name": "w1"})),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::CREATED);
+        assert_eq!(response.text().await.unwrap(), "widget:w1");
+
+        let err = client
+            .raw_request(Method::GET, "api/v2/preview", None)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, Error::Config(_)));
+    }
+
+    #[tokio::test]
+    async fn test_list_secrets_keys_only() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/api/v2/secrets/test-ns"))
+            .and(wiremock::matchers::query_param("fields", "key"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "namespace": "test-ns",
+                "secrets": [{"key": "key1"}, {"key": "key2"}],
+                "total": 2
+            })))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let client = create_test_client(&mock_server.uri());
+        let opts = ListOpts {
+            fields: Some(vec![ListField::Key]),
+            ..Default::default()
+        };
+        let listing = client.list_secrets("test-ns", opts).await.unwrap();
+
+        let keys: Vec<&str> = listing.secrets.iter().map(|s| s.key.as_str()).collect();
+        assert_eq!(keys, vec!["key1", "key2"]);
+        assert!(listing.secrets.iter().all(|s| s.version.is_none()));
+        assert!(listing.secrets.iter().all(|s| s.updated_at.is_none()));
+    }
+
+    #[tokio::test]
+    async fn test_list_secrets_pages_follows_cursors() {
+        use futures::StreamExt;
+
+        let mock_server = MockServer::start().await;
+        let page = |keys: &[&str], next_cursor: Option<&str>| {
+            let secrets: Vec<_> = keys
+                .iter()
+                .map(|key| {
+                    serde_json::json!({
+                        "key": key,
+                        "version": 1,
+                        "updated_at": "2024-01-01T00:00:00Z"
+                    })
+                })
+                .collect();
+            ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "namespace": "test-ns",
+                "secrets": secrets,
+                "total": 5,
+                "limit": 2,
+                "has_more": next_cursor.is_some(),
+                "next_cursor": next_cursor
+            }))
+        };
+
+        // Cursor-specific pages are mounted first so they take precedence
+        Mock::given(method("GET"))
+            .and(path("/api/v2/secrets/test-ns"))
+            .and(wiremock::matchers::query_param("cursor", "c2"))
+            .respond_with(page(&["e"], None))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/api/v2/secrets/test-ns"))
+            .and(wiremock::matchers::query_param("cursor", "c1"))
+            .respond_with(page(&["c", "d"], Some("c2")))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/api/v2/secrets/test-ns"))
+            .and(wiremock::matchers::query_param("limit", "2"))
+            .respond_with(page(&["a", "b"], Some("c1")))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let client = create_test_client(&mock_server.uri());
+        let opts = ListOpts {
+            limit: Some(2),
+            ..Default::default()
+        };
+        let pages: Vec<_> = client
+            .list_secrets_pages("test-ns", opts)
+            .collect::<Vec<_>>()
+            .await
+            .into_iter()
+            .collect::<Result<_>>()
+            .unwrap();
+
+        assert_eq!(pages.len(), 3);
+        let keys: Vec<_> = pages
+            .iter()
+            .flat_map(|page| page.secrets.iter().map(|s| s.key.as_str()))
+            .collect();
+        let unique: std::collections::HashSet<_> = keys.iter().collect();
+        assert_eq!(keys, vec!["a", "b", "c", "d", "e"]);
+        assert_eq!(unique.len(), keys.len());
+    }
+
+    #[tokio::test]
+    async fn test_list_secrets_pages_falls_back_to_offset() {
+        use futures::StreamExt;
+
+        let mock_server = MockServer::start().await;
+        let page = |keys: &[&str], has_more: bool| {
+            let secrets: Vec<_> = keys
+                .iter()
+                .map(|key| {
+                    serde_json::json!({
+                        "key": key,
+                        "version": 1,
+                        "updated_at": "2024-01-01T00:00:00Z"
+                    })
+                })
+                .collect();
+            ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "namespace": "test-ns",
+                "secrets": secrets,
+                "total": 3,
+                "limit": 2,
+                "has_more": has_more
+            }))
+        };
+
+        Mock::given(method("GET"))
+            .and(path("/api/v2/secrets/test-ns"))
+            .and(wiremock::matchers::query_param("offset", "2"))
+            .respond_with(page(&["c"], false))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/api/v2/secrets/test-ns"))
+            .respond_with(page(&["a", "b"], true))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let client = create_test_client(&mock_server.uri());
+        let opts = ListOpts {
+            limit: Some(2),
+            ..Default::default()
+        };
+        let keys: Vec<_> = client
+            .list_secrets_pages("test-ns", opts)
+            .map(|page| page.unwrap().secrets)
+            .collect::<Vec<_>>()
+            .await
+            .into_iter()
+            .flatten()
+            .map(|secret| secret.key)
+            .collect();
+        assert_eq!(keys, vec!["a", "b", "c"]);
+    }
+
+    #[tokio::test]
+    async fn test_audit_pages_falls_back_to_offset() {
+        use futures::StreamExt;
+
+        let mock_server = MockServer::start().await;
+        let entry = |id: i64| {
+            serde_json::json!({
+                "id": id,
+                "timestamp": "2024-01-01T00:00:00Z",
+                "action": "get",
+                "success": true
+            })
+        };
+
+        Mock::given(method("GET"))
+            .and(path("/api/v2/audit"))
+            .and(wiremock::matchers::query_param("offset", "2"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "logs": [entry(3)],
+                "total": 3,
+                "limit": 2,
+                "offset": 2,
+                "has_more": false,
+                "request_id": "req-2"
+            })))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/api/v2/audit"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "logs": [entry(1), entry(2)],
+                "total": 3,
+                "limit": 2,
+                "offset": 0,
+                "has_more": true,
+                "request_id": "req-1"
+            })))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let client = create_test_client(&mock_server.uri());
+        let query = AuditQuery {
+            limit: Some(2),
+            ..Default::default()
+        };
+        let ids: Vec<_> = client
+            .audit_pages(query)
+            .map(|page| page.unwrap().entries)
+            .collect::<Vec<_>>()
+            .await
+            .into_iter()
+            .flatten()
+            .map(|entry| entry.id)
+            .collect();
+        assert_eq!(ids, vec![1, 2, 3]);
+    }
+
+    #[tokio::test]
+    async fn test_audit_pages_stops_at_pagination_cap() {
+        use futures::StreamExt;
+
+        let mock_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/api/v2/audit"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "logs": [{
+                    "id": 1,
+                    "timestamp": "2024-01-01T00:00:00Z",
+                    "action": "get",
+                    "success": true
+                }],
+                "total": 1000000,
+                "limit": 1,
+                "offset": 0,
+                "has_more": true,
+                "request_id": "req-1"
+            })))
+            .expect(4)
+            .mount(&mock_server)
+            .await;
+
+        let client = create_test_client(&mock_server.uri());
+        let query = AuditQuery {
+            limit: Some(1),
+            max_pages: Some(3),
+            ..Default::default()
+        };
+        let pages: Vec<_> = client.audit_pages(query).collect().await;
+        assert_eq!(pages.len(), 4);
+        assert!(pages[..3].iter().all(|page| page.is_ok()));
+        let err = pages[3].as_ref().unwrap_err();
+        assert!(err.to_string().contains("pagination limit exceeded"));
+    }
+
+    #[tokio::test]
+    async fn test_list_api_keys_pages() {
+        use futures::StreamExt;
+        use wiremock::matchers::query_param;
+
+        let key = |id: &str| {
+            serde_json::json!({
+                "id": id,
+                "name": format!("key-{}", id),
+                "created_at": "2024-01-01T00:00:00Z",
+                "active": true,
+                "namespaces": ["prod"],
+                "permissions": ["read"]
+            })
+        };
+
+        let mock_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/api/v2/api-keys"))
+            .and(query_param("active", "true"))
+            .and(query_param("namespace", "prod"))
+            .and(query_param("offset", "2"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "keys": [key("c")],
+                "total": 3,
+                "has_more": false
+            })))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/api/v2/api-keys"))
+            .and(query_param("active", "true"))
+            .and(query_param("namespace", "prod"))
+            .and(query_param("limit", "2"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "keys": [key("a"), key("b")],
+                "total": 3,
+                "has_more": true
+            })))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let client = create_test_client(&mock_server.uri());
+        let opts = ListApiKeysOpts {
+            active_only: true,
+            namespace: Some("prod".to_string()),
+            limit: Some(2),
+            ..Default::default()
+        };
+        let pages: Vec<_> = client.list_api_keys_pages(opts).collect().await;
+        assert_eq!(pages.len(), 2);
+        let ids: Vec<_> = pages
+            .iter()
+            .flat_map(|page| page.as_ref().unwrap().keys.iter().map(|k| k.id.as_str()))
+            .collect();
+        assert_eq!(ids, vec!["a", "b", "c"]);
+        assert!(pages[0].as_ref().unwrap().has_more);
+        assert!(!pages[1].as_ref().unwrap().has_more);
+    }
+
+    #[tokio::test]
+    async fn test_list_versions() {
+        let mock_server = MockServer::start().await;
+
+        let response_body = serde_json::json!({
+            "namespace": "test-ns",
+            "key": "versioned-key",
+            "versions": [
+                {
+                    "version": 3,
+                    "created_at": "2024-01-03T00:00:00Z",
+                    "created_by": "user1",
+                    "is_current": true
+                },
+                {
+                    "version": 2,
+                    "created_at": "2024-01-02T00:00:00Z",
+                    "created_by": "user1",
+                    "is_current": false
+                },
+                {
+                    "version": 1,
+                    "created_at": "2024-01-01T00:00:00Z",
+                    "created_by": "user1",
+                    "is_current": false
+                }
+            ],
+            "total": 3,
+            "request_id": "req-versions"
+        });
+
+        Mock::given(method("GET"))
+            .and(path("/api/v2/secrets/test-ns/versioned-key/versions"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(&response_body))
+            .mount(&mock_server)
+            .await;
+
+        let client = create_test_client(&mock_server.uri());
+
+        let result = client.list_versions("test-ns", "versioned-key").await;
+        assert!(result.is_ok());
+
+        let version_list = result.unwrap();
+        assert_eq!(version_list.namespace, "test-ns");
+        assert_eq!(version_list.key, "versioned-key");
+        assert_eq!(version_list.versions.len(), 3);
+        assert_eq!(version_list.total, 3);
+        assert!(version_list.versions[0].is_current);
+    }
+
+    #[tokio::test]
+    async fn test_get_version() {
+        let mock_server = MockServer::start().await;
+
+        let response_body = serde_json::json!({
+            "value": "version-2-value",
+            "version": 2,
+            "expires_at": null,
+            "metadata": {"note": "version 2"},
+            "updated_at": "2024-01-02T00:00:00Z"
+        });
+
+        Mock::given(method("GET"))
+            .and(path("/api/v2/secrets/test-ns/versioned-key/versions/2"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(&response_body)
+                    .insert_header("etag", "\"etag-v2\""),
+            )
+            .mount(&mock_server)
+            .await;
+
+        let client = create_test_client(&mock_server.uri());
+
+        let result = client.get_version("test-ns", "versioned-key", 2).await;
+        assert!(result.is_ok());
+
+        let secret = result.unwrap();
+        assert_eq!(secret.namespace, "test-ns");
+        assert_eq!(secret.key, "versioned-key");
+        assert_eq!(secret.version, 2);
+        assert_eq!(secret.value.expose_secret(), "version-2-value");
+    }
+
+    #[tokio::test]
+    async fn test_get_versions_partial() {
+        let mock_server = MockServer::start().await;
+
+        for version in [1, 3] {
+            Mock::given(method("GET"))
+                .and(path(format!(
+                    "/api/v2/secrets/test-ns/versioned-key/versions/{}",
+                    version
+                )))
+                .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                    "value": format!("value-v{}", version),
+                    "version": version,
+                    "expires_at": null,
+                    "metadata": null,
+                    "updated_at": "2024-01-01T00:00:00Z"
+                })))
+                .mount(&mock_server)
+                .await;
+        }
+
+        Mock::given(method("GET"))
+            .and(path("/api/v2/secrets/test-ns/versioned-key/versions/2"))
+            .respond_with(ResponseTemplate::new(404).set_body_json(serde_json::json!({
+                "error": "not_found",
+                "message": "Version not found",
+                "timestamp": "2024-01-01T00:00:00Z",
+                "status": 404
+            })))
+            .mount(&mock_server)
+            .await;
+
+        let client = create_test_client(&mock_server.uri());
+
+        let results = client
+            .get_versions("test-ns", "versioned-key", &[3, 2, 1], 2)
+            .await;
+
+        let versions: Vec<i32> = results.iter().map(|(version, _)| *version).collect();
+        assert_eq!(versions, vec![3, 2, 1]);
+
+        let secret = results[0].1.as_ref().unwrap();
+        assert_eq!(secret.version, 3);
+        assert_eq!(secret.value.expose_secret(), "value-v3");
+
+        let err = results[1].1.as_ref().unwrap_err();
+        assert_eq!(err.status_code(), Some(404));
+
+        let secret = results[2].1.as_ref().unwrap();
+        assert_eq!(secret.version, 1);
+        assert_eq!(secret.value.expose_secret(), "value-v1");
+    }
+
+    #[tokio::test]
+    async fn test_init_namespaces_partial_failure() {
+        let mock_server = MockServer::start().await;
+
+        for namespace in ["team-a", "team-c"] {
+            Mock::given(method("POST"))
+                .and(path(format!("/api/v2/namespaces/{}/init", namespace)))
+                .and(wiremock::matchers::header_exists("x-idempotency-key"))
+                .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                    "message": "Namespace initialized",
+                    "namespace": namespace,
+                    "secrets_created": 2,
+                    "request_id": "req-1"
+                })))
+                .expect(1)
+                .mount(&mock_server)
+                .await;
+        }
+
+        Mock::given(method("POST"))
+            .and(path("/api/v2/namespaces/team-b/init"))
+            .respond_with(ResponseTemplate::new(400).set_body_json(serde_json::json!({
+                "error": "bad_request",
+                "message": "Unknown template",
+                "timestamp": "2024-01-01T00:00:00Z",
+                "status": 400
+            })))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let client = create_test_client(&mock_server.uri());
+        let template = NamespaceTemplate {
+            template: "web-app".to_string(),
+            params: serde_json::json!({ "environment": "staging" }),
+        };
+        let specs = ["team-a", "team-b", "team-c"]
+            .iter()
+            .map(|namespace| (namespace.to_string(), template.clone()))
+            .collect();
+
+        let results = client.init_namespaces(specs, 2).await;
+
+        let namespaces: Vec<&str> = results.iter().map(|(ns, _)| ns.as_str()).collect();
+        assert_eq!(namespaces, vec!["team-a", "team-b", "team-c"]);
+        assert_eq!(results[0].1.as_ref().unwrap().secrets_created, 2);
+        assert_eq!(results[1].1.as_ref().unwrap_err().status_code(), Some(400));
+        assert_eq!(results[2].1.as_ref().unwrap().namespace, "team-c");
+    }
+
+    #[tokio::test]
+    async fn test_batch_get_detailed() {
+        let mock_server = MockServer::start().await;
+
+        for (key, version, owner) in [("db-url", 3, "data"), ("api-key", 7, "platform")] {
+            Mock::given(method("GET"))
+                .and(path(format!("/api/v2/secrets/prod/{}", key)))
+                .respond_with(
+                    ResponseTemplate::new(200)
+                        .set_body_json(serde_json::json!({
+                            "value": format!("{}-value", key),
+                            "version": version,
+                            "metadata": {"owner": owner},
+                            "updated_at": "2024-01-01T00:00:00Z"
+                        }))
+                        .append_header("ETag", format!("\"{}-v{}\"", key, version).as_str()),
+                )
+                .expect(1)
+                .mount(&mock_server)
+                .await;
+        }
+
+        Mock::given(method("GET"))
+            .and(path("/api/v2/secrets/prod/missing"))
+            .respond_with(ResponseTemplate::new(404).set_body_json(serde_json::json!({
+                "error": "not_found",
+                "message": "Secret not found",
+                "timestamp": "2024-01-01T00:00:00Z",
+                "status": 404
+            })))
+            .mount(&mock_server)
+            .await;
+
+        let client = create_test_client(&mock_server.uri());
+        let keys = BatchKeys::Keys(vec![
+            "db-url".to_string(),
+            "api-key".to_string(),
+            "missing".to_string(),
+        ]);
+        let secrets = client.batch_get_detailed("prod", keys).await.unwrap();
+
+        assert_eq!(secrets.len(), 2);
+        let db_url = &secrets["db-url"];
+        assert_eq!(db_url.version, 3);
+        assert_eq!(db_url.metadata["owner"], "data");
+        assert_eq!(db_url.etag.as_deref(), Some("\"db-url-v3\""));
+        assert_eq!(db_url.value.expose_secret(), "db-url-value");
+        let api_key = &secrets["api-key"];
+        assert_eq!(api_key.version, 7);
+        assert_eq!(api_key.metadata["owner"], "platform");
+    }
+
+    #[tokio::test]
+    async fn test_list_with_values() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/api/v2/secrets/test-ns"))
+            .and(wiremock::matchers::query_param("prefix", "db-"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "namespace": "test-ns",
+                "secrets": [
+                    {"key": "db-host", "version": 1, "updated_at": "2024-01-01T00:00:00Z"},
+                    {"key": "db-pass", "version": 2, "updated_at": "2024-01-01T00:00:00Z"},
+                    {"key": "db-gone", "version": 1, "updated_at": "2024-01-01T00:00:00Z"}
+                ],
+                "total": 3
+            })))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        for (key, value, version) in [("db-host", "db.internal", 1), ("db-pass", "hunter2", 2)] {
+            Mock::given(method("GET"))
+                .and(path(format!("/api/v2/secrets/test-ns/{}", key)))
+                .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                    "value": value,
+                    "version": version,
+                    "expires_at": null,
+                    "metadata": null,
+                    "updated_at": "2024-01-01T00:00:00Z"
+                })))
+                .expect(1)
+                .mount(&mock_server)
+                .await;
+        }
+
+        Mock::given(method("GET"))
+            .and(path("/api/v2/secrets/test-ns/db-gone"))
+            .respond_with(ResponseTemplate::new(404).set_body_json(serde_json::json!({
+                "error": "not_found",
+                "message": "Secret not found",
+                "timestamp": "2024-01-01T00:00:00Z",
+                "status": 404
+            })))
+            .mount(&mock_server)
+            .await;
+
+        let client = create_test_client(&mock_server.uri());
+
+        let secrets = client
+            .list_with_values(
+                "test-ns",
+                ListWithValuesOpts {
+                    prefix: Some("db-".to_string()),
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap();
+
+        let values: Vec<(&str, &str)> = secrets
+            .iter()
+            .map(|s| (s.key.as_str(), s.value.expose_secret().as_str()))
+            .collect();
+        assert_eq!(
+            values,
+            vec![("db-host", "db.internal"), ("db-pass", "hunter2")]
+        );
+
+        // Values were cached
+        let cached = client
+            .get_secret("test-ns", "db-pass", GetOpts::default())
+            .await
+            .unwrap();
+        assert_eq!(cached.version, 2);
+    }
+
+    #[tokio::test]
+    async fn test_search_server_side() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/api/v2/secrets/test-ns/search"))
+            .and(wiremock::matchers::query_param(
+                "metadata.owner",
+                "backend-team",
+            ))
+            .and(wiremock::matchers::query_param("prefix", "db"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "secrets": [
+                    {"key": "db-pass", "version": 2, "updated_at": "2024-01-01T00:00:00Z"}
+                ]
+            })))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let client = create_test_client(&mock_server.uri());
+
+        let results = client
+            .search(
+                "test-ns",
+                MetadataFilter::new().eq("owner", "backend-team"),
+                SearchOpts {
+                    prefix: Some("db".to_string()),
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].key, "db-pass");
+        assert_eq!(results[0].version, Some(2));
+    }
+
+    #[tokio::test]
+    async fn test_search_client_side_fallback() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/api/v2/secrets/test-ns/search"))
+            .respond_with(ResponseTemplate::new(404).set_body_json(serde_json::json!({
+                "error": "not_found",
+                "message": "Not found",
+                "timestamp": "2024-01-01T00:00:00Z",
+                "status": 404
+            })))
+            .mount(&mock_server)
+            .await;
+
+        // The listing spans two pages; matches on the second must be found
+        Mock::given(method("GET"))
+            .and(path("/api/v2/secrets/test-ns"))
+            .and(wiremock::matchers::query_param("cursor", "c1"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "namespace": "test-ns",
+                "secrets": [
+                    {"key": "db-pass", "version": 3, "updated_at": "2024-01-01T00:00:00Z"}
+                ],
+                "total": 3,
+                "has_more": false
+            })))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/api/v2/secrets/test-ns"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "namespace": "test-ns",
+                "secrets": [
+                    {"key": "api-key", "version": 1, "updated_at": "2024-01-01T00:00:00Z"},
+                    {"key": "web-token", "version": 1, "updated_at": "2024-01-01T00:00:00Z"}
+                ],
+                "total": 3,
+                "has_more": true,
+                "next_cursor": "c1"
+            })))
+            .mount(&mock_server)
+            .await;
+
+        for (key, owner) in [
+            ("api-key", "backend-team"),
+            ("db-pass", "backend-team"),
+            ("web-token", "frontend-team"),
+        ] {
+            Mock::given(method("GET"))
+                .and(path(format!("/api/v2/secrets/test-ns/{}", key)))
+                .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                    "value": "value",
+                    "version": 1,
+                    "metadata": {"owner": owner},
+                    "updated_at": "2024-01-01T00:00:00Z"
+                })))
+                .mount(&mock_server)
+                .await;
+        }
+
+        let client = create_test_client(&mock_server.uri());
+        let filter = MetadataFilter::new().eq("owner", "backend-team");
+
+        let results = client
+            .search("test-ns", filter.clone(), SearchOpts::default())
+            .await
+            .unwrap();
+        let keys: Vec<&str> = results.iter().map(|info| info.key.as_str()).collect();
+        assert_eq!(keys, vec!["api-key", "db-pass"]);
+
+        let results = client
+            .search(
+                "test-ns",
+                filter,
+                SearchOpts {
+                    limit: Some(1),
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].key, "api-key");
+    }
+
+    /// Start a server that accepts connections and drops them without answering
+    #[cfg(feature = "danger-insecure-http")]
+    fn spawn_dropping_server() -> (String, std::sync::Arc<std::sync::atomic::AtomicUsize>) {
+        use std::io::Read;
+
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let accepted = std::sync::Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let counter = accepted.clone();
+        let _ = std::thread::spawn(move || {
+            for mut stream in listener.incoming().flatten() {
+                let _ = counter.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+                let mut buf = [0u8; 1024];
+                let _ = stream.read(&mut buf);
+            }
+        });
+        (url, accepted)
+    }
+
+    #[cfg(feature = "danger-insecure-http")]
+    #[tokio::test]
+    async fn test_post_without_idempotency_key_not_retried_on_connection_drop() {
+        let (url, accepted) = spawn_dropping_server();
+        let client = ClientBuilder::new(&url)
+            .auth(Auth::bearer("test-token"))
+            .retries(2)
+            .allow_insecure_http()
+            .build()
+            .unwrap();
+
+        let result = client
+            .batch_operate("test-ns", vec![BatchOp::delete("key")], false, None)
+            .await;
+        assert!(result.is_err());
+        assert_eq!(accepted.load(std::sync::atomic::Ordering::SeqCst), 1);
+
+        // Opting back in retries the POST
+        let (url, accepted) = spawn_dropping_server();
+        let client = ClientBuilder::new(&url)
+            .auth(Auth::bearer("test-token"))
+            .retries(2)
+            .retry_unsafe_without_idempotency(true)
+            .allow_insecure_http()
+            .build()
+            .unwrap();
+
+        let result = client
+            .batch_operate("test-ns", vec![BatchOp::delete("key")], false, None)
+            .await;
+        assert!(result.is_err());
+        assert_eq!(accepted.load(std::sync::atomic::Ordering::SeqCst), 3);
+    }
+
+    #[cfg(feature = "danger-insecure-http")]
+    #[tokio::test]
+    async fn test_get_retried_on_connection_drop() {
+        let (url, accepted) = spawn_dropping_server();
+        let client = ClientBuilder::new(&url)
+            .auth(Auth::bearer("test-token"))
+            .retries(2)
+            .allow_insecure_http()
+            .build()
+            .unwrap();
+
+        let result = client
+            .get_secret("test-ns", "test-key", GetOpts::default())
+            .await;
+        assert!(result.is_err());
+        assert_eq!(accepted.load(std::sync::atomic::Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_auth_fallback_chain() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/api/v2/secrets/test-ns/test-key"))
+            .and(header("Authorization", "Bearer revoked-token"))
+            .respond_with(ResponseTemplate::new(401).set_body_json(serde_json::json!({
+                "error": "auth",
+                "message": "Invalid token",
+                "timestamp": "2024-01-01T00:00:00Z",
+                "status": 401
+            })))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        Mock::given(method("GET"))
+            .and(path("/api/v2/secrets/test-ns/test-key"))
+            .and(header("X-API-Key", "valid-key"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "value": "secret-value",
+                "version": 1,
+                "expires_at": null,
+                "metadata": null,
+                "updated_at": "2024-01-01T00:00:00Z"
+            })))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let auth = Auth::fallback_chain(vec![
+            Auth::bearer("revoked-token"),
+            Auth::api_key("valid-key"),
+        ]);
+
+        #[cfg(feature = "danger-insecure-http")]
+        let client = ClientBuilder::new(mock_server.uri())
+            .auth(auth)
+            .allow_insecure_http()
+            .build()
+            .unwrap();
+
+        #[cfg(not(feature = "danger-insecure-http"))]
+        let client = ClientBuilder::new(mock_server.uri().replace("http://", "https://"))
+            .auth(auth)
+            .build()
+            .unwrap();
+
+        let secret = client
+            .get_secret("test-ns", "test-key", GetOpts::default())
+            .await
+            .unwrap();
+        assert_eq!(secret.value.expose_secret(), "secret-value");
+    }
+
+    #[tokio::test]
+    async fn test_create_secret_only_if_absent() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("PUT"))
+            .and(path("/api/v2/secrets/test-ns/defaults"))
+            .and(header("if-none-match", "*"))
+            .respond_with(ResponseTemplate::new(201).set_body_json(serde_json::json!({
+                "message": "Secret created",
+                "namespace": "test-ns",
+                "key": "defaults",
+                "created_at": "2024-01-01T00:00:00Z",
+                "request_id": "req-1"
+            })))
+            .up_to_n_times(1)
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        Mock::given(method("PUT"))
+            .and(path("/api/v2/secrets/test-ns/defaults"))
+            .and(header("if-none-match", "*"))
+            .respond_with(
+                ResponseTemplate::new(412)
+                    .set_body_json(serde_json::json!({
+                        "error": "precondition_failed",
+                        "message": "Secret exists",
+                        "timestamp": "2024-01-01T00:00:00Z",
+                        "status": 412
+                    }))
+                    .append_header("x-request-id", "req-2"),
+            )
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let client = create_test_client(&mock_server.uri());
+
+        let created = client
+            .create_secret("test-ns", "defaults", "{}", PutOpts::default())
+            .await
+            .unwrap();
+        assert_eq!(created.key, "defaults");
+
+        let err = client
+            .create_secret("test-ns", "defaults", "{}", PutOpts::default())
+            .await
+            .unwrap_err();
+        match &err {
+            Error::AlreadyExists { namespace, key, .. } => {
+                assert_eq!(namespace, "test-ns");
+                assert_eq!(key, "defaults");
+            }
+            e => panic!("Expected Error::AlreadyExists, got {:?}", e),
+        }
+        assert_eq!(err.kind(), crate::ErrorKind::AlreadyExists);
+        assert_eq!(err.status_code(), Some(412));
+        assert_eq!(err.request_id(), Some("req-2"));
+    }
+
+    #[tokio::test]
+    async fn test_put_secret_expected_version() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("PUT"))
+            .and(path("/api/v2/secrets/test-ns/counter"))
+            .and(header("if-match", "\"v3\""))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "message": "Secret updated",
+                "namespace": "test-ns",
+                "key": "counter",
+                "created_at": "2024-01-01T00:00:00Z",
+                "request_id": "req-1",
+                "version": 4
+            })))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        Mock::given(method("PUT"))
+            .and(path("/api/v2/secrets/test-ns/counter"))
+            .and(header("if-match", "\"v2\""))
+            .respond_with(
+                ResponseTemplate::new(409)
+                    .set_body_json(serde_json::json!({
+                        "error": "conflict",
+                        "message": "Version mismatch",
+                        "timestamp": "2024-01-01T00:00:00Z",
+                        "status": 409
+                    }))
+                    .append_header("x-request-id", "req-2"),
+            )
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let client = create_test_client(&mock_server.uri());
+        let opts = |version| PutOpts {
+            expected_version: Some(version),
+            idempotency_key: Some("idem-1".to_string()),
+            ..Default::default()
+        };
+
+        let result = client
+            .put_secret("test-ns", "counter", "4", opts(3))
+            .await
+            .unwrap();
+        assert_eq!(result.version, Some(4));
+
+        let err = client
+            .put_secret("test-ns", "counter", "4", opts(2))
+            .await
+            .unwrap_err();
+        match &err {
+            Error::VersionConflict {
+                key,
+                expected_version,
+                ..
+            } => {
+                assert_eq!(key, "counter");
+                assert_eq!(*expected_version, 2);
+            }
+            e => panic!("Expected Error::VersionConflict, got {:?}", e),
+        }
+        assert_eq!(err.kind(), crate::ErrorKind::VersionConflict);
+        assert_eq!(err.status_code(), Some(409));
+        assert_eq!(err.request_id(), Some("req-2"));
+    }
+
+    #[tokio::test]
+    async fn test_delete_secret_if_match() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("DELETE"))
+            .and(path("/api/v2/secrets/test-ns/test-key"))
+            .and(header("if-match", "\"etag-v2\""))
+            .respond_with(ResponseTemplate::new(204))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        Mock::given(method("DELETE"))
+            .and(path("/api/v2/secrets/test-ns/test-key"))
+            .and(header("if-match", "\"etag-v1\""))
+            .respond_with(ResponseTemplate::new(412).set_body_json(serde_json::json!({
+                "error": "precondition_failed",
+                "message": "ETag mismatch",
+                "timestamp": "2024-01-01T00:00:00Z",
+                "status": 412
+            })))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let client = create_test_client(&mock_server.uri());
+
+        let err = client
+            .delete_secret_opts(
+                "test-ns",
+                "test-key",
+                DeleteOpts {
+                    if_match: Some("\"etag-v1\"".to_string()),
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap_err();
+        assert_eq!(err.status_code(), Some(412));
+        assert_eq!(err.kind(), crate::ErrorKind::PreconditionFailed);
+
+        let result = client
+            .delete_secret_opts(
+                "test-ns",
+                "test-key",
+                DeleteOpts {
+                    if_match: Some("\"etag-v2\"".to_string()),
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap();
+        assert!(result.deleted);
+    }
+
+    #[tokio::test]
+    async fn test_soft_delete_and_restore() {
+        use wiremock::matchers::query_param;
+
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("DELETE"))
+            .and(path("/api/v2/secrets/test-ns/test-key"))
+            .and(query_param("soft", "true"))
+            .respond_with(ResponseTemplate::new(204))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        Mock::given(method("POST"))
+            .and(path("/api/v2/secrets/test-ns/test-key/restore"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "value": "restored-value",
+                "version": 4,
+                "updated_at": "2024-01-01T00:00:00Z"
+            })))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        Mock::given(method("POST"))
+            .and(path("/api/v2/secrets/test-ns/live-key/restore"))
+            .respond_with(
+                ResponseTemplate::new(409)
+                    .insert_header("x-request-id", "req-409")
+                    .set_body_json(serde_json::json!({
+                        "error": "conflict",
+                        "message": "Secret is not deleted",
+                        "timestamp": "2024-01-01T00:00:00Z",
+                        "status": 409
+                    })),
+            )
+            .mount(&mock_server)
+            .await;
+
+        Mock::given(method("POST"))
+            .and(path("/api/v2/secrets/test-ns/purged-key/restore"))
+            .respond_with(ResponseTemplate::new(410).set_body_json(serde_json::json!({
+                "error": "gone",
+                "message": "Retention window expired",
+                "timestamp": "2024-01-01T00:00:00Z",
+                "status": 410
+            })))
+            .mount(&mock_server)
+            .await;
+
+        let client = create_test_client(&mock_server.uri());
+        let opts = DeleteOpts {
+            soft: true,
+            ..Default::default()
+        };
+        assert!(
+            client
+                .delete_secret_opts("test-ns", "test-key", opts)
+                .await
+                .unwrap()
+                .deleted
+        );
+
+        let restored = client.restore_secret("test-ns", "test-key").await.unwrap();
+        assert_eq!(restored.value.expose_secret(), "restored-value");
+        assert_eq!(restored.version, 4);
+        assert!(!restored.from_cache);
+
+        let err = client
+            .restore_secret("test-ns", "live-key")
+            .await
+            .unwrap_err();
+        match &err {
+            Error::NotSoftDeleted { namespace, key, .. } => {
+                assert_eq!(namespace, "test-ns");
+                assert_eq!(key, "live-key");
+            }
+            e => panic!("Expected Error::NotSoftDeleted, got {:?}", e),
+        }
+        assert_eq!(err.kind(), crate::ErrorKind::NotSoftDeleted);
+        assert_eq!(err.request_id(), Some("req-409"));
+
+        let err = client
+            .restore_secret("test-ns", "purged-key")
+            .await
+            .unwrap_err();
+        assert_eq!(err.kind(), crate::ErrorKind::RetentionExpired);
+        assert_eq!(err.status_code(), Some(410));
+    }
+
+    #[tokio::test]
+    async fn test_cursor_pagination_gated_on_server_version() {
+        let opts = ListOpts {
+            cursor: Some("c1".to_string()),
+            ..Default::default()
+        };
+
+        for (api_version, supported) in [("v2", false), ("v2.1", true)] {
+            let mock_server = MockServer::start().await;
+
+            Mock::given(method("GET"))
+                .and(path("/api/v2"))
+                .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                    "service": "secret-store",
+                    "version": "2.3.1",
+                    "api_version": api_version,
+                    "features": [],
+                    "build": {
+                        "commit": "abc123",
+                        "timestamp": "2024-01-01T00:00:00Z",
+                        "rust_version": "1.75.0"
+                    },
+                    "endpoints": {
+                        "base_url": "/api/v2",
+                        "health_url": "/api/v2/livez",
+                        "metrics_url": "/metrics"
+                    }
+                })))
+                .expect(1)
+                .mount(&mock_server)
+                .await;
+
+            Mock::given(method("GET"))
+                .and(path("/api/v2/secrets/app"))
+                .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                    "namespace": "app",
+                    "secrets": [],
+                    "total": 0
+                })))
+                .expect(if supported { 2 } else { 1 })
+                .mount(&mock_server)
+                .await;
+
+            let client = create_test_client(&mock_server.uri());
+            assert_eq!(client.server_version(), None);
+
+            // Plain listings are never gated
+            let _ = client
+                .list_secrets("app", ListOpts::default())
+                .await
+                .unwrap();
+
+            let result = client.list_secrets("app", opts.clone()).await;
+            assert_eq!(client.server_version().as_deref(), Some(api_version));
+            if supported {
+                assert!(result.is_ok());
+            } else {
+                let err = result.unwrap_err().to_string();
+                assert!(err.contains("cursor pagination requires server >= v2.1"));
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_ping_reports_latency_and_cached_version() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/api/v2/livez"))
+            .respond_with(ResponseTemplate::new(200).set_delay(Duration::from_millis(20)))
+            .mount(&mock_server)
+            .await;
+
+        Mock::given(method("GET"))
+            .and(path("/api/v2"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "service": "secret-store",
+                "version": "2.3.1",
+                "api_version": "v2",
+                "features": [],
+                "build": {
+                    "commit": "abc123",
+                    "timestamp": "2024-01-01T00:00:00Z",
+                    "rust_version": "1.75.0"
+                },
+                "endpoints": {
+                    "base_url": "/api/v2",
+                    "health_url": "/api/v2/livez",
+                    "metrics_url": "/metrics"
+                }
+            })))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let client = create_test_client(&mock_server.uri());
+
+        let ping = client.ping().await.unwrap();
+        assert!(ping.reachable);
+        assert!(ping.latency >= Duration::from_millis(20));
+        assert_eq!(ping.server_version, None);
+
+        let _ = client.discovery().await.unwrap();
+
+        let ping = client.ping().await.unwrap();
+        assert!(ping.reachable);
+        assert_eq!(ping.server_version.as_deref(), Some("2.3.1"));
+    }
+
+    #[tokio::test]
+    async fn test_import_env_from_reader_chunks_batches() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .and(path("/api/v2/secrets/seed/batch"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "namespace": "seed",
+                "results": {
+                    "succeeded": [{"key": "K", "action": "put", "success": true}],
+                    "failed": [],
+                    "total": 1
+                },
+                "success_rate": 1.0
+            })))
+            .expect(3)
+            .mount(&mock_server)
+            .await;
+
+        let mut source = String::from("# generated seed file\n\n");
+        for i in 0..250 {
+            source.push_str(&format!("KEY_{}=value-{}\n", i, i));
+        }
+
+        let client = create_test_client(&mock_server.uri());
+        let reports = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+        let recorder = reports.clone();
+        let opts = ImportOpts {
+            chunk_size: 100,
+            on_progress: Some(std::sync::Arc::new(move |progress| {
+                recorder.lock().unwrap().push(progress);
+            })),
+            ..Default::default()
+        };
+
+        let result = client
+            .import_env_from_reader(
+                "seed",
+                tokio::io::BufReader::new(source.as_bytes()),
+                ImportFormat::Dotenv,
+                opts,
+            )
+            .await
+            .unwrap();
+        assert_eq!(result.batches, 3);
+        assert!(result.failed.is_empty());
+
+        let sizes: Vec<_> = mock_server
+            .received_requests()
+            .await
+            .unwrap()
+            .iter()
+            .map(|request| {
+                let body: serde_json::Value = serde_json::from_slice(&request.body).unwrap();
+                body["operations"].as_array().unwrap().len()
+            })
+            .collect();
+        assert_eq!(sizes, vec![100, 100, 50]);
+
+        let reports = reports.lock().unwrap();
+        assert_eq!(reports.len(), 3);
+        assert_eq!(reports[2].lines_read, 252);
+        assert_eq!(reports[2].batches, 3);
+    }
+
+    #[tokio::test]
+    async fn test_load_into_env() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/api/v2/env/app"))
+            .and(wiremock::matchers::query_param("format", "json"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "namespace": "app",
+                "environment": {
+                    "db_url": "postgres://db/app",
+                    "existing": "from-store",
+                    "bad=name": "skipped"
+                },
+                "etag": "\"env-v1\"",
+                "total": 3,
+                "request_id": "req-env"
+            })))
+            .mount(&mock_server)
+            .await;
+
+        std::env::set_var("SSDK_LOAD_TEST_EXISTING", "local");
+
+        let client = create_test_client(&mock_server.uri());
+        let count = client
+            .load_into_env(
+                "app",
+                LoadEnvOpts {
+                    prefix: Some("SSDK_LOAD_TEST_".to_string()),
+                    uppercase: true,
+                    overwrite: false,
+                },
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(count, 1);
+        assert_eq!(
+            std::env::var("SSDK_LOAD_TEST_DB_URL").unwrap(),
+            "postgres://db/app"
+        );
+        assert_eq!(std::env::var("SSDK_LOAD_TEST_EXISTING").unwrap(), "local");
+
+        let count = client
+            .load_into_env(
+                "app",
+                LoadEnvOpts {
+                    prefix: Some("SSDK_LOAD_TEST_".to_string()),
+                    uppercase: true,
+                    overwrite: true,
+                },
+            )
+            .await
+            .unwrap();
+        assert_eq!(count, 2);
+        assert_eq!(
+            std::env::var("SSDK_LOAD_TEST_EXISTING").unwrap(),
+            "from-store"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_export_custom_format() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/api/v2/env/app"))
+            .and(wiremock::matchers::query_param("format", "ini"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("[app]\nKEY=value\n"))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        Mock::given(method("GET"))
+            .and(path("/api/v2/secrets/app/batch"))
+            .and(wiremock::matchers::query_param("format", "ini"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("KEY=value\n"))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let client = create_test_client(&mock_server.uri());
+        match client
+            .export_env_format("app", ExportFormat::Custom("ini"))
+            .await
+            .unwrap()
+        {
+            EnvExport::Text(text) => assert_eq!(text, "[app]\nKEY=value\n"),
+            other => panic!("Expected text export, got {:?}", other),
+        }
+
+        match client
+            .batch_get("app", BatchKeys::All, ExportFormat::Custom("ini"))
+            .await
+            .unwrap()
+        {
+            BatchGetResult::Text(text) => assert_eq!(text, "KEY=value\n"),
+            other => panic!("Expected text result, got {:?}", other),
+        }
+
+        // Custom formats cannot be rendered client-side
+        let err = client
+            .export_env(
+                "app",
+                ExportEnvOpts {
+                    format: ExportFormat::Custom("ini"),
+                    interpolate: true,
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap_err();
+        assert!(matches!(err, Error::Config(_)));
+    }
+
+    #[tokio::test]
+    async fn test_export_env_format() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/api/v2/env/app"))
+            .and(wiremock::matchers::query_param("format", "shell"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("export KEY='value'\n"))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let client = create_test_client(&mock_server.uri());
+        match client
+            .export_env_format("app", ExportFormat::Shell)
+            .await
+            .unwrap()
+        {
+            EnvExport::Text(text) => assert_eq!(text, "export KEY='value'\n"),
+            other => panic!("Expected text export, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_export_env_use_cache() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/api/v2/env/app"))
+            .and(wiremock::matchers::query_param("format", "dotenv"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_string("KEY=value\n")
+                    .append_header("ETag", "\"env-v1\""),
+            )
+            .expect(2)
+            .mount(&mock_server)
+            .await;
+
+        Mock::given(method("PUT"))
+            .and(path("/api/v2/secrets/app/KEY"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "message": "Secret updated",
+                "namespace": "app",
+                "key": "KEY",
+                "created_at": "2024-01-01T00:00:00Z",
+                "request_id": "req-1"
+            })))
+            .mount(&mock_server)
+            .await;
+
+        let client = create_test_client(&mock_server.uri());
+        let opts = ExportEnvOpts {
+            format: ExportFormat::Dotenv,
+            use_cache: true,
+            ..Default::default()
+        };
+
+        // The second export is served from the cache
+        for _ in 0..2 {
+            match client.export_env("app", opts.clone()).await.unwrap() {
+                EnvExport::Text(text) => assert_eq!(text, "KEY=value\n"),
+                other => panic!("Expected text export, got {:?}", other),
+            }
+        }
+        // Export lookups stay out of the secret cache stats
+        assert_eq!(client.cache_stats().hits(), 0);
+        assert_eq!(client.cache_stats().misses(), 0);
+
+        // Writes to the namespace invalidate the cached export
+        let _ = client
+            .put_secret("app", "KEY", "new", PutOpts::default())
+            .await
+            .unwrap();
+        let _ = client.export_env("app", opts).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_export_env_not_modified() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/api/v2/env/app"))
+            .and(header("If-None-Match", "\"env-v1\""))
+            .respond_with(ResponseTemplate::new(304).insert_header("x-request-id", "req-304"))
+            .mount(&mock_server)
+            .await;
+
+        Mock::given(method("GET"))
+            .and(path("/api/v2/env/app"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_string("KEY=value\n")
+                    .append_header("ETag", "\"env-v1\""),
+            )
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let client = create_test_client(&mock_server.uri());
+        let opts = ExportEnvOpts {
+            format: ExportFormat::Dotenv,
+            if_none_match: Some("\"env-v1\"".to_string()),
+            ..Default::default()
+        };
+        match client.export_env("app", opts.clone()).await.unwrap() {
+            EnvExport::NotModified { etag, request_id } => {
+                assert_eq!(etag.as_deref(), Some("\"env-v1\""));
+                assert_eq!(request_id.as_deref(), Some("req-304"));
+            }
+            other => panic!("Expected NotModified, got {:?}", other),
+        }
+
+        // With a cached copy under the same ETag, the body is served instead
+        let cached_opts = ExportEnvOpts {
+            use_cache: true,
+            resolve_not_modified: true,
+            if_none_match: None,
+            ..opts.clone()
+        };
+        let _ = client.export_env("app", cached_opts.clone()).await.unwrap();
+        let resolved = ExportEnvOpts {
+            if_none_match: opts.if_none_match,
+            ..cached_opts
+        };
+        match client.export_env("app", resolved).await.unwrap() {
+            EnvExport::Text(text) => assert_eq!(text, "KEY=value\n"),
+            other => panic!("Expected text export, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_export_env_interpolate() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/api/v2/env/app"))
+            .and(wiremock::matchers::query_param("format", "json"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "namespace": "app",
+                "environment": {
+                    "DB_HOST": "db.internal",
+                    "DB_URL": "postgres://${DB_HOST}/app",
+                    "PRICE": "$$5"
+                },
+                "etag": "\"env-v1\"",
+                "total": 3,
+                "request_id": "req-1"
+            })))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let client = create_test_client(&mock_server.uri());
+
+        let export = client
+            .export_env(
+                "app",
+                ExportEnvOpts {
+                    format: ExportFormat::Dotenv,
+                    interpolate: true,
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap();
+
+        match export {
+            EnvExport::Text(text) => assert_eq!(
+                text,
+                "DB_HOST=db.internal\nDB_URL=postgres://db.internal/app\nPRICE=\"$5\"\n"
+            ),
+            other => panic!("expected text export, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_export_env_parallel_matches_server_export() {
+        let mock_server = MockServer::start().await;
+
+        // The listing spans two pages; both must be exported
+        Mock::given(method("GET"))
+            .and(path("/api/v2/secrets/app"))
+            .and(wiremock::matchers::query_param("cursor", "p2"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "namespace": "app",
+                "secrets": [
+                    {"key": "API_KEY", "version": 2, "updated_at": "2024-01-01T00:00:00Z", "kid": null}
+                ],
+                "total": 2
+            })))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/api/v2/secrets/app"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "namespace": "app",
+                "secrets": [
+                    {"key": "DB_URL", "version": 4, "updated_at": "2024-01-01T00:00:00Z", "kid": null}
+                ],
+                "total": 2,
+                "has_more": true,
+                "next_cursor": "p2"
+            })))
+            .mount(&mock_server)
+            .await;
+
+        for (key, value, version) in [("DB_URL", "postgres://db/app", 4), ("API_KEY", "abc123", 2)]
+        {
+            Mock::given(method("GET"))
+                .and(path(format!("/api/v2/secrets/app/{}", key)))
+                .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                    "value": value,
+                    "version": version,
+                    "expires_at": null,
+                    "metadata": null,
+                    "updated_at": "2024-01-01T00:00:00Z"
+                })))
+                .mount(&mock_server)
+                .await;
+        }
+
+        Mock::given(method("GET"))
+            .and(path("/api/v2/env/app"))
+            .and(wiremock::matchers::query_param("format", "dotenv"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_string("API_KEY=abc123\nDB_URL=postgres://db/app\n"),
+            )
+            .mount(&mock_server)
+            .await;
+
+        Mock::given(method("GET"))
+            .and(path("/api/v2/env/app"))
+            .and(wiremock::matchers::query_param("format", "json"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "namespace": "app",
+                "environment": {"API_KEY": "abc123", "DB_URL": "postgres://db/app"},
+                "etag": "\"server-etag\"",
+                "total": 2,
+                "request_id": "req-env"
+            })))
+            .mount(&mock_server)
+            .await;
+
+        let client = create_test_client(&mock_server.uri());
+
+        // Text export matches the server rendering
+        let server = client
+            .export_env(
+                "app",
+                ExportEnvOpts {
+                    format: ExportFormat::Dotenv,
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap();
+        let parallel = client
+            .export_env_parallel("app", ExportFormat::Dotenv, 4)
+            .await
+            .unwrap();
+        match (server, parallel.export) {
+            (EnvExport::Text(server), EnvExport::Text(parallel)) => assert_eq!(server, parallel),
+            other => panic!("Expected text exports, got {:?}", other),
+        }
+
+        // JSON export carries the same variables and the aggregate ETag
+        let server = client
+            .export_env(
+                "app",
+                ExportEnvOpts {
+                    format: ExportFormat::Json,
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap();
+        let parallel_json = client
+            .export_env_parallel("app", ExportFormat::Json, 1)
+            .await
+            .unwrap();
+        match (server, &parallel_json.export) {
+            (EnvExport::Json(server), EnvExport::Json(parallel)) => {
+                assert_eq!(server.environment, parallel.environment);
+                assert_eq!(server.total, parallel.total);
+                assert_eq!(parallel.etag, parallel_json.etag);
+            }
+            other => panic!("Expected JSON exports, got {:?}", other),
+        }
+
+        assert_eq!(parallel_json.etag, parallel.etag);
+    }
+
+    #[tokio::test]
+    async fn test_export_env_parallel_encodes_binary() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/api/v2/secrets/app"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "namespace": "app",
+                "secrets": [
+                    {"key": "API_KEY", "version": 1, "updated_at": "2024-01-01T00:00:00Z", "kid": null},
+                    {"key": "TLS_KEY", "version": 1, "updated_at": "2024-01-01T00:00:00Z", "kid": null}
+                ],
+                "total": 2
+            })))
+            .mount(&mock_server)
+            .await;
+
+        for (key, value, format) in [
+            ("API_KEY", "abc123", "plaintext"),
+            ("TLS_KEY", "\u{0}\u{1}\n\u{7f}", "binary"),
+        ] {
+            Mock::given(method("GET"))
+                .and(path(format!("/api/v2/secrets/app/{}", key)))
+                .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                    "value": value,
+                    "version": 1,
+                    "format": format,
+                    "updated_at": "2024-01-01T00:00:00Z"
+                })))
+                .mount(&mock_server)
+                .await;
+        }
+
+        let client = create_test_client(&mock_server.uri());
+        let result = client
+            .export_env_parallel("app", ExportFormat::Dotenv, 4)
+            .await
+            .unwrap();
+
+        match result.export {
+            EnvExport::Text(text) => assert_eq!(
+                text,
+                "API_KEY=abc123\n# TLS_KEY: binary value, base64-encoded\nTLS_KEY=\"AAEKfw==\"\n"
+            ),
+            other => panic!("Expected text export, got {:?}", other),
+        }
+
+        let skipped = client
+            .export_env_parallel_opts(
+                "app",
+                &ExportEnvOpts {
+                    format: ExportFormat::Json,
+                    binary_encoding: BinaryEncoding::SkipBinary,
+                    ..Default::default()
+                },
+                4,
+            )
+            .await
+            .unwrap();
+        match skipped.export {
+            EnvExport::Json(json) => {
+                assert_eq!(json.total, 1);
+                assert!(!json.environment.contains_key("TLS_KEY"));
+            }
+            other => panic!("Expected JSON export, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_rollback() {
+        let mock_server = MockServer::start().await;
+
+        let response_body = serde_json::json!({
+            "message": "Secret successfully rolled back to version 2",
+            "namespace": "test-ns",
+            "key": "versioned-key",
+            "from_version": 4,
+            "to_version": 2,
+            "request_id": "req-rollback"
+        });
+
+        Mock::given(method("POST"))
+            .and(path("/api/v2/secrets/test-ns/versioned-key/rollback/2"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(&response_body))
+            .mount(&mock_server)
+            .await;
+
+        let client = create_test_client(&mock_server.uri());
+
+        let result = client.rollback("test-ns", "versioned-key", 2).await;
+        assert!(result.is_ok());
+
+        let rollback_result = result.unwrap();
+        assert_eq!(rollback_result.namespace, "test-ns");
+        assert_eq!(rollback_result.key, "versioned-key");
+        assert_eq!(rollback_result.from_version, 4);
+        assert_eq!(rollback_result.to_version, 2);
+        assert_eq!(rollback_result.created_version(), Some(5));
+    }
+
+    #[tokio::test]
+    async fn test_audit_logs() {
+        let mock_server = MockServer::start().await;
+
+        let response_body = serde_json::json!({
+            "logs": [
+                {
+                    "id": 123,
+                    "timestamp": "2024-01-01T12:00:00Z",
+                    "actor": "user1",
+                    "action": "put",
+                    "namespace": "production",
+                    "key_name": "api-key",
+                    "success": true,
+                    "ip_address": "192.168.1.1",
+                    "user_agent": "SDK/1.0"
+                },
+                {
+                    "id": 124,
+                    "timestamp": "2024-01-01T12:05:00Z",
+                    "actor": "user2",
+                    "action": "get",
+                    "namespace": "production",
+                    "key_name": "db-pass",
+                    "success": false,
+                    "error": "not found"
+                }
+            ],
+            "total": 2,
+            "limit": 10,
+            "offset": 0,
+            "has_more": false,
+            "request_id": "req-audit"
+        });
+
+        Mock::given(method("GET"))
+            .and(path("/api/v2/audit"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(&response_body))
+            .mount(&mock_server)
+            .await;
+
+        let client = create_test_client(&mock_server.uri());
+
+        let query = AuditQuery::default();
+        let result = client.audit(query).await;
+        assert!(result.is_ok());
+
+        let audit_result = result.unwrap();
+        assert_eq!(audit_result.entries.len(), 2);
+        assert_eq!(audit_result.total, 2);
+        assert!(!audit_result.has_more);
+
+        // Check first entry
+        let first = &audit_result.entries[0];
+        assert_eq!(first.id, 123);
+        assert_eq!(first.action, "put");
+        assert!(first.success);
+        assert_eq!(first.namespace, Some("production".to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_audit_logs_with_filters() {
+        let mock_server = MockServer::start().await;
+
+        let response_body = serde_json::json!({
+            "logs": [
+                {
+                    "id": 200,
+                    "timestamp": "2024-01-02T10:00:00Z",
+                    "actor": "admin",
+                    "action": "delete",
+                    "namespace": "test",
+                    "key_name": "temp-key",
+                    "success": false,
+                    "error": "permission denied"
+                }
+            ],
+            "total": 1,
+            "limit": 5,
+            "offset": 0,
+            "has_more": false,
+            "request_id": "req-audit-filtered"
+        });
+
+        Mock::given(method("GET"))
+            .and(path("/api/v2/audit"))
+            .and(wiremock::matchers::query_param("namespace", "test"))
+            .and(wiremock::matchers::query_param("success", "false"))
+            .and(wiremock::matchers::query_param("limit", "5"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(&response_body))
+            .mount(&mock_server)
+            .await;
+
+        let client = create_test_client(&mock_server.uri());
+
+        let query = AuditQuery {
+            namespace: Some("test".to_string()),
+            success: Some(false),
+            limit: Some(5),
+            ..Default::default()
+        };
+
+        let result = client.audit(query).await;
+        assert!(result.is_ok());
+
+        let audit_result = result.unwrap();
+        assert_eq!(audit_result.entries.len(), 1);
+        assert_eq!(audit_result.entries[0].action, "delete");
+        assert!(!audit_result.entries[0].success);
+        assert_eq!(
+            audit_result.entries[0].error,
+            Some("permission denied".to_string())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_failover_to_fallback_host() {
+        let mock_server = MockServer::start().await;
+        let body = r#"{"value":"secret-value","version":1,"updated_at":"2024-01-01T00:00:00Z"}"#;
+
+        Mock::given(method("GET"))
+            .and(path("/api/v2/secrets/test-ns/test-key"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(body, "application/json"))
+            .expect(2)
+            .mount(&mock_server)
+            .await;
+
+        // Nothing listens on port 1, so the primary refuses connections
+        #[cfg(feature = "danger-insecure-http")]
+        let client = ClientBuilder::new("http://127.0.0.1:1")
+            .auth(Auth::bearer("test-token"))
+            .fallback_urls(vec![mock_server.uri()])
+            .retries(0)
+            .enable_cache(false)
+            .allow_insecure_http()
+            .build()
+            .unwrap();
+
+        #[cfg(not(feature = "danger-insecure-http"))]
+        let client = ClientBuilder::new("https://127.0.0.1:1")
+            .auth(Auth::bearer("test-token"))
+            .fallback_urls(vec![mock_server.uri().replace("http://", "https://")])
+            .retries(0)
+            .enable_cache(false)
+            .build()
+            .unwrap();
+
+        assert!(client.active_base_url().ends_with("127.0.0.1:1"));
+
+        let secret = client
+            .get_secret("test-ns", "test-key", GetOpts::default())
+            .await
+            .unwrap();
+        assert_eq!(secret.version, 1);
+        assert!(client
+            .active_base_url()
+            .ends_with(&mock_server.address().to_string()));
+
+        // The healthy host is remembered for later requests
+        let _ = client
+            .get_secret("test-ns", "test-key", GetOpts::default())
+            .await
+            .unwrap();
+    }
 
-                    let response_result = self.http.execute(req).await;
+    #[tokio::test]
+    async fn test_strict_deserialization() {
+        let mock_server = MockServer::start().await;
+        let body = r#"{"value":"secret-value","version":1,"updated_at":"2024-01-01T00:00:00Z","owner_team":"ops"}"#;
 
-                    // Decrement active connections
-                    #[cfg(feature = "metrics")]
-                    self.metrics.dec_active_connections();
+        Mock::given(method("GET"))
+            .and(path("/api/v2/secrets/test-ns/test-key"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(body, "application/json"))
+            .mount(&mock_server)
+            .await;
 
-                    match response_result {
-                        Ok(response) => {
-                            let status = response.status();
+        let opts = || GetOpts {
+            use_cache: false,
+            ..Default::default()
+        };
 
-                            // Handle 401 - but don't retry within backoff if we can refresh token
-                            if status == StatusCode::UNAUTHORIZED
-                                && token_refresh_count == 0
-                                && auth.supports_refresh()
-                            {
-                                // Return a special error that we'll handle outside the backoff retry
-                                return Err(backoff::Error::Permanent(Error::Http {
-                                    status: 401,
-                                    category: "auth_refresh_needed".to_string(),
-                                    message: "Token refresh required".to_string(),
-                                    request_id: header_str(response.headers(), "x-request-id"),
-                                }));
-                            }
+        // Lenient by default
+        let mut client = create_test_client(&mock_server.uri());
+        let secret = client
+            .get_secret("test-ns", "test-key", opts())
+            .await
+            .unwrap();
+        assert_eq!(secret.version, 1);
 
-                            // Check if error is retryable
-                            if status.is_server_error()
-                                || status == StatusCode::TOO_MANY_REQUESTS
-                                || status == StatusCode::REQUEST_TIMEOUT
-                            {
-                                let error = self.parse_error_response(response).await;
-                                if error.is_retryable() && current_retry < max_retries as usize {
-                                    debug!("Retrying request due to: {:?}", error);
-                                    #[cfg(feature = "metrics")]
-                                    self.metrics.record_retry(
-                                        (current_retry + 1) as u32,
-                                        &status.to_string(),
-                                    );
-                                    return Err(backoff::Error::transient(error));
-                                } else {
-                                    return Err(backoff::Error::Permanent(error));
-                                }
-                            }
+        client.config.strict_deserialization = true;
+        let err = client
+            .get_secret("test-ns", "test-key", opts())
+            .await
+            .unwrap_err();
+        assert!(matches!(&err, Error::Deserialize(msg) if msg.contains("owner_team")));
+    }
 
-                            // Non-retryable HTTP errors
-                            if !status.is_success() && status != StatusCode::NOT_MODIFIED {
-                                let error = self.parse_error_response(response).await;
-                                return Err(backoff::Error::Permanent(error));
-                            }
+    #[tokio::test]
+    async fn test_mutating_results_echo_idempotency_key() {
+        let mock_server = MockServer::start().await;
 
-                            // Record successful request metrics
-                            #[cfg(feature = "metrics")]
-                            {
-                                let duration_secs = start_time.elapsed().as_secs_f64();
-                                self.metrics.record_request(
-                                    &method,
-                                    &path,
-                                    status.as_u16(),
-                                    duration_secs,
-                                );
-                            }
+        Mock::given(method("PUT"))
+            .and(path("/api/v2/secrets/test-ns/test-key"))
+            .and(header("X-Idempotency-Key", "put-1"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "message": "Secret updated",
+                "namespace": "test-ns",
+                "key": "test-key",
+                "created_at": "2024-01-01T00:00:00Z",
+                "request_id": "req-put"
+            })))
+            .mount(&mock_server)
+            .await;
 
-                            Ok(response)
-                        }
-                        Err(e) => {
-                            let error = Error::from(e);
-                            if error.is_retryable() && current_retry < max_retries as usize {
-                                debug!("Retrying request due to network error: {:?}", error);
-                                #[cfg(feature = "metrics")]
-                                self.metrics
-                                    .record_retry((current_retry + 1) as u32, "network_error");
-                                Err(backoff::Error::transient(error))
-                            } else {
-                                Err(backoff::Error::Permanent(error))
-                            }
-                        }
-                    }
-                },
-                |err, dur| {
-                    let count =
-                        retry_count_clone.fetch_add(1, std::sync::atomic::Ordering::Relaxed) + 1;
-                    debug!("Retry {} after {:?} due to: {:?}", count, dur, err);
+        Mock::given(method("POST"))
+            .and(path("/api/v2/secrets/test-ns/batch"))
+            .and(header("X-Idempotency-Key", "batch-1"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("x-request-id", "req-batch")
+                    .set_body_json(serde_json::json!({
+                        "namespace": "test-ns",
+                        "results": {
+                            "succeeded": [{"key": "a", "action": "put", "success": true}],
+                            "failed": [],
+                            "total": 1
+                        },
+                        "success_rate": 1.0
+                    })),
+            )
+            .mount(&mock_server)
+            .await;
+
+        let client = create_test_client(&mock_server.uri());
+
+        let opts = PutOpts {
+            idempotency_key: Some("put-1".to_string()),
+            ..Default::default()
+        };
+        let put = client
+            .put_secret("test-ns", "test-key", "value", opts)
+            .await
+            .unwrap();
+        assert_eq!(put.idempotency_key.as_deref(), Some("put-1"));
+        assert_eq!(put.request_id, "req-put");
+
+        let batch = client
+            .batch_operate(
+                "test-ns",
+                vec![BatchOp::put("a", "1")],
+                false,
+                Some("batch-1".to_string()),
+            )
+            .await
+            .unwrap();
+        assert_eq!(batch.idempotency_key.as_deref(), Some("batch-1"));
+        assert_eq!(batch.request_id.as_deref(), Some("req-batch"));
+    }
+
+    #[cfg(feature = "tower")]
+    #[tokio::test]
+    async fn test_tower_service_through_layer() {
+        use crate::SecretRequest;
+        use tower::{ServiceBuilder, ServiceExt};
+
+        let mock_server = MockServer::start().await;
+        let body = r#"{"value":"secret-value","version":1,"updated_at":"2024-01-01T00:00:00Z"}"#;
+
+        Mock::given(method("GET"))
+            .and(path("/api/v2/secrets/test-ns/test-key"))
+            .and(header("authorization", "Bearer test-token"))
+            .and(header("x-tenant", "blue"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(body, "application/json"))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let client = create_test_client(&mock_server.uri());
+        let service = ServiceBuilder::new()
+            .concurrency_limit(1)
+            .map_request(|mut request: SecretRequest| {
+                let _ = request
+                    .headers
+                    .insert("x-tenant", http::HeaderValue::from_static("blue"));
+                request
+            })
+            .service(client.service());
+
+        let response = service
+            .oneshot(SecretRequest::get_secret("test-ns", "test-key"))
+            .await
+            .unwrap();
+        assert_eq!(response.status, 200);
+        let value: serde_json::Value = response.json().unwrap();
+        assert_eq!(value["value"], "secret-value");
+    }
+
+    #[tokio::test]
+    async fn test_raw_writes_invalidate_cache() {
+        let mock_server = MockServer::start().await;
+        let body = r#"{"value":"secret-value","version":1,"updated_at":"2024-01-01T00:00:00Z"}"#;
+
+        Mock::given(method("GET"))
+            .and(path("/api/v2/secrets/test-ns/test%20key"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(body, "application/json"))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("PUT"))
+            .and(path("/api/v2/secrets/test-ns/test%20key"))
+            .respond_with(ResponseTemplate::new(200))
+            .mount(&mock_server)
+            .await;
+
+        let client = create_test_client(&mock_server.uri());
+        let gets = || async {
+            let _ = client
+                .get_secret("test-ns", "test key", GetOpts::default())
+                .await
+                .unwrap();
+            mock_server
+                .received_requests()
+                .await
+                .unwrap()
+                .iter()
+                .filter(|request| request.method.as_str() == "GET")
+                .count()
+        };
+
+        assert_eq!(gets().await, 1);
+        assert_eq!(gets().await, 1);
+
+        let _ = client
+            .raw_request(
+                Method::PUT,
+                "/api/v2/secrets/test-ns/test%20key",
+                Some(serde_json::json!({"value": "new"})),
+            )
+            .await
+            .unwrap();
+        assert_eq!(gets().await, 2);
+
+        #[cfg(feature = "tower")]
+        {
+            use crate::SecretRequest;
+            use tower::ServiceExt;
+
+            let _ = client
+                .service()
+                .oneshot(SecretRequest::put_secret("test-ns", "test key", "newer"))
+                .await
+                .unwrap();
+            assert_eq!(gets().await, 3);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_export_env_output_is_sorted() {
+        let mock_server = MockServer::start().await;
+        let body = r#"{"namespace":"app","environment":{"ZETA":"3","MIDDLE":"2","ALPHA":"1"},"etag":"\"env-v1\"","total":3,"request_id":"req-1"}"#;
+
+        Mock::given(method("GET"))
+            .and(path("/api/v2/env/app"))
+            .and(wiremock::matchers::query_param("format", "json"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(body, "application/json"))
+            .mount(&mock_server)
+            .await;
+
+        let client = create_test_client(&mock_server.uri());
+        let export = |format| {
+            client.export_env(
+                "app",
+                ExportEnvOpts {
+                    format,
+                    interpolate: true,
+                    use_cache: false,
+                    ..Default::default()
                 },
             )
+        };
+
+        match export(ExportFormat::Dotenv).await.unwrap() {
+            EnvExport::Text(text) => assert_eq!(text, "ALPHA=1\nMIDDLE=2\nZETA=3\n"),
+            other => panic!("expected text export, got {:?}", other),
+        }
+
+        match export(ExportFormat::Json).await.unwrap() {
+            EnvExport::Json(json) => {
+                let keys: Vec<_> = json.environment.keys().map(String::as_str).collect();
+                assert_eq!(keys, ["ALPHA", "MIDDLE", "ZETA"]);
+            }
+            other => panic!("expected json export, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_namespace_fingerprint() {
+        let mock_server = MockServer::start().await;
+        let listing = |db_version: i32| {
+            ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "namespace": "test-ns",
+                "secrets": [
+                    {"key": "db-url", "version": db_version},
+                    {"key": "api-key", "version": 1}
+                ],
+                "total": 2
+            }))
+        };
+
+        Mock::given(method("GET"))
+            .and(path("/api/v2/secrets/test-ns"))
+            .and(wiremock::matchers::query_param("fields", "key,version"))
+            .respond_with(listing(1))
+            .up_to_n_times(1)
+            .mount(&mock_server)
+            .await;
+
+        Mock::given(method("GET"))
+            .and(path("/api/v2/secrets/test-ns"))
+            .respond_with(listing(2))
+            .mount(&mock_server)
+            .await;
+
+        Mock::given(method("PUT"))
+            .and(path("/api/v2/secrets/test-ns/db-url"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "message": "Secret updated",
+                "namespace": "test-ns",
+                "key": "db-url",
+                "created_at": "2024-01-01T00:00:00Z",
+                "request_id": "req-put"
+            })))
+            .mount(&mock_server)
+            .await;
+
+        let client = create_test_client(&mock_server.uri());
+
+        let first = client.namespace_fingerprint("test-ns").await.unwrap();
+        assert_eq!(first.len(), 64);
+        assert_eq!(
+            client.namespace_fingerprint("test-ns").await.unwrap(),
+            first
+        );
+
+        let _ = client
+            .put_secret("test-ns", "db-url", "new-value", PutOpts::default())
+            .await
+            .unwrap();
+        let second = client.namespace_fingerprint("test-ns").await.unwrap();
+        assert_ne!(second, first);
+    }
+
+    #[tokio::test]
+    async fn test_empty_value_round_trip() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/api/v2/secrets/test-ns/empty"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "value": "",
+                "version": 1,
+                "updated_at": "2024-01-01T00:00:00Z"
+            })))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        Mock::given(method("GET"))
+            .and(path("/api/v2/secrets/test-ns/null"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "value": null,
+                "version": 1,
+                "updated_at": "2024-01-01T00:00:00Z"
+            })))
+            .mount(&mock_server)
+            .await;
+
+        Mock::given(method("GET"))
+            .and(path("/api/v2/secrets/test-ns/missing-value"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "version": 1,
+                "updated_at": "2024-01-01T00:00:00Z"
+            })))
+            .mount(&mock_server)
+            .await;
+
+        let client = create_test_client(&mock_server.uri());
+
+        let secret = client
+            .get_secret("test-ns", "empty", GetOpts::default())
+            .await
+            .unwrap();
+        assert_eq!(secret.value.expose_secret(), "");
+        assert!(!secret.from_cache);
+
+        // Served from the cache as a present, empty secret
+        let cached = client
+            .get_secret("test-ns", "empty", GetOpts::default())
+            .await
+            .unwrap();
+        assert!(cached.from_cache);
+        assert_eq!(cached.value.expose_secret(), "");
+
+        let secret = client
+            .get_secret("test-ns", "null", GetOpts::default())
+            .await
+            .unwrap();
+        assert_eq!(secret.value.expose_secret(), "");
+
+        let err = client
+            .get_secret("test-ns", "missing-value", GetOpts::default())
+            .await
+            .unwrap_err();
+        assert!(matches!(err, Error::Deserialize(_)));
+    }
+
+    #[tokio::test]
+    async fn test_put_empty_value_guard() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("PUT"))
+            .and(path("/api/v2/secrets/test-ns/empty"))
+            .and(wiremock::matchers::body_json(
+                serde_json::json!({"value": ""}),
+            ))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "message": "Secret updated",
+                "namespace": "test-ns",
+                "key": "empty",
+                "created_at": "2024-01-01T00:00:00Z",
+                "request_id": "req-put"
+            })))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let client = create_test_client(&mock_server.uri());
+
+        let err = client
+            .put_secret("test-ns", "empty", "", PutOpts::default())
+            .await
+            .unwrap_err();
+        assert!(matches!(err, Error::Config(_)));
+
+        let opts = PutOpts {
+            allow_empty_value: true,
+            ..Default::default()
+        };
+        let _ = client
+            .put_secret("test-ns", "empty", "", opts)
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_expect_continue_on_large_bodies() {
+        let mock_server = MockServer::start().await;
+        let put_response = || {
+            ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "message": "Secret updated",
+                "namespace": "test-ns",
+                "key": "k",
+                "created_at": "2024-01-01T00:00:00Z",
+                "request_id": "req-put"
+            }))
+        };
+
+        Mock::given(method("PUT"))
+            .and(path("/api/v2/secrets/test-ns/large"))
+            .and(header("expect", "100-continue"))
+            .respond_with(put_response())
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        Mock::given(method("PUT"))
+            .and(path("/api/v2/secrets/test-ns/small"))
+            .respond_with(put_response())
+            .expect(1)
+            .mount(&mock_server)
             .await;
 
-            match result {
-                Ok(response) => return Ok(response),
-                Err(Error::Http {
-                    status: 401,
-                    category,
-                    ..
-                }) if category == "auth_refresh_needed" && token_refresh_count == 0 => {
-                    // Try to refresh token once
-                    warn!("Got 401, attempting token refresh");
-                    auth.refresh()
-                        .await
-                        .map_err(|e| Error::Network(format!("Token refresh failed: {}", e)))?;
-                    token_refresh_count += 1;
-                    // Continue to retry with new token
-                    continue;
-                }
-                Err(e) => return Err(e),
-            }
-        }
+        #[cfg(feature = "danger-insecure-http")]
+        let client = ClientBuilder::new(mock_server.uri())
+            .auth(Auth::bearer("test-token"))
+            .expect_continue_threshold(256)
+            .allow_insecure_http()
+            .build()
+            .unwrap();
+
+        #[cfg(not(feature = "danger-insecure-http"))]
+        let client = ClientBuilder::new(mock_server.uri().replace("http://", "https://"))
+            .auth(Auth::bearer("test-token"))
+            .expect_continue_threshold(256)
+            .build()
+            .unwrap();
+
+        let _ = client
+            .put_secret("test-ns", "large", "x".repeat(1024), PutOpts::default())
+            .await
+            .unwrap();
+        let _ = client
+            .put_secret("test-ns", "small", "x", PutOpts::default())
+            .await
+            .unwrap();
+
+        let requests = mock_server.received_requests().await.unwrap();
+        let small = requests
+            .iter()
+            .find(|r| r.url.path().ends_with("/small"))
+            .unwrap();
+        assert!(!small.headers.contains_key("expect"));
     }
 
-    /// Execute a request without retry logic (for health checks)
-    async fn execute_without_retry(
-        &self,
-        request_builder: reqwest::RequestBuilder,
-    ) -> Result<Response> {
-        // Get auth header
-        let (auth_header, auth_value) = self
-            .config
-            .auth
-            .get_header()
+    #[tokio::test]
+    async fn test_kid_on_listing_and_secret() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/api/v2/secrets/test-ns"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "namespace": "test-ns",
+                "secrets": [
+                    {"key": "db-url", "version": 2, "kid": "kms-old"},
+                    {"key": "api-key", "version": 1, "kid": "kms-new"},
+                    {"key": "legacy", "version": 1},
+                    {"key": "token", "version": 4, "kid": "kms-old"}
+                ],
+                "total": 4
+            })))
+            .mount(&mock_server)
+            .await;
+
+        Mock::given(method("GET"))
+            .and(path("/api/v2/secrets/test-ns/db-url"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "value": "postgres://db",
+                "version": 2,
+                "updated_at": "2024-01-01T00:00:00Z",
+                "kid": "kms-old"
+            })))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let client = create_test_client(&mock_server.uri());
+
+        let old = client.list_by_kid("test-ns", "kms-old").await.unwrap();
+        let keys: Vec<&str> = old.iter().map(|info| info.key.as_str()).collect();
+        assert_eq!(keys, ["db-url", "token"]);
+        assert!(client
+            .list_by_kid("test-ns", "kms-unknown")
             .await
-            .map_err(|e| Error::Config(format!("Failed to get auth header: {}", e)))?;
+            .unwrap()
+            .is_empty());
 
-        // Add auth header
-        let request = request_builder
-            .header(auth_header, auth_value)
-            .build()
-            .map_err(|e| Error::Other(format!("Failed to build request: {}", e)))?;
+        let secret = client
+            .get_secret("test-ns", "db-url", GetOpts::default())
+            .await
+            .unwrap();
+        assert_eq!(secret.kid.as_deref(), Some("kms-old"));
 
-        // Execute request
-        self.http.execute(request).await.map_err(Error::from)
+        // The kid survives the cache
+        let cached = client
+            .get_secret("test-ns", "db-url", GetOpts::default())
+            .await
+            .unwrap();
+        assert!(cached.from_cache);
+        assert_eq!(cached.kid.as_deref(), Some("kms-old"));
     }
 
-    /// Parse error response from server
-    async fn parse_error_response(&self, response: Response) -> Error {
-        let status = response.status().as_u16();
-        let request_id = header_str(response.headers(), "x-request-id");
+    #[tokio::test]
+    async fn test_panicking_token_provider_returns_error() {
+        struct PanickingProvider;
+
+        #[async_trait::async_trait]
+        impl crate::TokenProvider for PanickingProvider {
+            async fn get_token(
+                &self,
+            ) -> std::result::Result<SecretString, Box<dyn std::error::Error + Send + Sync>>
+            {
+                panic!("provider bug");
+            }
 
-        // Try to parse JSON error response
-        match response.json::<ErrorResponse>().await {
-            Ok(error_resp) => Error::from_response(
-                error_resp.status,
-                &error_resp.error,
-                &error_resp.message,
-                request_id,
-            ),
-            Err(_) => Error::Http {
-                status,
-                category: "unknown".to_string(),
-                message: format!("HTTP error {}", status),
-                request_id,
-            },
+            async fn refresh_token(
+                &self,
+            ) -> std::result::Result<(), Box<dyn std::error::Error + Send + Sync>> {
+                Ok(())
+            }
+
+            fn clone_box(&self) -> Box<dyn crate::TokenProvider> {
+                Box::new(PanickingProvider)
+            }
         }
-    }
 
-    /// Parse JSON response
-    async fn parse_json_response<T: serde::de::DeserializeOwned>(
-        &self,
-        response: Response,
-    ) -> Result<T> {
-        response.json().await.map_err(Error::from)
+        // The request fails before anything is sent
+        let client = ClientBuilder::new("https://example.com")
+            .auth(Auth::token_provider(PanickingProvider))
+            .build()
+            .unwrap();
+
+        let err = client
+            .get_secret("test-ns", "test-key", GetOpts::default())
+            .await
+            .unwrap_err();
+        assert!(matches!(&err, Error::Config(msg) if msg.contains("token provider panicked")));
     }
 
-    /// Parse get secret response
-    async fn parse_get_response(
-        &self,
-        response: Response,
-        namespace: &str,
-        key: &str,
-    ) -> Result<Secret> {
-        let headers = response.headers().clone();
+    #[tokio::test]
+    async fn test_export_env_delta() {
+        let mock_server = MockServer::start().await;
 
-        // Extract headers
-        let etag = header_str(&headers, "etag");
-        let last_modified = header_str(&headers, "last-modified");
-        let request_id = header_str(&headers, "x-request-id");
+        Mock::given(method("GET"))
+            .and(path("/api/v2/env/app"))
+            .and(wiremock::matchers::query_param(
+                "since",
+                "2024-01-01T00:00:00Z",
+            ))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "namespace": "app",
+                "changed": {"DB_URL": "postgres://db/v2", "NEW_FLAG": "on"},
+                "deleted": ["OLD_KEY"],
+                "etag": "\"env-v2\"",
+                "request_id": "req-delta"
+            })))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
 
-        // Parse body
-        #[derive(serde::Deserialize)]
-        struct GetResponse {
-            value: String,
-            version: i32,
-            expires_at: Option<String>,
-            metadata: Option<serde_json::Value>,
-            updated_at: String,
-        }
+        Mock::given(method("GET"))
+            .and(path("/api/v2/env/app"))
+            .and(header("if-none-match", "\"env-v2\""))
+            .respond_with(ResponseTemplate::new(304))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let client = create_test_client(&mock_server.uri());
+        let since = time::OffsetDateTime::from_unix_timestamp(1_704_067_200).unwrap();
+
+        let delta = client
+            .export_env_delta(
+                "app",
+                ExportDeltaOpts {
+                    since: Some(since),
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap();
+        assert!(!delta.full);
+        assert_eq!(delta.changed.len(), 2);
+        assert_eq!(delta.changed["NEW_FLAG"], "on");
+        assert_eq!(delta.deleted, ["OLD_KEY"]);
+        assert_eq!(delta.etag.as_deref(), Some("\"env-v2\""));
+
+        let unchanged = client
+            .export_env_delta(
+                "app",
+                ExportDeltaOpts {
+                    if_none_match: delta.etag.clone(),
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap();
+        assert!(unchanged.is_empty());
+        assert_eq!(unchanged.etag, delta.etag);
+    }
 
-        let body: GetResponse = response.json().await.map_err(Error::from)?;
+    #[tokio::test]
+    async fn test_export_env_delta_falls_back_to_full_export() {
+        let mock_server = MockServer::start().await;
 
-        // Parse timestamps
-        let updated_at = time::OffsetDateTime::parse(
-            &body.updated_at,
-            &time::format_description::well_known::Rfc3339,
+        // The server ignores `since` and returns everything
+        Mock::given(method("GET"))
+            .and(path("/api/v2/env/app"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "namespace": "app",
+                "environment": {"A": "1", "B": "2"},
+                "etag": "\"env-v1\"",
+                "total": 2,
+                "request_id": "req-full"
+            })))
+            .mount(&mock_server)
+            .await;
+
+        let client = create_test_client(&mock_server.uri());
+        let opts = ExportDeltaOpts {
+            since: Some(time::OffsetDateTime::from_unix_timestamp(1_704_067_200).unwrap()),
+            ..Default::default()
+        };
+        let delta = client.export_env_delta("app", opts).await.unwrap();
+        assert!(delta.full);
+        assert_eq!(delta.changed.len(), 2);
+        assert!(delta.deleted.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_check_time_skew() {
+        let mock_server = MockServer::start().await;
+        let http_date = time::format_description::parse(
+            "[weekday repr:short], [day] [month repr:short] [year] [hour]:[minute]:[second] GMT",
         )
-        .map_err(|e| Error::Deserialize(format!("Invalid updated_at timestamp: {}", e)))?;
+        .unwrap();
+        let server_now = time::OffsetDateTime::now_utc() + time::Duration::minutes(2);
 
-        let expires_at = body
-            .expires_at
-            .as_ref()
-            .map(|s| {
-                time::OffsetDateTime::parse(s, &time::format_description::well_known::Rfc3339)
-                    .map_err(|e| Error::Deserialize(format!("Invalid expires_at timestamp: {}", e)))
-            })
-            .transpose()?;
+        Mock::given(method("GET"))
+            .and(path("/api/v2/livez"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("date", server_now.format(&http_date).unwrap().as_str()),
+            )
+            .mount(&mock_server)
+            .await;
 
-        Ok(Secret {
-            namespace: namespace.to_string(),
-            key: key.to_string(),
-            value: SecretString::new(body.value),
-            version: body.version,
-            expires_at,
-            metadata: body.metadata.unwrap_or(serde_json::Value::Null),
-            updated_at,
-            etag,
-            last_modified,
-            request_id,
-        })
+        let client = create_test_client(&mock_server.uri());
+        let skew = client.check_time_skew().await.unwrap();
+        assert!(
+            (time::Duration::seconds(118)..=time::Duration::seconds(121)).contains(&skew),
+            "unexpected skew {}",
+            skew
+        );
     }
 
-    /// Get secret from cache
-    async fn get_from_cache(&self, cache_key: &str) -> Option<Secret> {
-        let cache = self.cache.as_ref()?;
+    #[tokio::test]
+    async fn test_retries_exhausted_reports_attempts() {
+        let mock_server = MockServer::start().await;
 
-        match cache.get(cache_key).await {
-            Some(cached) => {
-                // Check if expired
-                if cached.is_expired() {
-                    trace!("Cache entry expired for key: {}", cache_key);
-                    cache.invalidate(cache_key).await;
-                    self.stats.record_expiration();
-                    self.stats.record_miss();
-                    None
-                } else {
-                    debug!("Cache hit for key: {}", cache_key);
-                    self.stats.record_hit();
+        Mock::given(method("GET"))
+            .and(path("/api/v2/secrets/test-ns/flaky"))
+            .respond_with(ResponseTemplate::new(503))
+            .expect(3)
+            .mount(&mock_server)
+            .await;
 
-                    // Record cache hit metric
-                    #[cfg(feature = "metrics")]
-                    {
-                        let (namespace, _) = cache_key.split_once('/').unwrap_or(("", cache_key));
-                        self.metrics.record_cache_hit(namespace);
-                    }
+        Mock::given(method("GET"))
+            .and(path("/api/v2/secrets/test-ns/missing"))
+            .respond_with(ResponseTemplate::new(404))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
 
-                    let (namespace, key) = cache_key.split_once('/').unwrap_or(("", cache_key));
-                    Some(cached.into_secret(namespace.to_string(), key.to_string()))
-                }
+        #[cfg(feature = "danger-insecure-http")]
+        let client = ClientBuilder::new(mock_server.uri())
+            .auth(Auth::bearer("test-token"))
+            .retries(2)
+            .allow_insecure_http()
+            .build()
+            .unwrap();
+
+        #[cfg(not(feature = "danger-insecure-http"))]
+        let client = ClientBuilder::new(mock_server.uri().replace("http://", "https://"))
+            .auth(Auth::bearer("test-token"))
+            .retries(2)
+            .build()
+            .unwrap();
+
+        let err = client
+            .get_secret("test-ns", "flaky", GetOpts::default())
+            .await
+            .unwrap_err();
+        assert_eq!(err.status_code(), Some(503));
+        assert!(err.is_retryable());
+        assert!(err.to_string().contains("after 3 attempts"));
+        match &err {
+            Error::RetriesExhausted { last, attempts } => {
+                assert_eq!(last.status_code(), Some(503));
+                assert_eq!(attempts.len(), 3);
+                assert!(attempts.iter().all(|a| a.status == Some(503)));
+                assert!(attempts.iter().all(|a| !a.error.is_empty()));
             }
-            None => {
-                trace!("Cache miss for key: {}", cache_key);
-                self.stats.record_miss();
+            other => panic!("expected RetriesExhausted, got {:?}", other),
+        }
 
-                // Record cache miss metric
-                #[cfg(feature = "metrics")]
-                {
-                    let (namespace, _) = cache_key.split_once('/').unwrap_or(("", cache_key));
-                    self.metrics.record_cache_miss(namespace);
-                }
+        // Single-attempt failures keep their plain error
+        let err = client
+            .get_secret("test-ns", "missing", GetOpts::default())
+            .await
+            .unwrap_err();
+        assert!(matches!(err, Error::Http { status: 404, .. }));
+    }
+
+    #[tokio::test]
+    async fn test_generate_secret_password() {
+        let mock_server = MockServer::start().await;
+        let charset = "abcdefghjkmnpqrstuvwxyz23456789";
+
+        Mock::given(method("POST"))
+            .and(path("/api/v2/secrets/test-ns/db-password/generate"))
+            .and(wiremock::matchers::body_json(serde_json::json!({
+                "type": "password",
+                "length": 24,
+                "charset": charset,
+                "return_value": true,
+                "ttl_seconds": 3600,
+            })))
+            .respond_with(ResponseTemplate::new(201).set_body_json(serde_json::json!({
+                "message": "Secret generated",
+                "namespace": "test-ns",
+                "key": "db-password",
+                "created_at": "2024-01-01T00:00:00Z",
+                "request_id": "req-gen",
+                "version": 1,
+                "generated_value": "k7pq2mzx9hrt4wvn3bcd8fgs",
+            })))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let client = create_test_client(&mock_server.uri());
+        let result = client
+            .generate_secret(
+                "test-ns",
+                "db-password",
+                GenerateSpec::password(24)
+                    .charset(charset)
+                    .return_value(true),
+                PutOpts {
+                    ttl_seconds: Some(3600),
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap();
 
-                None
+        assert_eq!(result.version, Some(1));
+        let value = result.generated_value.unwrap();
+        let value = value.expose_secret();
+        assert_eq!(value.chars().count(), 24);
+        assert!(value.chars().all(|c| charset.contains(c)));
+    }
+
+    /// Start an HTTP/2 server answering every request with `chunks` as
+    /// separate DATA frames and no Content-Length
+    ///
+    /// The client only speaks HTTP/2 (prior knowledge), and wiremock always
+    /// declares a length, hence the hand-rolled fixture.
+    #[cfg(feature = "danger-insecure-http")]
+    async fn spawn_chunked_server(
+        content_type: &'static str,
+        chunks: &'static [&'static str],
+    ) -> String {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        drop(tokio::spawn(async move {
+            while let Ok((socket, _)) = listener.accept().await {
+                drop(tokio::spawn(async move {
+                    let Ok(mut connection) = h2::server::handshake(socket).await else {
+                        return;
+                    };
+                    // Accepting also drives the connection, flushing sent frames
+                    while let Some(Ok((_request, mut respond))) = connection.accept().await {
+                        let response = http::Response::builder()
+                            .status(200)
+                            .header("content-type", content_type)
+                            .body(())
+                            .unwrap();
+                        let mut body = respond.send_response(response, false).unwrap();
+                        for (i, chunk) in chunks.iter().enumerate() {
+                            let end_of_stream = i + 1 == chunks.len();
+                            body.send_data(
+                                bytes::Bytes::from_static(chunk.as_bytes()),
+                                end_of_stream,
+                            )
+                            .unwrap();
+                        }
+                    }
+                }));
             }
-        }
+        }));
+        url
     }
 
-    /// Cache a secret
-    async fn cache_secret(&self, cache_key: &str, secret: &Secret) {
-        let Some(cache) = &self.cache else { return };
+    #[cfg(feature = "danger-insecure-http")]
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_chunked_response_without_content_length() {
+        const CHUNKS: &[&str] = &[
+            r#"{"value":"secret-value","#,
+            r#""version":1,"#,
+            r#""updated_at":"2024-01-01T00:00:00Z"}"#,
+        ];
+        let body_len: usize = CHUNKS.iter().map(|chunk| chunk.len()).sum();
+        let url = spawn_chunked_server("application/json", CHUNKS).await;
+
+        #[allow(unused_mut)]
+        let mut client = ClientBuilder::new(&url)
+            .auth(Auth::bearer("test-token"))
+            .enable_cache(false)
+            .allow_insecure_http()
+            .build()
+            .unwrap();
 
-        // Determine TTL from Cache-Control or use default
-        let ttl = if let Some(_etag) = &secret.etag {
-            // If we have an etag, use a longer TTL since we can validate
-            Duration::from_secs(self.config.cache_config.default_ttl_secs * 2)
-        } else {
-            Duration::from_secs(self.config.cache_config.default_ttl_secs)
+        #[cfg(feature = "metrics")]
+        let (provider, exporter) = {
+            use opentelemetry::metrics::MeterProvider as _;
+            use opentelemetry_sdk::metrics::{MeterProvider, PeriodicReader};
+            use opentelemetry_sdk::testing::metrics::InMemoryMetricsExporter;
+
+            let exporter = InMemoryMetricsExporter::default();
+            let provider = MeterProvider::builder()
+                .with_reader(
+                    PeriodicReader::builder(exporter.clone(), opentelemetry_sdk::runtime::Tokio)
+                        .build(),
+                )
+                .build();
+            client.metrics = std::sync::Arc::new(telemetry::Metrics::from_meter(
+                &provider.meter("secret-store-sdk-test"),
+            ));
+            (provider, exporter)
         };
 
-        let cache_expires_at = time::OffsetDateTime::now_utc() + ttl;
+        let secret = client
+            .get_secret("test-ns", "test-key", GetOpts::default())
+            .await
+            .unwrap();
+        assert_eq!(secret.value.expose_secret(), "secret-value");
+        assert_eq!(secret.version, 1);
 
-        let cached = CachedSecret {
-            value: secret.value.clone(),
-            version: secret.version,
-            expires_at: secret.expires_at,
-            metadata: secret.metadata.clone(),
-            updated_at: secret.updated_at,
-            etag: secret.etag.clone(),
-            last_modified: secret.last_modified.clone(),
-            cache_expires_at,
-        };
+        // Without Content-Length the size is counted as the body streams in
+        #[cfg(feature = "metrics")]
+        {
+            use opentelemetry_sdk::metrics::data;
+
+            provider.force_flush().unwrap();
+            let metrics = exporter.get_finished_metrics().unwrap();
+            let response_bytes = metrics
+                .iter()
+                .flat_map(|rm| &rm.scope_metrics)
+                .flat_map(|sm| &sm.metrics)
+                .find(|m| m.name == "secret_store_sdk.response_bytes")
+                .expect("response size histogram recorded");
+            let histogram = response_bytes
+                .data
+                .as_any()
+                .downcast_ref::<data::Histogram<u64>>()
+                .unwrap();
+            assert_eq!(histogram.data_points[0].count, 1);
+            assert_eq!(histogram.data_points[0].sum, body_len as u64);
+        }
 
-        cache.insert(cache_key.to_string(), cached).await;
-        self.stats.record_insertion();
-        debug!("Cached secret for key: {} with TTL: {:?}", cache_key, ttl);
+        // The size limit applies even though no length was declared
+        let capped = ClientBuilder::new(&url)
+            .auth(Auth::bearer("test-token"))
+            .enable_cache(false)
+            .max_response_bytes(body_len as u64 - 1)
+            .allow_insecure_http()
+            .build()
+            .unwrap();
+        let err = capped
+            .get_secret("test-ns", "test-key", GetOpts::default())
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("exceeds limit"));
+
+        let exact = ClientBuilder::new(&url)
+            .auth(Auth::bearer("test-token"))
+            .enable_cache(false)
+            .max_response_bytes(body_len as u64)
+            .allow_insecure_http()
+            .build()
+            .unwrap();
+        assert!(exact
+            .get_secret("test-ns", "test-key", GetOpts::default())
+            .await
+            .is_ok());
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::{auth::Auth, ClientBuilder};
-    use secrecy::ExposeSecret;
-    use wiremock::matchers::{header, method, path};
-    use wiremock::{Mock, MockServer, ResponseTemplate};
+    #[cfg(all(feature = "metrics", feature = "danger-insecure-http"))]
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_get_secret_stream_records_chunked_size() {
+        use opentelemetry::metrics::MeterProvider as _;
+        use opentelemetry_sdk::metrics::{data, MeterProvider, PeriodicReader};
+        use opentelemetry_sdk::testing::metrics::InMemoryMetricsExporter;
+        use tokio::io::AsyncReadExt;
 
-    // Helper function to create test client that works with HTTP URLs
-    fn create_test_client(base_url: &str) -> Client {
-        #[cfg(feature = "danger-insecure-http")]
-        {
-            ClientBuilder::new(base_url)
-                .auth(Auth::bearer("test-token"))
-                .allow_insecure_http()
-                .build()
-                .unwrap()
-        }
-        #[cfg(not(feature = "danger-insecure-http"))]
-        {
-            // In tests without the feature, we'll just use a dummy HTTPS URL
-            // The actual URL doesn't matter since we're mocking
-            ClientBuilder::new(&base_url.replace("http://", "https://"))
-                .auth(Auth::bearer("test-token"))
-                .build()
-                .unwrap()
-        }
-    }
+        const CHUNKS: &[&str] = &["-----BEGIN ", "CERTIFICATE", "-----"];
+        let url = spawn_chunked_server("application/octet-stream", CHUNKS).await;
 
-    #[test]
-    fn test_client_creation() {
-        let client = ClientBuilder::new("https://example.com")
+        let mut client = ClientBuilder::new(&url)
             .auth(Auth::bearer("test-token"))
+            .allow_insecure_http()
+            .build()
+            .unwrap();
+        let exporter = InMemoryMetricsExporter::default();
+        let provider = MeterProvider::builder()
+            .with_reader(
+                PeriodicReader::builder(exporter.clone(), opentelemetry_sdk::runtime::Tokio)
+                    .build(),
+            )
             .build();
-        assert!(client.is_ok());
-    }
+        client.metrics = std::sync::Arc::new(telemetry::Metrics::from_meter(
+            &provider.meter("secret-store-sdk-test"),
+        ));
 
-    #[test]
-    fn test_cache_key_format() {
-        let cache_key = format!("{}/{}", "namespace", "key");
-        assert_eq!(cache_key, "namespace/key");
+        let (metadata, mut stream) = client
+            .get_secret_stream("test-ns", "bundle", GetOpts::default())
+            .await
+            .unwrap();
+        assert_eq!(metadata.content_length, None);
+        let mut value = Vec::new();
+        let _ = stream.read_to_end(&mut value).await.unwrap();
+        assert_eq!(value, CHUNKS.concat().as_bytes());
+
+        // The size is recorded once the stream has been read to the end
+        provider.force_flush().unwrap();
+        let metrics = exporter.get_finished_metrics().unwrap();
+        let response_bytes = metrics
+            .iter()
+            .flat_map(|rm| &rm.scope_metrics)
+            .flat_map(|sm| &sm.metrics)
+            .find(|m| m.name == "secret_store_sdk.response_bytes")
+            .expect("response size histogram recorded");
+        let histogram = response_bytes
+            .data
+            .as_any()
+            .downcast_ref::<data::Histogram<u64>>()
+            .unwrap();
+        assert_eq!(histogram.data_points[0].count, 1);
+        assert_eq!(histogram.data_points[0].sum, value.len() as u64);
     }
 
     #[tokio::test]
-    async fn test_get_secret_success() {
-        let mock_server = MockServer::start().await;
+    async fn test_batch_rollback_with_one_failure() {
+        use wiremock::matchers::body_partial_json;
 
-        // Mock successful response
-        let response_body = serde_json::json!({
-            "value": "secret-value",
-            "version": 1,
-            "expires_at": null,
-            "metadata": {"env": "prod"},
-            "updated_at": "2024-01-01T00:00:00Z"
-        });
+        let mock_server = MockServer::start().await;
+        let operations = serde_json::json!([
+            {"action": "rollback", "key": "db-url", "version": 4},
+            {"action": "rollback", "key": "api-key", "version": 99},
+        ]);
 
         Mock::given(method("GET"))
-            .and(path("/api/v2/secrets/test-namespace/test-key"))
-            .and(header("Authorization", "Bearer test-token"))
-            .respond_with(
-                ResponseTemplate::new(200)
-                    .set_body_json(&response_body)
-                    .insert_header("etag", "\"abc123\"")
-                    .insert_header("x-request-id", "req-123"),
-            )
+            .and(path("/api/v2/secrets/test-ns/db-url"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "value": "v",
+                "version": 5,
+                "updated_at": "2024-01-01T00:00:00Z"
+            })))
+            .expect(2)
             .mount(&mock_server)
             .await;
 
-        let client = create_test_client(&mock_server.uri());
-
-        let result = client
-            .get_secret("test-namespace", "test-key", GetOpts::default())
+        Mock::given(method("POST"))
+            .and(path("/api/v2/secrets/test-ns/batch"))
+            .and(body_partial_json(serde_json::json!({
+                "operations": operations,
+                "transactional": false,
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "namespace": "test-ns",
+                "results": {
+                    "succeeded": [{"key": "db-url", "action": "rollback", "success": true}],
+                    "failed": [{"key": "api-key", "action": "rollback", "success": false,
+                                "error": "version 99 not found"}],
+                    "total": 2
+                },
+                "success_rate": 0.5
+            })))
+            .expect(1)
+            .mount(&mock_server)
             .await;
-        if let Err(ref e) = result {
-            eprintln!("Error: {:?}", e);
-        }
-        assert!(result.is_ok());
-
-        let secret = result.unwrap();
-        assert_eq!(secret.namespace, "test-namespace");
-        assert_eq!(secret.key, "test-key");
-        assert_eq!(secret.version, 1);
-        assert_eq!(secret.etag, Some("\"abc123\"".to_string()));
-    }
-
-    #[tokio::test]
-    async fn test_get_secret_404() {
-        let mock_server = MockServer::start().await;
 
-        let error_body = serde_json::json!({
-            "error": "not_found",
-            "message": "Secret not found",
-            "timestamp": "2024-01-01T00:00:00Z",
-            "status": 404
-        });
-
-        Mock::given(method("GET"))
-            .and(path("/api/v2/secrets/test-namespace/missing-key"))
-            .respond_with(
-                ResponseTemplate::new(404)
-                    .set_body_json(&error_body)
-                    .insert_header("x-request-id", "req-456"),
-            )
+        Mock::given(method("POST"))
+            .and(path("/api/v2/secrets/test-ns/batch"))
+            .and(body_partial_json(serde_json::json!({
+                "operations": operations,
+                "transactional": true,
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "namespace": "test-ns",
+                "results": {
+                    "succeeded": [],
+                    "failed": [
+                        {"key": "db-url", "action": "rollback", "success": false,
+                         "error": "transaction aborted"},
+                        {"key": "api-key", "action": "rollback", "success": false,
+                         "error": "version 99 not found"}
+                    ],
+                    "total": 2
+                },
+                "success_rate": 0.0
+            })))
+            .expect(1)
             .mount(&mock_server)
             .await;
 
         let client = create_test_client(&mock_server.uri());
+        let targets = || vec![("db-url".to_string(), 4), ("api-key".to_string(), 99)];
+
+        // Warm the cache so the rollback has something to invalidate
+        let _ = client
+            .get_secret("test-ns", "db-url", GetOpts::default())
+            .await
+            .unwrap();
 
         let result = client
-            .get_secret("test-namespace", "missing-key", GetOpts::default())
-            .await;
-        assert!(result.is_err());
+            .batch_rollback("test-ns", targets(), false, None)
+            .await
+            .unwrap();
+        assert!(!result.is_full_success());
+        assert_eq!(result.succeeded_keys(), vec!["db-url"]);
+        assert_eq!(result.failed_keys(), vec!["api-key"]);
 
-        let err = result.unwrap_err();
-        assert_eq!(err.status_code(), Some(404));
-        assert_eq!(err.request_id(), Some("req-456"));
+        let secret = client
+            .get_secret("test-ns", "db-url", GetOpts::default())
+            .await
+            .unwrap();
+        assert!(!secret.from_cache);
+
+        let result = client
+            .batch_rollback("test-ns", targets(), true, Some("incident-42".to_string()))
+            .await
+            .unwrap();
+        assert!(result.succeeded_keys().is_empty());
+        assert_eq!(result.failed_keys(), vec!["db-url", "api-key"]);
+        assert_eq!(result.idempotency_key.as_deref(), Some("incident-42"));
     }
 
     #[tokio::test]
-    async fn test_get_secret_with_cache() {
-        let mock_server = MockServer::start().await;
+    async fn test_get_secret_stream_matches_buffered_read() {
+        use tokio::io::AsyncReadExt;
 
-        let response_body = serde_json::json!({
-            "value": "cached-value",
-            "version": 2,
-            "expires_at": null,
-            "metadata": null,
-            "updated_at": "2024-01-01T00:00:00Z"
-        });
+        let mock_server = MockServer::start().await;
+        let value: String = (0..2 * 1024 * 1024)
+            .map(|i| (b'a' + (i % 26) as u8) as char)
+            .collect();
 
-        // First request
         Mock::given(method("GET"))
-            .and(path("/api/v2/secrets/cache-ns/cache-key"))
+            .and(path("/api/v2/secrets/test-ns/bundle"))
+            .and(header("accept", "application/octet-stream"))
             .respond_with(
                 ResponseTemplate::new(200)
-                    .set_body_json(&response_body)
-                    .insert_header("etag", "\"etag123\""),
+                    .set_body_raw(value.clone().into_bytes(), "application/octet-stream")
+                    .insert_header("x-secret-version", "3")
+                    .insert_header("etag", "\"v3\""),
             )
-            .expect(1) // Should only be called once
+            .with_priority(1)
+            .expect(1)
             .mount(&mock_server)
             .await;
 
-        #[cfg(feature = "danger-insecure-http")]
-        let client = ClientBuilder::new(mock_server.uri())
-            .auth(Auth::bearer("test-token"))
-            .enable_cache(true)
-            .allow_insecure_http()
-            .build()
-            .unwrap();
-
-        #[cfg(not(feature = "danger-insecure-http"))]
-        let client = ClientBuilder::new(&mock_server.uri().replace("http://", "https://"))
-            .auth(Auth::bearer("test-token"))
-            .enable_cache(true)
-            .build()
-            .unwrap();
+        Mock::given(method("GET"))
+            .and(path("/api/v2/secrets/test-ns/bundle"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "value": value,
+                "version": 3,
+                "updated_at": "2024-01-01T00:00:00Z"
+            })))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
 
-        // First request - should hit server
-        let secret1 = client
-            .get_secret("cache-ns", "cache-key", GetOpts::default())
+        let client = create_test_client(&mock_server.uri());
+        let (metadata, mut stream) = client
+            .get_secret_stream("test-ns", "bundle", GetOpts::default())
             .await
             .unwrap();
-        assert_eq!(secret1.version, 2);
+        assert_eq!(metadata.version, Some(3));
+        assert_eq!(metadata.etag.as_deref(), Some("\"v3\""));
+        assert_eq!(metadata.content_length, Some(value.len() as u64));
 
-        // Small delay to ensure cache is populated
-        tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        let mut streamed = Vec::new();
+        let _ = stream.read_to_end(&mut streamed).await.unwrap();
 
-        // Second request - should hit cache
-        let secret2 = client
-            .get_secret("cache-ns", "cache-key", GetOpts::default())
+        let buffered = client
+            .get_secret("test-ns", "bundle", GetOpts::default())
             .await
             .unwrap();
-        assert_eq!(secret2.version, 2);
+        assert!(!buffered.from_cache);
+        assert_eq!(streamed, buffered.value.expose_secret().as_bytes());
+    }
 
-        // Verify cache hit
-        let stats = client.cache_stats();
-        assert_eq!(stats.hits(), 1);
-        assert_eq!(stats.misses(), 1);
+    #[tokio::test]
+    async fn test_get_secret_stream_verifies_digest() {
+        use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
+        use sha2::{Digest, Sha256};
+        use tokio::io::AsyncReadExt;
+
+        async fn read(client: &Client, key: &str) -> std::io::Result<Vec<u8>> {
+            let (_, mut stream) = client
+                .get_secret_stream("test-ns", key, GetOpts::default())
+                .await
+                .unwrap();
+            let mut value = Vec::new();
+            let _ = stream.read_to_end(&mut value).await?;
+            Ok(value)
+        }
+
+        let mock_server = MockServer::start().await;
+        let value = b"-----BEGIN CERTIFICATE-----".to_vec();
+        let digest = format!("sha-256=:{}:", BASE64.encode(Sha256::digest(&value)));
+        for (key, body, digest) in [
+            ("intact", value.clone(), Some(digest.as_str())),
+            (
+                "tampered",
+                b"-----BEGIN TAMPERED-----".to_vec(),
+                Some(&digest),
+            ),
+            ("unsigned", value.clone(), None),
+        ] {
+            let mut response =
+                ResponseTemplate::new(200).set_body_raw(body, "application/octet-stream");
+            if let Some(digest) = digest {
+                response = response.append_header("content-digest", digest);
+            }
+            Mock::given(method("GET"))
+                .and(path(format!("/api/v2/secrets/test-ns/{}", key)))
+                .respond_with(response)
+                .mount(&mock_server)
+                .await;
+        }
+
+        let builder = |require: bool| {
+            #[cfg(feature = "danger-insecure-http")]
+            let builder = ClientBuilder::new(mock_server.uri()).allow_insecure_http();
+            #[cfg(not(feature = "danger-insecure-http"))]
+            let builder = ClientBuilder::new(mock_server.uri().replace("http://", "https://"));
+            builder
+                .auth(Auth::bearer("test-token"))
+                .verify_content_digest(true)
+                .require_content_digest(require)
+                .build()
+                .unwrap()
+        };
+
+        let client = builder(false);
+        assert_eq!(read(&client, "intact").await.unwrap(), value);
+        let err = read(&client, "tampered").await.unwrap_err();
+        assert!(err.to_string().contains("content digest mismatch"));
+        assert_eq!(read(&client, "unsigned").await.unwrap(), value);
+
+        let strict = builder(true);
+        assert_eq!(read(&strict, "intact").await.unwrap(), value);
+        let err = read(&strict, "unsigned").await.unwrap_err();
+        assert!(err.to_string().contains("content digest missing"));
+    }
+
+    #[tokio::test]
+    async fn test_expiring_soon() {
+        let mock_server = MockServer::start().await;
+        let now = time::OffsetDateTime::now_utc();
+        let at = |hours: i64| {
+            (now + time::Duration::hours(hours))
+                .format(&time::format_description::well_known::Rfc3339)
+                .unwrap()
+        };
+
+        Mock::given(method("GET"))
+            .and(path("/api/v2/secrets/test-ns"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "namespace": "test-ns",
+                "secrets": [
+                    {"key": "cert", "version": 3, "expires_at": at(48)},
+                    {"key": "db-url", "version": 2},
+                    {"key": "token", "version": 7, "expires_at": at(2)},
+                    {"key": "license", "version": 1, "expires_at": at(24 * 90)}
+                ],
+                "total": 4
+            })))
+            .mount(&mock_server)
+            .await;
+
+        let client = create_test_client(&mock_server.uri());
+        let expiring = client
+            .expiring_soon("test-ns", Duration::from_secs(7 * 24 * 3600))
+            .await
+            .unwrap();
+
+        let keys: Vec<&str> = expiring.iter().map(|s| s.key.as_str()).collect();
+        assert_eq!(keys, ["token", "cert"]);
+        assert_eq!(expiring[0].version, Some(7));
+        assert_eq!(expiring[1].version, Some(3));
+        assert!(expiring[0].expires_at < expiring[1].expires_at);
+
+        // No value was fetched
+        let requests = mock_server.received_requests().await.unwrap();
+        assert!(requests
+            .iter()
+            .all(|r| r.url.path() == "/api/v2/secrets/test-ns"));
     }
 
     #[tokio::test]
-    async fn test_get_secret_304_not_modified() {
+    async fn test_retry_on_empty_body() {
         let mock_server = MockServer::start().await;
-
-        let response_body = serde_json::json!({
-            "value": "initial-value",
+        let secret_body = serde_json::json!({
+            "value": "secret-value",
             "version": 1,
-            "expires_at": null,
-            "metadata": null,
             "updated_at": "2024-01-01T00:00:00Z"
         });
 
-        // Mount both mocks at once with more specific one first
-        // Second request with etag - return 304 (more specific, so should match first)
         Mock::given(method("GET"))
-            .and(path("/api/v2/secrets/test-ns/test-key"))
-            .and(header("Authorization", "Bearer test-token"))
-            .and(header("if-none-match", "etag-v1"))
-            .respond_with(ResponseTemplate::new(304))
+            .and(path("/api/v2/secrets/test-ns/flaky"))
+            .respond_with(ResponseTemplate::new(200))
+            .up_to_n_times(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/api/v2/secrets/test-ns/flaky"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(&secret_body))
             .expect(1)
             .mount(&mock_server)
             .await;
 
-        // First request - return data (less specific)
+        // Well-formed JSON of the wrong shape is not retried
         Mock::given(method("GET"))
-            .and(path("/api/v2/secrets/test-ns/test-key"))
-            .and(header("Authorization", "Bearer test-token"))
+            .and(path("/api/v2/secrets/test-ns/wrong-shape"))
             .respond_with(
-                ResponseTemplate::new(200)
-                    .set_body_json(&response_body)
-                    .insert_header("etag", "\"etag-v1\""),
+                ResponseTemplate::new(200).set_body_json(serde_json::json!({"unexpected": true})),
             )
             .expect(1)
             .mount(&mock_server)
             .await;
 
-        #[cfg(feature = "danger-insecure-http")]
-        let client = ClientBuilder::new(mock_server.uri())
-            .auth(Auth::bearer("test-token"))
-            .enable_cache(true)
-            .allow_insecure_http()
-            .build()
-            .unwrap();
-
-        #[cfg(not(feature = "danger-insecure-http"))]
-        let client = ClientBuilder::new(&mock_server.uri().replace("http://", "https://"))
-            .auth(Auth::bearer("test-token"))
-            .enable_cache(true)
-            .build()
-            .unwrap();
+        let mut client = create_test_client(&mock_server.uri());
+        client.config.retry_on_empty_body = true;
 
-        // First request
-        let secret1 = client
-            .get_secret("test-ns", "test-key", GetOpts::default())
+        let secret = client
+            .get_secret("test-ns", "flaky", GetOpts::default())
             .await
             .unwrap();
-        assert_eq!(secret1.etag, Some("\"etag-v1\"".to_string()));
-
-        // Clear cache to force second request to hit server
-        client.clear_cache();
-
-        // Second request with etag
-        let opts = GetOpts {
-            use_cache: false, // Disable cache to ensure we hit the server
-            if_none_match: Some("etag-v1".to_string()), // Without quotes
-            if_modified_since: None,
-        };
-        // This should return error since cache was cleared and server returns 304
-        let result = client.get_secret("test-ns", "test-key", opts).await;
-        assert!(result.is_err());
+        assert_eq!(secret.value.expose_secret(), "secret-value");
+        assert_eq!(client.retry_stats().by_reason("bad_body"), 1);
 
-        // The error should indicate that we got 304 but have no cache
-        if let Err(e) = result {
-            match &e {
-                Error::Other(msg) => {
-                    assert!(msg.contains("304"));
-                    assert!(msg.contains("no cached entry"));
-                }
-                _ => panic!("Expected Error::Other, got {:?}", e),
-            }
-        }
+        let err = client
+            .get_secret("test-ns", "wrong-shape", GetOpts::default())
+            .await
+            .unwrap_err();
+        assert!(matches!(err, Error::Deserialize(_)));
+        assert_eq!(client.retry_stats().by_reason("bad_body"), 1);
     }
 
     #[tokio::test]
-    async fn test_put_secret_success() {
+    async fn test_get_or_create() {
         let mock_server = MockServer::start().await;
+        let secret_body = |value: &str| {
+            serde_json::json!({
+                "value": value,
+                "version": 1,
+                "updated_at": "2024-01-01T00:00:00Z"
+            })
+        };
+        let put_body = |key: &str| {
+            serde_json::json!({
+                "message": "Secret created",
+                "namespace": "test-ns",
+                "key": key,
+                "created_at": "2024-01-01T00:00:00Z",
+                "request_id": "req-create"
+            })
+        };
 
-        let response_body = serde_json::json!({
-            "message": "Secret created",
-            "namespace": "test-ns",
-            "key": "new-key",
-            "created_at": "2024-01-01T00:00:00Z",
-            "request_id": "req-789"
-        });
-
+        // Existing key: no write
+        Mock::given(method("GET"))
+            .and(path("/api/v2/secrets/test-ns/existing"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(secret_body("current")))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
         Mock::given(method("PUT"))
-            .and(path("/api/v2/secrets/test-ns/new-key"))
-            .respond_with(ResponseTemplate::new(201).set_body_json(&response_body))
+            .and(path("/api/v2/secrets/test-ns/existing"))
+            .respond_with(ResponseTemplate::new(201).set_body_json(put_body("existing")))
+            .expect(0)
             .mount(&mock_server)
             .await;
 
-        let client = create_test_client(&mock_server.uri());
-
-        let opts = PutOpts {
-            ttl_seconds: Some(3600),
-            metadata: Some(serde_json::json!({"env": "test"})),
-            idempotency_key: None,
-        };
-
-        let result = client
-            .put_secret("test-ns", "new-key", "new-value", opts)
+        // Missing key: created with the default
+        Mock::given(method("GET"))
+            .and(path("/api/v2/secrets/test-ns/missing"))
+            .respond_with(ResponseTemplate::new(404))
+            .up_to_n_times(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("PUT"))
+            .and(path("/api/v2/secrets/test-ns/missing"))
+            .and(header("if-none-match", "*"))
+            .and(wiremock::matchers::body_partial_json(
+                serde_json::json!({"value": "{}"}),
+            ))
+            .respond_with(ResponseTemplate::new(201).set_body_json(put_body("missing")))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/api/v2/secrets/test-ns/missing"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(secret_body("{}")))
+            .mount(&mock_server)
             .await;
-        assert!(result.is_ok());
-
-        let put_result = result.unwrap();
-        assert_eq!(put_result.namespace, "test-ns");
-        assert_eq!(put_result.key, "new-key");
-    }
-
-    #[tokio::test]
-    async fn test_delete_secret_success() {
-        let mock_server = MockServer::start().await;
 
-        Mock::given(method("DELETE"))
-            .and(path("/api/v2/secrets/test-ns/delete-key"))
-            .respond_with(ResponseTemplate::new(204).insert_header("x-request-id", "req-delete"))
+        // Race: another writer creates the key between the get and the put
+        Mock::given(method("GET"))
+            .and(path("/api/v2/secrets/test-ns/raced"))
+            .respond_with(ResponseTemplate::new(404))
+            .up_to_n_times(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("PUT"))
+            .and(path("/api/v2/secrets/test-ns/raced"))
+            .respond_with(ResponseTemplate::new(412))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/api/v2/secrets/test-ns/raced"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(secret_body("theirs")))
             .mount(&mock_server)
             .await;
 
         let client = create_test_client(&mock_server.uri());
 
-        let result = client.delete_secret("test-ns", "delete-key").await;
-        assert!(result.is_ok());
+        let result = client
+            .get_or_create("test-ns", "existing", "{}", PutOpts::default())
+            .await
+            .unwrap();
+        assert!(!result.created);
+        assert_eq!(result.secret.value.expose_secret(), "current");
 
-        let delete_result = result.unwrap();
-        assert!(delete_result.deleted);
-        assert_eq!(delete_result.request_id, Some("req-delete".to_string()));
+        let result = client
+            .get_or_create("test-ns", "missing", "{}", PutOpts::default())
+            .await
+            .unwrap();
+        assert!(result.created);
+        assert_eq!(result.secret.value.expose_secret(), "{}");
+
+        let result = client
+            .get_or_create("test-ns", "raced", "{}", PutOpts::default())
+            .await
+            .unwrap();
+        assert!(!result.created);
+        assert_eq!(result.secret.value.expose_secret(), "theirs");
     }
 
     #[tokio::test]
-    async fn test_retry_on_server_error() {
+    async fn test_export_env_overlay() {
         let mock_server = MockServer::start().await;
 
-        let error_body = serde_json::json!({
-            "error": "internal",
-            "message": "Internal server error",
-            "timestamp": "2024-01-01T00:00:00Z",
-            "status": 500
-        });
-
-        // First two requests fail, third succeeds
         Mock::given(method("GET"))
-            .and(path("/api/v2/secrets/test-ns/retry-key"))
-            .respond_with(ResponseTemplate::new(500).set_body_json(&error_body))
-            .up_to_n_times(2)
+            .and(path("/api/v2/env/app"))
+            .and(wiremock::matchers::query_param("format", "json"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "namespace": "app",
+                "environment": {
+                    "DATABASE_URL": "postgres://dev",
+                    "DATABASE_URL__prod": "postgres://prod",
+                    "LOG_LEVEL": "debug",
+                    "API_URL.prod": "https://api.prod"
+                },
+                "etag": "\"env-v1\"",
+                "total": 4,
+                "request_id": "req-1"
+            })))
             .mount(&mock_server)
             .await;
 
-        let success_body = serde_json::json!({
-            "value": "success-after-retry",
-            "version": 1,
-            "expires_at": null,
-            "metadata": null,
-            "updated_at": "2024-01-01T00:00:00Z"
-        });
-
-        Mock::given(method("GET"))
-            .and(path("/api/v2/secrets/test-ns/retry-key"))
-            .respond_with(ResponseTemplate::new(200).set_body_json(&success_body))
-            .mount(&mock_server)
-            .await;
+        let client = create_test_client(&mock_server.uri());
 
-        #[cfg(feature = "danger-insecure-http")]
-        let client = ClientBuilder::new(mock_server.uri())
-            .auth(Auth::bearer("test-token"))
-            .retries(3)
-            .allow_insecure_http()
-            .build()
+        let export = client
+            .export_env(
+                "app",
+                ExportEnvOpts {
+                    format: ExportFormat::Dotenv,
+                    overlay: Some("prod".to_string()),
+                    ..Default::default()
+                },
+            )
+            .await
             .unwrap();
+        match export {
+            EnvExport::Text(text) => assert_eq!(
+                text,
+                "API_URL.prod=https://api.prod\nDATABASE_URL=postgres://prod\nLOG_LEVEL=debug\n"
+            ),
+            other => panic!("expected text export, got {:?}", other),
+        }
 
-        #[cfg(not(feature = "danger-insecure-http"))]
-        let client = ClientBuilder::new(&mock_server.uri().replace("http://", "https://"))
-            .auth(Auth::bearer("test-token"))
-            .retries(3)
-            .build()
+        // Custom separator
+        let export = client
+            .export_env(
+                "app",
+                ExportEnvOpts {
+                    format: ExportFormat::Json,
+                    overlay: Some("prod".to_string()),
+                    overlay_separator: Some(".".to_string()),
+                    ..Default::default()
+                },
+            )
+            .await
             .unwrap();
-
-        let result = client
-            .get_secret("test-ns", "retry-key", GetOpts::default())
-            .await;
-        assert!(result.is_ok()); // Should succeed after retries
+        match export {
+            EnvExport::Json(json) => {
+                assert_eq!(json.environment["API_URL"], "https://api.prod");
+                assert_eq!(json.environment["DATABASE_URL"], "postgres://dev");
+                assert_eq!(json.environment["DATABASE_URL__prod"], "postgres://prod");
+                assert_eq!(json.total, 4);
+            }
+            other => panic!("expected json export, got {:?}", other),
+        }
     }
 
+    #[cfg(feature = "danger-insecure-http")]
     #[tokio::test]
-    async fn test_list_secrets() {
+    async fn test_connection_debug_logs_connect_phases() {
         let mock_server = MockServer::start().await;
-
-        let response_body = serde_json::json!({
-            "namespace": "test-ns",
-            "secrets": [
-                {"key": "key1", "ver": 1, "updated_at": "2024-01-01T00:00:00Z", "kid": null},
-                {"key": "key2", "ver": 2, "updated_at": "2024-01-01T00:00:00Z", "kid": "kid123"}
-            ],
-            "total": 2,
-            "limit": 10,
-            "has_more": false,
-            "request_id": "req-list"
-        });
-
         Mock::given(method("GET"))
-            .and(path("/api/v2/secrets/test-ns"))
-            .and(wiremock::matchers::query_param("prefix", "key"))
-            .and(wiremock::matchers::query_param("limit", "10"))
-            .respond_with(ResponseTemplate::new(200).set_body_json(&response_body))
+            .and(path("/api/v2/secrets/billing/stripe-live-secret"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "namespace": "billing",
+                "key": "stripe-live-secret",
+                "value": "sk_live_hunter2",
+                "version": 1,
+                "updated_at": "2024-01-01T00:00:00Z"
+            })))
             .mount(&mock_server)
             .await;
 
-        let client = create_test_client(&mock_server.uri());
+        // Use a host name so the timing resolver is consulted
+        let port = mock_server.address().port();
+        let client = ClientBuilder::new(format!("http://localhost:{}", port))
+            .auth(Auth::bearer("test-token"))
+            .connection_debug(true)
+            .allow_insecure_http()
+            .build()
+            .unwrap();
 
-        let opts = ListOpts {
-            prefix: Some("key".to_string()),
-            limit: Some(10),
-        };
+        let capture = LogCapture::default();
+        let subscriber = tracing_subscriber::fmt()
+            .with_max_level(tracing::Level::TRACE)
+            .with_ansi(false)
+            .with_writer(capture.clone())
+            .finish();
+        let _guard = tracing::subscriber::set_default(subscriber);
 
-        let result = client.list_secrets("test-ns", opts).await;
-        assert!(result.is_ok());
+        let secret = client
+            .get_secret("billing", "stripe-live-secret", GetOpts::default())
+            .await
+            .unwrap();
+        assert_eq!(secret.version, 1);
 
-        let list_result = result.unwrap();
-        assert_eq!(list_result.namespace, "test-ns");
-        assert_eq!(list_result.secrets.len(), 2);
-        assert_eq!(list_result.total, 2);
+        let output = String::from_utf8(capture.0.lock().unwrap().clone()).unwrap();
+        let connection_lines: Vec<&str> = output
+            .lines()
+            .filter(|line| line.contains("secret_store_sdk::connection"))
+            .collect();
+
+        assert!(connection_lines
+            .iter()
+            .any(|line| line.contains("phase=\"dns\"") && line.contains("host=localhost")));
+        assert!(connection_lines
+            .iter()
+            .any(|line| line.contains("phase=\"response\"") && line.contains("status=200")));
+        assert!(connection_lines.iter().all(|line| {
+            !line.contains("stripe-live-secret")
+                && !line.contains("sk_live_hunter2")
+                && !line.contains("test-token")
+        }));
     }
 
     #[tokio::test]
-    async fn test_list_versions() {
-        let mock_server = MockServer::start().await;
-
-        let response_body = serde_json::json!({
-            "namespace": "test-ns",
-            "key": "versioned-key",
-            "versions": [
-                {
-                    "version": 3,
-                    "created_at": "2024-01-03T00:00:00Z",
-                    "created_by": "user1",
-                    "is_current": true
-                },
-                {
-                    "version": 2,
-                    "created_at": "2024-01-02T00:00:00Z",
-                    "created_by": "user1",
-                    "is_current": false
-                },
-                {
-                    "version": 1,
-                    "created_at": "2024-01-01T00:00:00Z",
-                    "created_by": "user1",
-                    "is_current": false
-                }
-            ],
-            "total": 3,
-            "request_id": "req-versions"
-        });
-
+    async fn test_cache_predicate_rejects_empty_values() {
+        let mock_server = MockServer::start().await;
         Mock::given(method("GET"))
-            .and(path("/api/v2/secrets/test-ns/versioned-key/versions"))
-            .respond_with(ResponseTemplate::new(200).set_body_json(&response_body))
+            .and(path("/api/v2/secrets/prod/placeholder"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "namespace": "prod",
+                "key": "placeholder",
+                "value": "",
+                "version": 1,
+                "updated_at": "2024-01-01T00:00:00Z"
+            })))
+            .expect(2)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/api/v2/secrets/prod/real"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "namespace": "prod",
+                "key": "real",
+                "value": "s3cret",
+                "version": 1,
+                "updated_at": "2024-01-01T00:00:00Z"
+            })))
+            .expect(1)
             .mount(&mock_server)
             .await;
 
-        let client = create_test_client(&mock_server.uri());
+        let mut client = create_test_client(&mock_server.uri());
+        client.config.cache_config.cache_predicate =
+            Some(std::sync::Arc::new(|secret: &Secret| {
+                !secret.value.expose_secret().is_empty()
+            }));
 
-        let result = client.list_versions("test-ns", "versioned-key").await;
-        assert!(result.is_ok());
+        for _ in 0..2 {
+            let secret = client
+                .get_secret("prod", "placeholder", GetOpts::default())
+                .await
+                .unwrap();
+            assert!(!secret.from_cache);
+        }
+        assert_eq!(client.cache_stats().insertions(), 0);
 
-        let version_list = result.unwrap();
-        assert_eq!(version_list.namespace, "test-ns");
-        assert_eq!(version_list.key, "versioned-key");
-        assert_eq!(version_list.versions.len(), 3);
-        assert_eq!(version_list.total, 3);
-        assert!(version_list.versions[0].is_current);
+        let _ = client
+            .get_secret("prod", "real", GetOpts::default())
+            .await
+            .unwrap();
+        let cached = client
+            .get_secret("prod", "real", GetOpts::default())
+            .await
+            .unwrap();
+        assert!(cached.from_cache);
+        assert_eq!(client.cache_stats().insertions(), 1);
     }
 
     #[tokio::test]
-    async fn test_get_version() {
+    async fn test_read_only_client_blocks_mutations() {
         let mock_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/api/v2/secrets/prod/db-url"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "namespace": "prod",
+                "key": "db-url",
+                "value": "postgres://db",
+                "version": 1,
+                "updated_at": "2024-01-01T00:00:00Z"
+            })))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        // Nothing else may reach the server
+        Mock::given(wiremock::matchers::any())
+            .respond_with(ResponseTemplate::new(200))
+            .expect(0)
+            .mount(&mock_server)
+            .await;
 
-        let response_body = serde_json::json!({
-            "value": "version-2-value",
-            "version": 2,
-            "expires_at": null,
-            "metadata": {"note": "version 2"},
-            "updated_at": "2024-01-02T00:00:00Z"
-        });
+        let mut client = create_test_client(&mock_server.uri());
+        client.config.read_only = true;
+
+        let api_key = CreateApiKeyRequest::builder()
+            .name("deployer")
+            .namespace("prod")
+            .permission(Permission::Read)
+            .build()
+            .unwrap();
+        #[cfg_attr(not(feature = "tower"), allow(unused_mut))]
+        let mut results: Vec<(&str, Result<()>)> = vec![
+            (
+                "put_secret",
+                client
+                    .put_secret("prod", "k", "v", PutOpts::default())
+                    .await
+                    .map(drop),
+            ),
+            (
+                "create_secret",
+                client
+                    .create_secret("prod", "k", "v", PutOpts::default())
+                    .await
+                    .map(drop),
+            ),
+            (
+                "generate_secret",
+                client
+                    .generate_secret("prod", "k", GenerateSpec::hex(16), PutOpts::default())
+                    .await
+                    .map(drop),
+            ),
+            (
+                "delete_secret",
+                client.delete_secret("prod", "k").await.map(drop),
+            ),
+            (
+                "restore_secret",
+                client.restore_secret("prod", "k").await.map(drop),
+            ),
+            (
+                "batch_operate",
+                client
+                    .batch_operate("prod", vec![BatchOp::delete("k")], false, None)
+                    .await
+                    .map(drop),
+            ),
+            (
+                "batch_rollback",
+                client
+                    .batch_rollback("prod", vec![("k".to_string(), 1)], false, None)
+                    .await
+                    .map(drop),
+            ),
+            (
+                "import_env_from_reader",
+                client
+                    .import_env_from_reader(
+                        "prod",
+                        &b"K=v\n"[..],
+                        ImportFormat::Dotenv,
+                        ImportOpts::default(),
+                    )
+                    .await
+                    .map(drop),
+            ),
+            ("rollback", client.rollback("prod", "k", 1).await.map(drop)),
+            (
+                "create_namespace",
+                client.create_namespace("new", None, None).await.map(drop),
+            ),
+            (
+                "init_namespace",
+                client
+                    .init_namespace("new", NamespaceTemplate::default(), None)
+                    .await
+                    .map(drop),
+            ),
+            (
+                "delete_namespace",
+                client.delete_namespace("prod").await.map(drop),
+            ),
+            (
+                "delete_namespace_idempotent",
+                client
+                    .delete_namespace_idempotent("prod", None)
+                    .await
+                    .map(drop),
+            ),
+            (
+                "create_api_key",
+                client.create_api_key(api_key, None).await.map(drop),
+            ),
+            (
+                "revoke_api_key",
+                client.revoke_api_key("key-1").await.map(drop),
+            ),
+            (
+                "raw_request",
+                client
+                    .raw_request(Method::POST, "/api/v2/preview/widgets", None)
+                    .await
+                    .map(drop),
+            ),
+        ];
+        #[cfg(feature = "tower")]
+        {
+            use tower::ServiceExt;
+            results.push((
+                "service",
+                client
+                    .service()
+                    .oneshot(SecretRequest::delete_secret("prod", "k"))
+                    .await
+                    .map(drop),
+            ));
+        }
+        for (name, result) in results {
+            match result {
+                Err(Error::Config(message)) => {
+                    assert_eq!(message, "client is read-only", "{}", name)
+                }
+                other => panic!("{} was not blocked: {:?}", name, other),
+            }
+        }
+
+        let secret = client
+            .get_secret("prod", "db-url", GetOpts::default())
+            .await
+            .unwrap();
+        assert_eq!(secret.value.expose_secret(), "postgres://db");
+    }
 
+    #[tokio::test]
+    async fn test_deprecation_headers_warn_once() {
+        let mock_server = MockServer::start().await;
         Mock::given(method("GET"))
-            .and(path("/api/v2/secrets/test-ns/versioned-key/versions/2"))
+            .and(path("/api/v2/secrets/prod/db-url"))
             .respond_with(
                 ResponseTemplate::new(200)
-                    .set_body_json(&response_body)
-                    .insert_header("etag", "\"etag-v2\""),
+                    .insert_header("Deprecation", "@1735689600")
+                    .insert_header("Sunset", "Wed, 31 Dec 2025 23:59:59 GMT")
+                    .set_body_json(serde_json::json!({
+                        "namespace": "prod",
+                        "key": "db-url",
+                        "value": "postgres://db",
+                        "version": 1,
+                        "updated_at": "2024-01-01T00:00:00Z"
+                    })),
             )
+            .expect(2)
             .mount(&mock_server)
             .await;
 
         let client = create_test_client(&mock_server.uri());
+        let capture = LogCapture::default();
+        let subscriber = tracing_subscriber::fmt()
+            .with_max_level(tracing::Level::TRACE)
+            .with_ansi(false)
+            .with_writer(capture.clone())
+            .finish();
+        let _guard = tracing::subscriber::set_default(subscriber);
+
+        for _ in 0..2 {
+            let opts = GetOpts {
+                use_cache: false,
+                ..Default::default()
+            };
+            let _ = client.get_secret("prod", "db-url", opts).await.unwrap();
+        }
 
-        let result = client.get_version("test-ns", "versioned-key", 2).await;
-        assert!(result.is_ok());
-
-        let secret = result.unwrap();
-        assert_eq!(secret.namespace, "test-ns");
-        assert_eq!(secret.key, "versioned-key");
-        assert_eq!(secret.version, 2);
-        assert_eq!(secret.value.expose_secret(), "version-2-value");
+        let output = String::from_utf8(capture.0.lock().unwrap().clone()).unwrap();
+        let warnings: Vec<&str> = output
+            .lines()
+            .filter(|line| line.contains("WARN") && line.contains("deprecated"))
+            .collect();
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].contains("Wed, 31 Dec 2025 23:59:59 GMT"));
+        assert!(warnings[0].contains("@1735689600"));
     }
 
     #[tokio::test]
-    async fn test_rollback() {
+    async fn test_upgrade_required_maps_to_clear_error() {
         let mock_server = MockServer::start().await;
-
-        let response_body = serde_json::json!({
-            "message": "Secret successfully rolled back to version 2",
-            "namespace": "test-ns",
-            "key": "versioned-key",
-            "from_version": 4,
-            "to_version": 2,
-            "request_id": "req-rollback"
-        });
-
-        Mock::given(method("POST"))
-            .and(path("/api/v2/secrets/test-ns/versioned-key/rollback/2"))
-            .respond_with(ResponseTemplate::new(200).set_body_json(&response_body))
+        Mock::given(method("GET"))
+            .and(path("/api/v2/secrets/prod/db-url"))
+            .respond_with(
+                ResponseTemplate::new(426)
+                    .insert_header("x-min-api-version", "v3")
+                    .insert_header("x-request-id", "req-426"),
+            )
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/api/v2/secrets/prod/api-key"))
+            .respond_with(ResponseTemplate::new(426).set_body_json(serde_json::json!({
+                "error": "upgrade_required",
+                "message": "API v2 has been retired",
+                "timestamp": "2024-01-01T00:00:00Z",
+                "status": 426,
+                "min_api_version": "v3"
+            })))
+            .expect(1)
             .mount(&mock_server)
             .await;
 
         let client = create_test_client(&mock_server.uri());
 
-        let result = client.rollback("test-ns", "versioned-key", 2).await;
-        assert!(result.is_ok());
+        let err = client
+            .get_secret("prod", "api-key", GetOpts::default())
+            .await
+            .unwrap_err();
+        let message = err.to_string();
+        assert!(message.contains("server requires API version v3"));
+        assert!(message.contains("API v2 has been retired"));
 
-        let rollback_result = result.unwrap();
-        assert_eq!(rollback_result.namespace, "test-ns");
-        assert_eq!(rollback_result.key, "versioned-key");
-        assert_eq!(rollback_result.from_version, 4);
-        assert_eq!(rollback_result.to_version, 2);
+        let err = client
+            .get_secret("prod", "db-url", GetOpts::default())
+            .await
+            .unwrap_err();
+        match &err {
+            Error::Http {
+                status,
+                category,
+                message,
+                request_id,
+            } => {
+                assert_eq!(*status, 426);
+                assert_eq!(category, "upgrade_required");
+                assert!(message.contains("server requires API version v3"));
+                assert!(message.contains(crate::VERSION));
+                assert_eq!(request_id.as_deref(), Some("req-426"));
+            }
+            other => panic!("unexpected error: {:?}", other),
+        }
+        assert!(!err.is_retryable());
     }
 
     #[tokio::test]
-    async fn test_audit_logs() {
-        let mock_server = MockServer::start().await;
+    async fn test_put_secret_bytes_round_trip() {
+        use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
 
-        let response_body = serde_json::json!({
-            "logs": [
-                {
-                    "id": 123,
-                    "timestamp": "2024-01-01T12:00:00Z",
-                    "actor": "user1",
-                    "action": "put",
-                    "namespace": "production",
-                    "key_name": "api-key",
-                    "success": true,
-                    "ip_address": "192.168.1.1",
-                    "user_agent": "SDK/1.0"
-                },
-                {
-                    "id": 124,
-                    "timestamp": "2024-01-01T12:05:00Z",
-                    "actor": "user2",
-                    "action": "get",
-                    "namespace": "production",
-                    "key_name": "db-pass",
-                    "success": false,
-                    "error": "not found"
-                }
-            ],
-            "total": 2,
-            "limit": 10,
-            "offset": 0,
-            "has_more": false,
-            "request_id": "req-audit"
-        });
+        let bytes: Vec<u8> = vec![0x00, 0xff, 0x10, 0x80, b'\n', 0x7f];
+        let encoded = BASE64.encode(&bytes);
 
+        let mock_server = MockServer::start().await;
+        Mock::given(method("PUT"))
+            .and(path("/api/v2/secrets/prod/keystore"))
+            .and(wiremock::matchers::body_json(serde_json::json!({
+                "value": &encoded,
+                "format": "binary",
+                "encoding": "base64"
+            })))
+            .respond_with(ResponseTemplate::new(201).set_body_json(serde_json::json!({
+                "message": "Secret created",
+                "namespace": "prod",
+                "key": "keystore",
+                "created_at": "2024-01-01T00:00:00Z",
+                "request_id": "req-put-bytes"
+            })))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
         Mock::given(method("GET"))
-            .and(path("/api/v2/audit"))
-            .respond_with(ResponseTemplate::new(200).set_body_json(&response_body))
+            .and(path("/api/v2/secrets/prod/keystore"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "namespace": "prod",
+                "key": "keystore",
+                "value": encoded,
+                "format": "binary",
+                "version": 1,
+                "updated_at": "2024-01-01T00:00:00Z"
+            })))
+            .expect(1)
             .mount(&mock_server)
             .await;
 
         let client = create_test_client(&mock_server.uri());
+        let _ = client
+            .put_secret_bytes("prod", "keystore", &bytes, PutOpts::default())
+            .await
+            .unwrap();
 
-        let query = AuditQuery::default();
-        let result = client.audit(query).await;
-        assert!(result.is_ok());
-
-        let audit_result = result.unwrap();
-        assert_eq!(audit_result.entries.len(), 2);
-        assert_eq!(audit_result.total, 2);
-        assert!(!audit_result.has_more);
+        let read = client
+            .get_secret_bytes("prod", "keystore", GetOpts::default())
+            .await
+            .unwrap();
+        assert_eq!(read.expose_secret(), &bytes);
 
-        // Check first entry
-        let first = &audit_result.entries[0];
-        assert_eq!(first.id, 123);
-        assert_eq!(first.action, "put");
-        assert!(first.success);
-        assert_eq!(first.namespace, Some("production".to_string()));
+        let secret = client
+            .get_secret("prod", "keystore", GetOpts::default())
+            .await
+            .unwrap();
+        assert!(secret.from_cache);
+        assert!(secret.is_binary());
     }
 
     #[tokio::test]
-    async fn test_audit_logs_with_filters() {
-        let mock_server = MockServer::start().await;
+    async fn test_single_flight_coalesces_concurrent_gets() {
+        async fn concurrent_gets(single_flight: bool) -> (usize, u64) {
+            let mock_server = MockServer::start().await;
+            Mock::given(method("GET"))
+                .and(path("/api/v2/secrets/prod/db-url"))
+                .respond_with(
+                    ResponseTemplate::new(200)
+                        .set_delay(Duration::from_millis(200))
+                        .set_body_json(serde_json::json!({
+                            "namespace": "prod",
+                            "key": "db-url",
+                            "value": "postgres://db",
+                            "version": 1,
+                            "updated_at": "2024-01-01T00:00:00Z"
+                        })),
+                )
+                .mount(&mock_server)
+                .await;
 
-        let response_body = serde_json::json!({
-            "logs": [
-                {
-                    "id": 200,
-                    "timestamp": "2024-01-02T10:00:00Z",
-                    "actor": "admin",
-                    "action": "delete",
-                    "namespace": "test",
-                    "key_name": "temp-key",
-                    "success": false,
-                    "error": "permission denied"
-                }
-            ],
-            "total": 1,
-            "limit": 5,
-            "offset": 0,
-            "has_more": false,
-            "request_id": "req-audit-filtered"
-        });
+            #[cfg(feature = "danger-insecure-http")]
+            let client = ClientBuilder::new(mock_server.uri())
+                .auth(Auth::bearer("test-token"))
+                .single_flight(single_flight)
+                .allow_insecure_http()
+                .build()
+                .unwrap();
+            #[cfg(not(feature = "danger-insecure-http"))]
+            let client = ClientBuilder::new(mock_server.uri().replace("http://", "https://"))
+                .auth(Auth::bearer("test-token"))
+                .single_flight(single_flight)
+                .build()
+                .unwrap();
+            assert_eq!(client.single_flight.is_some(), single_flight);
 
-        Mock::given(method("GET"))
-            .and(path("/api/v2/audit"))
-            .and(wiremock::matchers::query_param("namespace", "test"))
-            .and(wiremock::matchers::query_param("success", "false"))
-            .and(wiremock::matchers::query_param("limit", "5"))
-            .respond_with(ResponseTemplate::new(200).set_body_json(&response_body))
+            let gets = (0..5).map(|_| client.get_secret("prod", "db-url", GetOpts::default()));
+            for secret in futures::future::join_all(gets).await {
+                assert_eq!(secret.unwrap().value.expose_secret(), "postgres://db");
+            }
+
+            let requests = mock_server.received_requests().await.unwrap().len();
+            (requests, client.cache_stats().snapshot().coalesced_requests)
+        }
+
+        assert_eq!(concurrent_gets(true).await, (1, 4));
+        assert_eq!(concurrent_gets(false).await, (5, 0));
+    }
+
+    #[tokio::test]
+    async fn test_delete_protected_namespace() {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("DELETE"))
+            .and(path("/api/v2/namespaces/prod"))
+            .respond_with(
+                ResponseTemplate::new(409)
+                    .set_body_json(serde_json::json!({
+                        "error": "conflict",
+                        "message": "Namespace has protection enabled",
+                        "timestamp": "2024-01-01T00:00:00Z",
+                        "status": 409
+                    }))
+                    .insert_header("x-request-id", "req-409"),
+            )
             .mount(&mock_server)
             .await;
 
         let client = create_test_client(&mock_server.uri());
 
-        let query = AuditQuery {
-            namespace: Some("test".to_string()),
-            success: Some(false),
-            limit: Some(5),
-            ..Default::default()
-        };
+        let err = client.delete_namespace("prod").await.unwrap_err();
+        match &err {
+            Error::Protected {
+                namespace,
+                request_id,
+            } => {
+                assert_eq!(namespace, "prod");
+                assert_eq!(request_id.as_deref(), Some("req-409"));
+            }
+            other => panic!("expected Error::Protected, got {:?}", other),
+        }
+        assert_eq!(err.kind(), crate::ErrorKind::Protected);
+        assert_eq!(err.status_code(), Some(409));
+        assert!(err.to_string().contains("disable protection first"));
+    }
 
-        let result = client.audit(query).await;
-        assert!(result.is_ok());
+    #[tokio::test]
+    async fn test_set_namespace_protection() {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("PUT"))
+            .and(path("/api/v2/namespaces/prod/protection"))
+            .and(wiremock::matchers::body_json(
+                serde_json::json!({"enabled": false}),
+            ))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(serde_json::json!({
+                        "namespace": "prod",
+                        "protected": false
+                    }))
+                    .insert_header("x-request-id", "req-protect"),
+            )
+            .expect(1)
+            .mount(&mock_server)
+            .await;
 
-        let audit_result = result.unwrap();
-        assert_eq!(audit_result.entries.len(), 1);
-        assert_eq!(audit_result.entries[0].action, "delete");
-        assert!(!audit_result.entries[0].success);
-        assert_eq!(
-            audit_result.entries[0].error,
-            Some("permission denied".to_string())
-        );
+        let mut client = create_test_client(&mock_server.uri());
+
+        let result = client
+            .set_namespace_protection("prod", false)
+            .await
+            .unwrap();
+        assert_eq!(result.namespace, "prod");
+        assert!(!result.protected);
+        assert_eq!(result.request_id.as_deref(), Some("req-protect"));
+
+        client.config.read_only = true;
+        let err = client
+            .set_namespace_protection("prod", true)
+            .await
+            .unwrap_err();
+        assert_eq!(err.kind(), crate::ErrorKind::Config);
     }
 }
@@ -0,0 +1,53 @@
+//! Time source abstraction used for cache expiry
+
+use std::fmt::Debug;
+use time::OffsetDateTime;
+
+/// Source of the current time
+///
+/// The client uses this for all cache expiry decisions. The default is
+/// [`SystemClock`]; tests can inject a controllable clock via
+/// `ClientBuilder::clock` to advance time without sleeping.
+///
+/// # Example
+///
+/// ```
+/// use secret_store_sdk::Clock;
+/// use std::sync::Mutex;
+/// use time::OffsetDateTime;
+///
+/// #[derive(Debug)]
+/// struct FixedClock(Mutex<OffsetDateTime>);
+///
+/// impl Clock for FixedClock {
+///     fn now(&self) -> OffsetDateTime {
+///         *self.0.lock().unwrap()
+///     }
+/// }
+/// ```
+pub trait Clock: Send + Sync + Debug {
+    /// Get the current time
+    fn now(&self) -> OffsetDateTime;
+}
+
+/// Clock backed by the system time
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> OffsetDateTime {
+        OffsetDateTime::now_utc()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_system_clock() {
+        let before = OffsetDateTime::now_utc();
+        let now = SystemClock.now();
+        assert!(now >= before);
+    }
+}
@@ -1,17 +1,51 @@
-use crate::{auth::Auth, cache::CacheConfig, errors::Result, telemetry::TelemetryConfig, Error};
+#[cfg(feature = "disk-cache")]
+use crate::disk_cache::{DiskCacheConfig, DiskCacheMode};
+use crate::{
+    auth::Auth,
+    cache::{CacheCipher, CacheConfig, CachePredicate},
+    clock::{Clock, SystemClock},
+    errors::Result,
+    models::Secret,
+    telemetry::TelemetryConfig,
+    Error,
+};
+#[cfg(feature = "disk-cache")]
+use std::path::PathBuf;
+use std::sync::Arc;
 use std::time::Duration;
+use tracing::{debug, warn};
 
 /// Client configuration
 #[derive(Debug, Clone)]
 pub struct ClientConfig {
     /// Base URL of the secret store service
     pub base_url: String,
+    /// Base URLs failed over to, in order, when a host cannot be reached
+    pub fallback_urls: Vec<String>,
     /// Authentication configuration
     pub auth: Auth,
     /// Request timeout
     pub timeout: Duration,
+    /// Timeout for [`Client::ping`](crate::Client::ping)
+    pub health_timeout: Duration,
+    /// Deadline for a call including all retries and backoff (none by default)
+    pub overall_timeout: Option<Duration>,
     /// Number of retries
     pub retries: u32,
+    /// Retries for GET/HEAD requests (`None` uses `retries`)
+    pub read_retries: Option<u32>,
+    /// Retries for mutating requests (`None` uses `retries`)
+    pub write_retries: Option<u32>,
+    /// Maximum idle HTTP connections kept per host
+    pub pool_max_idle_per_host: usize,
+    /// Interval of TCP keepalive probes on pooled connections (none by default)
+    pub tcp_keepalive: Option<Duration>,
+    /// Whether `TCP_NODELAY` is set on connections
+    pub tcp_nodelay: bool,
+    /// Body size above which requests send `Expect: 100-continue` (off by default)
+    pub expect_continue_threshold: Option<u64>,
+    /// Largest response body read, after decompression (unlimited by default)
+    pub max_response_bytes: Option<u64>,
     /// User agent suffix
     pub user_agent_suffix: Option<String>,
     /// Cache configuration
@@ -20,21 +54,194 @@ pub struct ClientConfig {
     pub telemetry_config: TelemetryConfig,
     /// Allow insecure HTTP (only with danger-insecure-http feature)
     pub allow_insecure_http: bool,
+    /// Whether non-HTTPS base URLs were rejected at build time
+    pub require_https: bool,
+    /// Whether key names appear in log output (namespaces are always logged)
+    pub log_key_names: bool,
+    /// Emit connection-level diagnostics (DNS, connect and response timings)
+    pub connection_debug: bool,
+    /// Reject every mutating call before it reaches the network
+    pub read_only: bool,
+    /// Coalesce concurrent cache misses for the same secret into one request
+    pub single_flight: bool,
+    /// Default for retrying 409 conflicts on idempotency-keyed puts
+    pub retry_on_conflict: bool,
+    /// Retry successful responses with an empty or malformed JSON body
+    pub retry_on_empty_body: bool,
+    /// Retry POST/PATCH requests without an idempotency key on network errors
+    pub retry_unsafe_without_idempotency: bool,
+    /// Check response bodies against a server-declared SHA-256 digest
+    pub verify_content_digest: bool,
+    /// Reject non-empty response bodies that carry no digest
+    pub require_content_digest: bool,
+    /// Fail on response fields the SDK does not know about
+    pub strict_deserialization: bool,
+    /// Time source for cache expiry
+    pub clock: Arc<dyn Clock>,
+    /// Keys pre-fetched by [`Client::warm`](crate::Client::warm), per namespace
+    pub warm_keys: Vec<(String, Vec<String>)>,
+    /// Cipher applied to cached secret values (none by default)
+    pub cache_cipher: Option<Arc<dyn CacheCipher>>,
+    /// Persistent on-disk cache (only with disk-cache feature)
+    #[cfg(feature = "disk-cache")]
+    pub disk_cache: Option<DiskCacheConfig>,
+}
+
+impl ClientConfig {
+    /// Retry cap for a request method
+    pub(crate) fn max_retries(&self, method: &reqwest::Method) -> u32 {
+        let class_retries = if matches!(*method, reqwest::Method::GET | reqwest::Method::HEAD) {
+            self.read_retries
+        } else {
+            self.write_retries
+        };
+        class_retries.unwrap_or(self.retries)
+    }
+
+    /// Create a builder pre-populated with this configuration
+    ///
+    /// Handy for deriving clients that differ in a setting or two, such as a
+    /// per-tenant client with different credentials. Everything is carried
+    /// over, including the authentication: the new builder uses the same
+    /// credentials (and shares any token provider) unless
+    /// [`ClientBuilder::auth`] is called again. Only
+    /// [`ClientBuilder::eager_connect`], which affects building rather than
+    /// the client, starts from its default.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use secret_store_sdk::{Auth, Client};
+    /// # fn example(client: &Client) -> Result<(), Box<dyn std::error::Error>> {
+    /// let tenant = client
+    ///     .config_builder()
+    ///     .auth(Auth::bearer("tenant-token"))
+    ///     .timeout_ms(5_000)
+    ///     .build()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn to_builder(&self) -> ClientBuilder {
+        ClientBuilder {
+            base_url: self.base_url.clone(),
+            fallback_urls: self.fallback_urls.clone(),
+            auth: Some(self.auth.clone()),
+            timeout_ms: self.timeout.as_millis() as u64,
+            health_timeout_ms: self.health_timeout.as_millis() as u64,
+            overall_timeout_ms: self.overall_timeout.map(|t| t.as_millis() as u64),
+            retries: self.retries,
+            read_retries: self.read_retries,
+            write_retries: self.write_retries,
+            pool_max_idle_per_host: self.pool_max_idle_per_host,
+            tcp_keepalive: self.tcp_keepalive,
+            tcp_nodelay: self.tcp_nodelay,
+            expect_continue_threshold: self.expect_continue_threshold,
+            max_response_bytes: self.max_response_bytes,
+            user_agent_suffix: self.user_agent_suffix.clone(),
+            cache_enabled: self.cache_config.enabled,
+            cache_max_entries: self.cache_config.max_entries,
+            cache_ttl_secs: self.cache_config.default_ttl_secs,
+            list_cache_ttl_secs: self.cache_config.list_ttl_secs,
+            stale_if_error_secs: self.cache_config.stale_if_error_secs,
+            cache_predicate: PredicateSlot(self.cache_config.cache_predicate.clone()),
+            telemetry_config: self.telemetry_config.clone(),
+            allow_insecure_http: self.allow_insecure_http,
+            require_https: self.require_https,
+            log_key_names: self.log_key_names,
+            connection_debug: self.connection_debug,
+            read_only: self.read_only,
+            single_flight: self.single_flight,
+            retry_on_conflict: self.retry_on_conflict,
+            retry_on_empty_body: self.retry_on_empty_body,
+            retry_unsafe_without_idempotency: self.retry_unsafe_without_idempotency,
+            verify_content_digest: self.verify_content_digest,
+            require_content_digest: self.require_content_digest,
+            strict_deserialization: self.strict_deserialization,
+            clock: self.clock.clone(),
+            warm_keys: self.warm_keys.clone(),
+            cache_cipher: self.cache_cipher.clone(),
+            eager_connect: false,
+            #[cfg(feature = "disk-cache")]
+            disk_cache: self.disk_cache.clone(),
+        }
+    }
+}
+
+/// Configuration presets for [`ClientBuilder::preset`]
+///
+/// Every preset sets the same group of values:
+///
+/// | Preset            | Timeout | Retries | Cache TTL | Cache entries | Idle connections/host |
+/// |-------------------|---------|---------|-----------|---------------|-----------------------|
+/// | `LowLatency`      | 5s      | 1       | 60s       | 10,000        | 10                    |
+/// | `HighReliability` | 60s     | 5       | 300s      | 10,000        | 10                    |
+/// | `BatchProcessing` | 120s    | 3       | 900s      | 100,000       | 64                    |
+///
+/// Retry backoff is always exponential with jitter (100ms initial interval,
+/// doubling up to 10s), so `HighReliability` spreads its extra retries out
+/// rather than hammering a struggling server.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Preset {
+    /// Fail fast for interactive or latency-sensitive callers
+    LowLatency,
+    /// Ride out transient outages with more, longer attempts
+    HighReliability,
+    /// Many requests in bulk: large connection pool and cache
+    BatchProcessing,
 }
 
 /// Builder for creating a configured Client
 #[derive(Debug)]
 pub struct ClientBuilder {
     base_url: String,
+    fallback_urls: Vec<String>,
     auth: Option<Auth>,
     timeout_ms: u64,
+    health_timeout_ms: u64,
+    overall_timeout_ms: Option<u64>,
     retries: u32,
+    read_retries: Option<u32>,
+    write_retries: Option<u32>,
+    pool_max_idle_per_host: usize,
+    tcp_keepalive: Option<Duration>,
+    tcp_nodelay: bool,
+    expect_continue_threshold: Option<u64>,
+    max_response_bytes: Option<u64>,
     user_agent_suffix: Option<String>,
     cache_enabled: bool,
     cache_max_entries: u64,
     cache_ttl_secs: u64,
+    list_cache_ttl_secs: u64,
+    stale_if_error_secs: u64,
+    cache_predicate: PredicateSlot,
     telemetry_config: TelemetryConfig,
     allow_insecure_http: bool,
+    require_https: bool,
+    log_key_names: bool,
+    connection_debug: bool,
+    read_only: bool,
+    single_flight: bool,
+    retry_on_conflict: bool,
+    retry_on_empty_body: bool,
+    retry_unsafe_without_idempotency: bool,
+    verify_content_digest: bool,
+    require_content_digest: bool,
+    strict_deserialization: bool,
+    clock: Arc<dyn Clock>,
+    warm_keys: Vec<(String, Vec<String>)>,
+    cache_cipher: Option<Arc<dyn CacheCipher>>,
+    eager_connect: bool,
+    #[cfg(feature = "disk-cache")]
+    disk_cache: Option<DiskCacheConfig>,
+}
+
+/// Cache predicate held by the builder, debug-printed as present or absent
+struct PredicateSlot(Option<CachePredicate>);
+
+impl std::fmt::Debug for PredicateSlot {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(if self.0.is_some() { "Some(..)" } else { "None" })
+    }
 }
 
 impl ClientBuilder {
@@ -46,15 +253,45 @@ impl ClientBuilder {
     pub fn new(base_url: impl Into<String>) -> Self {
         Self {
             base_url: base_url.into(),
+            fallback_urls: Vec::new(),
             auth: None,
             timeout_ms: crate::DEFAULT_TIMEOUT_MS,
+            health_timeout_ms: crate::DEFAULT_HEALTH_TIMEOUT_MS,
+            overall_timeout_ms: None,
             retries: crate::DEFAULT_RETRIES,
+            read_retries: None,
+            write_retries: None,
+            pool_max_idle_per_host: crate::DEFAULT_POOL_MAX_IDLE_PER_HOST,
+            tcp_keepalive: None,
+            tcp_nodelay: true,
+            expect_continue_threshold: None,
+            max_response_bytes: None,
             user_agent_suffix: None,
             cache_enabled: true,
             cache_max_entries: crate::DEFAULT_CACHE_MAX_ENTRIES,
             cache_ttl_secs: crate::DEFAULT_CACHE_TTL_SECS,
+            list_cache_ttl_secs: crate::DEFAULT_LIST_CACHE_TTL_SECS,
+            stale_if_error_secs: 0,
+            cache_predicate: PredicateSlot(None),
             telemetry_config: TelemetryConfig::default(),
             allow_insecure_http: false,
+            require_https: false,
+            log_key_names: true,
+            connection_debug: false,
+            read_only: false,
+            single_flight: true,
+            retry_on_conflict: false,
+            retry_on_empty_body: false,
+            retry_unsafe_without_idempotency: false,
+            verify_content_digest: false,
+            require_content_digest: false,
+            strict_deserialization: false,
+            clock: Arc::new(SystemClock),
+            warm_keys: Vec::new(),
+            cache_cipher: None,
+            eager_connect: false,
+            #[cfg(feature = "disk-cache")]
+            disk_cache: None,
         }
     }
 
@@ -70,12 +307,160 @@ impl ClientBuilder {
         self
     }
 
+    /// Set the timeout for health pings in milliseconds (default: 5000)
+    pub fn health_timeout_ms(mut self, timeout_ms: u64) -> Self {
+        self.health_timeout_ms = timeout_ms;
+        self
+    }
+
+    /// Bound each call, including all retries and backoff, in milliseconds
+    ///
+    /// Backoff sleeps are shortened so a final attempt still happens just
+    /// before the deadline instead of sleeping past it, and each attempt's
+    /// timeout is cut to the time that remains.
+    pub fn overall_timeout_ms(mut self, timeout_ms: u64) -> Self {
+        self.overall_timeout_ms = Some(timeout_ms);
+        self
+    }
+
     /// Set the number of retries for failed requests
     pub fn retries(mut self, retries: u32) -> Self {
         self.retries = retries;
         self
     }
 
+    /// Set the number of retries for GET and HEAD requests
+    ///
+    /// Reads are always safe to retry, so they can be given a higher cap than
+    /// writes. Defaults to [`retries`](Self::retries).
+    pub fn read_retries(mut self, retries: u32) -> Self {
+        self.read_retries = Some(retries);
+        self
+    }
+
+    /// Set the number of retries for mutating requests (PUT, POST, DELETE, ...)
+    ///
+    /// Defaults to [`retries`](Self::retries).
+    pub fn write_retries(mut self, retries: u32) -> Self {
+        self.write_retries = Some(retries);
+        self
+    }
+
+    /// Fail over to these base URLs when the current host cannot be reached
+    ///
+    /// When a request fails to connect (DNS or TCP/TLS connection errors, not
+    /// HTTP error responses), it is retried against the next host in the
+    /// list, wrapping around to the primary `base_url`. Each host is tried at
+    /// most once per request without consuming regular retries. The host
+    /// that last worked stays active for later requests; see
+    /// [`Client::active_base_url`](crate::Client::active_base_url).
+    ///
+    /// Fallback URLs are validated like the base URL.
+    pub fn fallback_urls(mut self, urls: Vec<String>) -> Self {
+        self.fallback_urls = urls;
+        self
+    }
+
+    /// Set the maximum number of idle connections kept per host (default: 10)
+    pub fn pool_max_idle_per_host(mut self, max_idle: usize) -> Self {
+        self.pool_max_idle_per_host = max_idle;
+        self
+    }
+
+    /// Send TCP keepalive probes on idle connections at this interval
+    /// (disabled by default)
+    ///
+    /// Keeps NAT and firewall state alive so the first request after a quiet
+    /// period does not fail with a connection reset. Pooled connections are
+    /// closed after 90 seconds idle regardless, so the interval only matters
+    /// when it is shorter than that, or for connections held by in-flight
+    /// requests such as long-polling subscriptions.
+    pub fn tcp_keepalive(mut self, interval: Duration) -> Self {
+        self.tcp_keepalive = Some(interval);
+        self
+    }
+
+    /// Set `TCP_NODELAY` on connections (enabled by default)
+    ///
+    /// Disabling it lets the kernel coalesce small writes (Nagle's
+    /// algorithm), trading latency for fewer packets.
+    pub fn tcp_nodelay(mut self, enabled: bool) -> Self {
+        self.tcp_nodelay = enabled;
+        self
+    }
+
+    /// Send `Expect: 100-continue` on large request bodies (disabled by default)
+    ///
+    /// Applies to bodies larger than
+    /// [`DEFAULT_EXPECT_CONTINUE_THRESHOLD`](crate::DEFAULT_EXPECT_CONTINUE_THRESHOLD)
+    /// (1 MiB), such as big batch writes and imports; see
+    /// [`expect_continue_threshold`](Self::expect_continue_threshold) to change
+    /// the cut-off. A server that honours the header can answer 401 or 413
+    /// from the headers alone instead of after reading the payload.
+    ///
+    /// Server and proxy support varies: servers that ignore the header simply
+    /// read the body as usual, and the underlying HTTP client starts sending
+    /// the body without waiting indefinitely for `100 Continue`, so the
+    /// bandwidth saved depends on how quickly the server rejects. HTTP/2
+    /// connections ignore the header.
+    pub fn expect_continue(mut self, enabled: bool) -> Self {
+        self.expect_continue_threshold =
+            enabled.then_some(crate::DEFAULT_EXPECT_CONTINUE_THRESHOLD);
+        self
+    }
+
+    /// Send `Expect: 100-continue` on request bodies larger than `bytes`
+    ///
+    /// Enables [`expect_continue`](Self::expect_continue) with a custom
+    /// threshold.
+    pub fn expect_continue_threshold(mut self, bytes: u64) -> Self {
+        self.expect_continue_threshold = Some(bytes);
+        self
+    }
+
+    /// Fail requests whose response body is larger than `bytes`
+    ///
+    /// The limit applies to the decompressed body and is enforced while it
+    /// streams in, so chunked responses without `Content-Length` are capped
+    /// too. A declared `Content-Length` above the limit fails before the body
+    /// is read.
+    pub fn max_response_bytes(mut self, bytes: u64) -> Self {
+        self.max_response_bytes = Some(bytes);
+        self
+    }
+
+    /// Apply a group of defaults tuned for a common workload
+    ///
+    /// See [`Preset`] for exactly which values each preset sets. Settings
+    /// applied after the preset override it.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use secret_store_sdk::{Auth, ClientBuilder, Preset};
+    ///
+    /// let client = ClientBuilder::new("https://secret.example.com")
+    ///     .auth(Auth::bearer("token"))
+    ///     .preset(Preset::LowLatency)
+    ///     .retries(2) // override the preset's single retry
+    ///     .build()?;
+    /// # Ok::<(), secret_store_sdk::Error>(())
+    /// ```
+    pub fn preset(mut self, preset: Preset) -> Self {
+        let (timeout_ms, retries, cache_ttl_secs, cache_max_entries, pool_max_idle_per_host) =
+            match preset {
+                Preset::LowLatency => (5_000, 1, 60, crate::DEFAULT_CACHE_MAX_ENTRIES, 10),
+                Preset::HighReliability => (60_000, 5, 300, crate::DEFAULT_CACHE_MAX_ENTRIES, 10),
+                Preset::BatchProcessing => (120_000, 3, 900, 100_000, 64),
+            };
+        self.timeout_ms = timeout_ms;
+        self.retries = retries;
+        self.cache_ttl_secs = cache_ttl_secs;
+        self.cache_max_entries = cache_max_entries;
+        self.pool_max_idle_per_host = pool_max_idle_per_host;
+        self
+    }
+
     /// Add a custom user agent suffix
     pub fn user_agent_extra(mut self, suffix: impl Into<String>) -> Self {
         self.user_agent_suffix = Some(suffix.into());
@@ -100,6 +485,220 @@ impl ClientBuilder {
         self
     }
 
+    /// Set the freshness TTL for cached `list_secrets`/`list_namespaces` results in seconds
+    pub fn list_cache_ttl_secs(mut self, ttl_secs: u64) -> Self {
+        self.list_cache_ttl_secs = ttl_secs;
+        self
+    }
+
+    /// Serve expired cache entries when the backend fails (disabled by default)
+    ///
+    /// Expired secrets are kept for up to `max_stale_secs` past their cache
+    /// TTL. When fetching a secret fails with a 5xx, rate-limit, network or
+    /// timeout error, such an entry is returned instead (with `from_cache`
+    /// set) and a warning is logged. Authentication, permission and 404
+    /// errors are never masked, and secrets past their own expiry are never
+    /// served. Can be overridden per request with `GetOpts::serve_stale_on_error`.
+    pub fn serve_stale_on_error(mut self, max_stale_secs: u64) -> Self {
+        self.stale_if_error_secs = max_stale_secs;
+        self
+    }
+
+    /// Only cache secrets for which `predicate` returns `true`
+    ///
+    /// Guards against caching known-bad responses for the full TTL, such as
+    /// placeholder values returned by a misbehaving server. Rejected secrets
+    /// are still returned to the caller but are fetched again next time. By
+    /// default every secret is cached.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use secret_store_sdk::ClientBuilder;
+    /// use secrecy::ExposeSecret;
+    ///
+    /// let builder = ClientBuilder::new("https://secret.example.com")
+    ///     .cache_predicate(|secret| !secret.value.expose_secret().is_empty());
+    /// ```
+    pub fn cache_predicate(
+        mut self,
+        predicate: impl Fn(&Secret) -> bool + Send + Sync + 'static,
+    ) -> Self {
+        self.cache_predicate = PredicateSlot(Some(Arc::new(predicate)));
+        self
+    }
+
+    /// Include secret key names in log output (enabled by default)
+    ///
+    /// When disabled, key names in `debug!`/`trace!` output are replaced with
+    /// `<redacted:HASH>`, where `HASH` is a short stable hash of the key so
+    /// log lines for the same key can still be correlated.
+    pub fn log_key_names(mut self, enabled: bool) -> Self {
+        self.log_key_names = enabled;
+        self
+    }
+
+    /// Log connection-level diagnostics (disabled by default)
+    ///
+    /// Emits `tracing` events at debug level under the
+    /// `secret_store_sdk::connection` target: DNS lookup times and address
+    /// counts, and for every attempt the peer address, HTTP version and time
+    /// to response headers (which includes connecting and the TLS handshake
+    /// when no pooled connection was reused), or the phase a failed attempt
+    /// broke down in. Useful for diagnosing TLS and proxy problems.
+    ///
+    /// Only host names, addresses and timings are logged; URLs, headers and
+    /// bodies never are. Hosts given as IP addresses skip the DNS event.
+    pub fn connection_debug(mut self, enabled: bool) -> Self {
+        self.connection_debug = enabled;
+        self
+    }
+
+    /// Make the client read-only (disabled by default)
+    ///
+    /// Every mutating method (writes, deletes, restores, rollbacks, batch
+    /// operations, imports, namespace creation, initialization, deletion and
+    /// protection changes, and API key creation and revocation) fails with
+    /// `Error::Config("client is read-only")` before anything is sent. Raw
+    /// requests and [`SecretService`](crate::SecretService) calls are only
+    /// allowed with safe methods such as GET. Reads work as usual.
+    ///
+    /// This is a client-side guard for least-privilege services, in addition
+    /// to whatever permissions the server enforces.
+    pub fn read_only(mut self, enabled: bool) -> Self {
+        self.read_only = enabled;
+        self
+    }
+
+    /// Coalesce concurrent gets of the same secret (enabled by default)
+    ///
+    /// When several tasks miss the cache for the same secret at once, only
+    /// the first sends a request and the others share its result; they are
+    /// counted in [`CacheStats::coalesced_requests`](crate::CacheStats::coalesced_requests).
+    /// Conditional gets and gets that bypass the cache are never coalesced,
+    /// and if the shared request fails each waiting caller retries on its
+    /// own. Disable this when every get must be a distinct request, e.g. for
+    /// load testing.
+    pub fn single_flight(mut self, enabled: bool) -> Self {
+        self.single_flight = enabled;
+        self
+    }
+
+    /// Retry 409 Conflict responses on puts that carry an idempotency key
+    /// (disabled by default)
+    ///
+    /// Conflicts are retried with the regular backoff, at most 3 times per
+    /// request. Can be overridden per request with `PutOpts::retry_on_conflict`.
+    pub fn retry_on_conflict(mut self, enabled: bool) -> Self {
+        self.retry_on_conflict = enabled;
+        self
+    }
+
+    /// Retry 2xx responses whose body is empty or not well-formed JSON
+    /// (disabled by default)
+    ///
+    /// Proxies occasionally answer 200 with an empty or truncated body. With
+    /// this enabled such responses count as transient failures and are
+    /// retried with the regular backoff. Well-formed JSON that does not match
+    /// the expected schema is still a permanent `Error::Deserialize`.
+    ///
+    /// Only applies to requests that are safe to replay, and not to deletes,
+    /// `204 No Content` or non-JSON responses. Checking requires buffering
+    /// the body before it is parsed.
+    pub fn retry_on_empty_body(mut self, enabled: bool) -> Self {
+        self.retry_on_empty_body = enabled;
+        self
+    }
+
+    /// Retry non-idempotent requests without an idempotency key on network
+    /// errors (disabled by default)
+    ///
+    /// By default a POST or PATCH without an `X-Idempotency-Key` header is not
+    /// retried after a timeout or dropped connection, since the server may
+    /// already have applied it and a retry could apply it twice. Connection
+    /// failures before the request was sent, and error responses such as 503,
+    /// are still retried.
+    pub fn retry_unsafe_without_idempotency(mut self, enabled: bool) -> Self {
+        self.retry_unsafe_without_idempotency = enabled;
+        self
+    }
+
+    /// Verify response bodies against a digest header when the server sends one
+    ///
+    /// Understands `Content-Digest: sha-256=:<base64>:` and
+    /// `X-Content-SHA256: <hex>`. The digest is checked against the body as
+    /// received (after decompression) before it is parsed; a mismatch fails
+    /// with `Error::Other("content digest mismatch")`. Responses without a
+    /// digest are accepted unless [`require_content_digest`](Self::require_content_digest)
+    /// is set. Values read with `Client::get_secret_stream` are hashed as they
+    /// stream, and a failed check surfaces as an `io::Error` on the last read.
+    pub fn verify_content_digest(mut self, enabled: bool) -> Self {
+        self.verify_content_digest = enabled;
+        self
+    }
+
+    /// Reject non-empty response bodies without a digest header
+    ///
+    /// Implies [`verify_content_digest`](Self::verify_content_digest).
+    pub fn require_content_digest(mut self, required: bool) -> Self {
+        self.require_content_digest = required;
+        self
+    }
+
+    /// Reject response bodies containing fields the SDK does not know about
+    ///
+    /// By default unknown fields are ignored so newer servers keep working
+    /// with older clients. With strict deserialization a renamed or added
+    /// field fails the call with [`Error::Deserialize`] instead of silently
+    /// leaving a value at its default, which helps catch schema drift in
+    /// staging. Error response bodies are always parsed leniently.
+    pub fn strict_deserialization(mut self, strict: bool) -> Self {
+        self.strict_deserialization = strict;
+        self
+    }
+
+    /// Set the time source used for cache expiry (defaults to [`SystemClock`])
+    ///
+    /// Mainly useful in tests, where a controllable clock lets cache TTLs
+    /// elapse instantly instead of sleeping.
+    pub fn clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Register keys to pre-fetch into the cache with [`Client::warm`](crate::Client::warm)
+    ///
+    /// Can be called once per namespace. Warming cuts the latency of the first
+    /// reads after startup; it has no effect when caching is disabled.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use secret_store_sdk::{Auth, ClientBuilder};
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = ClientBuilder::new("https://secret.example.com")
+    ///     .auth(Auth::bearer("token"))
+    ///     .warm_keys("production", vec!["db-url".to_string(), "api-key".to_string()])
+    ///     .build()?;
+    /// client.warm().await;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn warm_keys(mut self, namespace: impl Into<String>, keys: Vec<String>) -> Self {
+        self.warm_keys.push((namespace.into(), keys));
+        self
+    }
+
+    /// Encrypt cached secret values with a caller-provided cipher
+    ///
+    /// Values are encrypted before they enter the in-memory cache (and the
+    /// disk cache, if enabled) and decrypted on every cache hit, so a heap or
+    /// disk dump does not expose plaintext. By default no cipher is used.
+    pub fn cache_cipher(mut self, cipher: impl CacheCipher + 'static) -> Self {
+        self.cache_cipher = Some(Arc::new(cipher));
+        self
+    }
+
     /// Configure telemetry/metrics
     #[cfg(feature = "metrics")]
     pub fn with_telemetry(mut self, config: TelemetryConfig) -> Self {
@@ -114,6 +713,31 @@ impl ClientBuilder {
         self
     }
 
+    /// Persist cache entries to a local directory (requires disk-cache feature)
+    ///
+    /// Lets short-lived processes reuse cached secrets across runs by
+    /// revalidating them with `If-None-Match` instead of refetching. Entries
+    /// live in a `secret-store-sdk` subdirectory of `path`, created on
+    /// [`build`](Self::build) with owner-only permissions on Unix; `build`
+    /// fails if that subdirectory exists and other users can access it.
+    ///
+    /// # Security
+    ///
+    /// [`DiskCacheMode::FullValue`] writes secret values to disk encrypted
+    /// with the [`cache_cipher`](Self::cache_cipher), which it requires:
+    /// `build` fails without one. Anyone holding both the directory and the
+    /// cipher's key can read the secrets, so keep the key out of the cache
+    /// directory and its backups. [`DiskCacheMode::EtagOnly`] never writes
+    /// values at all.
+    #[cfg(feature = "disk-cache")]
+    pub fn disk_cache(mut self, path: impl Into<PathBuf>, mode: DiskCacheMode) -> Self {
+        self.disk_cache = Some(DiskCacheConfig {
+            path: path.into(),
+            mode,
+        });
+        self
+    }
+
     /// Allow insecure HTTP connections (requires danger-insecure-http feature)
     #[cfg(feature = "danger-insecure-http")]
     pub fn allow_insecure_http(mut self) -> Self {
@@ -121,23 +745,72 @@ impl ClientBuilder {
         self
     }
 
+    /// Reject non-`https://` base URLs at build time
+    ///
+    /// A guard for production builds: fails with `Error::Config` if the base
+    /// URL is not HTTPS, even when the `danger-insecure-http` feature is
+    /// enabled and [`allow_insecure_http`](Self::allow_insecure_http) was
+    /// called somewhere else in the configuration code.
+    pub fn require_https(mut self) -> Self {
+        self.require_https = true;
+        self
+    }
+
+    /// Make [`build_async`](Self::build_async) fail if the host is unreachable
+    ///
+    /// Without this, a failed warm-up connection is only logged.
+    pub fn eager_connect(mut self, eager: bool) -> Self {
+        self.eager_connect = eager;
+        self
+    }
+
+    /// Build the client and open a connection to the server up front
+    ///
+    /// Sends a liveness request (without retries, bounded by the health
+    /// timeout) so DNS resolution and the TLS handshake are done before the
+    /// first real request. Any HTTP response counts as connected. [`build`](Self::build)
+    /// stays lazy.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use secret_store_sdk::{Auth, ClientBuilder};
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = ClientBuilder::new("https://secret.example.com")
+    ///     .auth(Auth::bearer("token"))
+    ///     .eager_connect(true)
+    ///     .build_async()
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn build_async(self) -> Result<crate::Client> {
+        let eager_connect = self.eager_connect;
+        let client = self.build()?;
+
+        if let Err(e) = client.warm_connection().await {
+            if eager_connect {
+                return Err(e);
+            }
+            debug!("Warm-up connection failed: {}", e);
+        }
+        Ok(client)
+    }
+
     /// Build the client with the configured options
     pub fn build(self) -> Result<crate::Client> {
-        // Validate base URL
+        // Validate base URL and failover hosts
         let url = self.base_url.trim_end_matches('/');
-
-        // Check for insecure HTTP
-        if url.starts_with("http://") && !self.allow_insecure_http {
-            #[cfg(feature = "danger-insecure-http")]
-            return Err(Error::Config(
-                "HTTP URLs are not allowed by default. Use .allow_insecure_http() to enable (dangerous!)".to_string()
-            ));
-
-            #[cfg(not(feature = "danger-insecure-http"))]
-            return Err(Error::Config(
-                "HTTP URLs are not allowed. Enable the 'danger-insecure-http' feature and use .allow_insecure_http() (dangerous!)".to_string()
-            ));
-        }
+        validate_base_url(url, self.require_https, self.allow_insecure_http)?;
+        let fallback_urls = self
+            .fallback_urls
+            .iter()
+            .map(|fallback| {
+                let fallback = fallback.trim_end_matches('/');
+                validate_base_url(fallback, self.require_https, self.allow_insecure_http)
+                    .map(|()| fallback.to_string())
+            })
+            .collect::<Result<Vec<_>>>()?;
 
         // Require authentication
         let auth = self.auth.ok_or_else(|| {
@@ -146,32 +819,107 @@ impl ClientBuilder {
             )
         })?;
 
-        // Validate URL format
-        if !url.starts_with("http://") && !url.starts_with("https://") {
-            return Err(Error::Config(
-                "Base URL must start with http:// or https://".to_string(),
-            ));
+        if let Some(message) = insecure_http_warning(url, self.allow_insecure_http) {
+            warn!("{}", message);
         }
 
         let config = ClientConfig {
             base_url: url.to_string(),
+            fallback_urls,
             auth,
             timeout: Duration::from_millis(self.timeout_ms),
+            health_timeout: Duration::from_millis(self.health_timeout_ms),
+            overall_timeout: self.overall_timeout_ms.map(Duration::from_millis),
             retries: self.retries,
+            read_retries: self.read_retries,
+            write_retries: self.write_retries,
+            pool_max_idle_per_host: self.pool_max_idle_per_host,
+            tcp_keepalive: self.tcp_keepalive,
+            tcp_nodelay: self.tcp_nodelay,
+            expect_continue_threshold: self.expect_continue_threshold,
+            max_response_bytes: self.max_response_bytes,
             user_agent_suffix: self.user_agent_suffix,
             cache_config: CacheConfig {
                 enabled: self.cache_enabled,
                 max_entries: self.cache_max_entries,
                 default_ttl_secs: self.cache_ttl_secs,
+                list_ttl_secs: self.list_cache_ttl_secs,
+                stale_if_error_secs: self.stale_if_error_secs,
+                cache_predicate: self.cache_predicate.0,
             },
             telemetry_config: self.telemetry_config,
             allow_insecure_http: self.allow_insecure_http,
+            require_https: self.require_https,
+            log_key_names: self.log_key_names,
+            connection_debug: self.connection_debug,
+            read_only: self.read_only,
+            single_flight: self.single_flight,
+            retry_on_conflict: self.retry_on_conflict,
+            retry_on_empty_body: self.retry_on_empty_body,
+            retry_unsafe_without_idempotency: self.retry_unsafe_without_idempotency,
+            verify_content_digest: self.verify_content_digest || self.require_content_digest,
+            require_content_digest: self.require_content_digest,
+            strict_deserialization: self.strict_deserialization,
+            clock: self.clock,
+            warm_keys: self.warm_keys,
+            cache_cipher: self.cache_cipher,
+            #[cfg(feature = "disk-cache")]
+            disk_cache: self.disk_cache,
         };
 
         crate::client::Client::new(config)
     }
 }
 
+/// Check a base URL against the scheme requirements
+fn validate_base_url(url: &str, require_https: bool, allow_insecure_http: bool) -> Result<()> {
+    if require_https && !url.starts_with("https://") {
+        return Err(Error::Config(format!(
+            "HTTPS is required but the base URL scheme is '{}'",
+            url_scheme(url)
+        )));
+    }
+
+    // Check for insecure HTTP
+    if url.starts_with("http://") && !allow_insecure_http {
+        #[cfg(feature = "danger-insecure-http")]
+        return Err(Error::Config(
+            "HTTP URLs are not allowed by default. Use .allow_insecure_http() to enable (dangerous!)".to_string()
+        ));
+
+        #[cfg(not(feature = "danger-insecure-http"))]
+        return Err(Error::Config(
+            "HTTP URLs are not allowed. Enable the 'danger-insecure-http' feature and use .allow_insecure_http() (dangerous!)".to_string()
+        ));
+    }
+
+    // Validate URL format
+    if !url.starts_with("http://") && !url.starts_with("https://") {
+        return Err(Error::Config(
+            "Base URL must start with http:// or https://".to_string(),
+        ));
+    }
+
+    Ok(())
+}
+
+/// Scheme part of a URL, for error and log messages
+fn url_scheme(url: &str) -> &str {
+    url.split_once("://").map_or("none", |(scheme, _)| scheme)
+}
+
+/// Warning logged on every build that enables insecure HTTP
+fn insecure_http_warning(url: &str, allow_insecure_http: bool) -> Option<String> {
+    allow_insecure_http.then(|| {
+        format!(
+            "INSECURE: allow_insecure_http is enabled (base URL scheme '{}'); \
+             plaintext HTTP and invalid TLS certificates are accepted. \
+             Never use this in production.",
+            url_scheme(url)
+        )
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -191,6 +939,191 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_to_builder_carries_settings() {
+        let original = ClientBuilder::new("https://example.com")
+            .fallback_urls(vec!["https://backup.example.com".to_string()])
+            .auth(Auth::bearer("token"))
+            .timeout_ms(1_000)
+            .retries(7)
+            .cache_ttl_secs(60)
+            .log_key_names(false)
+            .read_only(true)
+            .require_https()
+            .user_agent_extra("tenant-a")
+            .build()
+            .unwrap();
+
+        let derived = original.config_builder().timeout_ms(5_000).build().unwrap();
+        let (original, derived) = (&original.config, &derived.config);
+
+        assert_eq!(original.timeout, Duration::from_secs(1));
+        assert_eq!(derived.timeout, Duration::from_secs(5));
+        assert_eq!(derived.base_url, original.base_url);
+        assert_eq!(derived.fallback_urls, original.fallback_urls);
+        assert_eq!(
+            format!("{:?}", derived.auth),
+            format!("{:?}", original.auth)
+        );
+        assert_eq!(derived.retries, 7);
+        assert_eq!(derived.cache_config.default_ttl_secs, 60);
+        assert!(!derived.log_key_names);
+        assert!(derived.read_only);
+        assert!(derived.require_https);
+        assert_eq!(derived.user_agent_suffix.as_deref(), Some("tenant-a"));
+        assert_eq!(derived.health_timeout, original.health_timeout);
+    }
+
+    #[test]
+    fn test_builder_presets() {
+        let build = |preset| {
+            ClientBuilder::new("https://example.com")
+                .auth(Auth::bearer("token"))
+                .preset(preset)
+                .build()
+                .unwrap()
+                .config
+        };
+
+        let config = build(Preset::LowLatency);
+        assert_eq!(config.timeout, Duration::from_secs(5));
+        assert_eq!(config.retries, 1);
+        assert_eq!(config.cache_config.default_ttl_secs, 60);
+        assert_eq!(config.cache_config.max_entries, 10_000);
+        assert_eq!(config.pool_max_idle_per_host, 10);
+
+        let config = build(Preset::HighReliability);
+        assert_eq!(config.timeout, Duration::from_secs(60));
+        assert_eq!(config.retries, 5);
+        assert_eq!(config.cache_config.default_ttl_secs, 300);
+        assert_eq!(config.cache_config.max_entries, 10_000);
+        assert_eq!(config.pool_max_idle_per_host, 10);
+
+        let config = build(Preset::BatchProcessing);
+        assert_eq!(config.timeout, Duration::from_secs(120));
+        assert_eq!(config.retries, 3);
+        assert_eq!(config.cache_config.default_ttl_secs, 900);
+        assert_eq!(config.cache_config.max_entries, 100_000);
+        assert_eq!(config.pool_max_idle_per_host, 64);
+
+        // Later settings override the preset
+        let config = ClientBuilder::new("https://example.com")
+            .auth(Auth::bearer("token"))
+            .preset(Preset::LowLatency)
+            .retries(4)
+            .build()
+            .unwrap()
+            .config;
+        assert_eq!(config.retries, 4);
+        assert_eq!(config.timeout, Duration::from_secs(5));
+    }
+
+    #[test]
+    fn test_builder_tcp_settings() {
+        let config = ClientBuilder::new("https://example.com")
+            .auth(Auth::bearer("token"))
+            .build()
+            .unwrap()
+            .config;
+        assert_eq!(config.tcp_keepalive, None);
+        assert!(config.tcp_nodelay);
+
+        let config = ClientBuilder::new("https://example.com")
+            .auth(Auth::bearer("token"))
+            .tcp_keepalive(Duration::from_secs(30))
+            .tcp_nodelay(false)
+            .build()
+            .unwrap()
+            .config;
+        assert_eq!(config.tcp_keepalive, Some(Duration::from_secs(30)));
+        assert!(!config.tcp_nodelay);
+    }
+
+    #[test]
+    fn test_builder_fallback_urls() {
+        let config = ClientBuilder::new("https://primary.example.com")
+            .auth(Auth::bearer("token"))
+            .fallback_urls(vec!["https://backup.example.com/".to_string()])
+            .build()
+            .unwrap()
+            .config;
+        assert_eq!(config.fallback_urls, vec!["https://backup.example.com"]);
+
+        let result = ClientBuilder::new("https://primary.example.com")
+            .auth(Auth::bearer("token"))
+            .fallback_urls(vec!["backup.example.com".to_string()])
+            .build();
+        assert!(matches!(result, Err(Error::Config(_))));
+    }
+
+    #[test]
+    fn test_read_write_retries_default_to_retries() {
+        let build = |builder: ClientBuilder| builder.auth(Auth::bearer("token")).build().unwrap();
+
+        let config = build(ClientBuilder::new("https://example.com").retries(2)).config;
+        assert_eq!(config.max_retries(&reqwest::Method::GET), 2);
+        assert_eq!(config.max_retries(&reqwest::Method::PUT), 2);
+
+        let config = build(
+            ClientBuilder::new("https://example.com")
+                .retries(2)
+                .read_retries(5)
+                .write_retries(0),
+        )
+        .config;
+        assert_eq!(config.max_retries(&reqwest::Method::GET), 5);
+        assert_eq!(config.max_retries(&reqwest::Method::HEAD), 5);
+        assert_eq!(config.max_retries(&reqwest::Method::POST), 0);
+        assert_eq!(config.max_retries(&reqwest::Method::DELETE), 0);
+    }
+
+    #[test]
+    fn test_require_https_rejects_http() {
+        let builder = ClientBuilder::new("http://example.com")
+            .auth(Auth::bearer("token"))
+            .require_https();
+        #[cfg(feature = "danger-insecure-http")]
+        let builder = builder.allow_insecure_http();
+
+        let err = builder.build().unwrap_err();
+        assert!(matches!(err, Error::Config(ref msg) if msg.contains("'http'")));
+
+        assert!(ClientBuilder::new("https://example.com")
+            .auth(Auth::bearer("token"))
+            .require_https()
+            .build()
+            .is_ok());
+    }
+
+    #[test]
+    fn test_insecure_http_warning() {
+        assert_eq!(insecure_http_warning("https://example.com", false), None);
+
+        let message = insecure_http_warning("http://localhost:8080", true).unwrap();
+        assert!(message.contains("scheme 'http'"));
+        assert!(message.contains("Never use this in production"));
+    }
+
+    #[tokio::test]
+    async fn test_build_async_eager_connect_unreachable() {
+        // Nothing listens on port 1, so the connection is refused
+        let builder = || {
+            ClientBuilder::new("https://127.0.0.1:1")
+                .auth(Auth::bearer("token"))
+                .health_timeout_ms(2_000)
+        };
+
+        let err = builder()
+            .eager_connect(true)
+            .build_async()
+            .await
+            .unwrap_err();
+        assert!(matches!(err, Error::Network { .. } | Error::Timeout));
+
+        // Without eager_connect the failed warm-up is not fatal
+        assert!(builder().build_async().await.is_ok());
+    }
+
     #[test]
     #[cfg(not(feature = "danger-insecure-http"))]
     fn test_builder_rejects_http() {
@@ -0,0 +1,88 @@
+//! Connection-level diagnostics for `ClientBuilder::connection_debug`
+//!
+//! Events are emitted at debug level under the `secret_store_sdk::connection`
+//! target. They carry host names, peer addresses and timings only; URLs,
+//! headers and bodies are never logged.
+
+use reqwest::dns::{Addrs, Name, Resolve, Resolving};
+use std::net::SocketAddr;
+use std::time::Instant;
+use tracing::debug;
+
+/// Tracing target of connection events
+pub(crate) const TARGET: &str = "secret_store_sdk::connection";
+
+/// System resolver that logs how long each lookup took
+#[derive(Debug, Default)]
+pub(crate) struct TimingResolver;
+
+impl Resolve for TimingResolver {
+    fn resolve(&self, name: Name) -> Resolving {
+        let host = name.as_str().to_string();
+        Box::pin(async move {
+            let started = Instant::now();
+            let result = tokio::net::lookup_host((host.as_str(), 0)).await;
+            let elapsed_ms = started.elapsed().as_secs_f64() * 1000.0;
+            match result {
+                Ok(addrs) => {
+                    let addrs: Vec<SocketAddr> = addrs.collect();
+                    debug!(
+                        target: TARGET,
+                        phase = "dns",
+                        host = %host,
+                        addresses = addrs.len(),
+                        elapsed_ms,
+                        "DNS lookup finished"
+                    );
+                    Ok(Box::new(addrs.into_iter()) as Addrs)
+                }
+                Err(e) => {
+                    debug!(
+                        target: TARGET,
+                        phase = "dns",
+                        host = %host,
+                        error = %e,
+                        elapsed_ms,
+                        "DNS lookup failed"
+                    );
+                    Err(e.into())
+                }
+            }
+        })
+    }
+}
+
+/// Log the outcome of one HTTP attempt
+///
+/// `elapsed` covers connecting (including TLS) when no pooled connection was
+/// available, plus the wait for response headers.
+pub(crate) fn log_attempt(result: &reqwest::Result<reqwest::Response>, started: Instant) {
+    let elapsed_ms = started.elapsed().as_secs_f64() * 1000.0;
+    match result {
+        Ok(response) => debug!(
+            target: TARGET,
+            phase = "response",
+            remote_addr = ?response.remote_addr(),
+            version = ?response.version(),
+            status = response.status().as_u16(),
+            elapsed_ms,
+            "Response headers received"
+        ),
+        Err(e) => {
+            let phase = if e.is_connect() {
+                "connect"
+            } else if e.is_timeout() {
+                "timeout"
+            } else {
+                "request"
+            };
+            // The error's URL could name secrets, so only its kind is logged
+            debug!(
+                target: TARGET,
+                phase,
+                elapsed_ms,
+                "Request failed before a response arrived"
+            );
+        }
+    }
+}
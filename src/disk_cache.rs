@@ -0,0 +1,540 @@
+//! Persistent on-disk secret cache
+//!
+//! Lets short-lived processes such as CLI tools reuse cache entries across
+//! runs. Entries are stored one file per secret in a dedicated
+//! `secret-store-sdk` subdirectory of a user-provided directory.
+//!
+//! # Security
+//!
+//! **Read this before enabling the disk cache.** Depending on the mode, secret
+//! material is written to the local filesystem:
+//!
+//! * [`DiskCacheMode::EtagOnly`] stores only the ETag and version metadata.
+//!   No secret values ever touch the disk, but every read still needs a full
+//!   response from the server.
+//! * [`DiskCacheMode::FullValue`] stores the secret value encrypted with the
+//!   client's [`CacheCipher`](crate::CacheCipher), and is rejected at build
+//!   time without one. The ciphertext is only as safe as the cipher's key:
+//!   anyone who can read both the cache directory and the key can read your
+//!   secrets.
+//!
+//! On Unix the subdirectory is created with mode `0700` and files with mode
+//! `0600`; an existing subdirectory that is accessible to other users is
+//! refused rather than silently tightened. The user-provided directory itself
+//! is never modified. On other platforms no permission checks are made.
+//! Point the cache at a directory that is excluded from backups and
+//! synchronisation.
+
+use crate::cache::{decrypt_value, CacheCipher};
+use crate::errors::{Error, Result};
+use crate::models::Secret;
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
+use secrecy::ExposeSecret;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fmt::Write as _;
+use std::fs;
+use std::io::Write as _;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use time::format_description::well_known::Rfc3339;
+use time::OffsetDateTime;
+use tracing::warn;
+
+/// Subdirectory of the configured path that holds the cache files
+const SUBDIR: &str = "secret-store-sdk";
+
+/// What the disk cache persists for each secret
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiskCacheMode {
+    /// Persist only the ETag and version metadata, never the value
+    EtagOnly,
+    /// Persist the full secret, with its value encrypted by the client's
+    /// [`CacheCipher`](crate::CacheCipher)
+    ///
+    /// Allows repeated invocations to revalidate with `If-None-Match` and
+    /// receive a bodyless 304 instead of refetching. Requires
+    /// [`ClientBuilder::cache_cipher`](crate::ClientBuilder::cache_cipher);
+    /// values are never written in plaintext. See the module-level security
+    /// notes before using this mode.
+    FullValue,
+}
+
+/// Disk cache configuration
+#[derive(Debug, Clone)]
+pub struct DiskCacheConfig {
+    /// Directory under which the cache's own subdirectory is created
+    pub path: PathBuf,
+    /// What to persist per entry
+    pub mode: DiskCacheMode,
+}
+
+/// Serialized form of a cache entry
+#[derive(Debug, Serialize, Deserialize)]
+struct DiskEntry {
+    key: String,
+    version: i32,
+    etag: Option<String>,
+    last_modified: Option<String>,
+    updated_at: String,
+    expires_at: Option<String>,
+    #[serde(default)]
+    metadata: serde_json::Value,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    value: Option<String>,
+    /// `value` is base64 ciphertext produced by the cache cipher
+    #[serde(default)]
+    encrypted: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    format: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    kid: Option<String>,
+}
+
+/// File-backed cache store
+#[derive(Debug, Clone)]
+pub(crate) struct DiskCache {
+    dir: PathBuf,
+    mode: DiskCacheMode,
+    cipher: Option<Arc<dyn CacheCipher>>,
+}
+
+impl DiskCache {
+    /// Open (and create if needed) the cache directory
+    pub fn open(config: &DiskCacheConfig, cipher: Option<Arc<dyn CacheCipher>>) -> Result<Self> {
+        if config.mode == DiskCacheMode::FullValue && cipher.is_none() {
+            return Err(Error::Config(
+                "DiskCacheMode::FullValue requires a cache cipher; configure one with \
+                 ClientBuilder::cache_cipher or use DiskCacheMode::EtagOnly"
+                    .to_string(),
+            ));
+        }
+
+        let dir = config.path.join(SUBDIR);
+        create_private_dir(&config.path, &dir).map_err(|e| {
+            Error::Config(format!(
+                "Failed to open disk cache directory {}: {}",
+                dir.display(),
+                e
+            ))
+        })?;
+
+        Ok(Self {
+            dir,
+            mode: config.mode,
+            cipher,
+        })
+    }
+
+    /// Run filesystem work on the blocking thread pool
+    ///
+    /// Keeps file I/O off the async runtime's worker threads. Returns `None`
+    /// if the work panicked.
+    pub async fn run<T, F>(&self, work: F) -> Option<T>
+    where
+        F: FnOnce(&DiskCache) -> T + Send + 'static,
+        T: Send + 'static,
+    {
+        let cache = self.clone();
+        tokio::task::spawn_blocking(move || work(&cache)).await.ok()
+    }
+
+    /// Load a full secret (only available in `FullValue` mode)
+    pub fn load(&self, cache_key: &str, now: OffsetDateTime) -> Option<Secret> {
+        if self.mode != DiskCacheMode::FullValue {
+            return None;
+        }
+
+        let entry = self.read_entry(cache_key)?;
+        let updated_at = OffsetDateTime::parse(&entry.updated_at, &Rfc3339).ok()?;
+        let expires_at = match &entry.expires_at {
+            Some(s) => Some(OffsetDateTime::parse(s, &Rfc3339).ok()?),
+            None => None,
+        };
+        if expires_at.is_some_and(|expires_at| now >= expires_at) {
+            self.remove(cache_key);
+            return None;
+        }
+
+        // Plaintext entries (written by older versions) are not trusted
+        if !entry.encrypted {
+            return None;
+        }
+        let ciphertext = BASE64.decode(entry.value?).ok()?;
+        let value = match decrypt_value(self.cipher.as_deref()?, &ciphertext) {
+            Ok(value) => value,
+            Err(e) => {
+                warn!("Failed to decrypt disk cache entry: {}", e);
+                self.remove(cache_key);
+                return None;
+            }
+        };
+
+        let (namespace, key) = cache_key.split_once('/').unwrap_or(("", cache_key));
+        Some(Secret {
+            namespace: namespace.to_string(),
+            key: key.to_string(),
+            value,
+            version: entry.version,
+            expires_at,
+            metadata: entry.metadata,
+            updated_at,
+            etag: entry.etag,
+            last_modified: entry.last_modified,
+            request_id: None,
+            from_cache: false,
+            format: entry.format,
+            kid: entry.kid,
+        })
+    }
+
+    /// Get the stored ETag for an entry
+    pub fn etag(&self, cache_key: &str) -> Option<String> {
+        self.read_entry(cache_key)?.etag
+    }
+
+    /// Persist a secret according to the configured mode
+    pub fn store(&self, cache_key: &str, secret: &Secret) {
+        let format = |dt: OffsetDateTime| dt.format(&Rfc3339).ok();
+        let (value, encrypted) = match (self.mode, &self.cipher) {
+            (DiskCacheMode::FullValue, Some(cipher)) => {
+                let ciphertext = cipher.encrypt(secret.value.expose_secret().as_bytes());
+                (Some(BASE64.encode(ciphertext)), true)
+            }
+            // `open` rejects FullValue without a cipher; never write plaintext
+            _ => (None, false),
+        };
+        let entry = DiskEntry {
+            key: cache_key.to_string(),
+            version: secret.version,
+            etag: secret.etag.clone(),
+            last_modified: secret.last_modified.clone(),
+            updated_at: match format(secret.updated_at) {
+                Some(s) => s,
+                None => return,
+            },
+            expires_at: secret.expires_at.and_then(format),
+            metadata: secret.metadata.clone(),
+            value,
+            encrypted,
+            format: secret.format.clone(),
+            kid: secret.kid.clone(),
+        };
+
+        if let Err(e) = self.write_entry(cache_key, &entry) {
+            warn!("Failed to write disk cache entry: {}", e);
+        }
+    }
+
+    /// Remove a single entry
+    pub fn remove(&self, cache_key: &str) {
+        let path = self.entry_path(cache_key);
+        if let Err(e) = fs::remove_file(&path) {
+            if e.kind() != std::io::ErrorKind::NotFound {
+                warn!("Failed to remove disk cache entry: {}", e);
+            }
+        }
+    }
+
+    /// Remove all entries belonging to a namespace
+    pub fn remove_namespace(&self, namespace: &str) {
+        let prefix = format!("{}/", namespace);
+        self.remove_where(|key| key.starts_with(&prefix));
+    }
+
+    /// Remove all entries
+    pub fn clear(&self) {
+        self.remove_where(|_| true);
+    }
+
+    fn remove_where(&self, predicate: impl Fn(&str) -> bool) {
+        let Ok(dir) = fs::read_dir(&self.dir) else {
+            return;
+        };
+        for path in dir.flatten().map(|entry| entry.path()) {
+            if path.extension().and_then(|ext| ext.to_str()) != Some("json") {
+                continue;
+            }
+            let matches = fs::read(&path)
+                .ok()
+                .and_then(|bytes| serde_json::from_slice::<DiskEntry>(&bytes).ok())
+                .is_some_and(|entry| predicate(&entry.key));
+            if matches {
+                let _ = fs::remove_file(&path);
+            }
+        }
+    }
+
+    fn entry_path(&self, cache_key: &str) -> PathBuf {
+        let mut name = String::with_capacity(69);
+        for byte in Sha256::digest(cache_key.as_bytes()) {
+            let _ = write!(name, "{:02x}", byte);
+        }
+        name.push_str(".json");
+        self.dir.join(name)
+    }
+
+    fn read_entry(&self, cache_key: &str) -> Option<DiskEntry> {
+        let bytes = fs::read(self.entry_path(cache_key)).ok()?;
+        let entry: DiskEntry = serde_json::from_slice(&bytes).ok()?;
+        // Guard against hash collisions and tampered files
+        (entry.key == cache_key).then_some(entry)
+    }
+
+    fn write_entry(&self, cache_key: &str, entry: &DiskEntry) -> std::io::Result<()> {
+        let path = self.entry_path(cache_key);
+        let tmp = path.with_extension(format!("tmp-{}", uuid::Uuid::new_v4()));
+
+        let result = (|| {
+            let mut file = create_private_file(&tmp)?;
+            file.write_all(&serde_json::to_vec(entry)?)?;
+            file.sync_all()?;
+            fs::rename(&tmp, &path)
+        })();
+        if result.is_err() {
+            let _ = fs::remove_file(&tmp);
+        }
+        result
+    }
+}
+
+/// Create the cache's own directory `dir` inside the user-provided `parent`
+///
+/// Missing directories are created owner-only. An existing `dir` must be a
+/// real directory inaccessible to other users; its permissions are never
+/// changed, and neither are those of `parent`.
+#[cfg(unix)]
+fn create_private_dir(parent: &Path, dir: &Path) -> std::io::Result<()> {
+    use std::os::unix::fs::{DirBuilderExt, PermissionsExt};
+
+    fs::DirBuilder::new()
+        .recursive(true)
+        .mode(0o700)
+        .create(parent)?;
+    match fs::DirBuilder::new().mode(0o700).create(dir) {
+        Ok(()) => return Ok(()),
+        Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => {}
+        Err(e) => return Err(e),
+    }
+
+    let metadata = fs::symlink_metadata(dir)?;
+    if !metadata.is_dir() {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            "not a directory (symlinks are not followed)",
+        ));
+    }
+    let mode = metadata.permissions().mode() & 0o777;
+    if mode & 0o077 != 0 {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::PermissionDenied,
+            format!("directory is accessible to other users (mode {:o})", mode),
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(not(unix))]
+fn create_private_dir(_parent: &Path, dir: &Path) -> std::io::Result<()> {
+    fs::create_dir_all(dir)
+}
+
+#[cfg(unix)]
+fn create_private_file(path: &Path) -> std::io::Result<fs::File> {
+    use std::os::unix::fs::OpenOptionsExt;
+
+    fs::OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .mode(0o600)
+        .open(path)
+}
+
+#[cfg(not(unix))]
+fn create_private_file(path: &Path) -> std::io::Result<fs::File> {
+    fs::OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use secrecy::SecretString;
+
+    fn temp_config(mode: DiskCacheMode) -> DiskCacheConfig {
+        DiskCacheConfig {
+            path: std::env::temp_dir().join(format!("secret-store-sdk-{}", uuid::Uuid::new_v4())),
+            mode,
+        }
+    }
+
+    fn secret() -> Secret {
+        Secret {
+            namespace: "prod".to_string(),
+            key: "db-pass".to_string(),
+            value: SecretString::new("hunter2".to_string()),
+            version: 3,
+            expires_at: None,
+            metadata: serde_json::json!({"owner": "team"}),
+            updated_at: OffsetDateTime::from_unix_timestamp(1_704_067_200).unwrap(),
+            etag: Some("\"v3\"".to_string()),
+            last_modified: None,
+            request_id: Some("req-1".to_string()),
+            from_cache: false,
+            format: Some("plaintext".to_string()),
+            kid: None,
+        }
+    }
+
+    #[test]
+    fn test_full_value_round_trip() {
+        let config = temp_config(DiskCacheMode::FullValue);
+        let cache = DiskCache::open(&config, Some(Arc::new(XorCipher(0x5a)))).unwrap();
+        cache.store("prod/db-pass", &secret());
+
+        let loaded = DiskCache::open(&config, Some(Arc::new(XorCipher(0x5a))))
+            .unwrap()
+            .load("prod/db-pass", OffsetDateTime::now_utc())
+            .unwrap();
+        assert_eq!(loaded.namespace, "prod");
+        assert_eq!(loaded.key, "db-pass");
+        assert_eq!(loaded.value.expose_secret(), "hunter2");
+        assert_eq!(loaded.version, 3);
+        assert_eq!(loaded.etag.as_deref(), Some("\"v3\""));
+        assert_eq!(loaded.request_id, None);
+
+        cache.remove_namespace("prod");
+        assert!(cache.etag("prod/db-pass").is_none());
+        let _ = fs::remove_dir_all(&config.path);
+    }
+
+    #[derive(Debug)]
+    struct XorCipher(u8);
+
+    impl CacheCipher for XorCipher {
+        fn encrypt(&self, plaintext: &[u8]) -> Vec<u8> {
+            plaintext.iter().map(|b| b ^ self.0).collect()
+        }
+
+        fn decrypt(&self, ciphertext: &[u8]) -> Result<Vec<u8>> {
+            Ok(ciphertext.iter().map(|b| b ^ self.0).collect())
+        }
+    }
+
+    #[test]
+    fn test_encrypted_round_trip() {
+        let config = temp_config(DiskCacheMode::FullValue);
+        let cache = DiskCache::open(&config, Some(Arc::new(XorCipher(0x5a)))).unwrap();
+        cache.store("prod/db-pass", &secret());
+
+        let contents = fs::read_to_string(cache.entry_path("prod/db-pass")).unwrap();
+        assert!(!contents.contains("hunter2"));
+
+        let loaded = cache
+            .load("prod/db-pass", OffsetDateTime::now_utc())
+            .unwrap();
+        assert_eq!(loaded.value.expose_secret(), "hunter2");
+
+        // FullValue refuses to run without a cipher
+        let err = DiskCache::open(&config, None).unwrap_err();
+        assert!(matches!(err, Error::Config(_)));
+        let _ = fs::remove_dir_all(&config.path);
+    }
+
+    #[test]
+    fn test_plaintext_entries_are_ignored() {
+        let config = temp_config(DiskCacheMode::FullValue);
+        let cache = DiskCache::open(&config, Some(Arc::new(XorCipher(0x5a)))).unwrap();
+        let mut entry: DiskEntry = {
+            cache.store("prod/db-pass", &secret());
+            let bytes = fs::read(cache.entry_path("prod/db-pass")).unwrap();
+            serde_json::from_slice(&bytes).unwrap()
+        };
+        entry.value = Some("hunter2".to_string());
+        entry.encrypted = false;
+        cache.write_entry("prod/db-pass", &entry).unwrap();
+
+        assert!(cache
+            .load("prod/db-pass", OffsetDateTime::now_utc())
+            .is_none());
+        let _ = fs::remove_dir_all(&config.path);
+    }
+
+    #[test]
+    fn test_etag_only_never_stores_value() {
+        let config = temp_config(DiskCacheMode::EtagOnly);
+        let cache = DiskCache::open(&config, None).unwrap();
+        cache.store("prod/db-pass", &secret());
+
+        assert_eq!(cache.etag("prod/db-pass").as_deref(), Some("\"v3\""));
+        assert!(cache
+            .load("prod/db-pass", OffsetDateTime::now_utc())
+            .is_none());
+
+        let contents = fs::read_to_string(cache.entry_path("prod/db-pass")).unwrap();
+        assert!(!contents.contains("hunter2"));
+
+        cache.clear();
+        assert!(cache.etag("prod/db-pass").is_none());
+        let _ = fs::remove_dir_all(&config.path);
+    }
+
+    #[test]
+    fn test_expired_entry_is_dropped() {
+        let config = temp_config(DiskCacheMode::FullValue);
+        let cache = DiskCache::open(&config, Some(Arc::new(XorCipher(0x5a)))).unwrap();
+        let mut expiring = secret();
+        expiring.expires_at = Some(OffsetDateTime::now_utc() - time::Duration::minutes(1));
+        cache.store("prod/db-pass", &expiring);
+
+        assert!(cache
+            .load("prod/db-pass", OffsetDateTime::now_utc())
+            .is_none());
+        assert!(cache.etag("prod/db-pass").is_none());
+        let _ = fs::remove_dir_all(&config.path);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_permissions_are_private() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let config = temp_config(DiskCacheMode::EtagOnly);
+        let cache = DiskCache::open(&config, None).unwrap();
+        cache.store("prod/db-pass", &secret());
+
+        let dir_mode = fs::metadata(&cache.dir).unwrap().permissions().mode();
+        let file_mode = fs::metadata(cache.entry_path("prod/db-pass"))
+            .unwrap()
+            .permissions()
+            .mode();
+        assert_eq!(dir_mode & 0o777, 0o700);
+        assert_eq!(file_mode & 0o777, 0o600);
+        let _ = fs::remove_dir_all(&config.path);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_existing_directories_are_not_chmodded() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let config = temp_config(DiskCacheMode::EtagOnly);
+        fs::create_dir_all(&config.path).unwrap();
+        fs::set_permissions(&config.path, fs::Permissions::from_mode(0o755)).unwrap();
+
+        let cache = DiskCache::open(&config, None).unwrap();
+        let parent_mode = fs::metadata(&config.path).unwrap().permissions().mode();
+        assert_eq!(parent_mode & 0o777, 0o755);
+        assert_eq!(cache.dir, config.path.join(SUBDIR));
+
+        // A cache directory others can read is refused, not tightened
+        fs::set_permissions(&cache.dir, fs::Permissions::from_mode(0o755)).unwrap();
+        let err = DiskCache::open(&config, None).unwrap_err();
+        assert!(err.to_string().contains("accessible to other users"));
+        let dir_mode = fs::metadata(&cache.dir).unwrap().permissions().mode();
+        assert_eq!(dir_mode & 0o777, 0o755);
+        let _ = fs::remove_dir_all(&config.path);
+    }
+}
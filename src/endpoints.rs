@@ -56,6 +56,14 @@ impl Endpoints {
         ))
     }
 
+    pub fn search_secrets(&self, namespace: &str) -> String {
+        self.url(&format!(
+            "{}/secrets/{}/search",
+            API_V2_BASE,
+            encode_path(namespace)
+        ))
+    }
+
     // Batch
     #[allow(dead_code)]
     pub fn batch_get(&self, namespace: &str) -> String {
@@ -104,6 +112,24 @@ impl Endpoints {
         ))
     }
 
+    pub fn restore_secret(&self, namespace: &str, key: &str) -> String {
+        self.url(&format!(
+            "{}/secrets/{}/{}/restore",
+            API_V2_BASE,
+            encode_path(namespace),
+            encode_path(key)
+        ))
+    }
+
+    pub fn generate_secret(&self, namespace: &str, key: &str) -> String {
+        self.url(&format!(
+            "{}/secrets/{}/{}/generate",
+            API_V2_BASE,
+            encode_path(namespace),
+            encode_path(key)
+        ))
+    }
+
     // Namespaces
     #[allow(dead_code)]
     pub fn list_namespaces(&self) -> String {
@@ -140,6 +166,23 @@ impl Endpoints {
         ))
     }
 
+    pub fn namespace_protection(&self, namespace: &str) -> String {
+        self.url(&format!(
+            "{}/namespaces/{}/protection",
+            API_V2_BASE,
+            encode_path(namespace)
+        ))
+    }
+
+    // Events
+    pub fn events(&self, namespace: &str) -> String {
+        self.url(&format!(
+            "{}/namespaces/{}/events",
+            API_V2_BASE,
+            encode_path(namespace)
+        ))
+    }
+
     // Environment
     #[allow(dead_code)]
     pub fn export_env(&self, namespace: &str) -> String {
@@ -180,10 +223,107 @@ impl Endpoints {
         self.get_api_key(key_id)
     }
 
+    pub fn whoami(&self) -> String {
+        self.url(&format!("{}/whoami", API_V2_BASE))
+    }
+
     // Metrics
     pub fn metrics(&self) -> String {
         self.url(&format!("{}/metrics", API_V2_BASE))
     }
+
+    // Caller-supplied path, used as-is
+    pub fn custom(&self, path: &str) -> String {
+        self.url(path)
+    }
+}
+
+/// Endpoint template and namespace of a request, used as metrics labels
+///
+/// Keys, versions and API key IDs are replaced by placeholders so that label
+/// cardinality stays bounded no matter how many secrets are accessed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(not(feature = "metrics"), allow(dead_code))]
+pub struct RouteLabel {
+    /// Route template, e.g. `/api/v2/secrets/{namespace}/{key}`
+    pub route: &'static str,
+    /// Namespace segment of the path, as sent (percent-encoded)
+    pub namespace: Option<String>,
+}
+
+#[cfg_attr(not(feature = "metrics"), allow(dead_code))]
+impl RouteLabel {
+    /// Match a request path against the endpoints above
+    ///
+    /// Paths outside the known endpoints (e.g. from `Client::raw_request`)
+    /// map to the route `"other"`.
+    ///
+    /// Parsing the path gives the same template the `Endpoints` method would
+    /// have: every SDK request URL comes from one of those methods, and they
+    /// percent-encode namespaces, keys and versions, so a caller-supplied
+    /// value is always exactly one segment and the segment count plus the
+    /// fixed words identify the method. The one overlap is a key named
+    /// `search` or `batch`, which is labelled with that namespace-level route.
+    pub fn from_path(path: &str) -> Self {
+        let rest = match path.split_once(API_V2_BASE) {
+            Some((_, rest)) if rest.is_empty() || rest.starts_with('/') => rest,
+            _ => return Self::other(),
+        };
+        let segments: Vec<&str> = rest.split('/').filter(|s| !s.is_empty()).collect();
+
+        let (route, namespace) = match segments.as_slice() {
+            [] => ("/api/v2", None),
+            ["secrets", ns] => ("/api/v2/secrets/{namespace}", Some(ns)),
+            ["secrets", ns, "search"] => ("/api/v2/secrets/{namespace}/search", Some(ns)),
+            ["secrets", ns, "batch"] => ("/api/v2/secrets/{namespace}/batch", Some(ns)),
+            ["secrets", ns, _] => ("/api/v2/secrets/{namespace}/{key}", Some(ns)),
+            ["secrets", ns, _, "versions"] => {
+                ("/api/v2/secrets/{namespace}/{key}/versions", Some(ns))
+            }
+            ["secrets", ns, _, "versions", _] => (
+                "/api/v2/secrets/{namespace}/{key}/versions/{version}",
+                Some(ns),
+            ),
+            ["secrets", ns, _, "restore"] => {
+                ("/api/v2/secrets/{namespace}/{key}/restore", Some(ns))
+            }
+            ["secrets", ns, _, "generate"] => {
+                ("/api/v2/secrets/{namespace}/{key}/generate", Some(ns))
+            }
+            ["secrets", ns, _, "rollback", _] => (
+                "/api/v2/secrets/{namespace}/{key}/rollback/{version}",
+                Some(ns),
+            ),
+            ["namespaces"] => ("/api/v2/namespaces", None),
+            ["namespaces", ns] => ("/api/v2/namespaces/{namespace}", Some(ns)),
+            ["namespaces", ns, "init"] => ("/api/v2/namespaces/{namespace}/init", Some(ns)),
+            ["namespaces", ns, "events"] => ("/api/v2/namespaces/{namespace}/events", Some(ns)),
+            ["namespaces", ns, "protection"] => {
+                ("/api/v2/namespaces/{namespace}/protection", Some(ns))
+            }
+            ["env", ns] => ("/api/v2/env/{namespace}", Some(ns)),
+            ["audit"] => ("/api/v2/audit", None),
+            ["livez"] => ("/api/v2/livez", None),
+            ["readyz"] => ("/api/v2/readyz", None),
+            ["metrics"] => ("/api/v2/metrics", None),
+            ["whoami"] => ("/api/v2/whoami", None),
+            ["api-keys"] => ("/api/v2/api-keys", None),
+            ["api-keys", _] => ("/api/v2/api-keys/{key_id}", None),
+            _ => return Self::other(),
+        };
+
+        Self {
+            route,
+            namespace: namespace.map(|ns| ns.to_string()),
+        }
+    }
+
+    fn other() -> Self {
+        Self {
+            route: "other",
+            namespace: None,
+        }
+    }
 }
 
 #[cfg(test)]
@@ -205,6 +345,99 @@ mod tests {
         );
 
         assert_eq!(endpoints.discovery(), "https://api.example.com/api/v2");
+
+        assert_eq!(
+            endpoints.events("prod"),
+            "https://api.example.com/api/v2/namespaces/prod/events"
+        );
+    }
+
+    #[test]
+    fn test_search_endpoint() {
+        let endpoints = Endpoints::new("https://api.example.com");
+        assert_eq!(
+            endpoints.search_secrets("prod env"),
+            "https://api.example.com/api/v2/secrets/prod%20env/search"
+        );
+    }
+
+    #[test]
+    fn test_encoded_path_segments() {
+        let endpoints = Endpoints::new("https://api.example.com/");
+
+        for key in [
+            "config/db/url",
+            "my key",
+            "100%",
+            "schlüssel",
+            r"a\b",
+            "a?b#c",
+        ] {
+            let url = endpoints.get_version("prod env", key, 2);
+            let parsed = reqwest::Url::parse(&url).unwrap();
+            let segments: Vec<String> = parsed
+                .path_segments()
+                .unwrap()
+                .map(|s| {
+                    percent_encoding::percent_decode_str(s)
+                        .decode_utf8()
+                        .unwrap()
+                        .into_owned()
+                })
+                .collect();
+
+            // The server sees exactly one namespace and one key segment
+            assert_eq!(
+                segments,
+                vec!["api", "v2", "secrets", "prod env", key, "versions", "2"],
+                "{}",
+                url
+            );
+        }
+
+        assert_eq!(
+            endpoints.get_secret("prod", "config/db url%"),
+            "https://api.example.com/api/v2/secrets/prod/config%2Fdb%20url%25"
+        );
+    }
+
+    #[test]
+    fn test_route_label() {
+        let endpoints = Endpoints::new("https://api.example.com/prefix");
+        let label = |url: String| RouteLabel::from_path(reqwest::Url::parse(&url).unwrap().path());
+
+        let first = label(endpoints.get_secret("prod", "db-pass"));
+        let second = label(endpoints.get_secret("prod", "config/api key"));
+        assert_eq!(first, second);
+        assert_eq!(first.route, "/api/v2/secrets/{namespace}/{key}");
+        assert_eq!(first.namespace.as_deref(), Some("prod"));
+
+        assert_eq!(
+            label(endpoints.get_version("prod", "db-pass", 3)).route,
+            "/api/v2/secrets/{namespace}/{key}/versions/{version}"
+        );
+        assert_eq!(
+            label(endpoints.restore_secret("prod", "db-pass")).route,
+            "/api/v2/secrets/{namespace}/{key}/restore"
+        );
+        assert_eq!(
+            label(endpoints.generate_secret("prod", "db-pass")).route,
+            "/api/v2/secrets/{namespace}/{key}/generate"
+        );
+        assert_eq!(
+            label(endpoints.namespace_protection("prod")).route,
+            "/api/v2/namespaces/{namespace}/protection"
+        );
+        assert_eq!(
+            label(endpoints.search_secrets("prod")).route,
+            "/api/v2/secrets/{namespace}/search"
+        );
+        assert_eq!(
+            label(endpoints.get_api_key("key-123")).route,
+            "/api/v2/api-keys/{key_id}"
+        );
+        assert_eq!(label(endpoints.audit()).namespace, None);
+        assert_eq!(label(endpoints.custom("/preview/widgets")).route, "other");
     }
 
     #[test]
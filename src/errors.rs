@@ -17,13 +17,15 @@
 //! # Example
 //!
 //! ```no_run
-//! # use secret_store_sdk::{Client, Error};
+//! # use secret_store_sdk::{Client, ErrorKind};
 //! # async fn example(client: &Client) -> Result<(), Box<dyn std::error::Error>> {
+//! // Retried failures arrive as `Error::RetriesExhausted`; `status_code()`
+//! // and `kind()` report the error of the last attempt
 //! match client.get_secret("prod", "key", Default::default()).await {
 //!     Ok(secret) => println!("Got secret v{}", secret.version),
-//!     Err(Error::Http { status: 404, .. }) => println!("Secret not found"),
-//!     Err(Error::Http { status: 403, .. }) => println!("Access denied"),
-//!     Err(Error::Timeout) => println!("Request timed out"),
+//!     Err(e) if e.status_code() == Some(404) => println!("Secret not found"),
+//!     Err(e) if e.status_code() == Some(403) => println!("Access denied"),
+//!     Err(e) if e.kind() == ErrorKind::Timeout => println!("Request timed out"),
 //!     Err(e) => return Err(e.into()),
 //! }
 //! # Ok(())
@@ -44,6 +46,10 @@ pub enum Error {
         /// HTTP status code
         status: u16,
         /// Error category from server (auth, validation, rate_limit, etc.)
+        ///
+        /// A 426 response always has the category `upgrade_required`, and
+        /// its message names the API version the server requires (from the
+        /// `X-Min-API-Version` header or the body's `min_api_version`).
         category: String,
         /// Error message from server
         message: String,
@@ -56,8 +62,14 @@ pub enum Error {
     Deserialize(String),
 
     /// Network error
-    #[error("network: {0}")]
-    Network(String),
+    #[error("network: {message}")]
+    Network {
+        /// Error message, including its source chain
+        message: String,
+        /// Failure class: one of `ErrorKind::NetworkDns`, `NetworkConnect`,
+        /// `NetworkTls`, `NetworkBody` or the generic `Network`
+        kind: ErrorKind,
+    },
 
     /// Request timeout
     #[error("timeout")]
@@ -67,11 +79,141 @@ pub enum Error {
     #[error("config: {0}")]
     Config(String),
 
+    /// Server returned 304 Not Modified but there was no cached entry to serve
+    ///
+    /// Happens when conditional headers are sent for a secret that is not
+    /// cached locally. Use `Stale304Policy::Refetch` to refetch automatically.
+    #[error("not modified: server returned 304 but no cached entry found (req={request_id:?})")]
+    NotModifiedNoCache {
+        /// Request ID from x-request-id header
+        request_id: Option<String>,
+    },
+
+    /// A create-only write found an existing secret
+    ///
+    /// Returned by `Client::create_secret` when the server rejects the write
+    /// with 409 Conflict or 412 Precondition Failed.
+    #[error("already exists: {namespace}/{key} (req={request_id:?})")]
+    AlreadyExists {
+        /// Namespace of the secret
+        namespace: String,
+        /// Key of the secret
+        key: String,
+        /// HTTP status code returned by the server (409 or 412)
+        status: u16,
+        /// Request ID from x-request-id header
+        request_id: Option<String>,
+    },
+
+    /// A versioned write found a different current version
+    ///
+    /// Returned by `Client::put_secret` when `PutOpts::expected_version` is
+    /// set and the server rejects the write with 409 Conflict or 412
+    /// Precondition Failed.
+    #[error("version conflict: {namespace}/{key} is not at version {expected_version} (req={request_id:?})")]
+    VersionConflict {
+        /// Namespace of the secret
+        namespace: String,
+        /// Key of the secret
+        key: String,
+        /// Version the write expected to replace
+        expected_version: i32,
+        /// HTTP status code returned by the server (409 or 412)
+        status: u16,
+        /// Request ID from x-request-id header
+        request_id: Option<String>,
+    },
+
+    /// A restore targeted a secret that is not soft-deleted
+    ///
+    /// Returned by `Client::restore_secret` when the server answers 409
+    /// Conflict, e.g. because the secret was never deleted.
+    #[error("not soft-deleted: {namespace}/{key} (req={request_id:?})")]
+    NotSoftDeleted {
+        /// Namespace of the secret
+        namespace: String,
+        /// Key of the secret
+        key: String,
+        /// HTTP status code returned by the server
+        status: u16,
+        /// Request ID from x-request-id header
+        request_id: Option<String>,
+    },
+
+    /// A restore came after the soft-delete retention window
+    ///
+    /// Returned by `Client::restore_secret` when the server answers 410 Gone.
+    /// The secret has been purged and can only be recovered from backups.
+    #[error("retention expired: {namespace}/{key} can no longer be restored (req={request_id:?})")]
+    RetentionExpired {
+        /// Namespace of the secret
+        namespace: String,
+        /// Key of the secret
+        key: String,
+        /// HTTP status code returned by the server
+        status: u16,
+        /// Request ID from x-request-id header
+        request_id: Option<String>,
+    },
+
+    /// A namespace deletion was refused because the namespace is protected
+    ///
+    /// Returned by `Client::delete_namespace` when the server answers 409
+    /// Conflict. Disable protection with `Client::set_namespace_protection`
+    /// first if the deletion is intended.
+    #[error("namespace {namespace} is protected; disable protection first (req={request_id:?})")]
+    Protected {
+        /// Namespace that could not be deleted
+        namespace: String,
+        /// Request ID from x-request-id header
+        request_id: Option<String>,
+    },
+
+    /// The request was cancelled through its cancellation token
+    ///
+    /// See `Client::with_cancellation`.
+    #[error("cancelled")]
+    Cancelled,
+
+    /// Retries gave up on a request that kept failing with retryable errors
+    ///
+    /// `kind()`, `status_code()`, `request_id()` and `is_retryable()` report
+    /// the last error. Requests that failed on their first attempt return
+    /// that error directly.
+    #[error("retries exhausted after {} attempts: {last}", .attempts.len())]
+    RetriesExhausted {
+        /// Error of the final attempt
+        last: Box<Error>,
+        /// Every attempt, in order
+        attempts: Vec<AttemptRecord>,
+    },
+
     /// Other errors
     #[error("other: {0}")]
     Other(String),
 }
 
+/// Outcome of one attempt of a retried request
+#[derive(Debug, Clone)]
+pub struct AttemptRecord {
+    /// HTTP status code, if the attempt got an error response
+    pub status: Option<u16>,
+    /// Error the attempt failed with
+    pub error: String,
+    /// Time spent on the attempt
+    pub duration: std::time::Duration,
+}
+
+impl AttemptRecord {
+    pub(crate) fn new(error: &Error, duration: std::time::Duration) -> Self {
+        Self {
+            status: error.status_code(),
+            error: error.to_string(),
+            duration,
+        }
+    }
+}
+
 /// Error categories returned by the server
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum ErrorKind {
@@ -89,10 +231,34 @@ pub enum ErrorKind {
     Internal,
     /// Service unavailable (503)
     ServiceUnavailable,
+    /// Conditional request precondition failed (412)
+    PreconditionFailed,
+    /// Resource already exists (create-only writes)
+    AlreadyExists,
+    /// Current version differs from the expected one (versioned writes)
+    VersionConflict,
+    /// Restore of a secret that is not soft-deleted
+    NotSoftDeleted,
+    /// Restore after the soft-delete retention window
+    RetentionExpired,
+    /// Deletion of a protected namespace
+    Protected,
     /// Cryptographic operation error
     Crypto,
     /// Configuration error
     Config,
+    /// DNS resolution failed (often a wrong base URL)
+    NetworkDns,
+    /// Connection could not be established or was reset
+    NetworkConnect,
+    /// TLS handshake or certificate validation failed (not retried)
+    NetworkTls,
+    /// Connection failed while transferring a request or response body
+    NetworkBody,
+    /// Other network failure
+    Network,
+    /// Request cancelled by the caller
+    Cancelled,
     /// Other/unknown error
     Other,
 }
@@ -108,8 +274,10 @@ impl ErrorKind {
             "timeout" => ErrorKind::Timeout,
             "internal" => ErrorKind::Internal,
             "service" => ErrorKind::ServiceUnavailable,
+            "precondition_failed" => ErrorKind::PreconditionFailed,
             "crypto" => ErrorKind::Crypto,
             "config" => ErrorKind::Config,
+            "protected" => ErrorKind::Protected,
             _ => ErrorKind::Other,
         }
     }
@@ -119,9 +287,18 @@ impl Error {
     /// Get the error kind for categorization
     pub fn kind(&self) -> ErrorKind {
         match self {
+            Error::Http { status: 412, .. } => ErrorKind::PreconditionFailed,
             Error::Http { category, .. } => ErrorKind::from_category(category),
+            Error::AlreadyExists { .. } => ErrorKind::AlreadyExists,
+            Error::VersionConflict { .. } => ErrorKind::VersionConflict,
+            Error::NotSoftDeleted { .. } => ErrorKind::NotSoftDeleted,
+            Error::RetentionExpired { .. } => ErrorKind::RetentionExpired,
+            Error::Protected { .. } => ErrorKind::Protected,
+            Error::Network { kind, .. } => kind.clone(),
             Error::Timeout => ErrorKind::Timeout,
             Error::Config(_) => ErrorKind::Config,
+            Error::Cancelled => ErrorKind::Cancelled,
+            Error::RetriesExhausted { last, .. } => last.kind(),
             _ => ErrorKind::Other,
         }
     }
@@ -130,8 +307,10 @@ impl Error {
     pub fn is_retryable(&self) -> bool {
         match self {
             Error::Http { status, .. } => matches!(status, 429 | 500 | 502 | 503 | 504),
-            Error::Network(_) => true,
+            // Retrying a failed TLS handshake won't fix a bad certificate
+            Error::Network { kind, .. } => *kind != ErrorKind::NetworkTls,
             Error::Timeout => true,
+            Error::RetriesExhausted { last, .. } => last.is_retryable(),
             _ => false,
         }
     }
@@ -140,6 +319,13 @@ impl Error {
     pub fn status_code(&self) -> Option<u16> {
         match self {
             Error::Http { status, .. } => Some(*status),
+            Error::NotModifiedNoCache { .. } => Some(304),
+            Error::Protected { .. } => Some(409),
+            Error::AlreadyExists { status, .. }
+            | Error::VersionConflict { status, .. }
+            | Error::NotSoftDeleted { status, .. }
+            | Error::RetentionExpired { status, .. } => Some(*status),
+            Error::RetriesExhausted { last, .. } => last.status_code(),
             _ => None,
         }
     }
@@ -147,7 +333,14 @@ impl Error {
     /// Get the request ID if available
     pub fn request_id(&self) -> Option<&str> {
         match self {
-            Error::Http { request_id, .. } => request_id.as_deref(),
+            Error::Http { request_id, .. }
+            | Error::NotModifiedNoCache { request_id }
+            | Error::AlreadyExists { request_id, .. }
+            | Error::VersionConflict { request_id, .. }
+            | Error::NotSoftDeleted { request_id, .. }
+            | Error::RetentionExpired { request_id, .. }
+            | Error::Protected { request_id, .. } => request_id.as_deref(),
+            Error::RetriesExhausted { last, .. } => last.request_id(),
             _ => None,
         }
     }
@@ -166,6 +359,90 @@ impl Error {
             request_id,
         }
     }
+
+    /// Create a network error that did not come from reqwest
+    pub(crate) fn network(message: impl Into<String>) -> Self {
+        Error::Network {
+            message: message.into(),
+            kind: ErrorKind::Network,
+        }
+    }
+}
+
+/// Classify a reqwest error by its flags and the types in its source chain
+///
+/// Messages are never inspected: reqwest's include the request URL, so a key
+/// named e.g. `tls-certificate` must not make a refused connection look like
+/// a TLS failure.
+fn classify_network_error(err: &reqwest::Error) -> ErrorKind {
+    use std::io::ErrorKind as Io;
+
+    let mut connection_lost = false;
+    let mut next = std::error::Error::source(err);
+    while let Some(cause) = next {
+        if is_tls_error(cause) {
+            return ErrorKind::NetworkTls;
+        }
+        // hyper-util labels resolver failures with this fixed message, which
+        // never contains the host
+        if cause.to_string() == "dns error" {
+            return ErrorKind::NetworkDns;
+        }
+
+        next = match cause.downcast_ref::<std::io::Error>() {
+            Some(io) => {
+                match io.kind() {
+                    // TLS stacks report handshake failures as invalid data
+                    Io::InvalidData if err.is_connect() => return ErrorKind::NetworkTls,
+                    Io::ConnectionRefused
+                    | Io::ConnectionReset
+                    | Io::ConnectionAborted
+                    | Io::NotConnected
+                    | Io::BrokenPipe
+                    | Io::AddrNotAvailable => connection_lost = true,
+                    _ => {}
+                }
+                // An I/O error's `source` skips its payload, so descend manually
+                io.get_ref()
+                    .map(|inner| inner as &(dyn std::error::Error + 'static))
+            }
+            None => cause.source(),
+        };
+    }
+
+    if err.is_body() {
+        ErrorKind::NetworkBody
+    } else if err.is_connect() || connection_lost {
+        ErrorKind::NetworkConnect
+    } else {
+        ErrorKind::Network
+    }
+}
+
+/// Whether `cause` is an error type of the enabled TLS backend
+fn is_tls_error(cause: &(dyn std::error::Error + 'static)) -> bool {
+    #[cfg(feature = "rustls-tls")]
+    if cause.is::<rustls::Error>() {
+        return true;
+    }
+    #[cfg(feature = "native-tls")]
+    if cause.is::<native_tls::Error>() {
+        return true;
+    }
+    let _ = cause;
+    false
+}
+
+/// Render an error together with its source chain
+fn with_sources(err: &dyn std::error::Error) -> String {
+    let mut message = err.to_string();
+    let mut source = err.source();
+    while let Some(cause) = source {
+        message.push_str(": ");
+        message.push_str(&cause.to_string());
+        source = cause.source();
+    }
+    message
 }
 
 /// Server error response structure
@@ -176,14 +453,24 @@ pub(crate) struct ErrorResponse {
     #[allow(dead_code)]
     pub timestamp: String,
     pub status: u16,
+    /// Oldest API version the server accepts, sent with 426 responses
+    #[serde(default)]
+    pub min_api_version: Option<String>,
 }
 
 impl From<reqwest::Error> for Error {
     fn from(err: reqwest::Error) -> Self {
+        // The URL names the namespace and key, and these messages end up in
+        // logs and retry histories regardless of `log_key_names`
+        let err = err.without_url();
         if err.is_timeout() {
             Error::Timeout
-        } else if err.is_connect() || err.is_request() {
-            Error::Network(err.to_string())
+        } else if err.is_connect() || err.is_request() || err.is_body() {
+            // The source chain tells DNS, TLS and connect failures apart
+            Error::Network {
+                message: with_sources(&err),
+                kind: classify_network_error(&err),
+            }
         } else if err.is_decode() {
             Error::Deserialize(err.to_string())
         } else {
@@ -210,7 +497,16 @@ mod tests {
             ErrorKind::Validation
         );
         assert_eq!(ErrorKind::from_category("not_found"), ErrorKind::NotFound);
+        assert_eq!(ErrorKind::from_category("protected"), ErrorKind::Protected);
         assert_eq!(ErrorKind::from_category("unknown"), ErrorKind::Other);
+
+        let err = Error::Http {
+            status: 412,
+            category: "unknown".to_string(),
+            message: "HTTP error 412".to_string(),
+            request_id: None,
+        };
+        assert_eq!(err.kind(), ErrorKind::PreconditionFailed);
     }
 
     #[test]
@@ -231,13 +527,68 @@ mod tests {
         };
         assert!(!err.is_retryable());
 
-        let err = Error::Network("Connection failed".to_string());
+        let err = Error::network("Connection failed");
         assert!(err.is_retryable());
 
+        let err = Error::Network {
+            message: "invalid peer certificate: UnknownIssuer".to_string(),
+            kind: ErrorKind::NetworkTls,
+        };
+        assert!(!err.is_retryable());
+
         let err = Error::Config("Invalid URL".to_string());
         assert!(!err.is_retryable());
     }
 
+    #[tokio::test]
+    async fn test_network_error_classification() {
+        let http = reqwest::Client::new();
+        let kind = |url: String| {
+            let request = http.get(url).send();
+            async move { Error::from(request.await.unwrap_err()).kind() }
+        };
+
+        // Words in the URL no longer sway the classification
+        let refused = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = refused.local_addr().unwrap().port();
+        drop(refused);
+        assert_eq!(
+            kind(format!(
+                "http://127.0.0.1:{}/api/v2/secrets/tls/ssl-certificate-handshake-body",
+                port
+            ))
+            .await,
+            ErrorKind::NetworkConnect
+        );
+
+        assert_eq!(
+            kind("http://secrets.invalid/api/v2/secrets/prod/key".to_string()).await,
+            ErrorKind::NetworkDns
+        );
+
+        // A plain-text server behind an https URL fails the TLS handshake
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        drop(tokio::spawn(async move {
+            use tokio::io::AsyncWriteExt;
+            while let Ok((mut socket, _)) = listener.accept().await {
+                let _ = socket.write_all(b"HTTP/1.1 400 Bad Request\r\n\r\n").await;
+            }
+        }));
+        let tls = kind(format!(
+            "https://127.0.0.1:{}/api/v2/secrets/prod/key",
+            port
+        ))
+        .await;
+        assert_eq!(tls, ErrorKind::NetworkTls);
+
+        assert_eq!(
+            Error::network("Token refresh failed").kind(),
+            ErrorKind::Network
+        );
+        assert!(Error::network("Token refresh failed").is_retryable());
+    }
+
     #[test]
     fn test_error_status_code() {
         let err = Error::Http {
@@ -262,7 +613,13 @@ mod tests {
         };
         assert_eq!(err.request_id(), Some("req-456"));
 
-        let err = Error::Network("Failed".to_string());
+        let err = Error::network("Failed");
         assert_eq!(err.request_id(), None);
+
+        let err = Error::NotModifiedNoCache {
+            request_id: Some("req-789".to_string()),
+        };
+        assert_eq!(err.request_id(), Some("req-789"));
+        assert_eq!(err.status_code(), Some(304));
     }
 }
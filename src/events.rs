@@ -0,0 +1,303 @@
+//! Server-sent events support for change notifications
+
+use crate::{
+    client::Client,
+    errors::{Error, Result},
+    models::{ChangeEvent, SubscribeOpts},
+};
+use reqwest::Response;
+use std::collections::VecDeque;
+use std::time::Duration;
+use tracing::{debug, warn};
+
+/// A single dispatched SSE frame
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(crate) struct SseFrame {
+    /// Event type (`event:` field)
+    pub event: Option<String>,
+    /// Event payload, multiple `data:` lines joined by `\n`
+    pub data: String,
+    /// Event ID (`id:` field)
+    pub id: Option<String>,
+}
+
+/// Incremental parser for `text/event-stream` bodies
+#[derive(Debug, Default)]
+pub(crate) struct SseParser {
+    buf: Vec<u8>,
+    event: Option<String>,
+    data: String,
+    id: Option<String>,
+    retry: Option<Duration>,
+}
+
+impl SseParser {
+    /// Create a new parser
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Reconnect delay requested by the server via the `retry:` field
+    pub fn retry(&self) -> Option<Duration> {
+        self.retry
+    }
+
+    /// Discard any partially received frame
+    pub fn reset(&mut self) {
+        self.buf.clear();
+        self.event = None;
+        self.data.clear();
+        self.id = None;
+    }
+
+    /// Feed a chunk of body bytes, returning all frames completed by it
+    pub fn feed(&mut self, chunk: &[u8]) -> Vec<SseFrame> {
+        self.buf.extend_from_slice(chunk);
+
+        let mut frames = Vec::new();
+        while let Some(pos) = self.buf.iter().position(|&b| b == b'\n') {
+            let mut line: Vec<u8> = self.buf.drain(..=pos).collect();
+            let _ = line.pop();
+            if line.last() == Some(&b'\r') {
+                let _ = line.pop();
+            }
+            let line = String::from_utf8_lossy(&line);
+            if let Some(frame) = self.process_line(&line) {
+                frames.push(frame);
+            }
+        }
+        frames
+    }
+
+    fn process_line(&mut self, line: &str) -> Option<SseFrame> {
+        if line.is_empty() {
+            return self.dispatch();
+        }
+        if line.starts_with(':') {
+            // Comment / keep-alive
+            return None;
+        }
+
+        let (field, value) = match line.split_once(':') {
+            Some((field, value)) => (field, value.strip_prefix(' ').unwrap_or(value)),
+            None => (line, ""),
+        };
+
+        match field {
+            "event" => self.event = Some(value.to_string()),
+            "data" => {
+                self.data.push_str(value);
+                self.data.push('\n');
+            }
+            "id" if !value.contains('\0') => self.id = Some(value.to_string()),
+            "retry" => {
+                if let Ok(ms) = value.parse::<u64>() {
+                    self.retry = Some(Duration::from_millis(ms));
+                }
+            }
+            _ => {}
+        }
+        None
+    }
+
+    fn dispatch(&mut self) -> Option<SseFrame> {
+        let event = self.event.take();
+        let id = self.id.take();
+        if self.data.is_empty() {
+            return None;
+        }
+        let mut data = std::mem::take(&mut self.data);
+        let _ = data.pop();
+        Some(SseFrame { event, data, id })
+    }
+}
+
+/// State driving a [`Client::subscribe_changes`] stream
+#[derive(Debug)]
+pub(crate) struct ChangeSubscription {
+    client: Client,
+    namespace: String,
+    opts: SubscribeOpts,
+    last_event_id: Option<String>,
+    response: Option<Response>,
+    parser: SseParser,
+    pending: VecDeque<Result<ChangeEvent>>,
+    next_delay: Option<Duration>,
+    failures: u32,
+    done: bool,
+}
+
+impl ChangeSubscription {
+    /// Create a new, not yet connected, subscription
+    pub fn new(client: Client, namespace: &str, opts: SubscribeOpts) -> Self {
+        Self {
+            client,
+            namespace: namespace.to_string(),
+            last_event_id: opts.last_event_id.clone(),
+            opts,
+            response: None,
+            parser: SseParser::new(),
+            pending: VecDeque::new(),
+            next_delay: None,
+            failures: 0,
+            done: false,
+        }
+    }
+
+    /// Get the next change event, reconnecting as needed
+    ///
+    /// Returns `None` once the subscription has given up after a
+    /// non-retryable error or too many failed reconnects.
+    pub async fn next_event(&mut self) -> Option<Result<ChangeEvent>> {
+        loop {
+            if let Some(item) = self.pending.pop_front() {
+                return Some(item);
+            }
+            if self.done {
+                return None;
+            }
+
+            let Some(response) = self.response.as_mut() else {
+                if let Err(e) = self.connect().await {
+                    self.done = true;
+                    return Some(Err(e));
+                }
+                continue;
+            };
+
+            match response.chunk().await {
+                Ok(Some(chunk)) => {
+                    for frame in self.parser.feed(&chunk) {
+                        if let Some(id) = &frame.id {
+                            self.last_event_id = Some(id.clone());
+                        }
+                        match self.decode(frame) {
+                            Ok(Some(event)) => {
+                                self.client
+                                    .invalidate_cache(&event.namespace, &event.key)
+                                    .await;
+                                self.pending.push_back(Ok(event));
+                            }
+                            Ok(None) => {}
+                            Err(e) => self.pending.push_back(Err(e)),
+                        }
+                    }
+                }
+                Ok(None) => {
+                    debug!("Event stream for {} closed by server", self.namespace);
+                    self.disconnect();
+                }
+                Err(e) => {
+                    debug!("Event stream for {} interrupted: {}", self.namespace, e);
+                    self.disconnect();
+                }
+            }
+        }
+    }
+
+    /// Open the event stream, retrying with backoff on transient failures
+    async fn connect(&mut self) -> Result<()> {
+        loop {
+            if let Some(delay) = self.next_delay.take() {
+                tokio::time::sleep(delay).await;
+            }
+
+            match self
+                .client
+                .open_event_stream(&self.namespace, self.last_event_id.as_deref())
+                .await
+            {
+                Ok(response) => {
+                    self.failures = 0;
+                    self.response = Some(response);
+                    return Ok(());
+                }
+                Err(e)
+                    if e.is_retryable()
+                        && self
+                            .opts
+                            .max_reconnects
+                            .map_or(true, |max| self.failures < max) =>
+                {
+                    self.failures += 1;
+                    let delay = self.backoff_delay();
+                    warn!(
+                        "Failed to open event stream for {} (attempt {}), retrying in {:?}: {}",
+                        self.namespace, self.failures, delay, e
+                    );
+                    self.next_delay = Some(delay);
+                }
+                Err(e) => return Err(e),
+            }
+        }
+    }
+
+    fn disconnect(&mut self) {
+        self.response = None;
+        self.parser.reset();
+        self.next_delay = Some(self.backoff_delay());
+    }
+
+    fn backoff_delay(&self) -> Duration {
+        let base = self.parser.retry().unwrap_or(self.opts.reconnect_delay);
+        let factor = 2u32.saturating_pow(self.failures.saturating_sub(1).min(16));
+        base.saturating_mul(factor)
+            .min(self.opts.max_reconnect_delay)
+    }
+
+    fn decode(&self, frame: SseFrame) -> Result<Option<ChangeEvent>> {
+        if matches!(frame.event.as_deref(), Some("ping") | Some("heartbeat")) {
+            return Ok(None);
+        }
+
+        let mut event: ChangeEvent = serde_json::from_str(&frame.data)
+            .map_err(|e| Error::Deserialize(format!("Invalid change event: {}", e)))?;
+        if event.namespace.is_empty() {
+            event.namespace = self.namespace.clone();
+        }
+        event.event_id = frame.id;
+        Ok(Some(event))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sse_parser_frames() {
+        let mut parser = SseParser::new();
+
+        let frames = parser.feed(b": keep-alive\nid: 1\nevent: change\ndata: {\"a\":");
+        assert!(frames.is_empty());
+
+        let frames = parser.feed(b"1}\r\n\r\ndata: line1\ndata: line2\n\n");
+        assert_eq!(
+            frames,
+            vec![
+                SseFrame {
+                    event: Some("change".to_string()),
+                    data: "{\"a\":1}".to_string(),
+                    id: Some("1".to_string()),
+                },
+                SseFrame {
+                    event: None,
+                    data: "line1\nline2".to_string(),
+                    id: None,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_sse_parser_retry_and_empty_data() {
+        let mut parser = SseParser::new();
+        let frames = parser.feed(b"retry: 2500\n\nevent: ping\n\n");
+        assert!(frames.is_empty());
+        assert_eq!(parser.retry(), Some(Duration::from_millis(2500)));
+
+        let _ = parser.feed(b"data: partial");
+        parser.reset();
+        assert!(parser.feed(b"\n\n").is_empty());
+    }
+}
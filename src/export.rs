@@ -0,0 +1,477 @@
+//! Client-side rendering of environment exports
+
+use crate::errors::{Error, Result};
+use crate::models::{
+    BinaryEncoding, EnvExport, EnvJsonExport, ExportFormat, MissingReferencePolicy,
+};
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, HashSet};
+use std::fmt::Write;
+
+/// Check that a format can be rendered client-side
+pub(crate) fn ensure_renderable(format: ExportFormat) -> Result<()> {
+    match format {
+        ExportFormat::Custom(name) => Err(Error::Config(format!(
+            "Export format '{}' can only be rendered by the server",
+            name
+        ))),
+        _ => Ok(()),
+    }
+}
+
+/// Render environment variables locally in the given export format
+///
+/// Variables are emitted in key order so that output is stable across calls
+/// and written files do not churn.
+/// Values of `binary_keys` are encoded (or dropped) according to `encoding`.
+pub(crate) fn render_env(
+    namespace: &str,
+    mut vars: BTreeMap<String, String>,
+    format: ExportFormat,
+    etag: &str,
+    request_id: String,
+    binary_keys: &HashSet<String>,
+    encoding: BinaryEncoding,
+) -> EnvExport {
+    for key in binary_keys {
+        match encoding {
+            BinaryEncoding::SkipBinary => {
+                let _ = vars.remove(key);
+            }
+            BinaryEncoding::Base64 | BinaryEncoding::Hex => {
+                if let Some(value) = vars.get_mut(key) {
+                    *value = encode_binary(value.as_bytes(), encoding);
+                }
+            }
+        }
+    }
+
+    if format == ExportFormat::Json {
+        return EnvExport::Json(EnvJsonExport {
+            namespace: namespace.to_string(),
+            total: vars.len(),
+            environment: vars,
+            etag: etag.to_string(),
+            request_id,
+        });
+    }
+
+    let mut out = String::new();
+    for (key, value) in &vars {
+        if binary_keys.contains(key) {
+            let marker = match encoding {
+                BinaryEncoding::Hex => "hex",
+                _ => "base64",
+            };
+            let indent = if format == ExportFormat::DockerCompose {
+                "  "
+            } else {
+                ""
+            };
+            let _ = writeln!(out, "{}# {}: binary value, {}-encoded", indent, key, marker);
+        }
+        let line = match format {
+            ExportFormat::Shell => format!("export {}={}\n", key, shell_quote(value)),
+            ExportFormat::DockerCompose => format!("  {}: {}\n", key, double_quote(value)),
+            _ => format!("{}={}\n", key, dotenv_value(value)),
+        };
+        out.push_str(&line);
+    }
+    EnvExport::Text(out)
+}
+
+/// Encode a binary value for a text export
+fn encode_binary(bytes: &[u8], encoding: BinaryEncoding) -> String {
+    match encoding {
+        BinaryEncoding::Hex => {
+            let mut hex = String::with_capacity(bytes.len() * 2);
+            for byte in bytes {
+                let _ = write!(hex, "{:02x}", byte);
+            }
+            hex
+        }
+        _ => BASE64.encode(bytes),
+    }
+}
+
+/// Derive an aggregate ETag from `(key, version)` pairs
+///
+/// The pairs are sorted first, so the ETag only depends on the set of keys
+/// and their versions, not on the order in which they were fetched.
+pub(crate) fn aggregate_etag(versions: &[(String, i32)]) -> String {
+    let mut etag = String::from("\"");
+    for byte in versions_digest(versions).iter().take(16) {
+        let _ = write!(etag, "{:02x}", byte);
+    }
+    etag.push('"');
+    etag
+}
+
+/// Hex SHA-256 over sorted `key:version` pairs
+pub(crate) fn fingerprint(versions: &[(String, i32)]) -> String {
+    let mut hex = String::with_capacity(64);
+    for byte in versions_digest(versions) {
+        let _ = write!(hex, "{:02x}", byte);
+    }
+    hex
+}
+
+fn versions_digest(versions: &[(String, i32)]) -> Vec<u8> {
+    let mut sorted: Vec<_> = versions.iter().collect();
+    sorted.sort();
+
+    let mut hasher = Sha256::new();
+    for (key, version) in sorted {
+        hasher.update(key.as_bytes());
+        hasher.update(b":");
+        hasher.update(version.to_string().as_bytes());
+        hasher.update(b"\n");
+    }
+    hasher.finalize().to_vec()
+}
+
+/// Separator between a base key and its environment in overlay keys
+pub(crate) const DEFAULT_OVERLAY_SEPARATOR: &str = "__";
+
+/// Map each exported key to the key its value is taken from
+///
+/// `KEY{separator}{environment}` overrides `KEY` and is exported as `KEY`.
+/// Every other key, including overlays for other environments, maps to
+/// itself.
+pub(crate) fn overlay_sources<'a>(
+    keys: impl IntoIterator<Item = &'a str>,
+    environment: &str,
+    separator: &str,
+) -> BTreeMap<String, String> {
+    let suffix = format!("{}{}", separator, environment);
+    let mut sources = BTreeMap::new();
+    for key in keys {
+        match key
+            .strip_suffix(suffix.as_str())
+            .filter(|base| !base.is_empty())
+        {
+            Some(base) => {
+                let _ = sources.insert(base.to_string(), key.to_string());
+            }
+            None => {
+                let _ = sources
+                    .entry(key.to_string())
+                    .or_insert_with(|| key.to_string());
+            }
+        }
+    }
+    sources
+}
+
+/// Apply an environment's overlays to a set of variables
+pub(crate) fn apply_overlay(
+    mut vars: BTreeMap<String, String>,
+    environment: &str,
+    separator: &str,
+) -> BTreeMap<String, String> {
+    overlay_sources(vars.keys().map(String::as_str), environment, separator)
+        .into_iter()
+        .filter_map(|(key, source)| vars.remove(&source).map(|value| (key, value)))
+        .collect()
+}
+
+/// Maximum nesting depth of `${KEY}` references
+const MAX_INTERPOLATION_DEPTH: usize = 32;
+
+/// Resolve `${KEY}` references between variables
+///
+/// `$$` produces a literal `$`; a `$` not starting a valid reference is kept
+/// as-is. Substituted values are not rescanned for escapes.
+pub(crate) fn interpolate(
+    vars: &BTreeMap<String, String>,
+    on_missing: MissingReferencePolicy,
+) -> Result<BTreeMap<String, String>> {
+    let mut resolver = Resolver {
+        vars,
+        on_missing,
+        resolved: BTreeMap::new(),
+        stack: Vec::new(),
+    };
+    for key in vars.keys() {
+        let _ = resolver.resolve(key)?;
+    }
+    Ok(resolver.resolved)
+}
+
+struct Resolver<'a> {
+    vars: &'a BTreeMap<String, String>,
+    on_missing: MissingReferencePolicy,
+    resolved: BTreeMap<String, String>,
+    stack: Vec<String>,
+}
+
+impl Resolver<'_> {
+    fn resolve(&mut self, key: &str) -> Result<String> {
+        if let Some(value) = self.resolved.get(key) {
+            return Ok(value.clone());
+        }
+        if self.stack.iter().any(|k| k == key) {
+            return Err(Error::Other(format!(
+                "interpolation cycle: {} -> {}",
+                self.stack.join(" -> "),
+                key
+            )));
+        }
+        if self.stack.len() >= MAX_INTERPOLATION_DEPTH {
+            return Err(Error::Other(format!(
+                "interpolation depth limit of {} exceeded at {}",
+                MAX_INTERPOLATION_DEPTH, key
+            )));
+        }
+
+        self.stack.push(key.to_string());
+        let vars = self.vars;
+        let mut rest = vars[key].as_str();
+        let mut out = String::with_capacity(rest.len());
+        while let Some(pos) = rest.find('$') {
+            out.push_str(&rest[..pos]);
+            rest = &rest[pos..];
+
+            if let Some(after) = rest.strip_prefix("$$") {
+                out.push('$');
+                rest = after;
+                continue;
+            }
+
+            let reference = rest
+                .strip_prefix("${")
+                .and_then(|r| r.split_once('}'))
+                .filter(|(name, _)| is_var_name(name));
+            match reference {
+                Some((name, after)) => {
+                    if vars.contains_key(name) {
+                        out.push_str(&self.resolve(name)?);
+                    } else if self.on_missing == MissingReferencePolicy::Keep {
+                        out.push_str(&rest[..name.len() + 3]);
+                    } else {
+                        return Err(Error::Other(format!(
+                            "unresolved reference ${{{}}} in {}",
+                            name, key
+                        )));
+                    }
+                    rest = after;
+                }
+                None => {
+                    out.push('$');
+                    rest = &rest[1..];
+                }
+            }
+        }
+        out.push_str(rest);
+        let _ = self.stack.pop();
+
+        let _ = self.resolved.insert(key.to_string(), out.clone());
+        Ok(out)
+    }
+}
+
+fn is_var_name(name: &str) -> bool {
+    !name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+/// Quote a dotenv value only when it contains characters that need it
+fn dotenv_value(value: &str) -> String {
+    let is_plain = value.chars().all(|c| {
+        c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.' | '/' | ':' | '@' | '+' | ',')
+    });
+    if is_plain {
+        value.to_string()
+    } else {
+        double_quote(value)
+    }
+}
+
+/// Double-quote a value, escaping backslashes, quotes and control characters
+fn double_quote(value: &str) -> String {
+    // JSON string syntax is valid for both dotenv and YAML double-quoted values
+    serde_json::Value::String(value.to_string()).to_string()
+}
+
+/// Single-quote a value for POSIX shells
+fn shell_quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', r"'\''"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn vars() -> BTreeMap<String, String> {
+        let mut vars = BTreeMap::new();
+        let _ = vars.insert("B_KEY".to_string(), "it's \"quoted\"".to_string());
+        let _ = vars.insert("A_KEY".to_string(), "plain-value".to_string());
+        vars
+    }
+
+    #[test]
+    fn test_render_text_formats() {
+        let render = |format| match render_env(
+            "ns",
+            vars(),
+            format,
+            "\"e\"",
+            String::new(),
+            &HashSet::new(),
+            BinaryEncoding::default(),
+        ) {
+            EnvExport::Text(text) => text,
+            other => panic!("expected text export, got {:?}", other),
+        };
+
+        assert_eq!(
+            render(ExportFormat::Dotenv),
+            "A_KEY=plain-value\nB_KEY=\"it's \\\"quoted\\\"\"\n"
+        );
+        assert_eq!(
+            render(ExportFormat::Shell),
+            "export A_KEY='plain-value'\nexport B_KEY='it'\\''s \"quoted\"'\n"
+        );
+        assert_eq!(
+            render(ExportFormat::DockerCompose),
+            "  A_KEY: \"plain-value\"\n  B_KEY: \"it's \\\"quoted\\\"\"\n"
+        );
+    }
+
+    #[test]
+    fn test_render_json() {
+        match render_env(
+            "ns",
+            vars(),
+            ExportFormat::Json,
+            "\"e\"",
+            "req".to_string(),
+            &HashSet::new(),
+            BinaryEncoding::default(),
+        ) {
+            EnvExport::Json(json) => {
+                assert_eq!(json.namespace, "ns");
+                assert_eq!(json.total, 2);
+                assert_eq!(json.etag, "\"e\"");
+                assert_eq!(json.environment["A_KEY"], "plain-value");
+            }
+            other => panic!("expected json export, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_render_binary_values() {
+        let binary: HashSet<String> = ["A_KEY".to_string()].into();
+        let render = |encoding| match render_env(
+            "ns",
+            vars(),
+            ExportFormat::Dotenv,
+            "\"e\"",
+            String::new(),
+            &binary,
+            encoding,
+        ) {
+            EnvExport::Text(text) => text,
+            other => panic!("expected text export, got {:?}", other),
+        };
+
+        assert!(render(BinaryEncoding::Base64)
+            .starts_with("# A_KEY: binary value, base64-encoded\nA_KEY=\"cGxhaW4tdmFsdWU=\"\n"));
+        assert!(render(BinaryEncoding::Hex)
+            .starts_with("# A_KEY: binary value, hex-encoded\nA_KEY=706c61696e2d76616c7565\n"));
+        assert!(render(BinaryEncoding::SkipBinary).starts_with("B_KEY="));
+    }
+
+    #[test]
+    fn test_aggregate_etag_is_order_independent() {
+        let a = aggregate_etag(&[("x".to_string(), 1), ("y".to_string(), 2)]);
+        let b = aggregate_etag(&[("y".to_string(), 2), ("x".to_string(), 1)]);
+        let c = aggregate_etag(&[("x".to_string(), 1), ("y".to_string(), 3)]);
+
+        assert_eq!(a, b);
+        assert_ne!(a, c);
+        assert!(a.starts_with('"') && a.ends_with('"'));
+    }
+
+    fn interpolate_pairs(
+        pairs: &[(&str, &str)],
+        on_missing: MissingReferencePolicy,
+    ) -> Result<BTreeMap<String, String>> {
+        let vars = pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        interpolate(&vars, on_missing)
+    }
+
+    #[test]
+    fn test_interpolate_references() {
+        let vars = interpolate_pairs(
+            &[
+                ("HOST", "db.internal"),
+                ("URL", "postgres://${HOST}:${PORT}/app"),
+                ("PORT", "5432"),
+                ("DSN", "${URL}?cost=$$5 ${ not-a-ref} $"),
+            ],
+            MissingReferencePolicy::Keep,
+        )
+        .unwrap();
+
+        assert_eq!(vars["URL"], "postgres://db.internal:5432/app");
+        assert_eq!(
+            vars["DSN"],
+            "postgres://db.internal:5432/app?cost=$5 ${ not-a-ref} $"
+        );
+    }
+
+    #[test]
+    fn test_interpolate_missing_reference() {
+        let pairs = [("URL", "http://${HOST}/")];
+
+        let vars = interpolate_pairs(&pairs, MissingReferencePolicy::Keep).unwrap();
+        assert_eq!(vars["URL"], "http://${HOST}/");
+
+        let err = interpolate_pairs(&pairs, MissingReferencePolicy::Error).unwrap_err();
+        assert!(err.to_string().contains("HOST"));
+    }
+
+    #[test]
+    fn test_interpolate_cycle() {
+        let err = interpolate_pairs(
+            &[("A", "${B}"), ("B", "x${C}"), ("C", "${A}")],
+            MissingReferencePolicy::Keep,
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("cycle"));
+
+        let err =
+            interpolate_pairs(&[("SELF", "${SELF}")], MissingReferencePolicy::Keep).unwrap_err();
+        assert!(err.to_string().contains("cycle"));
+    }
+
+    #[test]
+    fn test_apply_overlay() {
+        let vars: BTreeMap<String, String> = [
+            ("DATABASE_URL__prod", "postgres://prod"),
+            ("DATABASE_URL", "postgres://dev"),
+            ("LOG_LEVEL", "debug"),
+            ("CACHE_URL__staging", "redis://staging"),
+            ("__prod", "no base key"),
+        ]
+        .into_iter()
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .collect();
+
+        let prod = apply_overlay(vars.clone(), "prod", DEFAULT_OVERLAY_SEPARATOR);
+        assert_eq!(prod["DATABASE_URL"], "postgres://prod");
+        assert_eq!(prod["LOG_LEVEL"], "debug");
+        assert_eq!(prod["CACHE_URL__staging"], "redis://staging");
+        assert_eq!(prod["__prod"], "no base key");
+        assert!(!prod.contains_key("DATABASE_URL__prod"));
+        assert_eq!(prod.len(), 4);
+
+        let staging = apply_overlay(vars, "staging", "__");
+        assert_eq!(staging["CACHE_URL"], "redis://staging");
+        assert_eq!(staging["DATABASE_URL"], "postgres://dev");
+    }
+}
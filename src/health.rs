@@ -0,0 +1,158 @@
+//! Background readiness monitoring
+
+use crate::{client::Client, models::HealthStatus};
+use std::time::Duration;
+use time::format_description::well_known::Rfc3339;
+use tokio::task::JoinHandle;
+use tracing::debug;
+
+/// Handle to a running health monitor
+///
+/// Returned by [`Client::start_health_monitor`]. The monitor stops when
+/// [`HealthMonitorHandle::stop`] is called or the handle is dropped.
+#[derive(Debug)]
+pub struct HealthMonitorHandle {
+    task: JoinHandle<()>,
+}
+
+impl HealthMonitorHandle {
+    /// Stop the monitor
+    pub fn stop(self) {
+        self.task.abort();
+    }
+
+    /// Check whether the monitor loop is still running
+    pub fn is_running(&self) -> bool {
+        !self.task.is_finished()
+    }
+}
+
+impl Drop for HealthMonitorHandle {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+/// Spawn the polling loop on the current tokio runtime
+pub(crate) fn spawn<F>(client: Client, interval: Duration, on_change: F) -> HealthMonitorHandle
+where
+    F: Fn(HealthStatus) + Send + 'static,
+{
+    let task = tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(interval);
+        ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+        let mut last_ready = None;
+
+        loop {
+            let _ = ticker.tick().await;
+
+            let status = match client.readyz().await {
+                Ok(status) => status,
+                Err(e) => {
+                    debug!("Readiness check failed: {}", e);
+                    unready_status(&client)
+                }
+            };
+            let ready = status.status != UNHEALTHY;
+
+            if last_ready != Some(ready) {
+                last_ready = Some(ready);
+                on_change(status);
+            }
+        }
+    });
+
+    HealthMonitorHandle { task }
+}
+
+const UNHEALTHY: &str = "unhealthy";
+
+/// Status reported when the readiness endpoint fails or is unreachable
+fn unready_status(client: &Client) -> HealthStatus {
+    HealthStatus {
+        status: UNHEALTHY.to_string(),
+        timestamp: client
+            .config
+            .clock
+            .now()
+            .format(&Rfc3339)
+            .unwrap_or_default(),
+        version: None,
+        checks: Default::default(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Auth, ClientBuilder};
+    use std::sync::{Arc, Mutex};
+    use std::time::Duration;
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn create_test_client(base_url: &str) -> crate::Client {
+        #[cfg(feature = "danger-insecure-http")]
+        {
+            ClientBuilder::new(base_url)
+                .auth(Auth::bearer("test-token"))
+                .allow_insecure_http()
+                .build()
+                .unwrap()
+        }
+        #[cfg(not(feature = "danger-insecure-http"))]
+        {
+            ClientBuilder::new(base_url.replace("http://", "https://"))
+                .auth(Auth::bearer("test-token"))
+                .build()
+                .unwrap()
+        }
+    }
+
+    async fn mount_ready(server: &MockServer, times: u64) {
+        Mock::given(method("GET"))
+            .and(path("/api/v2/readyz"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "status": "healthy",
+                "timestamp": "2024-01-01T00:00:00Z"
+            })))
+            .up_to_n_times(times)
+            .mount(server)
+            .await;
+    }
+
+    #[tokio::test]
+    async fn test_health_monitor_reports_transitions_only() {
+        let mock_server = MockServer::start().await;
+
+        // ready x2, unready x2, then ready for good
+        mount_ready(&mock_server, 2).await;
+        Mock::given(method("GET"))
+            .and(path("/api/v2/readyz"))
+            .respond_with(ResponseTemplate::new(503))
+            .up_to_n_times(2)
+            .mount(&mock_server)
+            .await;
+        mount_ready(&mock_server, u64::MAX).await;
+
+        let client = create_test_client(&mock_server.uri());
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let recorder = seen.clone();
+        let monitor = client.start_health_monitor(Duration::from_millis(10), move |status| {
+            recorder.lock().unwrap().push(status.status);
+        });
+
+        for _ in 0..200 {
+            if mock_server.received_requests().await.unwrap().len() >= 7 {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        assert!(monitor.is_running());
+        monitor.stop();
+
+        assert_eq!(
+            *seen.lock().unwrap(),
+            vec!["healthy", "unhealthy", "healthy"]
+        );
+    }
+}
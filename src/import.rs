@@ -0,0 +1,460 @@
+//! Streaming bulk import of environment files
+
+use crate::{
+    errors::{Error, Result},
+    models::{BatchOp, BatchOperationResult},
+};
+use std::fmt;
+use std::sync::Arc;
+
+/// Source format for [`Client::import_env_from_reader`](crate::Client::import_env_from_reader)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ImportFormat {
+    /// `KEY=value` lines as found in `.env` files
+    ///
+    /// * Blank lines and lines starting with `#` are skipped
+    /// * An `export ` prefix is ignored
+    /// * Everything after the first `=` is the value, so values may contain
+    ///   `=`; `KEY=` sets an empty value
+    /// * Unquoted values are trimmed and end at a `#` preceded by whitespace
+    /// * Single-quoted values are taken literally
+    /// * Double-quoted values unescape `\n`, `\r` and `\t`; any other
+    ///   escaped character stands for itself, e.g. `\"` and `\\`
+    /// * Quoted values may span several lines, the line breaks becoming part
+    ///   of the value; only a comment may follow the closing quote
+    #[default]
+    Dotenv,
+    /// One JSON object per line: `{"key": "...", "value": "..."}`
+    ///
+    /// Optional `ttl_seconds` and `metadata` fields are passed through.
+    JsonLines,
+}
+
+/// Progress report passed to [`ImportOpts::on_progress`]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ImportProgress {
+    /// Lines read from the source so far
+    pub lines_read: usize,
+    /// Batches submitted so far
+    pub batches: usize,
+    /// Operations the server reported as succeeded
+    pub succeeded: usize,
+    /// Operations the server reported as failed
+    pub failed: usize,
+}
+
+/// Options for a streaming import
+#[derive(Clone)]
+pub struct ImportOpts {
+    /// Number of secrets submitted per batch request (default: 100)
+    pub chunk_size: usize,
+    /// Submit each batch transactionally
+    pub transactional: bool,
+    /// Called after every submitted batch
+    pub on_progress: Option<Arc<dyn Fn(ImportProgress) + Send + Sync>>,
+}
+
+impl Default for ImportOpts {
+    fn default() -> Self {
+        Self {
+            chunk_size: 100,
+            transactional: false,
+            on_progress: None,
+        }
+    }
+}
+
+impl fmt::Debug for ImportOpts {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ImportOpts")
+            .field("chunk_size", &self.chunk_size)
+            .field("transactional", &self.transactional)
+            .field("on_progress", &self.on_progress.is_some())
+            .finish()
+    }
+}
+
+/// Result of a streaming import
+#[derive(Debug, Clone, Default)]
+pub struct ImportResult {
+    /// Number of batch requests submitted
+    pub batches: usize,
+    /// Number of secrets imported successfully
+    pub succeeded: usize,
+    /// Operations the server rejected
+    pub failed: Vec<BatchOperationResult>,
+}
+
+#[derive(serde::Deserialize)]
+struct JsonLine {
+    key: String,
+    value: String,
+    #[serde(default)]
+    ttl_seconds: Option<i64>,
+    #[serde(default)]
+    metadata: Option<serde_json::Value>,
+}
+
+/// Turns source lines into put operations
+///
+/// Dotenv entries whose quoted value spans several lines are buffered until
+/// the closing quote arrives.
+#[derive(Debug)]
+pub(crate) struct LineParser {
+    format: ImportFormat,
+    /// First line number and text of an unterminated dotenv entry
+    pending: Option<(usize, String)>,
+}
+
+impl LineParser {
+    pub(crate) fn new(format: ImportFormat) -> Self {
+        Self {
+            format,
+            pending: None,
+        }
+    }
+
+    /// Feed the next source line
+    ///
+    /// Returns `Ok(None)` for lines that carry no secret (blank lines,
+    /// comments) and while a multi-line value is still open.
+    pub(crate) fn push(&mut self, line: &str, line_number: usize) -> Result<Option<BatchOp>> {
+        match self.format {
+            ImportFormat::Dotenv => {
+                let (start, text) = match self.pending.take() {
+                    Some((start, mut text)) => {
+                        text.push('\n');
+                        text.push_str(line);
+                        (start, text)
+                    }
+                    None => (line_number, line.to_string()),
+                };
+                match parse_dotenv(&text) {
+                    Ok(entry) => Ok(entry.map(|(key, value)| BatchOp::put(key, value))),
+                    Err(DotenvError::Unterminated) => {
+                        self.pending = Some((start, text));
+                        Ok(None)
+                    }
+                    Err(DotenvError::Invalid(reason)) => Err(invalid_line(start, reason)),
+                }
+            }
+            ImportFormat::JsonLines => {
+                let line = line.trim();
+                if line.is_empty() {
+                    return Ok(None);
+                }
+                let entry: JsonLine = serde_json::from_str(line)
+                    .map_err(|e| invalid_line(line_number, &e.to_string()))?;
+                let mut op = BatchOp::put(entry.key, entry.value);
+                op.ttl_seconds = entry.ttl_seconds;
+                op.metadata = entry.metadata;
+                Ok(Some(op))
+            }
+        }
+    }
+
+    /// Check that the source did not end inside a quoted value
+    pub(crate) fn finish(&self) -> Result<()> {
+        match &self.pending {
+            Some((start, _)) => Err(invalid_line(*start, "unterminated quoted value")),
+            None => Ok(()),
+        }
+    }
+}
+
+fn invalid_line(line_number: usize, reason: &str) -> Error {
+    Error::Deserialize(format!("Invalid import line {}: {}", line_number, reason))
+}
+
+enum DotenvError {
+    /// A quoted value is still open; more lines are needed
+    Unterminated,
+    Invalid(&'static str),
+}
+
+/// Parse one dotenv entry, which spans several lines if its value is quoted
+fn parse_dotenv(text: &str) -> std::result::Result<Option<(String, String)>, DotenvError> {
+    let text = text.trim_start();
+    if text.is_empty() || text.starts_with('#') {
+        return Ok(None);
+    }
+    let text = match text.strip_prefix("export") {
+        Some(rest) if rest.starts_with(char::is_whitespace) => rest.trim_start(),
+        _ => text,
+    };
+
+    let (key, rest) = text
+        .split_once('=')
+        .ok_or(DotenvError::Invalid("missing '='"))?;
+    let key = key.trim();
+    if key.is_empty() {
+        return Err(DotenvError::Invalid("empty key"));
+    }
+    if key.contains(|c: char| c.is_whitespace() || c == '"' || c == '\'') {
+        return Err(DotenvError::Invalid("invalid key"));
+    }
+
+    let trimmed = rest.trim_start_matches([' ', '\t']);
+    let value = match trimmed.chars().next() {
+        Some(quote @ ('\'' | '"')) => {
+            let (value, after) = quoted_value(&trimmed[1..], quote)?;
+            let after = after.trim();
+            if !after.is_empty() && !after.starts_with('#') {
+                return Err(DotenvError::Invalid(
+                    "unexpected characters after closing quote",
+                ));
+            }
+            value
+        }
+        _ => unquoted_value(rest),
+    };
+    Ok(Some((key.to_string(), value)))
+}
+
+/// Read a quoted value up to its closing quote, returning the rest of the text
+fn quoted_value(text: &str, quote: char) -> std::result::Result<(String, &str), DotenvError> {
+    let mut out = String::with_capacity(text.len());
+    let mut chars = text.char_indices();
+    while let Some((i, c)) = chars.next() {
+        if c == quote {
+            return Ok((out, &text[i + 1..]));
+        }
+        if c != '\\' || quote == '\'' {
+            out.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some((_, 'n')) => out.push('\n'),
+            Some((_, 'r')) => out.push('\r'),
+            Some((_, 't')) => out.push('\t'),
+            Some((_, other)) => out.push(other),
+            None => break,
+        }
+    }
+    Err(DotenvError::Unterminated)
+}
+
+/// Trim an unquoted value and cut off a trailing comment
+fn unquoted_value(text: &str) -> String {
+    let mut prev_is_space = false;
+    for (i, c) in text.char_indices() {
+        if c == '#' && prev_is_space {
+            return text[..i].trim().to_string();
+        }
+        prev_is_space = c.is_whitespace();
+    }
+    text.trim().to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Parse a source consisting of a single line
+    fn parse_line(format: ImportFormat, line: &str, line_number: usize) -> Result<Option<BatchOp>> {
+        let mut parser = LineParser::new(format);
+        let op = parser.push(line, line_number)?;
+        parser.finish()?;
+        Ok(op)
+    }
+
+    /// Parse a whole source into `(key, value)` pairs
+    fn parse_source(source: &str) -> Result<Vec<(String, String)>> {
+        let mut parser = LineParser::new(ImportFormat::Dotenv);
+        let mut entries = Vec::new();
+        for (i, line) in source.lines().enumerate() {
+            if let Some(op) = parser.push(line, i + 1)? {
+                entries.push((op.key, op.value.unwrap()));
+            }
+        }
+        parser.finish()?;
+        Ok(entries)
+    }
+
+    fn pair(key: &str, value: &str) -> (String, String) {
+        (key.to_string(), value.to_string())
+    }
+
+    fn put(line: &str) -> (String, Option<String>) {
+        let op = parse_line(ImportFormat::Dotenv, line, 1).unwrap().unwrap();
+        (op.key, op.value)
+    }
+
+    #[test]
+    fn test_parse_dotenv_lines() {
+        assert!(parse_line(ImportFormat::Dotenv, "   ", 1)
+            .unwrap()
+            .is_none());
+        assert!(parse_line(ImportFormat::Dotenv, "# comment", 1)
+            .unwrap()
+            .is_none());
+
+        assert_eq!(put("KEY=value"), ("KEY".into(), Some("value".into())));
+        assert_eq!(
+            put("export KEY = value # note"),
+            ("KEY".into(), Some("value".into()))
+        );
+        assert_eq!(put("KEY='a # b'"), ("KEY".into(), Some("a # b".into())));
+        assert_eq!(
+            put(r#"KEY="line1\nsay \"hi\"""#),
+            ("KEY".into(), Some("line1\nsay \"hi\"".into()))
+        );
+        assert_eq!(put("KEY=a=b"), ("KEY".into(), Some("a=b".into())));
+
+        let err = parse_line(ImportFormat::Dotenv, "NO_EQUALS", 7).unwrap_err();
+        assert!(err.to_string().contains("line 7"));
+        assert!(parse_line(ImportFormat::Dotenv, "=value", 1).is_err());
+    }
+
+    #[test]
+    fn test_dotenv_export_prefix() {
+        assert_eq!(put("export FOO=bar"), ("FOO".into(), Some("bar".into())));
+        assert_eq!(put("export\tFOO=bar"), ("FOO".into(), Some("bar".into())));
+        // Only a separate word is a prefix
+        assert_eq!(
+            put("exportFOO=bar"),
+            ("exportFOO".into(), Some("bar".into()))
+        );
+        assert_eq!(put("export=1"), ("export".into(), Some("1".into())));
+    }
+
+    #[test]
+    fn test_dotenv_empty_values() {
+        assert_eq!(put("KEY="), ("KEY".into(), Some("".into())));
+        assert_eq!(put("KEY=   "), ("KEY".into(), Some("".into())));
+        assert_eq!(put("KEY=\"\""), ("KEY".into(), Some("".into())));
+        assert_eq!(put("KEY=''"), ("KEY".into(), Some("".into())));
+        assert_eq!(put("KEY= # nothing here"), ("KEY".into(), Some("".into())));
+    }
+
+    #[test]
+    fn test_dotenv_values_containing_equals() {
+        assert_eq!(
+            put("DSN=postgres://u:p@db/app?sslmode=require"),
+            (
+                "DSN".into(),
+                Some("postgres://u:p@db/app?sslmode=require".into())
+            )
+        );
+        assert_eq!(put("B64=YWJj=="), ("B64".into(), Some("YWJj==".into())));
+        assert_eq!(put("KEY=\"a=b=c\""), ("KEY".into(), Some("a=b=c".into())));
+    }
+
+    #[test]
+    fn test_dotenv_comments() {
+        assert!(parse_line(ImportFormat::Dotenv, "   # indented", 1)
+            .unwrap()
+            .is_none());
+        assert_eq!(
+            put("KEY=value\t# tab comment"),
+            ("KEY".into(), Some("value".into()))
+        );
+        // A '#' inside a word is part of the value
+        assert_eq!(
+            put("COLOR=#ff0000"),
+            ("COLOR".into(), Some("#ff0000".into()))
+        );
+        assert_eq!(
+            put("URL=http://x/#frag"),
+            ("URL".into(), Some("http://x/#frag".into()))
+        );
+        assert_eq!(
+            put("KEY=\"a # b\" # note"),
+            ("KEY".into(), Some("a # b".into()))
+        );
+        assert_eq!(put("KEY='a'#note"), ("KEY".into(), Some("a".into())));
+    }
+
+    #[test]
+    fn test_dotenv_quoting_and_escapes() {
+        assert_eq!(
+            put(r#"KEY="tab\tcr\rback\\slash""#),
+            ("KEY".into(), Some("tab\tcr\rback\\slash".into()))
+        );
+        // Unknown escapes keep the escaped character
+        assert_eq!(put(r#"KEY="\$HOME""#), ("KEY".into(), Some("$HOME".into())));
+        // Single quotes are literal
+        assert_eq!(
+            put(r#"KEY='a\nb "c"'"#),
+            ("KEY".into(), Some(r#"a\nb "c""#.into()))
+        );
+        assert_eq!(put(r#"KEY="it's""#), ("KEY".into(), Some("it's".into())));
+        // Whitespace inside quotes is kept
+        assert_eq!(
+            put("KEY=\"  padded  \""),
+            ("KEY".into(), Some("  padded  ".into()))
+        );
+        // Quotes inside an unquoted value are kept
+        assert_eq!(
+            put(r#"KEY=say "hi""#),
+            ("KEY".into(), Some(r#"say "hi""#.into()))
+        );
+
+        let err = parse_line(ImportFormat::Dotenv, r#"KEY="a" b"#, 3).unwrap_err();
+        assert!(err.to_string().contains("line 3"));
+        assert!(err.to_string().contains("after closing quote"));
+    }
+
+    #[test]
+    fn test_dotenv_multiline_values() {
+        let source = "\
+FIRST=1
+CERT=\"-----BEGIN CERT-----
+abc=
+
+# not a comment
+-----END CERT-----\" # trailing comment
+RAW='line one
+line \\n two'
+export LAST=end
+";
+        assert_eq!(
+            parse_source(source).unwrap(),
+            vec![
+                pair("FIRST", "1"),
+                pair(
+                    "CERT",
+                    "-----BEGIN CERT-----\nabc=\n\n# not a comment\n-----END CERT-----"
+                ),
+                pair("RAW", "line one\nline \\n two"),
+                pair("LAST", "end"),
+            ]
+        );
+
+        // An escaped quote does not close the value
+        assert_eq!(
+            parse_source("KEY=\"a\\\"\nb\"").unwrap(),
+            vec![pair("KEY", "a\"\nb")]
+        );
+    }
+
+    #[test]
+    fn test_dotenv_invalid_entries() {
+        let err = parse_source("OK=1\nKEY=\"never closed\nMORE=2\n").unwrap_err();
+        assert!(err.to_string().contains("line 2"));
+        assert!(err.to_string().contains("unterminated"));
+        // Values never appear in errors
+        assert!(!err.to_string().contains("never closed"));
+
+        let err = parse_source("A=1\nB C=2\n").unwrap_err();
+        assert!(err.to_string().contains("line 2"));
+        assert!(err.to_string().contains("invalid key"));
+    }
+
+    #[test]
+    fn test_parse_json_lines() {
+        let op = parse_line(
+            ImportFormat::JsonLines,
+            r#"{"key": "API_KEY", "value": "abc", "ttl_seconds": 60, "metadata": {"owner": "ops"}}"#,
+            1,
+        )
+        .unwrap()
+        .unwrap();
+        assert_eq!(op.key, "API_KEY");
+        assert_eq!(op.value.as_deref(), Some("abc"));
+        assert_eq!(op.ttl_seconds, Some(60));
+        assert_eq!(op.metadata.unwrap()["owner"], "ops");
+
+        assert!(parse_line(ImportFormat::JsonLines, r#"{"key": "A"}"#, 1).is_err());
+    }
+}
@@ -13,6 +13,7 @@
 //! - Batch operations with transactional support
 //! - Environment export in multiple formats
 //! - Version management and rollback
+//! - Real-time change notifications via server-sent events
 //! - Comprehensive error handling
 //! - Secure value handling with zeroization
 //!
@@ -46,10 +47,25 @@
 mod auth;
 mod cache;
 mod client;
+mod clock;
 mod config;
+mod connection_debug;
+#[cfg(feature = "disk-cache")]
+mod disk_cache;
 mod endpoints;
 mod errors;
+mod events;
+mod export;
+mod health;
+mod import;
 mod models;
+mod prometheus;
+mod retry;
+mod scoped;
+#[cfg(feature = "tower")]
+mod service;
+mod single_flight;
+mod stream;
 /// Telemetry and observability support
 #[cfg(feature = "metrics")]
 pub mod telemetry;
@@ -58,15 +74,28 @@ pub mod telemetry;
 mod telemetry;
 mod util;
 
-pub use auth::{Auth, TokenProvider};
-pub use cache::{CacheConfig, CacheStats};
+pub use auth::{Auth, FileTokenProvider, TokenProvider};
+pub use cache::{CacheCipher, CacheConfig, CachePredicate, CacheStats, CacheStatsSnapshot};
 pub use client::Client;
-pub use config::{ClientBuilder, ClientConfig};
-pub use errors::{Error, ErrorKind, Result};
+pub use clock::{Clock, SystemClock};
+pub use config::{ClientBuilder, ClientConfig, Preset};
+#[cfg(feature = "disk-cache")]
+pub use disk_cache::{DiskCacheConfig, DiskCacheMode};
+pub use errors::{AttemptRecord, Error, ErrorKind, Result};
+pub use health::HealthMonitorHandle;
+pub use import::{ImportFormat, ImportOpts, ImportProgress, ImportResult};
 pub use models::*;
+pub use prometheus::{MetricType, PrometheusMetric};
+pub use retry::RetryStats;
+pub use scoped::ScopedClient;
+#[cfg(feature = "tower")]
+pub use service::{SecretRequest, SecretResponse, SecretService};
+pub use stream::SecretStream;
 
 // Re-export commonly used types
+pub use reqwest::Method;
 pub use secrecy::SecretString;
+pub use tokio_util::sync::CancellationToken;
 
 /// SDK version, matches Cargo.toml version
 pub const VERSION: &str = env!("CARGO_PKG_VERSION");
@@ -80,9 +109,27 @@ pub const DEFAULT_RETRIES: u32 = 3;
 /// Maximum cache entries
 pub const DEFAULT_CACHE_MAX_ENTRIES: u64 = 10_000;
 
+/// Default maximum idle HTTP connections kept per host
+pub const DEFAULT_POOL_MAX_IDLE_PER_HOST: usize = 10;
+
 /// Default cache TTL in seconds
 pub const DEFAULT_CACHE_TTL_SECS: u64 = 300;
 
+/// Default freshness TTL for cached listings in seconds
+pub const DEFAULT_LIST_CACHE_TTL_SECS: u64 = 30;
+
+/// Default timeout for health checks in milliseconds
+pub const DEFAULT_HEALTH_TIMEOUT_MS: u64 = 5_000;
+
+/// Default cap on pages fetched by auto-paginating streams
+pub const DEFAULT_PAGINATION_MAX_PAGES: usize = 10_000;
+
+/// Default cap on items fetched by auto-paginating streams
+pub const DEFAULT_PAGINATION_MAX_ITEMS: usize = 100_000;
+
+/// Default body size in bytes above which `Expect: 100-continue` is sent
+pub const DEFAULT_EXPECT_CONTINUE_THRESHOLD: u64 = 1024 * 1024;
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,272 @@
+//! Parser for the Prometheus text exposition format
+
+use crate::errors::{Error, Result};
+use std::collections::{BTreeMap, HashMap};
+
+/// Metric family type from a `# TYPE` comment
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MetricType {
+    /// Monotonically increasing counter
+    Counter,
+    /// Value that can go up and down
+    Gauge,
+    /// Histogram (`_bucket`, `_sum` and `_count` samples)
+    Histogram,
+    /// Summary (quantile, `_sum` and `_count` samples)
+    Summary,
+    /// No or unknown `# TYPE` declaration
+    #[default]
+    Untyped,
+}
+
+impl MetricType {
+    fn parse(s: &str) -> Self {
+        match s {
+            "counter" => MetricType::Counter,
+            "gauge" => MetricType::Gauge,
+            "histogram" => MetricType::Histogram,
+            "summary" => MetricType::Summary,
+            _ => MetricType::Untyped,
+        }
+    }
+}
+
+/// A single sample from a Prometheus exposition
+#[derive(Debug, Clone, PartialEq)]
+pub struct PrometheusMetric {
+    /// Sample name (e.g. `http_requests_total` or `latency_seconds_bucket`)
+    pub name: String,
+    /// Sample labels
+    pub labels: BTreeMap<String, String>,
+    /// Sample value
+    pub value: f64,
+    /// Optional timestamp in milliseconds since the epoch
+    pub timestamp_ms: Option<i64>,
+    /// Type of the metric family the sample belongs to
+    pub metric_type: MetricType,
+    /// Help text of the metric family, if declared
+    pub help: Option<String>,
+}
+
+impl PrometheusMetric {
+    /// Get a label value
+    pub fn label(&self, name: &str) -> Option<&str> {
+        self.labels.get(name).map(String::as_str)
+    }
+}
+
+/// Parse a text exposition body into samples
+pub(crate) fn parse(text: &str) -> Result<Vec<PrometheusMetric>> {
+    let mut types: HashMap<&str, MetricType> = HashMap::new();
+    let mut helps: HashMap<&str, String> = HashMap::new();
+    let mut samples = Vec::new();
+
+    for (index, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+
+        if let Some(comment) = line.strip_prefix('#') {
+            let mut parts = comment.trim_start().splitn(3, char::is_whitespace);
+            match (parts.next(), parts.next(), parts.next()) {
+                (Some("TYPE"), Some(name), Some(kind)) => {
+                    let _ = types.insert(name, MetricType::parse(kind.trim()));
+                }
+                (Some("HELP"), Some(name), help) => {
+                    let _ = helps.insert(name, unescape(help.unwrap_or("").trim(), false));
+                }
+                _ => {}
+            }
+            continue;
+        }
+
+        let (name, labels, value, timestamp_ms) = parse_sample(line).map_err(|reason| {
+            Error::Deserialize(format!("Invalid metrics line {}: {}", index + 1, reason))
+        })?;
+
+        let family = family_name(&name, &types);
+        samples.push(PrometheusMetric {
+            metric_type: types.get(family).copied().unwrap_or_default(),
+            help: helps.get(family).cloned(),
+            name,
+            labels,
+            value,
+            timestamp_ms,
+        });
+    }
+
+    Ok(samples)
+}
+
+/// Map a sample name to its declared family (`foo_bucket` -> `foo`)
+fn family_name<'a>(name: &'a str, types: &HashMap<&str, MetricType>) -> &'a str {
+    if types.contains_key(name) {
+        return name;
+    }
+    ["_bucket", "_sum", "_count"]
+        .iter()
+        .filter_map(|suffix| name.strip_suffix(suffix))
+        .find(|family| types.contains_key(family))
+        .unwrap_or(name)
+}
+
+type Sample = (String, BTreeMap<String, String>, f64, Option<i64>);
+
+fn parse_sample(line: &str) -> std::result::Result<Sample, String> {
+    let name_end = line
+        .find(|c: char| c == '{' || c.is_whitespace())
+        .ok_or("missing value")?;
+    let name = &line[..name_end];
+    if name.is_empty() {
+        return Err("missing metric name".to_string());
+    }
+
+    let mut labels = BTreeMap::new();
+    let mut rest = &line[name_end..];
+    if let Some(label_str) = rest.strip_prefix('{') {
+        rest = parse_labels(label_str, &mut labels)?;
+    }
+
+    let mut fields = rest.split_whitespace();
+    let value = fields.next().ok_or("missing value")?;
+    let value = value
+        .parse::<f64>()
+        .map_err(|_| format!("invalid value {:?}", value))?;
+    let timestamp_ms = fields
+        .next()
+        .map(|ts| {
+            ts.parse::<i64>()
+                .map_err(|_| format!("invalid timestamp {:?}", ts))
+        })
+        .transpose()?;
+
+    Ok((name.to_string(), labels, value, timestamp_ms))
+}
+
+/// Parse `a="b",c="d"}` and return the remainder after the closing brace
+fn parse_labels<'a>(
+    mut s: &'a str,
+    labels: &mut BTreeMap<String, String>,
+) -> std::result::Result<&'a str, String> {
+    loop {
+        s = s.trim_start();
+        if let Some(rest) = s.strip_prefix('}') {
+            return Ok(rest);
+        }
+
+        let (name, rest) = s.split_once('=').ok_or("malformed label")?;
+        let rest = rest
+            .trim_start()
+            .strip_prefix('"')
+            .ok_or("unquoted label value")?;
+
+        // Find the closing quote, skipping escaped characters
+        let mut end = None;
+        let mut escaped = false;
+        for (i, c) in rest.char_indices() {
+            match c {
+                _ if escaped => escaped = false,
+                '\\' => escaped = true,
+                '"' => {
+                    end = Some(i);
+                    break;
+                }
+                _ => {}
+            }
+        }
+        let end = end.ok_or("unterminated label value")?;
+
+        let _ = labels.insert(name.trim().to_string(), unescape(&rest[..end], true));
+        s = rest[end + 1..].trim_start();
+        s = s.strip_prefix(',').unwrap_or(s);
+    }
+}
+
+/// Undo `\\`, `\n` and (in label values) `\"` escapes
+fn unescape(s: &str, quotes: bool) -> String {
+    let mut out = String::with_capacity(s.len());
+    let mut chars = s.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            out.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') => out.push('\n'),
+            Some('\\') => out.push('\\'),
+            Some('"') if quotes => out.push('"'),
+            Some(other) => {
+                out.push('\\');
+                out.push(other);
+            }
+            None => out.push('\\'),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = r#"
+# HELP http_requests_total Total HTTP requests.
+# TYPE http_requests_total counter
+http_requests_total{method="GET",path="/api/v2/secrets"} 1027 1704067200000
+http_requests_total{method="POST",path="/api/v2/secrets"} 3
+# HELP cache_entries Entries in the cache.
+# TYPE cache_entries gauge
+cache_entries 42
+# TYPE latency_seconds histogram
+latency_seconds_bucket{le="0.1"} 10
+latency_seconds_bucket{le="+Inf"} 12
+latency_seconds_sum 1.5
+latency_seconds_count 12
+process_info{version="1.2.3",note="say \"hi\"\\n"} 1
+"#;
+
+    #[test]
+    fn test_parse_exposition() {
+        let metrics = parse(SAMPLE).unwrap();
+        assert_eq!(metrics.len(), 8);
+
+        let first = &metrics[0];
+        assert_eq!(first.name, "http_requests_total");
+        assert_eq!(first.label("method"), Some("GET"));
+        assert_eq!(first.label("path"), Some("/api/v2/secrets"));
+        assert_eq!(first.value, 1027.0);
+        assert_eq!(first.timestamp_ms, Some(1_704_067_200_000));
+        assert_eq!(first.metric_type, MetricType::Counter);
+        assert_eq!(first.help.as_deref(), Some("Total HTTP requests."));
+
+        let gauge = metrics.iter().find(|m| m.name == "cache_entries").unwrap();
+        assert_eq!(gauge.metric_type, MetricType::Gauge);
+        assert!(gauge.labels.is_empty());
+        assert_eq!(gauge.value, 42.0);
+
+        let inf = &metrics[4];
+        assert_eq!(inf.name, "latency_seconds_bucket");
+        assert_eq!(inf.metric_type, MetricType::Histogram);
+        assert_eq!(inf.label("le"), Some("+Inf"));
+        assert_eq!(metrics[5].metric_type, MetricType::Histogram);
+
+        let info = metrics.last().unwrap();
+        assert_eq!(info.metric_type, MetricType::Untyped);
+        assert_eq!(info.help, None);
+        assert_eq!(info.label("note"), Some("say \"hi\"\\n"));
+    }
+
+    #[test]
+    fn test_parse_special_values_and_errors() {
+        let metrics = parse("a +Inf\nb -Inf\nc NaN\n").unwrap();
+        assert_eq!(metrics[0].value, f64::INFINITY);
+        assert_eq!(metrics[1].value, f64::NEG_INFINITY);
+        assert!(metrics[2].value.is_nan());
+
+        let err = parse("ok 1\nbroken{le=\"1\" 2\n").unwrap_err();
+        assert!(err.to_string().contains("line 2"));
+        assert!(parse("no_value").is_err());
+        assert!(parse("bad_value abc").is_err());
+    }
+}
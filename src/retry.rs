@@ -0,0 +1,191 @@
+//! Retry bookkeeping for the request execution path
+
+use backoff::backoff::Backoff;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Retry statistics
+///
+/// Lightweight counters that are always collected, independent of the
+/// `metrics` feature, so retry rates are visible without OpenTelemetry.
+#[derive(Debug, Clone)]
+pub struct RetryStats {
+    inner: Arc<RetryStatsInner>,
+}
+
+#[derive(Debug, Default)]
+struct RetryStatsInner {
+    total: AtomicU64,
+    by_reason: Mutex<HashMap<String, u64>>,
+}
+
+impl RetryStats {
+    /// Create new retry statistics
+    pub(crate) fn new() -> Self {
+        Self {
+            inner: Arc::new(RetryStatsInner::default()),
+        }
+    }
+
+    /// Get the total number of retries performed
+    pub fn total(&self) -> u64 {
+        self.inner.total.load(Ordering::Relaxed)
+    }
+
+    /// Get the number of retries performed for a specific reason
+    ///
+    /// Reasons are the HTTP status code (e.g. `"503"`) for server responses
+    /// and `"network_error"` for transport failures.
+    pub fn by_reason(&self, reason: &str) -> u64 {
+        self.reasons_guard().get(reason).copied().unwrap_or(0)
+    }
+
+    /// Get a copy of all per-reason retry counts
+    pub fn reasons(&self) -> HashMap<String, u64> {
+        self.reasons_guard().clone()
+    }
+
+    /// Reset all statistics to zero
+    pub fn reset(&self) {
+        self.inner.total.store(0, Ordering::Relaxed);
+        self.reasons_guard().clear();
+    }
+
+    // Internal methods for updating stats
+    pub(crate) fn record_retry(&self, reason: &str) {
+        let _ = self.inner.total.fetch_add(1, Ordering::Relaxed);
+        *self.reasons_guard().entry(reason.to_string()).or_insert(0) += 1;
+    }
+
+    fn reasons_guard(&self) -> std::sync::MutexGuard<'_, HashMap<String, u64>> {
+        self.inner
+            .by_reason
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+/// Maximum number of retries spent on 409 conflicts for a single request
+pub(crate) const MAX_CONFLICT_RETRIES: usize = 3;
+
+/// Per-request adjustments to the default retry behaviour
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct RetryPolicy {
+    /// Treat 409 Conflict as transient (capped at [`MAX_CONFLICT_RETRIES`])
+    pub retry_on_conflict: bool,
+    /// The response body stays open indefinitely (event streams), so neither
+    /// the client timeout nor the overall deadline applies
+    pub long_lived: bool,
+}
+
+/// Time kept free for the final attempt before an overall deadline
+pub(crate) const FINAL_ATTEMPT_RESERVE: Duration = Duration::from_millis(100);
+
+/// Backoff that never sleeps past an overall deadline
+///
+/// Each interval is clamped so the next attempt starts at least
+/// [`FINAL_ATTEMPT_RESERVE`] before the deadline. Once that is no longer
+/// possible, retrying stops.
+#[derive(Debug)]
+pub(crate) struct DeadlineBackoff<B> {
+    inner: B,
+    deadline: Option<Instant>,
+}
+
+impl<B> DeadlineBackoff<B> {
+    pub fn new(inner: B, deadline: Option<Instant>) -> Self {
+        Self { inner, deadline }
+    }
+}
+
+impl<B: Backoff> Backoff for DeadlineBackoff<B> {
+    fn reset(&mut self) {
+        self.inner.reset();
+    }
+
+    fn next_backoff(&mut self) -> Option<Duration> {
+        let interval = self.inner.next_backoff()?;
+        let Some(deadline) = self.deadline else {
+            return Some(interval);
+        };
+        let remaining = deadline
+            .saturating_duration_since(Instant::now())
+            .checked_sub(FINAL_ATTEMPT_RESERVE)
+            .filter(|remaining| !remaining.is_zero())?;
+        Some(interval.min(remaining))
+    }
+}
+
+/// Whether a request can be replayed after an ambiguous network failure
+///
+/// The server may already have applied a request that timed out or lost its
+/// connection. Replaying is harmless for idempotent methods, and for POST or
+/// PATCH only when an idempotency key lets the server deduplicate.
+pub(crate) fn is_replay_safe(request: &reqwest::Request) -> bool {
+    request.method().is_idempotent() || request.headers().contains_key("x-idempotency-key")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_retry_stats() {
+        let stats = RetryStats::new();
+        assert_eq!(stats.total(), 0);
+        assert_eq!(stats.by_reason("503"), 0);
+
+        stats.record_retry("503");
+        stats.record_retry("503");
+        stats.record_retry("network_error");
+
+        assert_eq!(stats.total(), 3);
+        assert_eq!(stats.by_reason("503"), 2);
+        assert_eq!(stats.by_reason("network_error"), 1);
+        assert_eq!(stats.reasons().len(), 2);
+
+        stats.reset();
+        assert_eq!(stats.total(), 0);
+        assert!(stats.reasons().is_empty());
+    }
+
+    #[test]
+    fn test_deadline_backoff() {
+        let constant = || backoff::backoff::Constant::new(Duration::from_secs(10));
+
+        let mut unbounded = DeadlineBackoff::new(constant(), None);
+        assert_eq!(unbounded.next_backoff(), Some(Duration::from_secs(10)));
+
+        let deadline = Instant::now() + Duration::from_secs(1);
+        let interval = DeadlineBackoff::new(constant(), Some(deadline))
+            .next_backoff()
+            .unwrap();
+        assert!(interval <= Duration::from_secs(1) - FINAL_ATTEMPT_RESERVE);
+        assert!(interval > Duration::from_millis(500));
+
+        let short = Instant::now() + FINAL_ATTEMPT_RESERVE / 2;
+        assert_eq!(
+            DeadlineBackoff::new(constant(), Some(short)).next_backoff(),
+            None
+        );
+    }
+
+    #[test]
+    fn test_is_replay_safe() {
+        let request =
+            |method| reqwest::Request::new(method, "https://example.com/".parse().unwrap());
+
+        assert!(is_replay_safe(&request(reqwest::Method::GET)));
+        assert!(is_replay_safe(&request(reqwest::Method::PUT)));
+        assert!(is_replay_safe(&request(reqwest::Method::DELETE)));
+        assert!(!is_replay_safe(&request(reqwest::Method::POST)));
+
+        let mut post = request(reqwest::Method::POST);
+        let _ = post
+            .headers_mut()
+            .insert("X-Idempotency-Key", "key-1".parse().unwrap());
+        assert!(is_replay_safe(&post));
+    }
+}
@@ -0,0 +1,172 @@
+//! Namespace-scoped client wrapper
+
+use crate::{
+    client::Client,
+    errors::Result,
+    models::{DeleteResult, GetOpts, ListOpts, ListSecretsResult, PutOpts, PutResult, Secret},
+};
+
+/// A [`Client`] bound to a single namespace
+///
+/// Created with [`Client::scoped`]. Shares the HTTP connection pool, cache and
+/// statistics with the client it was created from, so it is cheap to create
+/// and clone.
+///
+/// # Example
+///
+/// ```no_run
+/// # use secret_store_sdk::{Client, GetOpts};
+/// # async fn example(client: &Client) -> Result<(), Box<dyn std::error::Error>> {
+/// let db_secrets = client.scoped("database");
+/// let url = db_secrets.get("url", GetOpts::default()).await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct ScopedClient {
+    client: Client,
+    namespace: String,
+}
+
+impl ScopedClient {
+    pub(crate) fn new(client: Client, namespace: String) -> Self {
+        Self { client, namespace }
+    }
+
+    /// The namespace this client is bound to
+    pub fn namespace(&self) -> &str {
+        &self.namespace
+    }
+
+    /// The underlying unscoped client
+    pub fn client(&self) -> &Client {
+        &self.client
+    }
+
+    /// Get a secret, see [`Client::get_secret`]
+    pub async fn get(&self, key: &str, opts: GetOpts) -> Result<Secret> {
+        self.client.get_secret(&self.namespace, key, opts).await
+    }
+
+    /// Create or update a secret, see [`Client::put_secret`]
+    pub async fn put(
+        &self,
+        key: &str,
+        value: impl Into<String>,
+        opts: PutOpts,
+    ) -> Result<PutResult> {
+        self.client
+            .put_secret(&self.namespace, key, value, opts)
+            .await
+    }
+
+    /// Delete a secret, see [`Client::delete_secret`]
+    pub async fn delete(&self, key: &str) -> Result<DeleteResult> {
+        self.client.delete_secret(&self.namespace, key).await
+    }
+
+    /// List secrets, see [`Client::list_secrets`]
+    pub async fn list(&self, opts: ListOpts) -> Result<ListSecretsResult> {
+        self.client.list_secrets(&self.namespace, opts).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Auth, ClientBuilder, GetOpts, ListOpts, PutOpts};
+    use secrecy::ExposeSecret;
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn create_test_client(base_url: &str) -> crate::Client {
+        #[cfg(feature = "danger-insecure-http")]
+        {
+            ClientBuilder::new(base_url)
+                .auth(Auth::bearer("test-token"))
+                .allow_insecure_http()
+                .build()
+                .unwrap()
+        }
+        #[cfg(not(feature = "danger-insecure-http"))]
+        {
+            ClientBuilder::new(base_url.replace("http://", "https://"))
+                .auth(Auth::bearer("test-token"))
+                .build()
+                .unwrap()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_scoped_client() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/api/v2/secrets/database/url"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "value": "postgres://db/app",
+                "version": 1,
+                "updated_at": "2024-01-01T00:00:00Z"
+            })))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        Mock::given(method("PUT"))
+            .and(path("/api/v2/secrets/database/password"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "message": "Secret updated",
+                "namespace": "database",
+                "key": "password",
+                "created_at": "2024-01-01T00:00:00Z",
+                "request_id": "req-1"
+            })))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        Mock::given(method("DELETE"))
+            .and(path("/api/v2/secrets/database/password"))
+            .respond_with(ResponseTemplate::new(204))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        Mock::given(method("GET"))
+            .and(path("/api/v2/secrets/database"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "namespace": "database",
+                "secrets": [
+                    {"key": "url", "version": 1, "updated_at": "2024-01-01T00:00:00Z"}
+                ],
+                "total": 1
+            })))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let client = create_test_client(&mock_server.uri());
+        let db_secrets = client.scoped("database");
+        assert_eq!(db_secrets.namespace(), "database");
+
+        let secret = db_secrets.get("url", GetOpts::default()).await.unwrap();
+        assert_eq!(secret.namespace, "database");
+        assert_eq!(secret.value.expose_secret(), "postgres://db/app");
+
+        // The cache is shared with the parent client
+        let cached = client
+            .get_secret("database", "url", GetOpts::default())
+            .await
+            .unwrap();
+        assert!(cached.from_cache);
+
+        let _ = db_secrets
+            .put("password", "hunter2", PutOpts::default())
+            .await
+            .unwrap();
+        assert!(db_secrets.delete("password").await.unwrap().deleted);
+
+        let listing = db_secrets.list(ListOpts::default()).await.unwrap();
+        assert_eq!(listing.secrets.len(), 1);
+        assert_eq!(listing.secrets[0].key, "url");
+    }
+}
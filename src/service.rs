@@ -0,0 +1,140 @@
+//! Tower `Service` adapter over the client's request path
+
+use crate::{client::Client, errors::Result, Error};
+use http::HeaderMap;
+use reqwest::Method;
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+/// A raw API request sent through [`SecretService`]
+///
+/// `path` is relative to the client's base URL, e.g.
+/// `/api/v2/secrets/production/db-url`. Path segments must already be
+/// percent-encoded; the constructors below take care of that.
+#[derive(Debug, Clone)]
+pub struct SecretRequest {
+    /// HTTP method
+    pub method: Method,
+    /// Path (and optional query string) relative to the base URL
+    pub path: String,
+    /// Extra headers; authentication and request IDs are added by the client
+    pub headers: HeaderMap,
+    /// JSON request body
+    pub body: Option<serde_json::Value>,
+}
+
+impl SecretRequest {
+    /// Create a request without headers or body
+    pub fn new(method: Method, path: impl Into<String>) -> Self {
+        Self {
+            method,
+            path: path.into(),
+            headers: HeaderMap::new(),
+            body: None,
+        }
+    }
+
+    /// Request for a secret's current value
+    pub fn get_secret(namespace: &str, key: &str) -> Self {
+        Self::new(Method::GET, secret_path(namespace, key))
+    }
+
+    /// Request that writes a secret's value
+    pub fn put_secret(namespace: &str, key: &str, value: impl Into<String>) -> Self {
+        Self::new(Method::PUT, secret_path(namespace, key))
+            .json(serde_json::json!({ "value": value.into() }))
+    }
+
+    /// Request that deletes a secret
+    pub fn delete_secret(namespace: &str, key: &str) -> Self {
+        Self::new(Method::DELETE, secret_path(namespace, key))
+    }
+
+    /// Set the JSON body
+    pub fn json(mut self, body: serde_json::Value) -> Self {
+        self.body = Some(body);
+        self
+    }
+}
+
+fn secret_path(namespace: &str, key: &str) -> String {
+    format!(
+        "{}/secrets/{}/{}",
+        crate::endpoints::API_V2_BASE,
+        crate::util::encode_path(namespace),
+        crate::util::encode_path(key)
+    )
+}
+
+/// Response returned by [`SecretService`]
+///
+/// Only successful and `304 Not Modified` responses are returned; error
+/// statuses surface as [`Error`] just like the high-level methods.
+#[derive(Debug, Clone)]
+pub struct SecretResponse {
+    /// HTTP status code
+    pub status: u16,
+    /// Response headers
+    pub headers: HeaderMap,
+    /// Response body
+    pub body: Vec<u8>,
+}
+
+impl SecretResponse {
+    /// Deserialize the body as JSON
+    pub fn json<T: serde::de::DeserializeOwned>(&self) -> Result<T> {
+        serde_json::from_slice(&self.body).map_err(Error::from)
+    }
+}
+
+/// [`tower::Service`] executing raw requests with the client's
+/// authentication, retries and metrics
+///
+/// Created with [`Client::service`]. The service is always ready; wrap it in
+/// Tower layers for rate limiting, load shedding and the like. Layers only
+/// apply to requests sent through the service: the client's high-level
+/// methods bypass it.
+///
+/// # Example
+///
+/// ```no_run
+/// # use secret_store_sdk::{Client, SecretRequest};
+/// # async fn example(client: &Client) -> Result<(), Box<dyn std::error::Error>> {
+/// use tower::{ServiceBuilder, ServiceExt};
+///
+/// let service = ServiceBuilder::new()
+///     .concurrency_limit(8)
+///     .service(client.service());
+/// let response = service
+///     .oneshot(SecretRequest::get_secret("production", "db-url"))
+///     .await?;
+/// println!("status {}", response.status);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct SecretService {
+    client: Client,
+}
+
+impl SecretService {
+    pub(crate) fn new(client: Client) -> Self {
+        Self { client }
+    }
+}
+
+impl tower::Service<SecretRequest> for SecretService {
+    type Response = SecretResponse;
+    type Error = Error;
+    type Future = Pin<Box<dyn Future<Output = Result<SecretResponse>> + Send>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: SecretRequest) -> Self::Future {
+        let client = self.client.clone();
+        Box::pin(async move { client.send_raw(request).await })
+    }
+}
@@ -0,0 +1,80 @@
+//! Coalescing of concurrent reads of the same secret
+
+use crate::models::Secret;
+use futures::channel::oneshot;
+use futures::future::{FutureExt, Shared};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+/// Outcome shared with followers: the leader's secret, or `None` if its fetch failed
+type Flight = Shared<oneshot::Receiver<Option<Secret>>>;
+
+/// In-flight fetches, keyed by cache key
+#[derive(Default)]
+pub(crate) struct SingleFlight {
+    calls: Mutex<HashMap<String, Flight>>,
+}
+
+/// Role of a caller joining a flight
+pub(crate) enum Role {
+    /// No fetch was in flight: this caller fetches and publishes the result
+    Leader(Leader),
+    /// Another caller is fetching: wait for its result
+    Follower(Flight),
+}
+
+impl SingleFlight {
+    /// Join the flight for `key`, starting one if none is in flight
+    pub(crate) fn join(self: &Arc<Self>, key: &str) -> Role {
+        let mut calls = self.calls.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(flight) = calls.get(key) {
+            return Role::Follower(flight.clone());
+        }
+        let (sender, receiver) = oneshot::channel();
+        let _ = calls.insert(key.to_string(), receiver.shared());
+        Role::Leader(Leader {
+            flight: Arc::clone(self),
+            key: key.to_string(),
+            sender: Some(sender),
+        })
+    }
+
+    fn remove(&self, key: &str) {
+        let _ = self
+            .calls
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .remove(key);
+    }
+}
+
+/// Handle of the caller performing the fetch
+///
+/// Dropping it without [`Leader::finish`] (e.g. when the caller is cancelled)
+/// releases the followers, which then fetch on their own.
+pub(crate) struct Leader {
+    flight: Arc<SingleFlight>,
+    key: String,
+    sender: Option<oneshot::Sender<Option<Secret>>>,
+}
+
+impl Leader {
+    /// Publish the fetched secret to the followers
+    ///
+    /// Errors are not shared: followers of a failed fetch retry on their own
+    /// so each caller sees an error carrying its own request ID.
+    pub(crate) fn finish(mut self, secret: Option<&Secret>) {
+        self.flight.remove(&self.key);
+        if let Some(sender) = self.sender.take() {
+            let _ = sender.send(secret.cloned());
+        }
+    }
+}
+
+impl Drop for Leader {
+    fn drop(&mut self) {
+        if self.sender.is_some() {
+            self.flight.remove(&self.key);
+        }
+    }
+}
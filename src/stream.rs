@@ -0,0 +1,185 @@
+//! Streaming reader over a secret's value
+
+use crate::errors::{Error, Result};
+use futures::stream::{BoxStream, Stream, StreamExt};
+use sha2::{Digest, Sha256};
+use std::fmt;
+use std::io;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use tokio::io::{AsyncRead, ReadBuf};
+use zeroize::Zeroizing;
+
+/// [`AsyncRead`] over a secret value returned by
+/// [`Client::get_secret_stream`](crate::Client::get_secret_stream)
+///
+/// Only one network chunk is held at a time, and each chunk is zeroed once
+/// it has been read. When the client verifies content digests, a digest
+/// mismatch (or a missing digest, if one is required) fails the final read
+/// with an [`io::Error`]; treat everything read up to then as untrusted.
+pub struct SecretStream {
+    chunks: BoxStream<'static, Result<Vec<u8>>>,
+    current: Zeroizing<Vec<u8>>,
+    position: usize,
+}
+
+/// Checks applied to a streamed response body once it has been read
+#[derive(Default)]
+pub(crate) struct BodyCheck {
+    /// SHA-256 digest the body must match
+    pub expected_sha256: Option<Vec<u8>>,
+    /// Fail a non-empty body that comes without a digest
+    pub require_digest: bool,
+    /// Called with the body size once the body has been read to the end
+    pub on_complete: Option<Box<dyn FnOnce(u64) + Send>>,
+}
+
+/// Progress through a streamed response body
+struct BodyState {
+    response: reqwest::Response,
+    digest: Option<(Sha256, Vec<u8>)>,
+    require_digest: bool,
+    on_complete: Option<Box<dyn FnOnce(u64) + Send>>,
+    bytes: u64,
+}
+
+impl BodyState {
+    fn new(response: reqwest::Response, check: BodyCheck) -> Self {
+        Self {
+            response,
+            digest: check
+                .expected_sha256
+                .map(|expected| (Sha256::new(), expected)),
+            require_digest: check.require_digest,
+            on_complete: check.on_complete,
+            bytes: 0,
+        }
+    }
+
+    /// Run the end-of-body checks
+    fn finish(self) -> Result<()> {
+        if let Some(on_complete) = self.on_complete {
+            on_complete(self.bytes);
+        }
+        let digest_matches = self
+            .digest
+            .map(|(hasher, expected)| hasher.finalize().as_slice() == expected.as_slice());
+        match digest_matches {
+            Some(false) => Err(Error::Other("content digest mismatch".to_string())),
+            None if self.require_digest && self.bytes > 0 => {
+                Err(Error::Other("content digest missing".to_string()))
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+impl SecretStream {
+    /// Stream the body of a response, checking it once it ends
+    pub(crate) fn from_response(response: reqwest::Response, check: BodyCheck) -> Self {
+        let state = BodyState::new(response, check);
+        let chunks = futures::stream::unfold(Some(state), |state| async move {
+            let mut state = state?;
+            match state.response.chunk().await {
+                Ok(Some(chunk)) => {
+                    state.bytes += chunk.len() as u64;
+                    if let Some((hasher, _)) = &mut state.digest {
+                        hasher.update(&chunk);
+                    }
+                    // Take ownership of the chunk's allocation so it can be wiped
+                    Some((Ok(Vec::from(chunk)), Some(state)))
+                }
+                Ok(None) => state.finish().err().map(|e| (Err(e), None)),
+                Err(e) => Some((Err(Error::from(e)), None)),
+            }
+        });
+        Self::new(chunks)
+    }
+
+    /// Stream an already buffered value
+    pub(crate) fn from_bytes(bytes: Vec<u8>) -> Self {
+        Self::new(futures::stream::once(async move { Ok(bytes) }))
+    }
+
+    fn new(chunks: impl Stream<Item = Result<Vec<u8>>> + Send + 'static) -> Self {
+        Self {
+            chunks: chunks.boxed(),
+            current: Zeroizing::new(Vec::new()),
+            position: 0,
+        }
+    }
+}
+
+impl AsyncRead for SecretStream {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        let this = self.get_mut();
+        loop {
+            if this.position < this.current.len() {
+                let n = buf.remaining().min(this.current.len() - this.position);
+                buf.put_slice(&this.current[this.position..this.position + n]);
+                this.position += n;
+                return Poll::Ready(Ok(()));
+            }
+
+            match this.chunks.as_mut().poll_next(cx) {
+                Poll::Ready(Some(Ok(chunk))) => {
+                    // Dropping the previous chunk zeroes it
+                    this.current = Zeroizing::new(chunk);
+                    this.position = 0;
+                }
+                Poll::Ready(Some(Err(e))) => return Poll::Ready(Err(io::Error::other(e))),
+                Poll::Ready(None) => return Poll::Ready(Ok(())),
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+    }
+}
+
+impl fmt::Debug for SecretStream {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SecretStream")
+            .field("buffered", &(self.current.len() - self.position))
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::AsyncReadExt;
+
+    #[tokio::test]
+    async fn test_reads_across_chunks() {
+        let chunks = futures::stream::iter(vec![
+            Ok(b"abc".to_vec()),
+            Ok(Vec::new()),
+            Ok(b"defgh".to_vec()),
+        ]);
+        let mut stream = SecretStream::new(chunks);
+
+        let mut small = [0u8; 2];
+        assert_eq!(stream.read_exact(&mut small).await.unwrap(), 2);
+        assert_eq!(&small, b"ab");
+
+        let mut rest = Vec::new();
+        let _ = stream.read_to_end(&mut rest).await.unwrap();
+        assert_eq!(rest, b"cdefgh");
+    }
+
+    #[tokio::test]
+    async fn test_surfaces_stream_errors() {
+        let chunks = futures::stream::iter(vec![
+            Ok(b"abc".to_vec()),
+            Err(Error::Other("connection reset".to_string())),
+        ]);
+        let mut stream = SecretStream::new(chunks);
+
+        let mut out = Vec::new();
+        let err = stream.read_to_end(&mut out).await.unwrap_err();
+        assert!(err.to_string().contains("connection reset"));
+    }
+}
@@ -1,10 +1,11 @@
 //! Telemetry and observability utilities
 
+use crate::endpoints::RouteLabel;
 use std::sync::Arc;
 
 #[cfg(feature = "metrics")]
 use opentelemetry::{
-    metrics::{Counter, Histogram, UpDownCounter},
+    metrics::{Counter, Histogram, Meter, UpDownCounter},
     KeyValue,
 };
 
@@ -52,6 +53,12 @@ pub struct Metrics {
 
     #[cfg(feature = "metrics")]
     pub(crate) retry_attempts: Counter<u64>,
+
+    #[cfg(feature = "metrics")]
+    pub(crate) request_bytes: Histogram<u64>,
+
+    #[cfg(feature = "metrics")]
+    pub(crate) response_bytes: Histogram<u64>,
 }
 
 impl Metrics {
@@ -60,8 +67,12 @@ impl Metrics {
     pub fn new(config: &TelemetryConfig) -> Self {
         use opentelemetry::global;
 
-        let meter = global::meter(config.service_name.clone());
+        Self::from_meter(&global::meter(config.service_name.clone()))
+    }
 
+    /// Create metrics instruments on a specific meter
+    #[cfg(feature = "metrics")]
+    pub(crate) fn from_meter(meter: &Meter) -> Self {
         let requests_total = meter
             .u64_counter("secret_store_sdk.requests_total")
             .with_description("Total number of requests made")
@@ -97,6 +108,16 @@ impl Metrics {
             .with_description("Total number of retry attempts")
             .init();
 
+        let request_bytes = meter
+            .u64_histogram("secret_store_sdk.request_bytes")
+            .with_description("Request body size in bytes")
+            .init();
+
+        let response_bytes = meter
+            .u64_histogram("secret_store_sdk.response_bytes")
+            .with_description("Response body size in bytes")
+            .init();
+
         Self {
             requests_total,
             request_duration,
@@ -105,6 +126,8 @@ impl Metrics {
             cache_misses,
             active_connections,
             retry_attempts,
+            request_bytes,
+            response_bytes,
         }
     }
 
@@ -117,15 +140,18 @@ impl Metrics {
 
     /// Record a request
     #[cfg(feature = "metrics")]
-    pub fn record_request(&self, method: &str, path: &str, status: u16, duration_secs: f64) {
-        let labels = &[
-            KeyValue::new("method", method.to_string()),
-            KeyValue::new("path", path.to_string()),
-            KeyValue::new("status", status.to_string()),
-        ];
-
-        self.requests_total.add(1, labels);
-        self.request_duration.record(duration_secs, labels);
+    pub fn record_request(
+        &self,
+        method: &str,
+        route: &RouteLabel,
+        status: u16,
+        duration_secs: f64,
+    ) {
+        let mut labels = route_labels(method, route);
+        labels.push(KeyValue::new("status", status.to_string()));
+
+        self.requests_total.add(1, &labels);
+        self.request_duration.record(duration_secs, &labels);
 
         if status >= 400 {
             self.errors_total.add(
@@ -141,7 +167,38 @@ impl Metrics {
     /// Record a request (no-op when metrics disabled)
     #[cfg(not(feature = "metrics"))]
     #[allow(dead_code)]
-    pub fn record_request(&self, _method: &str, _path: &str, _status: u16, _duration_secs: f64) {}
+    pub fn record_request(
+        &self,
+        _method: &str,
+        _route: &RouteLabel,
+        _status: u16,
+        _duration_secs: f64,
+    ) {
+    }
+
+    /// Record the body size of a sent request
+    #[cfg(feature = "metrics")]
+    pub fn record_request_bytes(&self, method: &str, route: &RouteLabel, bytes: u64) {
+        self.request_bytes
+            .record(bytes, &route_labels(method, route));
+    }
+
+    /// Record the body size of a sent request (no-op)
+    #[cfg(not(feature = "metrics"))]
+    #[allow(dead_code)]
+    pub fn record_request_bytes(&self, _method: &str, _route: &RouteLabel, _bytes: u64) {}
+
+    /// Record the body size of a received response
+    #[cfg(feature = "metrics")]
+    pub fn record_response_bytes(&self, method: &str, route: &RouteLabel, bytes: u64) {
+        self.response_bytes
+            .record(bytes, &route_labels(method, route));
+    }
+
+    /// Record the body size of a received response (no-op)
+    #[cfg(not(feature = "metrics"))]
+    #[allow(dead_code)]
+    pub fn record_response_bytes(&self, _method: &str, _route: &RouteLabel, _bytes: u64) {}
 
     /// Record a cache hit
     #[cfg(feature = "metrics")]
@@ -207,6 +264,22 @@ impl Metrics {
     pub fn record_retry(&self, _attempt: u32, _reason: &str) {}
 }
 
+/// `method`, `route` and (if any) `namespace` labels of a request
+///
+/// Requests are labelled by route template rather than raw path, so every
+/// key of a namespace shares one time series.
+#[cfg(feature = "metrics")]
+fn route_labels(method: &str, route: &RouteLabel) -> Vec<KeyValue> {
+    let mut labels = vec![
+        KeyValue::new("method", method.to_string()),
+        KeyValue::new("route", route.route),
+    ];
+    if let Some(namespace) = &route.namespace {
+        labels.push(KeyValue::new("namespace", namespace.clone()));
+    }
+    labels
+}
+
 impl std::fmt::Debug for Metrics {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("Metrics")
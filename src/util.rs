@@ -23,30 +23,157 @@ pub fn header_str(headers: &http::HeaderMap, name: &str) -> Option<String> {
     headers.get(name)?.to_str().ok().map(|s| s.to_string())
 }
 
+/// Size of a request body, measured for buffered bodies
+///
+/// Streamed bodies fall back to the `Content-Length` header, if set.
+pub fn request_body_len(request: &reqwest::Request) -> Option<u64> {
+    request
+        .body()
+        .and_then(|body| body.as_bytes())
+        .map(|bytes| bytes.len() as u64)
+        .or_else(|| {
+            header_str(request.headers(), "content-length").and_then(|len| len.parse().ok())
+        })
+}
+
+/// Compare API versions such as `v2` or `2.1`, treating missing parts as 0
+///
+/// Returns `None` if either version is not made of numeric parts.
+pub fn api_version_at_least(version: &str, minimum: &str) -> Option<bool> {
+    fn parts(version: &str) -> Option<Vec<u64>> {
+        let version = version.trim();
+        let version = version.strip_prefix(['v', 'V']).unwrap_or(version);
+        version.split('.').map(|part| part.parse().ok()).collect()
+    }
+
+    let mut version = parts(version)?;
+    let mut minimum = parts(minimum)?;
+    let len = version.len().max(minimum.len());
+    version.resize(len, 0);
+    minimum.resize(len, 0);
+    Some(version >= minimum)
+}
+
+/// Safety cap for auto-paginating streams
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct PageLimits {
+    max_pages: usize,
+    max_items: usize,
+    pages: usize,
+    items: usize,
+}
+
+impl PageLimits {
+    pub fn new(max_pages: Option<usize>, max_items: Option<usize>) -> Self {
+        Self {
+            max_pages: max_pages.unwrap_or(crate::DEFAULT_PAGINATION_MAX_PAGES),
+            max_items: max_items.unwrap_or(crate::DEFAULT_PAGINATION_MAX_ITEMS),
+            pages: 0,
+            items: 0,
+        }
+    }
+
+    /// Account for a fetched page, failing once a cap is exceeded
+    pub fn admit(&mut self, items: usize) -> crate::Result<()> {
+        self.pages += 1;
+        self.items += items;
+        if self.pages > self.max_pages {
+            return Err(crate::Error::Other(format!(
+                "pagination limit exceeded: more than {} pages",
+                self.max_pages
+            )));
+        }
+        if self.items > self.max_items {
+            return Err(crate::Error::Other(format!(
+                "pagination limit exceeded: more than {} items",
+                self.max_items
+            )));
+        }
+        Ok(())
+    }
+}
+
 /// Generate a new request ID
 pub fn generate_request_id() -> String {
     format!("sdk-{}", uuid::Uuid::new_v4())
 }
 
+/// Format a `namespace/key` cache key for log output
+///
+/// When `log_key_names` is false the part after the namespace is replaced with
+/// a short stable hash so that log lines remain correlatable without leaking
+/// key names.
+pub fn log_cache_key(cache_key: &str, log_key_names: bool) -> String {
+    use std::hash::{Hash, Hasher};
+
+    let Some((namespace, key)) = cache_key.split_once('/').filter(|_| !log_key_names) else {
+        return cache_key.to_string();
+    };
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    key.hash(&mut hasher);
+    format!("{}/<redacted:{:08x}>", namespace, hasher.finish() as u32)
+}
+
+/// SHA-256 digest the server declared for a response body
+///
+/// Reads `Content-Digest: sha-256=:<base64>:` (RFC 9530) or
+/// `X-Content-SHA256: <hex>`. Returns `None` if neither header carries a
+/// SHA-256 digest; a malformed digest comes back empty and never matches.
+pub(crate) fn declared_sha256(headers: &http::HeaderMap) -> Option<Vec<u8>> {
+    use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
+
+    let content_digest = headers
+        .get("content-digest")
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| {
+            v.split(',').find_map(|entry| {
+                let (algorithm, value) = entry.trim().split_once('=')?;
+                algorithm
+                    .eq_ignore_ascii_case("sha-256")
+                    .then(|| value.trim().trim_matches(':').to_string())
+            })
+        });
+    if let Some(value) = content_digest {
+        return Some(BASE64.decode(value).unwrap_or_default());
+    }
+
+    let hex = headers.get("x-content-sha256")?.to_str().ok()?.trim();
+    let bytes = (0..hex.len())
+        .step_by(2)
+        .map(|i| {
+            hex.get(i..i + 2)
+                .and_then(|pair| u8::from_str_radix(pair, 16).ok())
+        })
+        .collect::<Option<Vec<u8>>>();
+    Some(bytes.unwrap_or_default())
+}
+
+/// Parse an HTTP date (`Sun, 06 Nov 1994 08:49:37 GMT`, RFC 9110 IMF-fixdate)
+pub(crate) fn parse_http_date(value: &str) -> Option<time::OffsetDateTime> {
+    const FORMAT: &[time::format_description::FormatItem<'static>] = time::macros::format_description!(
+        "[weekday repr:short], [day] [month repr:short] [year] [hour]:[minute]:[second] GMT"
+    );
+    time::PrimitiveDateTime::parse(value.trim(), FORMAT)
+        .ok()
+        .map(time::PrimitiveDateTime::assume_utc)
+}
+
 /// URL encode a path segment
+///
+/// Everything except RFC 3986 unreserved characters (`A-Z a-z 0-9 - . _ ~`)
+/// is percent-encoded, so `/`, `\`, `%`, spaces and non-ASCII characters in
+/// namespaces and keys stay inside a single segment. Segments that are
+/// exactly `.` or `..` are still treated as dot segments by URL parsers.
 pub fn encode_path(s: &str) -> String {
-    use percent_encoding::{AsciiSet, CONTROLS};
-
-    // Define which characters to encode - RFC 3986 unreserved characters plus common safe chars
-    const FRAGMENT: &AsciiSet = &CONTROLS
-        .add(b' ')
-        .add(b'"')
-        .add(b'<')
-        .add(b'>')
-        .add(b'`')
-        .add(b'#')
-        .add(b'?')
-        .add(b'{')
-        .add(b'}')
-        .add(b'/')
-        .add(b'%');
-
-    percent_encoding::utf8_percent_encode(s, FRAGMENT).to_string()
+    use percent_encoding::{AsciiSet, NON_ALPHANUMERIC};
+
+    const SEGMENT: &AsciiSet = &NON_ALPHANUMERIC
+        .remove(b'-')
+        .remove(b'.')
+        .remove(b'_')
+        .remove(b'~');
+
+    percent_encoding::utf8_percent_encode(s, SEGMENT).to_string()
 }
 
 #[cfg(test)]
@@ -65,6 +192,14 @@ mod tests {
         assert_eq!(duration.as_secs(), 300);
     }
 
+    #[test]
+    fn test_parse_http_date() {
+        let date = parse_http_date("Sun, 06 Nov 1994 08:49:37 GMT").unwrap();
+        assert_eq!(date.unix_timestamp(), 784_111_777);
+        assert!(parse_http_date("Sunday, 06-Nov-94 08:49:37 GMT").is_none());
+        assert!(parse_http_date("not a date").is_none());
+    }
+
     #[test]
     fn test_encode_path() {
         assert_eq!(encode_path("hello world"), "hello%20world");
@@ -73,5 +208,60 @@ mod tests {
         assert_eq!(encode_path("my-key"), "my-key");
         assert_eq!(encode_path("my_key"), "my_key");
         assert_eq!(encode_path("my.key"), "my.key");
+        assert_eq!(encode_path("my~key"), "my~key");
+        assert_eq!(encode_path("100%"), "100%25");
+        assert_eq!(encode_path(r"a\b"), "a%5Cb");
+        assert_eq!(encode_path("a+b=c&d;e"), "a%2Bb%3Dc%26d%3Be");
+        assert_eq!(encode_path("schlüssel"), "schl%C3%BCssel");
+    }
+
+    #[test]
+    fn test_declared_sha256() {
+        // sha256("hello")
+        let hex = "2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824";
+        let expected: Vec<u8> = (0..32)
+            .map(|i| u8::from_str_radix(&hex[i * 2..i * 2 + 2], 16).unwrap())
+            .collect();
+
+        let mut headers = http::HeaderMap::new();
+        assert_eq!(declared_sha256(&headers), None);
+
+        let _ = headers.insert("x-content-sha256", hex.to_uppercase().parse().unwrap());
+        assert_eq!(declared_sha256(&headers), Some(expected.clone()));
+
+        let _ = headers.insert(
+            "content-digest",
+            "sha-512=:AAAA:, sha-256=:LPJNul+wow4m6DsqxbninhsWHlwfp0JecwQzYpOLmCQ=:"
+                .parse()
+                .unwrap(),
+        );
+        assert_eq!(declared_sha256(&headers), Some(expected));
+
+        let _ = headers.insert("content-digest", "sha-256=:not base64:".parse().unwrap());
+        assert_eq!(declared_sha256(&headers), Some(Vec::new()));
+    }
+
+    #[test]
+    fn test_api_version_at_least() {
+        assert_eq!(api_version_at_least("v2", "v2"), Some(true));
+        assert_eq!(api_version_at_least("v2", "v2.1"), Some(false));
+        assert_eq!(api_version_at_least("2.1", "v2.1.0"), Some(true));
+        assert_eq!(api_version_at_least("v3", "v2.9"), Some(true));
+        assert_eq!(api_version_at_least("v2.10", "v2.9"), Some(true));
+        assert_eq!(api_version_at_least("beta", "v2"), None);
+    }
+
+    #[test]
+    fn test_log_cache_key() {
+        assert_eq!(log_cache_key("prod/db-pass", true), "prod/db-pass");
+
+        let redacted = log_cache_key("prod/db-pass", false);
+        assert!(redacted.starts_with("prod/<redacted:"));
+        assert!(!redacted.contains("db-pass"));
+        assert_eq!(redacted, log_cache_key("prod/db-pass", false));
+        assert_ne!(redacted, log_cache_key("prod/api-key", false));
+
+        // Keys without a namespace component carry no key name
+        assert_eq!(log_cache_key("namespaces", false), "namespaces");
     }
 }
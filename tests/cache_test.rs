@@ -1,11 +1,13 @@
 //! Integration tests for caching functionality
 
 use secrecy::ExposeSecret;
-use secret_store_sdk::{Auth, ClientBuilder, GetOpts};
+use secret_store_sdk::{Auth, ClientBuilder, Clock, GetOpts, ListOpts};
 use serde_json::json;
+use std::sync::{Arc, Mutex};
 use std::time::Duration;
+use time::OffsetDateTime;
 use wiremock::{
-    matchers::{method, path},
+    matchers::{header, method, path},
     Mock, MockServer, ResponseTemplate,
 };
 
@@ -81,6 +83,36 @@ async fn test_cache_hit() {
     assert_eq!(stats.misses(), 1);
 }
 
+#[tokio::test]
+async fn test_secret_reports_cache_source() {
+    let server = MockServer::start().await;
+    let client = create_test_client(&server, true, 10).await;
+
+    Mock::given(method("GET"))
+        .and(path("/api/v2/secrets/production/source-key"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "value": "source-value",
+            "version": 1,
+            "updated_at": "2024-01-01T00:00:00Z"
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let first = client
+        .get_secret("production", "source-key", GetOpts::default())
+        .await
+        .expect("Failed to get secret");
+    assert!(!first.from_cache);
+
+    let second = client
+        .get_secret("production", "source-key", GetOpts::default())
+        .await
+        .expect("Failed to get cached secret");
+    assert!(second.from_cache);
+    assert_eq!(second.value.expose_secret(), "source-value");
+}
+
 #[tokio::test]
 async fn test_cache_disabled() {
     let server = MockServer::start().await;
@@ -337,12 +369,48 @@ async fn test_etag_cache_validation() {
     assert_eq!(cached.value.expose_secret(), "etag-value");
 }
 
+/// Clock that only moves when told to
+#[derive(Debug)]
+struct ManualClock(Mutex<OffsetDateTime>);
+
+impl ManualClock {
+    fn new() -> Arc<Self> {
+        Arc::new(Self(Mutex::new(OffsetDateTime::now_utc())))
+    }
+
+    fn advance(&self, by: Duration) {
+        *self.0.lock().unwrap() += by;
+    }
+}
+
+impl Clock for ManualClock {
+    fn now(&self) -> OffsetDateTime {
+        *self.0.lock().unwrap()
+    }
+}
+
 #[tokio::test]
 async fn test_cache_ttl_expiration() {
     let server = MockServer::start().await;
-    let client = create_test_client(&server, true, 1).await; // 1 second TTL
+    let clock = ManualClock::new();
+
+    #[cfg(feature = "danger-insecure-http")]
+    let client = ClientBuilder::new(server.uri())
+        .auth(Auth::bearer("test-token"))
+        .cache_ttl_secs(1)
+        .clock(clock.clone())
+        .allow_insecure_http()
+        .build()
+        .expect("Failed to build client");
+
+    #[cfg(not(feature = "danger-insecure-http"))]
+    let client = ClientBuilder::new(server.uri().replace("http://", "https://"))
+        .auth(Auth::bearer("test-token"))
+        .cache_ttl_secs(1)
+        .clock(clock.clone())
+        .build()
+        .expect("Failed to build client");
 
-    // First request
     Mock::given(method("GET"))
         .and(path("/api/v2/secrets/production/ttl-key"))
         .respond_with(ResponseTemplate::new(200).set_body_json(json!({
@@ -362,17 +430,237 @@ async fn test_cache_ttl_expiration() {
         .get_secret("production", "ttl-key", GetOpts::default())
         .await
         .expect("Failed to get secret");
-
     assert_eq!(secret1.value.expose_secret(), "ttl-value");
 
-    // Wait for TTL to expire
-    tokio::time::sleep(Duration::from_secs(2)).await;
+    // Still within the TTL - served from cache
+    clock.advance(Duration::from_millis(500));
+    let _ = client
+        .get_secret("production", "ttl-key", GetOpts::default())
+        .await
+        .expect("Failed to get cached secret");
+    assert_eq!(client.cache_stats().hits(), 1);
+
+    // Move past the TTL without sleeping
+    clock.advance(Duration::from_secs(1));
 
-    // Second request - should hit server again
+    // Third request - should hit server again
     let secret2 = client
         .get_secret("production", "ttl-key", GetOpts::default())
         .await
         .expect("Failed to get secret after TTL");
 
     assert_eq!(secret2.value.expose_secret(), "ttl-value");
+    assert_eq!(client.cache_stats().expirations(), 1);
+}
+
+fn list_body(version: i32) -> serde_json::Value {
+    json!({
+        "namespace": "production",
+        "secrets": [
+            {"key": "list-key", "version": version, "updated_at": "2024-01-01T00:00:00Z", "kid": null}
+        ],
+        "total": 1
+    })
+}
+
+#[tokio::test]
+async fn test_list_cache_hit_and_invalidation() {
+    let server = MockServer::start().await;
+    let client = create_test_client(&server, true, 60).await;
+
+    Mock::given(method("GET"))
+        .and(path("/api/v2/secrets/production"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(list_body(1)))
+        .up_to_n_times(1)
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    // Two identical listings - only the first should reach the server
+    let first = client
+        .list_secrets("production", ListOpts::default())
+        .await
+        .expect("Failed to list secrets");
+    let second = client
+        .list_secrets("production", ListOpts::default())
+        .await
+        .expect("Failed to list cached secrets");
+    assert_eq!(first.secrets[0].version, Some(1));
+    assert_eq!(second.secrets[0].version, Some(1));
+
+    // Listing lookups do not count towards the secret cache stats
+    assert_eq!(client.cache_stats().hits(), 0);
+    assert_eq!(client.cache_stats().misses(), 0);
+
+    // A put in the namespace invalidates its listings
+    Mock::given(method("PUT"))
+        .and(path("/api/v2/secrets/production/list-key"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "message": "Secret updated",
+            "namespace": "production",
+            "key": "list-key",
+            "created_at": "2024-01-01T00:01:00Z",
+            "request_id": "req-123"
+        })))
+        .mount(&server)
+        .await;
+
+    let _ = client
+        .put_secret("production", "list-key", "new-value", Default::default())
+        .await
+        .expect("Failed to put secret");
+
+    Mock::given(method("GET"))
+        .and(path("/api/v2/secrets/production"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(list_body(2)))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let third = client
+        .list_secrets("production", ListOpts::default())
+        .await
+        .expect("Failed to list secrets after put");
+    assert_eq!(third.secrets[0].version, Some(2));
+}
+
+#[tokio::test]
+async fn test_list_cache_revalidates_with_etag() {
+    let server = MockServer::start().await;
+
+    // Zero freshness TTL: every listing is revalidated with its ETag
+    #[cfg(feature = "danger-insecure-http")]
+    let client = ClientBuilder::new(server.uri())
+        .auth(Auth::bearer("test-token"))
+        .list_cache_ttl_secs(0)
+        .allow_insecure_http()
+        .build()
+        .expect("Failed to build client");
+
+    #[cfg(not(feature = "danger-insecure-http"))]
+    let client = ClientBuilder::new(server.uri().replace("http://", "https://"))
+        .auth(Auth::bearer("test-token"))
+        .list_cache_ttl_secs(0)
+        .build()
+        .expect("Failed to build client");
+
+    Mock::given(method("GET"))
+        .and(path("/api/v2/secrets/production"))
+        .and(header("If-None-Match", "\"list-v1\""))
+        .respond_with(ResponseTemplate::new(304))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    Mock::given(method("GET"))
+        .and(path("/api/v2/secrets/production"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(list_body(1))
+                .append_header("ETag", "\"list-v1\""),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let first = client
+        .list_secrets("production", ListOpts::default())
+        .await
+        .expect("Failed to list secrets");
+    let revalidated = client
+        .list_secrets("production", ListOpts::default())
+        .await
+        .expect("Failed to revalidate listing");
+
+    assert_eq!(first.secrets[0].key, revalidated.secrets[0].key);
+    assert_eq!(revalidated.secrets[0].version, Some(1));
+}
+
+#[tokio::test]
+async fn test_serve_stale_on_error() {
+    let server = MockServer::start().await;
+    let clock = ManualClock::new();
+
+    #[cfg(feature = "danger-insecure-http")]
+    let client = ClientBuilder::new(server.uri())
+        .auth(Auth::bearer("test-token"))
+        .cache_ttl_secs(1)
+        .serve_stale_on_error(60)
+        .retries(0)
+        .clock(clock.clone())
+        .allow_insecure_http()
+        .build()
+        .expect("Failed to build client");
+
+    #[cfg(not(feature = "danger-insecure-http"))]
+    let client = ClientBuilder::new(server.uri().replace("http://", "https://"))
+        .auth(Auth::bearer("test-token"))
+        .cache_ttl_secs(1)
+        .serve_stale_on_error(60)
+        .retries(0)
+        .clock(clock.clone())
+        .build()
+        .expect("Failed to build client");
+
+    Mock::given(method("GET"))
+        .and(path("/api/v2/secrets/production/stale-key"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "value": "stale-value",
+            "version": 1,
+            "updated_at": "2024-01-01T00:00:00Z"
+        })))
+        .up_to_n_times(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/api/v2/secrets/production/stale-key"))
+        .respond_with(ResponseTemplate::new(503))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/api/v2/secrets/production/missing-key"))
+        .respond_with(ResponseTemplate::new(404))
+        .mount(&server)
+        .await;
+
+    let fresh = client
+        .get_secret("production", "stale-key", GetOpts::default())
+        .await
+        .expect("Failed to get secret");
+    assert!(!fresh.from_cache);
+
+    // The backend is down once the entry expires: the stale value is served
+    clock.advance(Duration::from_secs(5));
+    let stale = client
+        .get_secret("production", "stale-key", GetOpts::default())
+        .await
+        .expect("Stale entry should be served");
+    assert!(stale.from_cache);
+    assert_eq!(stale.value.expose_secret(), "stale-value");
+
+    // Opting out per request surfaces the error
+    let opts = GetOpts {
+        serve_stale_on_error: Some(false),
+        ..Default::default()
+    };
+    let err = client
+        .get_secret("production", "stale-key", opts)
+        .await
+        .unwrap_err();
+    assert_eq!(err.status_code(), Some(503));
+
+    // Past the stale window nothing is served
+    clock.advance(Duration::from_secs(120));
+    let err = client
+        .get_secret("production", "stale-key", GetOpts::default())
+        .await
+        .unwrap_err();
+    assert_eq!(err.status_code(), Some(503));
+
+    // Client errors are never masked
+    let err = client
+        .get_secret("production", "missing-key", GetOpts::default())
+        .await
+        .unwrap_err();
+    assert_eq!(err.status_code(), Some(404));
 }
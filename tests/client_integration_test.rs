@@ -240,6 +240,7 @@ async fn test_list_secrets() {
     let opts = ListOpts {
         limit: Some(10),
         prefix: Some("app-".to_string()),
+        ..Default::default()
     };
 
     let list = client
@@ -250,7 +251,7 @@ async fn test_list_secrets() {
     assert_eq!(list.total, 2);
     assert_eq!(list.secrets.len(), 2);
     assert_eq!(list.secrets[0].key, "app-config");
-    assert_eq!(list.secrets[0].version, 3);
+    assert_eq!(list.secrets[0].version, Some(3));
 }
 
 #[tokio::test]
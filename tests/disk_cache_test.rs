@@ -0,0 +1,240 @@
+//! Integration tests for the persistent on-disk cache
+
+#![cfg(feature = "disk-cache")]
+
+use secrecy::ExposeSecret;
+use secret_store_sdk::{Auth, CacheCipher, ClientBuilder, DiskCacheMode, GetOpts};
+use serde_json::json;
+use std::path::{Path, PathBuf};
+use wiremock::{
+    matchers::{header, method, path},
+    Mock, MockServer, ResponseTemplate,
+};
+
+/// Toy cipher; real callers would use an AEAD with a key kept elsewhere
+#[derive(Debug)]
+struct XorCipher(u8);
+
+impl CacheCipher for XorCipher {
+    fn encrypt(&self, plaintext: &[u8]) -> Vec<u8> {
+        plaintext.iter().map(|b| b ^ self.0).collect()
+    }
+
+    fn decrypt(&self, ciphertext: &[u8]) -> secret_store_sdk::Result<Vec<u8>> {
+        Ok(ciphertext.iter().map(|b| b ^ self.0).collect())
+    }
+}
+
+/// Helper to create a test client backed by the given cache directory
+fn create_test_client(
+    server: &MockServer,
+    dir: &Path,
+    mode: DiskCacheMode,
+) -> secret_store_sdk::Client {
+    builder(server)
+        .disk_cache(dir, mode)
+        .cache_cipher(XorCipher(0x5a))
+        .build()
+        .expect("Failed to build client")
+}
+
+fn builder(server: &MockServer) -> ClientBuilder {
+    #[cfg(feature = "danger-insecure-http")]
+    let builder = ClientBuilder::new(server.uri())
+        .auth(Auth::bearer("test-token"))
+        .allow_insecure_http();
+
+    #[cfg(not(feature = "danger-insecure-http"))]
+    let builder = ClientBuilder::new(server.uri().replace("http://", "https://"))
+        .auth(Auth::bearer("test-token"));
+
+    builder
+}
+
+/// Contents of every cache file under `dir`
+fn cache_files(dir: &Path) -> Vec<String> {
+    std::fs::read_dir(dir.join("secret-store-sdk"))
+        .unwrap()
+        .map(|entry| std::fs::read_to_string(entry.unwrap().path()).unwrap())
+        .collect()
+}
+
+fn temp_cache_dir() -> PathBuf {
+    std::env::temp_dir().join(format!("secret-store-sdk-test-{}", uuid::Uuid::new_v4()))
+}
+
+#[tokio::test]
+async fn test_disk_cache_round_trip_across_clients() {
+    let server = MockServer::start().await;
+    let dir = temp_cache_dir();
+
+    // Revalidation from the second client gets a bodyless 304
+    Mock::given(method("GET"))
+        .and(path("/api/v2/secrets/production/db-pass"))
+        .and(header("if-none-match", "\"v1\""))
+        .respond_with(ResponseTemplate::new(304))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    Mock::given(method("GET"))
+        .and(path("/api/v2/secrets/production/db-pass"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(json!({
+                    "value": "hunter2",
+                    "version": 1,
+                    "metadata": {"owner": "platform"},
+                    "updated_at": "2024-01-01T00:00:00Z"
+                }))
+                .append_header("ETag", "\"v1\""),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let first = create_test_client(&server, &dir, DiskCacheMode::FullValue);
+    let secret = first
+        .get_secret("production", "db-pass", GetOpts::default())
+        .await
+        .expect("Failed to get secret");
+    assert_eq!(secret.value.expose_secret(), "hunter2");
+    drop(first);
+
+    // Only ciphertext reaches the disk
+    let files = cache_files(&dir);
+    assert_eq!(files.len(), 1);
+    assert!(!files[0].contains("hunter2"));
+
+    // A fresh client has an empty memory cache but shares the directory
+    let second = create_test_client(&server, &dir, DiskCacheMode::FullValue);
+    let secret = second
+        .get_secret("production", "db-pass", GetOpts::default())
+        .await
+        .expect("Failed to revalidate secret");
+    assert_eq!(secret.value.expose_secret(), "hunter2");
+    assert_eq!(secret.version, 1);
+    assert_eq!(secret.etag.as_deref(), Some("\"v1\""));
+    assert_eq!(secret.metadata["owner"], "platform");
+
+    // Invalidation removes the persisted entry as well
+    second.invalidate_cache("production", "db-pass").await;
+    assert!(second.disk_cache_etag("production", "db-pass").is_none());
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[tokio::test]
+async fn test_disk_cache_etag_only() {
+    let server = MockServer::start().await;
+    let dir = temp_cache_dir();
+
+    Mock::given(method("GET"))
+        .and(path("/api/v2/secrets/production/api-key"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(json!({
+                    "value": "super-secret",
+                    "version": 4,
+                    "updated_at": "2024-01-01T00:00:00Z"
+                }))
+                .append_header("ETag", "\"v4\""),
+        )
+        .expect(2)
+        .mount(&server)
+        .await;
+
+    let first = create_test_client(&server, &dir, DiskCacheMode::EtagOnly);
+    let _ = first
+        .get_secret("production", "api-key", GetOpts::default())
+        .await
+        .expect("Failed to get secret");
+
+    // The value never reaches the disk
+    for contents in cache_files(&dir) {
+        assert!(!contents.contains("super-secret"));
+    }
+
+    // A second client sees the ETag but must refetch the value
+    let second = create_test_client(&server, &dir, DiskCacheMode::EtagOnly);
+    assert_eq!(
+        second.disk_cache_etag("production", "api-key").as_deref(),
+        Some("\"v4\"")
+    );
+    let secret = second
+        .get_secret("production", "api-key", GetOpts::default())
+        .await
+        .expect("Failed to get secret");
+    assert_eq!(secret.value.expose_secret(), "super-secret");
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[tokio::test]
+async fn test_full_value_requires_cipher() {
+    let server = MockServer::start().await;
+    let dir = temp_cache_dir();
+
+    let err = builder(&server)
+        .disk_cache(&dir, DiskCacheMode::FullValue)
+        .build()
+        .unwrap_err();
+    assert!(err.to_string().contains("cache cipher"));
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[tokio::test]
+async fn test_caller_etag_does_not_serve_disk_entry() {
+    let server = MockServer::start().await;
+    let dir = temp_cache_dir();
+
+    Mock::given(method("GET"))
+        .and(path("/api/v2/secrets/production/db-pass"))
+        .and(header("if-none-match", "\"v2\""))
+        .respond_with(ResponseTemplate::new(304))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/api/v2/secrets/production/db-pass"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(json!({
+                    "value": "old-value",
+                    "version": 1,
+                    "updated_at": "2024-01-01T00:00:00Z"
+                }))
+                .append_header("ETag", "\"v1\""),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let first = create_test_client(&server, &dir, DiskCacheMode::FullValue);
+    let _ = first
+        .get_secret("production", "db-pass", GetOpts::default())
+        .await
+        .expect("Failed to get secret");
+    drop(first);
+
+    // The 304 confirms the caller's v2, not the v1 entry on disk
+    let second = create_test_client(&server, &dir, DiskCacheMode::FullValue);
+    let err = second
+        .get_secret(
+            "production",
+            "db-pass",
+            GetOpts {
+                if_none_match: Some("\"v2\"".to_string()),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        err,
+        secret_store_sdk::Error::NotModifiedNoCache { .. }
+    ));
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
@@ -265,10 +265,13 @@ async fn test_rollback_caching_behavior() {
         .mount(&server)
         .await;
 
-    client
+    let result = client
         .rollback("production", "cached-rollback", 1)
         .await
         .expect("Failed to rollback");
+    assert_eq!(result.from_version, 3);
+    assert_eq!(result.to_version, 1);
+    assert_eq!(result.created_version(), Some(4));
 
     // After rollback, cache should be invalidated
     // Next get should fetch from server
@@ -292,7 +295,8 @@ async fn test_rollback_caching_behavior() {
         .await
         .expect("Failed to get secret after rollback");
 
-    assert_eq!(updated.version, 4);
+    // The restored content lives in the version the rollback created
+    assert_eq!(Some(updated.version), result.created_version());
     assert_eq!(updated.value.expose_secret(), "rolled-back-value-v1");
 }
 